@@ -1,8 +1,11 @@
 #[cfg(feature = "neon-sys")]
 mod legacy;
+mod loader;
 #[cfg(not(feature = "neon-sys"))]
 mod napi;
 
+pub use loader::Loader;
+
 #[cfg(not(feature = "neon-sys"))]
 pub use napi::Setup;
 
@@ -0,0 +1,584 @@
+// Generation of a JavaScript loader and TypeScript declarations from the
+// descriptions that `#[neon::export]` embeds in a compiled library.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Starts the description of an export, which ends with a NUL. Must match
+// `neon-macros`.
+const EXPORT_PREFIX: &[u8] = b"\0neon-export:1\n";
+
+// Embedded by `#[neon::main(camel_case)]`
+const CAMEL_CASE: &[u8] = b"\0neon-main:camel_case\0";
+
+/// `Loader` generates a JavaScript loader with TypeScript declarations for
+/// the functions of a compiled Neon library marked with `#[neon::export]`.
+///
+/// The loader requires the library and wraps each function in a named
+/// function that validates the number of arguments before calling into the
+/// addon. Other exports are passed through. Declarations are written next to
+/// the loader, e.g., `index.d.ts` for `index.js`. Types are inferred from the
+/// names of the Rust types and are `unknown` when not recognized.
+///
+/// Each exported function embeds a description of itself in the library, so
+/// the loader is generated after the library is built, e.g., by a small
+/// binary or example that the `install` script of the package runs after
+/// `cargo build`:
+///
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     neon_build::Loader::new("index.node").generate("lib/index.js")
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Loader {
+    library: PathBuf,
+    addon: Option<String>,
+}
+
+impl Loader {
+    /// Creates a loader for the compiled library at `library`, e.g., the
+    /// `index.node` copied by `cargo-cp-artifact`.
+    pub fn new(library: impl AsRef<Path>) -> Self {
+        Self {
+            library: library.as_ref().to_path_buf(),
+            addon: None,
+        }
+    }
+
+    /// Sets the path that the loader passes to `require`. Defaults to the
+    /// path of the library relative to the loader.
+    pub fn addon(&mut self, addon: impl Into<String>) -> &mut Self {
+        self.addon = Some(addon.into());
+        self
+    }
+
+    /// Writes the loader to `loader` and its declarations next to it. Files
+    /// whose contents did not change are not rewritten.
+    pub fn generate(&self, loader: impl AsRef<Path>) -> io::Result<()> {
+        let loader = absolute(loader.as_ref())?;
+        let library = fs::read(&self.library)?;
+        let camel_case = find(&library, CAMEL_CASE).is_some();
+
+        let addon = match &self.addon {
+            Some(addon) => addon.clone(),
+            None => {
+                let library = absolute(&self.library)?;
+
+                loader
+                    .parent()
+                    .and_then(|dir| relative_path(dir, &library))
+                    .unwrap_or_else(|| library.display().to_string().replace('\\', "/"))
+            }
+        };
+
+        let mut entries = Vec::<Entry>::new();
+
+        for entry in descriptions(&library).filter_map(Entry::deserialize) {
+            let duplicate = entries
+                .iter()
+                .any(|other| other.namespace == entry.namespace && other.name == entry.name);
+
+            if !duplicate {
+                entries.push(entry);
+            }
+        }
+
+        for entry in &mut entries {
+            if camel_case && !entry.explicit {
+                entry.name = camel_case_name(&entry.name);
+            }
+        }
+
+        entries.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+
+        if let Some(parent) = loader.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        write_if_changed(&loader, &javascript(&addon, &entries))?;
+        write_if_changed(&loader.with_extension("d.ts"), &declarations(&entries))
+    }
+}
+
+fn absolute(path: &Path) -> io::Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+// The descriptions of exports embedded in `library`
+fn descriptions(library: &[u8]) -> impl Iterator<Item = &str> {
+    let mut rest = library;
+
+    std::iter::from_fn(move || loop {
+        let start = find(rest, EXPORT_PREFIX)? + EXPORT_PREFIX.len();
+        let len = rest[start..].iter().position(|&b| b == 0)?;
+        let description = std::str::from_utf8(&rest[start..start + len]);
+
+        rest = &rest[start + len..];
+
+        if let Ok(description) = description {
+            return Some(description);
+        }
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+// The path of `file` relative to the directory `dir`, as required from
+// JavaScript, or `None` if they do not share a root
+fn relative_path(dir: &Path, file: &Path) -> Option<String> {
+    let dir = dir.components().collect::<Vec<_>>();
+    let file = file.components().collect::<Vec<_>>();
+    let common = dir.iter().zip(&file).take_while(|(a, b)| a == b).count();
+
+    if common == 0 {
+        return None;
+    }
+
+    // `require` treats paths without a leading `.` as packages
+    let mut segments = if common == dir.len() {
+        vec![String::from(".")]
+    } else {
+        Vec::new()
+    };
+
+    segments.extend(dir[common..].iter().map(|_| String::from("..")));
+    segments.extend(
+        file[common..]
+            .iter()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+    );
+
+    Some(segments.join("/"))
+}
+
+// Description of an export embedded in the library.
+struct Entry {
+    namespace: String,
+    name: String,
+    // An explicit `name` is not converted to camelCase
+    explicit: bool,
+    kind: Kind,
+    params: Vec<LoaderParam>,
+    output: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Function,
+    Lazy,
+    Task,
+}
+
+struct LoaderParam {
+    name: String,
+    ty: String,
+    optional: bool,
+    rest: bool,
+}
+
+impl Entry {
+    fn deserialize(text: &str) -> Option<Self> {
+        let mut lines = text.lines();
+        let mut fields = lines.next()?.split('\t');
+        let namespace = fields.next()?.to_string();
+        let name = fields.next()?.to_string();
+        let explicit = fields.next()? == "true";
+        let kind = match fields.next()? {
+            "lazy" => Kind::Lazy,
+            "task" => Kind::Task,
+            _ => Kind::Function,
+        };
+        let output = fields.next()?.to_string();
+        let params = lines
+            .map(|line| {
+                let mut fields = line.split('\t');
+
+                Some(LoaderParam {
+                    name: fields.next()?.to_string(),
+                    ty: fields.next()?.to_string(),
+                    optional: fields.next()? == "true",
+                    rest: fields.next()? == "true",
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            namespace,
+            name,
+            explicit,
+            kind,
+            params,
+            output,
+        })
+    }
+}
+
+fn write_if_changed(path: &Path, contents: &str) -> io::Result<()> {
+    if fs::read_to_string(path).ok().as_deref() == Some(contents) {
+        return Ok(());
+    }
+
+    fs::write(path, contents)
+}
+
+// Mirrors `neon::macro_internal::export_name`
+fn camel_case_name(name: &str) -> String {
+    let trimmed = name.trim_start_matches('_');
+    let mut result = name[..name.len() - trimmed.len()].to_string();
+    let mut upper = false;
+
+    for c in trimmed.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+const HEADER: &str = "// Generated by Neon from `#[neon::export]` functions. Do not edit.\n";
+
+fn javascript(addon: &str, entries: &[Entry]) -> String {
+    let mut js = String::from(HEADER);
+
+    let _ = write!(
+        js,
+        r#""use strict";
+
+const addon = require({addon:?});
+
+// Copies the properties of an exports object, keeping lazy exports lazy
+function copy(object) {{
+  return Object.defineProperties({{}}, Object.getOwnPropertyDescriptors(object));
+}}
+
+function checkArguments(name, length, min, max) {{
+  if (length >= min && length <= max) {{
+    return;
+  }}
+
+  const expected =
+    min === max ? `${{min}}` : max === Infinity ? `at least ${{min}}` : `${{min}} to ${{max}}`;
+
+  throw new TypeError(`${{name}} expected ${{expected}} argument(s), found ${{length}}`);
+}}
+
+module.exports = copy(addon);
+"#,
+        addon = addon,
+    );
+
+    let mut namespaces = entries
+        .iter()
+        .map(|entry| entry.namespace.as_str())
+        .filter(|namespace| !namespace.is_empty())
+        .collect::<Vec<_>>();
+
+    namespaces.dedup();
+
+    // Namespaces are copied from the outside in, so that inner copies replace
+    // the objects shared with the addon
+    let mut copied = Vec::<String>::new();
+
+    for namespace in namespaces {
+        let mut path = String::new();
+
+        for segment in namespace.split('.') {
+            if !path.is_empty() {
+                path.push('.');
+            }
+
+            path.push_str(segment);
+
+            if !copied.contains(&path) {
+                let access = property_path(&path);
+
+                let _ = writeln!(js, "module.exports{} = copy(addon{});", access, access);
+                copied.push(path.clone());
+            }
+        }
+    }
+
+    for entry in entries.iter().filter(|entry| entry.kind != Kind::Lazy) {
+        let path = if entry.namespace.is_empty() {
+            entry.name.clone()
+        } else {
+            format!("{}.{}", entry.namespace, entry.name)
+        };
+
+        let namespace = property_path(&entry.namespace);
+        let property = property_path(&entry.name);
+        let params = entry
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let name = js_identifier(&param.name, i);
+
+                if param.rest {
+                    format!("...{}", name)
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let (min, max) = arity(&entry.params);
+        let max = max
+            .map(|max| max.to_string())
+            .unwrap_or_else(|| "Infinity".to_string());
+
+        let _ = write!(
+            js,
+            r#"
+module.exports{namespace}{property} = function {function}({params}) {{
+  checkArguments({path:?}, arguments.length, {min}, {max});
+
+  return addon{namespace}{property}.apply(this, arguments);
+}};
+"#,
+            namespace = namespace,
+            property = property,
+            function = js_identifier(&entry.name, 0),
+            params = params,
+            path = path,
+            min = min,
+            max = max,
+        );
+    }
+
+    js
+}
+
+fn declarations(entries: &[Entry]) -> String {
+    let mut ts = String::from(HEADER);
+    let mut open = Vec::<&str>::new();
+
+    for entry in entries {
+        if !is_identifier(&entry.name) {
+            continue;
+        }
+
+        let namespace = entry
+            .namespace
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        let common = open
+            .iter()
+            .zip(&namespace)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        while open.len() > common {
+            open.pop();
+            let _ = writeln!(ts, "{}}}", "  ".repeat(open.len()));
+        }
+
+        for segment in &namespace[common..] {
+            let indent = "  ".repeat(open.len());
+
+            if open.is_empty() {
+                let _ = writeln!(ts, "\nexport declare namespace {} {{", segment);
+            } else {
+                let _ = writeln!(ts, "{}export namespace {} {{", indent, segment);
+            }
+
+            open.push(segment);
+        }
+
+        let indent = "  ".repeat(open.len());
+        let export = if open.is_empty() {
+            "\nexport "
+        } else {
+            "export "
+        };
+
+        if entry.kind == Kind::Lazy {
+            let _ = writeln!(
+                ts,
+                "{}{}const {}: {};",
+                indent, export, entry.name, entry.output
+            );
+            continue;
+        }
+
+        let optional_from = entry
+            .params
+            .iter()
+            .rposition(|param| !param.optional && !param.rest)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        let params = entry
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let name = js_identifier(&param.name, i);
+
+                if param.rest {
+                    format!("...{}: {}[]", name, param.ty)
+                } else if param.optional && i >= optional_from {
+                    format!("{}?: {}", name, param.ty)
+                } else if param.optional {
+                    format!("{}: {} | undefined", name, param.ty)
+                } else {
+                    format!("{}: {}", name, param.ty)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let output = if entry.kind == Kind::Task {
+            format!("Promise<{}>", entry.output)
+        } else {
+            entry.output.clone()
+        };
+
+        let _ = writeln!(
+            ts,
+            "{}{}function {}({}): {};",
+            indent, export, entry.name, params, output
+        );
+    }
+
+    while !open.is_empty() {
+        open.pop();
+        let _ = writeln!(ts, "{}}}", "  ".repeat(open.len()));
+    }
+
+    ts
+}
+
+// The minimum and maximum number of arguments, without a maximum if the last
+// parameter is `#[neon(rest)]`
+fn arity(params: &[LoaderParam]) -> (usize, Option<usize>) {
+    let min = params
+        .iter()
+        .rposition(|param| !param.optional && !param.rest)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    let max = if params.iter().any(|param| param.rest) {
+        None
+    } else {
+        Some(params.len())
+    };
+
+    (min, max)
+}
+
+// `["a"]["b"]` for the path `a.b`, or an empty string for the root
+fn property_path(path: &str) -> String {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| format!("[{:?}]", segment))
+        .collect()
+}
+
+const RESERVED: &[&str] = &[
+    "arguments",
+    "case",
+    "catch",
+    "class",
+    "debugger",
+    "default",
+    "delete",
+    "eval",
+    "export",
+    "finally",
+    "function",
+    "import",
+    "instanceof",
+    "interface",
+    "new",
+    "null",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "with",
+];
+
+// A valid JavaScript identifier for the parameter or function `name`
+fn js_identifier(name: &str, i: usize) -> String {
+    if name.is_empty() {
+        return format!("arg{}", i);
+    }
+
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if RESERVED.contains(&name.as_str()) || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty() && js_identifier(name, 0) == name
+}
+
+#[test]
+fn test_relative_path() {
+    let dir = PathBuf::from("/project/lib");
+
+    assert_eq!(
+        relative_path(&dir, Path::new("/project/index.node")).as_deref(),
+        Some("../index.node")
+    );
+    assert_eq!(
+        relative_path(&dir, Path::new("/project/lib/native/index.node")).as_deref(),
+        Some("./native/index.node")
+    );
+}
+
+#[test]
+fn test_descriptions() {
+    let library =
+        b"\x7fELF\0neon-export:1\n\ta\tfalse\tfunction\tnumber\nx\tnumber\tfalse\tfalse\n\0\
+        \0\0neon-export:1\nmath\tb\ttrue\ttask\tvoid\n\0";
+    let entries = descriptions(library)
+        .filter_map(Entry::deserialize)
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].name, "a");
+    assert_eq!(entries[0].params.len(), 1);
+    assert_eq!(entries[0].params[0].ty, "number");
+    assert_eq!(entries[1].namespace, "math");
+    assert!(entries[1].explicit);
+    assert!(entries[1].kind == Kind::Task);
+    assert!(find(library, CAMEL_CASE).is_none());
+}
@@ -79,3 +79,45 @@ pub(crate) fn main(
     )
     .into()
 }
+
+pub(crate) fn export(
+    _attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    quote::quote!(
+        compile_error!("`#[neon::export]` requires an N-API backend feature, e.g. `napi-6`");
+
+        #item
+    )
+    .into()
+}
+
+pub(crate) fn class(
+    _attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    quote::quote!(
+        compile_error!("`#[neon::class]` requires an N-API backend feature, e.g. `napi-6`");
+
+        #item
+    )
+    .into()
+}
+
+pub(crate) fn test(
+    _attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item = proc_macro2::TokenStream::from(item);
+
+    quote::quote!(
+        compile_error!("`#[neon::test]` requires an N-API backend feature, e.g. `napi-6`");
+
+        #item
+    )
+    .into()
+}
@@ -17,8 +17,8 @@ use legacy as macros;
 /// Marks a function as the main entry point for initialization in
 /// a Neon module.
 ///
-/// This attribute should only be used _once_ in a module and will
-/// be called each time the module is initialized in a context.
+/// The function is called each time the module is initialized in a context,
+/// after the items marked with [`#[neon::export]`](macro@export) are exported.
 ///
 /// ```ignore
 /// #[neon::main]
@@ -31,7 +31,31 @@ use legacy as macros;
 /// }
 /// ```
 ///
-/// If multiple functions are marked with `#[neon::main]`, there may be a compile error:
+/// Passing `camel_case`, as in `#[neon::main(camel_case)]`, exports functions
+/// marked with [`#[neon::export]`](macro@export) under `camelCase` names unless
+/// they specify a `name`.
+///
+/// Large modules can split initialization across several functions, in the
+/// same crate or in crates it depends on, by marking each of the others with
+/// `order`. They run after the entry point in ascending `order`, or before it
+/// if `order` is negative, since the entry point has order `0`. Functions with
+/// the same `order` run in the order of their paths. An error stops the
+/// functions after it from running.
+///
+/// ```ignore
+/// #[neon::main(order = 10)]
+/// fn init_cache(mut cx: ModuleContext) -> NeonResult<()> {
+///     let size = cx.number(64);
+///
+///     cx.export_value("cacheSize", size)?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// Exactly one function must be marked with `#[neon::main]` without `order`,
+/// since it defines the entry point of the module. If there are more, there is
+/// a compile error:
 ///
 /// ```sh
 /// error: symbol `napi_register_module_v1` is already defined
@@ -42,3 +66,372 @@ pub fn main(
 ) -> proc_macro::TokenStream {
     macros::main(attr, item)
 }
+
+#[proc_macro_attribute]
+/// Exports a function on `module.exports` without registering it in
+/// `#[neon::main]`.
+///
+/// Arguments are converted with [`TryFromJs`] and the return value with
+/// [`TryIntoJs`]. A function that needs the context may take
+/// `cx: &mut FunctionContext` or `cx: &mut Cx` as its first parameter.
+///
+/// ```ignore
+/// #[neon::export]
+/// fn add(a: f64, b: f64) -> f64 {
+///     a + b
+/// }
+/// ```
+///
+/// If an argument cannot be extracted, the error names its position and the
+/// parameter, and the type of the value that was passed, e.g.,
+/// `TypeError: argument 2 ("b") expected number, found string`.
+///
+/// With the `tracing` feature of `neon` enabled, each call runs in a `DEBUG`
+/// span named `export` with the target `neon`, and the Rust name of the
+/// function in the `function` field.
+///
+/// A parameter named `this` (or marked `#[neon(this)]`) receives the `this`
+/// binding of the call, which is useful for exporting prototype methods. It
+/// must come before any other argument, following the context if present:
+///
+/// ```ignore
+/// #[neon::export]
+/// fn get_name(cx: &mut FunctionContext, this: Handle<JsObject>) -> JsResult<JsValue> {
+///     this.get(cx, "name")
+/// }
+/// ```
+///
+/// Missing arguments are passed to the extractor as `undefined`, so trailing
+/// `Option<T>` parameters may be omitted by the caller. A parameter marked
+/// `#[neon(default = expr)]` evaluates `expr` instead when its argument is
+/// missing or `undefined`:
+///
+/// ```ignore
+/// #[neon::export]
+/// fn repeat(s: String, #[neon(default = 2)] n: u32) -> String {
+///     s.repeat(n as usize)
+/// }
+/// ```
+///
+/// The last parameter may be marked `#[neon(rest)]` to collect all remaining
+/// arguments into a `Vec`:
+///
+/// ```ignore
+/// #[neon::export]
+/// fn sum(#[neon(rest)] values: Vec<f64>) -> f64 {
+///     values.into_iter().sum()
+/// }
+/// ```
+///
+/// A parameter of type `Arc<T>` may be marked `#[neon(arc)]` to take a
+/// `JsBox<Arc<T>>` argument and receive a clone of the `Arc`. Unlike a
+/// `Handle<JsBox<T>>`, the clone is not tied to the call, so shared state can
+/// be moved into a `task` or another thread:
+///
+/// ```ignore
+/// #[neon::export(task)]
+/// fn lookup(#[neon(arc)] cache: Arc<Cache>, key: String) -> Option<String> {
+///     cache.get(&key)
+/// }
+/// ```
+///
+/// Functions are exported under their Rust name. Use `name` to choose a
+/// different one, or `#[neon::main(camel_case)]` to export every function
+/// without an explicit name in `camelCase`:
+///
+/// ```ignore
+/// #[neon::export(name = "isValid")]
+/// fn validate(input: String) -> bool {
+///     !input.is_empty()
+/// }
+/// ```
+///
+/// Use `namespace` to attach the function to nested objects of
+/// `module.exports`, which are created as needed. The following is available
+/// to JavaScript as `addon.fs.sync.read`:
+///
+/// ```ignore
+/// #[neon::export(namespace = "fs.sync")]
+/// fn read(path: String) -> Result<String, String> {
+///     std::fs::read_to_string(path).map_err(|err| err.to_string())
+/// }
+/// ```
+///
+/// With `lazy`, the function is not exported itself. Instead, it runs the
+/// first time the export is accessed and its return value replaces the export,
+/// deferring expensive setup until it is needed. Lazy exports may only take
+/// the context as a parameter:
+///
+/// ```ignore
+/// #[neon::export(lazy)]
+/// fn primes() -> Vec<u32> {
+///     (2..10_000).filter(|n| (2..*n).all(|d| n % d != 0)).collect()
+/// }
+/// ```
+///
+/// With `task`, the body runs on the Node worker pool and the function returns
+/// a `Promise` for its result. Arguments are extracted before the task is
+/// scheduled, so they and the return value must be `Send`; the context and
+/// `this` are not available. Requires the `promise-api` and `task-api`
+/// features:
+///
+/// ```ignore
+/// #[neon::export(task)]
+/// fn fibonacci(n: u32) -> f64 {
+///     (0..n).fold((0.0, 1.0), |(a, b), _| (b, a + b)).0
+/// }
+/// ```
+///
+/// With `json`, arguments and the return value are converted through JSON
+/// with `serde` instead of [`TryFromJs`] and [`TryIntoJs`]. Arguments must
+/// implement `DeserializeOwned` and the return value `Serialize`; the `Ok`
+/// value of a `Result` is serialized and the error is thrown as usual. It
+/// requires the `serde` feature of Neon, which provides `serde_json`; only
+/// `serde` itself is needed to derive the traits:
+///
+/// ```ignore
+/// #[derive(Deserialize, Serialize)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// #[neon::export(json)]
+/// fn midpoint(a: Point, b: Point) -> Point {
+///     Point { x: (a.x + b.x) / 2.0, y: (a.y + b.y) / 2.0 }
+/// }
+/// ```
+///
+/// Exports that depend on newer engine features can be made conditional, so
+/// that the rest of the module still loads on older runtimes. With
+/// `requires = "napi-N"`, the function is only exported if the host supports
+/// N-API version `N`. With `when = path`, it is only exported if the function
+/// at `path`, taking no arguments and returning `bool`, returns `true` when
+/// the module is loaded. Without `stub`, an export whose conditions do not
+/// hold is left out; with `stub`, calling it throws an error instead:
+///
+/// ```ignore
+/// fn has_type_tags() -> bool {
+///     neon::feature::supports(neon::feature::Feature::TypeTag)
+/// }
+///
+/// #[neon::export(requires = "napi-9", stub)]
+/// fn parse(source: String) -> Result<String, String> {
+///     // ...
+/// #   Ok(source)
+/// }
+///
+/// #[neon::export(when = has_type_tags)]
+/// fn tagged() -> bool {
+///     true
+/// }
+/// ```
+///
+/// Conditional exports are still listed by `neon::registered()`.
+///
+/// Addons that need configuration before they can be used may export one
+/// function with `init`, which is exported as `init` unless it has a `name`.
+/// Until it has returned successfully, every other function exported with
+/// `#[neon::export]` throws an error saying that the module is not
+/// initialized, except those marked with `before_init`. Each instance of the
+/// module, e.g., in a worker thread, is initialized separately and `init` may
+/// only succeed once per instance. Requires the `napi-6` feature:
+///
+/// ```ignore
+/// static CONFIG: ContextLocal<Config> = ContextLocal::new();
+///
+/// // `Config` implements `TryFromJs`
+/// #[neon::export(init)]
+/// fn init(cx: &mut FunctionContext, config: Config) -> Result<(), String> {
+///     config.validate()?;
+///     CONFIG.get_or_init(cx, || config);
+///
+///     Ok(())
+/// }
+///
+/// #[neon::export(before_init)]
+/// fn version() -> String {
+///     env!("CARGO_PKG_VERSION").to_string()
+/// }
+/// ```
+///
+/// With the `standalone` feature, exported functions can also be called from
+/// Rust without Node through `neon::standalone`, e.g., in tests, benchmarks
+/// and fuzz targets.
+///
+/// After the library is built, `neon_build::Loader` can generate a
+/// JavaScript loader with TypeScript declarations for the exports, from
+/// descriptions that each export embeds in the library. Each function is
+/// wrapped in a named function that checks the number of arguments before
+/// calling into the addon.
+///
+/// The name, kind and signature of every export are also available at
+/// runtime from `neon::registered()`.
+///
+/// Exports are registered by global constructors, which are not run on
+/// `wasm32`, so `#[neon::export]` is a compile error there. Modules built
+/// for WebAssembly must export functions from `#[neon::main]` instead.
+///
+/// [`TryFromJs`]: https://docs.rs/neon/latest/neon/types/extract/trait.TryFromJs.html
+/// [`TryIntoJs`]: https://docs.rs/neon/latest/neon/types/extract/trait.TryIntoJs.html
+pub fn export(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    macros::export(attr, item)
+}
+
+#[proc_macro_attribute]
+/// Exports a Rust type as a JavaScript class, from an `impl` block.
+///
+/// A public associated function named `new` is called by the constructor and
+/// its return value, which may be a `Result`, becomes the state of the
+/// instance. Public methods taking `&self` or `&mut self` are added to the
+/// prototype, taking arguments like functions marked
+/// [`#[neon::export]`](macro@export). Private items are left as they are.
+///
+/// ```ignore
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// #[neon::class]
+/// impl Counter {
+///     pub fn new(count: u32) -> Self {
+///         Self { count }
+///     }
+///
+///     pub fn increment(&mut self) -> u32 {
+///         self.count += 1;
+///         self.count
+///     }
+/// }
+/// ```
+///
+/// The state is kept in a `Mutex` behind an `Arc`, so the type must be `Send`.
+/// Calling a method while the state is in use by a method further up the
+/// stack throws an `Error`.
+///
+/// An `async` method returns a `Promise`. It takes `&self` and runs on a
+/// clone of the state, which is taken when the method is called, so the type
+/// must be `Clone`, and fields shared by every clone, such as a connection
+/// pool, should be kept in an `Arc`. The instance is not locked while the
+/// method is pending. The future is driven on the JavaScript thread with
+/// `Context::spawn_local`, so it does not need to be `Send`, but the context
+/// and `this` are not available. Futures that need a runtime, e.g., `tokio`,
+/// must enter it or spawn onto it themselves. Requires the `channel-api` and
+/// `promise-api` features:
+///
+/// ```ignore
+/// #[derive(Clone)]
+/// struct Client {
+///     pool: Arc<Pool>,
+/// }
+///
+/// #[neon::class]
+/// impl Client {
+///     pub async fn query(&self, sql: String) -> Result<Vec<String>, String> {
+///         self.pool.query(&sql).await
+///     }
+/// }
+/// ```
+///
+/// Methods marked `#[neon(getter)]` or `#[neon(setter)]` define a property of
+/// the prototype instead. A getter takes no arguments and a setter takes the
+/// new value. The property has the name of the getter or, without the `set_`
+/// prefix, of the setter:
+///
+/// ```ignore
+/// #[neon::class]
+/// impl Counter {
+///     #[neon(getter)]
+///     pub fn count(&self) -> u32 {
+///         self.count
+///     }
+///
+///     #[neon(setter)]
+///     pub fn set_count(&mut self, count: u32) {
+///         self.count = count;
+///     }
+/// }
+/// ```
+///
+/// Other public associated functions become static methods of the class and
+/// public associated constants become static properties. A static method
+/// returning `Self`, or a `Result` of it, creates an instance with the value
+/// as its state without calling `new`, which requires the `napi-6` feature:
+///
+/// ```ignore
+/// #[neon::class]
+/// impl Counter {
+///     pub const MAX: u32 = 100;
+///
+///     pub fn parse(count: String) -> Result<Self, String> {
+///         let count = count.parse().map_err(|_| format!("invalid count `{}`", count))?;
+///
+///         Ok(Self { count })
+///     }
+/// }
+/// ```
+///
+/// Classes may be extended in JavaScript. The constructor of a subclass must
+/// call `super()`, which calls `new`. A static method that creates an instance
+/// and is called on a subclass, as in `Subclass.parse("1")`, creates an
+/// instance of the subclass without running the constructor of the subclass.
+///
+/// The class is exported under the name of the type and methods under their
+/// Rust name, converted to `camelCase` by `#[neon::main(camel_case)]`. Use
+/// `name` and `namespace` as with `#[neon::export]` to change where the class
+/// is exported, and `#[neon(name = "...")]` to rename a method, property or
+/// constant.
+pub fn class(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    macros::class(attr, item)
+}
+
+#[proc_macro_attribute]
+/// Compiles a function into the addon as a test, to be run under Node.js.
+///
+/// A test may take no arguments or `cx: &mut FunctionContext`, and returns
+/// `()` or `NeonResult<()>`. It passes unless it throws or panics.
+///
+/// ```ignore
+/// #[neon::test]
+/// fn creates_objects(cx: &mut FunctionContext) -> NeonResult<()> {
+///     let obj = cx.empty_object();
+///     let n = cx.number(1);
+///
+///     obj.set(cx, "n", n)?;
+///     let n = obj.get(cx, "n")?.downcast_or_throw::<JsNumber, _>(cx)?;
+///
+///     assert_eq!(n.value(cx), 1.0);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// If a module has any tests, it exports a `__neon_run_tests` function that
+/// runs them, in the order they were registered, and returns an array of
+/// `{ name, passed, error }` objects. An optional string argument runs only
+/// the tests whose name contains it. `error` is the exception thrown by the
+/// test, or an `Error` describing the panic.
+///
+/// ```js
+/// const addon = require("./index.node");
+///
+/// for (const { name, passed, error } of addon.__neon_run_tests()) {
+///     console.log(passed ? "ok" : "FAILED", name, error ?? "");
+/// }
+/// ```
+///
+/// Like `#[cfg(test)]` code, tests are left out of release builds: a test and
+/// its registration are only compiled with `debug_assertions`, and
+/// `__neon_run_tests` is only exported by builds that have them.
+pub fn test(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    macros::test(attr, item)
+}
@@ -0,0 +1,757 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+
+use super::export::{
+    extract_params, metadata, parse_params, registration, returns_result, rust_type, ExportAttr,
+    Param,
+};
+
+pub(crate) fn class(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let parser = syn::punctuated::Punctuated::<ExportAttr, syn::Token![,]>::parse_terminated;
+    let attrs = syn::parse_macro_input!(attr with parser);
+    let attrs = match ClassAttrs::new(attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut input = syn::parse_macro_input!(item as syn::ItemImpl);
+
+    match class_impl(&attrs, &mut input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// Options accepted by `#[neon::class(...)]`, a subset of those accepted by
+// `#[neon::export(...)]`.
+#[derive(Default)]
+struct ClassAttrs {
+    name: Option<syn::LitStr>,
+    namespace: Option<syn::LitStr>,
+}
+
+impl ClassAttrs {
+    fn new(list: syn::punctuated::Punctuated<ExportAttr, syn::Token![,]>) -> syn::Result<Self> {
+        let mut attrs = ClassAttrs::default();
+
+        for item in list {
+            match item {
+                ExportAttr::Name(name) => {
+                    if attrs.name.is_some() {
+                        return Err(syn::Error::new(name.span(), "duplicate `name`"));
+                    }
+
+                    attrs.name = Some(name);
+                }
+                ExportAttr::Namespace(namespace) => {
+                    if attrs.namespace.is_some() {
+                        return Err(syn::Error::new(namespace.span(), "duplicate `namespace`"));
+                    }
+
+                    let value = namespace.value();
+
+                    if value.is_empty() || value.split('.').any(str::is_empty) {
+                        return Err(syn::Error::new(
+                            namespace.span(),
+                            "expected a dot-separated path, e.g. `\"fs.sync\"`",
+                        ));
+                    }
+
+                    attrs.namespace = Some(namespace);
+                }
+                ExportAttr::Lazy(ident)
+                | ExportAttr::Task(ident)
+                | ExportAttr::Json(ident)
+                | ExportAttr::Stub(ident)
+                | ExportAttr::Init(ident)
+                | ExportAttr::BeforeInit(ident) => {
+                    return Err(syn::Error::new(ident.span(), "unknown class option"));
+                }
+                ExportAttr::Requires(requires) => {
+                    return Err(syn::Error::new(requires.span(), "unknown class option"));
+                }
+                ExportAttr::When(when) => {
+                    return Err(syn::Error::new(when.span(), "unknown class option"));
+                }
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+fn class_impl(attrs: &ClassAttrs, input: &mut syn::ItemImpl) -> syn::Result<TokenStream> {
+    if let Some((_, path, _)) = &input.trait_ {
+        return Err(syn::Error::new(
+            path.span(),
+            "`#[neon::class]` must be used on an inherent `impl` block",
+        ));
+    }
+
+    if input.generics.params.iter().next().is_some() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "classes cannot be generic",
+        ));
+    }
+
+    let ty = input.self_ty.clone();
+    let class_name = match &attrs.name {
+        Some(name) => name.value(),
+        None => match &*ty {
+            syn::Type::Path(path) if path.qself.is_none() => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .unwrap_or_default(),
+            _ => {
+                return Err(syn::Error::new(
+                    ty.span(),
+                    "expected a named type; use `name` to name the class",
+                ))
+            }
+        },
+    };
+
+    let namespace = match &attrs.namespace {
+        Some(namespace) => quote!(#namespace),
+        None => quote!(""),
+    };
+
+    let mut constructor = None;
+    let mut wrappers = Vec::new();
+    let mut members = Vec::new();
+    let mut accessors = Vec::new();
+    let mut constants = Vec::new();
+    let mut factories = false;
+
+    for item in input.items.iter_mut() {
+        let method = match item {
+            syn::ImplItem::Method(method) => method,
+            // Public constants become static properties of the class
+            syn::ImplItem::Const(constant) => {
+                let attrs = MethodAttrs::take(&mut constant.attrs)?;
+
+                if attrs.accessor.is_some() {
+                    return Err(syn::Error::new(
+                        constant.ident.span(),
+                        "constants cannot be getters or setters",
+                    ));
+                }
+
+                if matches!(constant.vis, syn::Visibility::Public(_)) {
+                    let ident = &constant.ident;
+                    let name = match attrs.name {
+                        Some(name) => name,
+                        None => syn::LitStr::new(&ident.to_string(), ident.span()),
+                    };
+
+                    constants.push(quote!(
+                        ::neon::macro_internal::define_constant(cx, class, #name, <#ty>::#ident)?;
+                    ));
+                }
+
+                continue;
+            }
+            _ => continue,
+        };
+
+        let method_attrs = MethodAttrs::take(&mut method.attrs)?;
+
+        // Only public functions are part of the class
+        if !matches!(method.vis, syn::Visibility::Public(_)) {
+            continue;
+        }
+
+        let sig = &mut method.sig;
+
+        if sig.generics.type_params().next().is_some()
+            || sig.generics.const_params().next().is_some()
+        {
+            return Err(syn::Error::new(
+                sig.generics.span(),
+                "class methods cannot be generic over types",
+            ));
+        }
+
+        let receiver = match sig.receiver() {
+            Some(syn::FnArg::Receiver(receiver)) => Some(receiver.clone()),
+            Some(syn::FnArg::Typed(input)) => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "class methods must take `&self` or `&mut self`",
+                ))
+            }
+            None => None,
+        };
+
+        let ident = sig.ident.clone();
+
+        let receiver = match receiver {
+            Some(receiver) => receiver,
+            None if ident == "new" => {
+                if let Some(asyncness) = &sig.asyncness {
+                    return Err(syn::Error::new(
+                        asyncness.span(),
+                        "class constructors cannot be `async`",
+                    ));
+                }
+
+                let params = parse_params(sig, false)?;
+
+                constructor = Some(constructor_wrapper(&ty, sig, &params));
+                continue;
+            }
+            // Other associated functions are static methods
+            None => {
+                if let Some(asyncness) = &sig.asyncness {
+                    return Err(syn::Error::new(
+                        asyncness.span(),
+                        "static methods cannot be `async`",
+                    ));
+                }
+
+                if method_attrs.accessor.is_some() {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "getters and setters must take `&self` or `&mut self`",
+                    ));
+                }
+
+                let params = parse_params(sig, false)?;
+                let (extract, args) = extract_params(&params, false);
+                let wrapper = format_ident!("__neon_class_static_{}", ident);
+
+                // A function returning the type, like `new`, creates an
+                // instance of the class
+                let result = match returns_self(&sig.output, &ty) {
+                    Some(result) => {
+                        factories = true;
+
+                        let unwrap = if result { unwrap_result() } else { quote!() };
+
+                        quote!(
+                            #unwrap
+                            __NEON_CLASS.construct(&mut cx, value)
+                        )
+                    }
+                    None => quote!(::neon::macro_internal::into_js_value(&mut cx, value)),
+                };
+
+                wrappers.push(quote!(
+                    fn #wrapper(
+                        mut cx: ::neon::context::FunctionContext,
+                    ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                        #(#extract)*
+                        let value = <#ty>::#ident(#(#args),*);
+
+                        #result
+                    }
+                ));
+
+                let member_name = match &method_attrs.name {
+                    Some(name) => quote!(#name),
+                    None => {
+                        let name = ident.to_string();
+
+                        quote!(&*::neon::macro_internal::export_name(#name))
+                    }
+                };
+
+                members.push(quote!(
+                    (#member_name, ::neon::macro_internal::ClassMember::StaticMethod(#wrapper))
+                ));
+
+                continue;
+            }
+        };
+
+        let mutability = match (&receiver.reference, &receiver.mutability) {
+            (Some(_), Some(_)) => quote!(mut),
+            (Some(_), None) => quote!(),
+            (None, _) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "class methods must take `&self` or `&mut self`",
+                ))
+            }
+        };
+
+        let params = parse_params(sig, true)?;
+
+        if let Some(kind) = method_attrs.accessor {
+            if let Some(asyncness) = &sig.asyncness {
+                return Err(syn::Error::new(
+                    asyncness.span(),
+                    "getters and setters cannot be `async`",
+                ));
+            }
+
+            let arguments = params
+                .iter()
+                .filter(|param| !matches!(param, Param::Context))
+                .collect::<Vec<_>>();
+
+            let key = match kind {
+                AccessorKind::Getter => {
+                    if !arguments.is_empty() {
+                        return Err(syn::Error::new(
+                            sig.inputs.span(),
+                            "getters can only take the context as a parameter",
+                        ));
+                    }
+
+                    ident.to_string()
+                }
+                AccessorKind::Setter => {
+                    if !matches!(arguments.as_slice(), [Param::Argument(0, _)]) {
+                        return Err(syn::Error::new(
+                            sig.inputs.span(),
+                            "setters must take exactly one argument",
+                        ));
+                    }
+
+                    let name = ident.to_string();
+
+                    match (&method_attrs.name, name.strip_prefix("set_")) {
+                        (Some(_), _) => name,
+                        (None, Some(name)) => name.to_string(),
+                        (None, None) => {
+                            return Err(syn::Error::new(
+                                ident.span(),
+                                "setters must be named `set_*` or have a `name`",
+                            ))
+                        }
+                    }
+                }
+            };
+
+            // A getter and setter of the same property share one accessor
+            let key = match &method_attrs.name {
+                Some(name) => (true, name.value()),
+                None => (false, key),
+            };
+
+            let body = method_call(&ty, &class_name, &ident, &mutability, &params);
+            let index = match accessors
+                .iter()
+                .position(|accessor: &Accessor| accessor.key == key)
+            {
+                Some(index) => index,
+                None => {
+                    accessors.push(Accessor {
+                        key,
+                        getter: None,
+                        setter: None,
+                    });
+                    accessors.len() - 1
+                }
+            };
+
+            let slot = match kind {
+                AccessorKind::Getter => &mut accessors[index].getter,
+                AccessorKind::Setter => &mut accessors[index].setter,
+            };
+
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "duplicate accessor for this property",
+                ));
+            }
+
+            *slot = Some(body);
+            continue;
+        }
+
+        let wrapper = format_ident!("__neon_class_method_{}", ident);
+
+        // An `async` method runs on a clone of the state, which is taken
+        // before the future is spawned, so the instance is not locked while it
+        // is pending. The future runs on the JavaScript thread, but cannot use
+        // the context or `this` of the call.
+        let body = if sig.asyncness.is_some() {
+            if receiver.mutability.is_some() {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "`async` class methods must take `&self`, since they run on a clone of the instance",
+                ));
+            }
+
+            if let Some(param) = params
+                .iter()
+                .zip(sig.inputs.iter().skip(1))
+                .find(|(param, _)| matches!(param, Param::Context | Param::This))
+                .map(|(_, input)| input)
+            {
+                return Err(syn::Error::new(
+                    param.span(),
+                    "`async` class methods cannot take the context or `this`",
+                ));
+            }
+
+            let (extract, args) = extract_params(&params, false);
+
+            quote!(
+                #(#extract)*
+
+                let this = ::neon::macro_internal::clone_state(&mut cx, &state, #class_name)?;
+
+                ::neon::macro_internal::spawn_method(&mut cx, async move {
+                    <#ty>::#ident(&this, #(#args),*).await
+                })
+            )
+        } else {
+            method_call(&ty, &class_name, &ident, &mutability, &params)
+        };
+
+        wrappers.push(quote!(
+            fn #wrapper(
+                mut cx: ::neon::context::FunctionContext,
+            ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                let state = ::neon::macro_internal::class_state::<#ty>(&mut cx, #class_name)?;
+
+                #body
+            }
+        ));
+
+        let member_name = match &method_attrs.name {
+            Some(name) => quote!(#name),
+            None => {
+                let name = ident.to_string();
+
+                quote!(&*::neon::macro_internal::export_name(#name))
+            }
+        };
+
+        members.push(quote!(
+            (#member_name, ::neon::macro_internal::ClassMember::Method(#wrapper))
+        ));
+    }
+
+    for (i, accessor) in accessors.into_iter().enumerate() {
+        let wrapper = format_ident!("__neon_class_accessor_{}", i);
+        let get = accessor.getter.is_some();
+        let set = accessor.setter.is_some();
+
+        // The setter is called with the new value and the getter without
+        // arguments
+        let body = match (accessor.getter, accessor.setter) {
+            (Some(getter), Some(setter)) => quote!(
+                if cx.len() == 0 {
+                    #getter
+                } else {
+                    #setter
+                }
+            ),
+            (Some(body), None) | (None, Some(body)) => body,
+            (None, None) => unreachable!(),
+        };
+
+        wrappers.push(quote!(
+            fn #wrapper(
+                mut cx: ::neon::context::FunctionContext,
+            ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                let state = ::neon::macro_internal::class_state::<#ty>(&mut cx, #class_name)?;
+
+                #body
+            }
+        ));
+
+        let member_name = match accessor.key {
+            (true, name) => quote!(#name),
+            (false, name) => quote!(&*::neon::macro_internal::export_name(#name)),
+        };
+
+        members.push(quote!(
+            (
+                #member_name,
+                ::neon::macro_internal::ClassMember::Accessor {
+                    wrapper: #wrapper,
+                    get: #get,
+                    set: #set,
+                },
+            )
+        ));
+    }
+
+    // Without `new`, instances cannot be created from JavaScript
+    let constructor = constructor.unwrap_or_else(|| {
+        let message = format!("{} does not have a constructor", class_name);
+
+        quote!(::neon::context::Context::throw_type_error(&mut cx, #message))
+    });
+
+    // Static methods that create instances keep the constructor, which
+    // attaches the state they pass to it instead of calling `new`
+    let (class_ref, pending, keep_class) = if factories {
+        (
+            quote!(
+                static __NEON_CLASS: ::neon::macro_internal::ClassRef =
+                    ::neon::macro_internal::ClassRef::new();
+            ),
+            quote!(
+                if let Some(this) = ::neon::macro_internal::wrap_pending(&mut cx)? {
+                    return Ok(this);
+                }
+            ),
+            quote!(__NEON_CLASS.set(cx, class);),
+        )
+    } else {
+        (quote!(), quote!(), quote!())
+    };
+
+    let rust_name = match &*ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_else(|| class_name.clone()),
+        _ => class_name.clone(),
+    };
+
+    // Instances are typed by the name of the class in TypeScript
+    let output = rust_type(&ty);
+    let metadata = metadata(
+        &rust_name,
+        Some(class_name.clone()),
+        attrs
+            .namespace
+            .as_ref()
+            .map(|namespace| namespace.value())
+            .unwrap_or_default(),
+        quote!(Class),
+        Vec::new(),
+        quote!(::neon::registry::Type::new(#output, #class_name)),
+    );
+
+    let registration = registration(
+        quote!(
+            let class = ::neon::macro_internal::define_class(
+                cx,
+                #class_name,
+                __neon_class_constructor,
+                &[#(#members),*],
+            )?;
+
+            #(#constants)*
+            #keep_class
+
+            ::neon::macro_internal::export_value(cx, #namespace, #class_name, class)
+        ),
+        Some(metadata),
+        None,
+        None,
+    );
+
+    Ok(quote!(
+        #input
+
+        #[doc(hidden)]
+        #[allow(non_snake_case, non_upper_case_globals)]
+        const _: () = {
+            #class_ref
+
+            fn __neon_class_constructor(
+                mut cx: ::neon::context::FunctionContext,
+            ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                ::neon::macro_internal::check_construct(&mut cx, #class_name)?;
+
+                #pending
+                #constructor
+            }
+
+            #(#wrappers)*
+
+            #registration
+        };
+    ))
+}
+
+// Generates a call of a method on the locked state of `this`, after the
+// arguments are extracted so that any JavaScript they run cannot observe the
+// lock.
+fn method_call(
+    ty: &syn::Type,
+    class_name: &str,
+    ident: &syn::Ident,
+    mutability: &TokenStream,
+    params: &[Param],
+) -> TokenStream {
+    let (extract, args) = extract_params(params, false);
+
+    quote!(
+        #(#extract)*
+
+        let #mutability guard = ::neon::macro_internal::lock(&mut cx, &state, #class_name)?;
+        let result = <#ty>::#ident(&#mutability *guard, #(#args),*);
+
+        drop(guard);
+
+        ::neon::macro_internal::into_js_value(&mut cx, result)
+    )
+}
+
+// A property of the prototype with a getter, setter or both. The key is the
+// name of the property and whether it is used verbatim.
+struct Accessor {
+    key: (bool, String),
+    getter: Option<TokenStream>,
+    setter: Option<TokenStream>,
+}
+
+// Generates the body of the constructor, which calls `new` and attaches the
+// result to the object being constructed.
+fn constructor_wrapper(ty: &syn::Type, sig: &syn::Signature, params: &[Param]) -> TokenStream {
+    let (extract, args) = extract_params(params, false);
+    let unwrap = if returns_result(&sig.output) {
+        unwrap_result()
+    } else {
+        quote!()
+    };
+
+    quote!(
+        #(#extract)*
+        let value = <#ty>::new(#(#args),*);
+        #unwrap
+
+        ::neon::macro_internal::wrap(&mut cx, value)
+    )
+}
+
+// Unwraps the `Result` in `value`, throwing the error as it would be thrown by
+// a method.
+fn unwrap_result() -> TokenStream {
+    quote!(
+        let value = match value {
+            Ok(value) => value,
+            Err(err) => {
+                return ::neon::macro_internal::into_js_value(&mut cx, Err::<(), _>(err));
+            }
+        };
+    )
+}
+
+// Checks syntactically for a return type of `Self`, the type of the class or
+// a `Result` of either, returning whether it is a `Result`.
+fn returns_self(output: &syn::ReturnType, ty: &syn::Type) -> Option<bool> {
+    let output = match output {
+        syn::ReturnType::Type(_, output) => &**output,
+        syn::ReturnType::Default => return None,
+    };
+
+    let is_self = |output: &syn::Type| match output {
+        syn::Type::Path(path) => {
+            path.path.is_ident("Self") || quote!(#output).to_string() == quote!(#ty).to_string()
+        }
+        _ => false,
+    };
+
+    if is_self(output) {
+        return Some(false);
+    }
+
+    let segment = match output {
+        syn::Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Result" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(ok)) if is_self(ok) => Some(true),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Attributes accepted on the methods of a class.
+#[derive(Default)]
+struct MethodAttrs {
+    name: Option<syn::LitStr>,
+    accessor: Option<AccessorKind>,
+}
+
+#[derive(Clone, Copy)]
+enum AccessorKind {
+    Getter,
+    Setter,
+}
+
+impl MethodAttrs {
+    fn take(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut result = MethodAttrs::default();
+        let mut err = Ok(());
+
+        attrs.retain(|attr| {
+            if !attr.path.is_ident("neon") {
+                return true;
+            }
+
+            if let Err(e) = result.parse(attr) {
+                err = Err(e);
+            }
+
+            false
+        });
+
+        err.map(|_| result)
+    }
+
+    fn parse(&mut self, attr: &syn::Attribute) -> syn::Result<()> {
+        let list = attr.parse_args_with(
+            syn::punctuated::Punctuated::<MethodAttr, syn::Token![,]>::parse_terminated,
+        )?;
+
+        for item in list {
+            match item {
+                MethodAttr::Name(name) => self.name = Some(name),
+                MethodAttr::Accessor(ident, kind) => {
+                    if self.accessor.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "a method cannot be both a getter and a setter",
+                        ));
+                    }
+
+                    self.accessor = Some(kind);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+enum MethodAttr {
+    Name(syn::LitStr),
+    Accessor(syn::Ident, AccessorKind),
+}
+
+impl syn::parse::Parse for MethodAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+
+        if ident == "name" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(MethodAttr::Name(input.parse()?))
+        } else if ident == "getter" {
+            Ok(MethodAttr::Accessor(ident, AccessorKind::Getter))
+        } else if ident == "setter" {
+            Ok(MethodAttr::Accessor(ident, AccessorKind::Setter))
+        } else {
+            Err(syn::Error::new(ident.span(), "unknown method attribute"))
+        }
+    }
+}
@@ -0,0 +1,1030 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+use super::loader::{self, Entry, Kind};
+
+pub(crate) fn export(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let parser = syn::punctuated::Punctuated::<ExportAttr, syn::Token![,]>::parse_terminated;
+    let attrs = syn::parse_macro_input!(attr with parser);
+    let attrs = match ExportAttrs::new(attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    match export_fn(&attrs, &mut input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// Options accepted by `#[neon::export(...)]`.
+#[derive(Default)]
+struct ExportAttrs {
+    name: Option<syn::LitStr>,
+    namespace: Option<syn::LitStr>,
+    lazy: bool,
+    task: bool,
+    json: bool,
+    // The N-API version from `requires = "napi-N"`, or zero
+    requires: u32,
+    when: Option<syn::Path>,
+    stub: Option<syn::Ident>,
+    init: bool,
+    before_init: bool,
+}
+
+impl ExportAttrs {
+    fn new(list: syn::punctuated::Punctuated<ExportAttr, syn::Token![,]>) -> syn::Result<Self> {
+        let mut attrs = ExportAttrs::default();
+
+        for item in list {
+            match item {
+                ExportAttr::Name(name) => {
+                    if attrs.name.is_some() {
+                        return Err(syn::Error::new(name.span(), "duplicate `name`"));
+                    }
+
+                    attrs.name = Some(name);
+                }
+                ExportAttr::Namespace(namespace) => {
+                    if attrs.namespace.is_some() {
+                        return Err(syn::Error::new(namespace.span(), "duplicate `namespace`"));
+                    }
+
+                    let value = namespace.value();
+
+                    if value.is_empty() || value.split('.').any(str::is_empty) {
+                        return Err(syn::Error::new(
+                            namespace.span(),
+                            "expected a dot-separated path, e.g. `\"fs.sync\"`",
+                        ));
+                    }
+
+                    attrs.namespace = Some(namespace);
+                }
+                ExportAttr::Lazy(ident) => {
+                    if attrs.init {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`lazy` cannot be combined with `init`",
+                        ));
+                    }
+
+                    if attrs.task {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`lazy` cannot be combined with `task`",
+                        ));
+                    }
+
+                    attrs.lazy = true;
+                }
+                ExportAttr::Task(ident) => {
+                    if attrs.init {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`task` cannot be combined with `init`",
+                        ));
+                    }
+
+                    if attrs.lazy {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`task` cannot be combined with `lazy`",
+                        ));
+                    }
+
+                    if attrs.json {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`task` cannot be combined with `json`",
+                        ));
+                    }
+
+                    attrs.task = true;
+                }
+                ExportAttr::Json(ident) => {
+                    if attrs.task {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`json` cannot be combined with `task`",
+                        ));
+                    }
+
+                    attrs.json = true;
+                }
+                ExportAttr::Requires(requires) => {
+                    if attrs.requires != 0 {
+                        return Err(syn::Error::new(requires.span(), "duplicate `requires`"));
+                    }
+
+                    attrs.requires = requires
+                        .value()
+                        .strip_prefix("napi-")
+                        .and_then(|version| version.parse().ok())
+                        .filter(|&version| version > 0)
+                        .ok_or_else(|| {
+                            syn::Error::new(
+                                requires.span(),
+                                "expected a N-API version, e.g. `\"napi-8\"`",
+                            )
+                        })?;
+                }
+                ExportAttr::When(when) => {
+                    if attrs.when.is_some() {
+                        return Err(syn::Error::new(when.span(), "duplicate `when`"));
+                    }
+
+                    attrs.when = Some(when);
+                }
+                ExportAttr::Stub(ident) => {
+                    attrs.stub = Some(ident);
+                }
+                ExportAttr::Init(ident) => {
+                    if attrs.lazy || attrs.task || attrs.before_init {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`init` cannot be combined with `lazy`, `task` or `before_init`",
+                        ));
+                    }
+
+                    attrs.init = true;
+                }
+                ExportAttr::BeforeInit(ident) => {
+                    if attrs.init {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`before_init` cannot be combined with `init`",
+                        ));
+                    }
+
+                    attrs.before_init = true;
+                }
+            }
+        }
+
+        // The initialization function is exported as `init` by default
+        if attrs.init && attrs.name.is_none() {
+            attrs.name = Some(syn::LitStr::new("init", proc_macro2::Span::call_site()));
+        }
+
+        if let Some(stub) = &attrs.stub {
+            if attrs.requires == 0 && attrs.when.is_none() {
+                return Err(syn::Error::new(
+                    stub.span(),
+                    "`stub` requires `requires` or `when`",
+                ));
+            }
+        }
+
+        Ok(attrs)
+    }
+}
+
+pub(super) enum ExportAttr {
+    Name(syn::LitStr),
+    Namespace(syn::LitStr),
+    Lazy(syn::Ident),
+    Task(syn::Ident),
+    Json(syn::Ident),
+    Requires(syn::LitStr),
+    When(syn::Path),
+    Stub(syn::Ident),
+    Init(syn::Ident),
+    BeforeInit(syn::Ident),
+}
+
+impl syn::parse::Parse for ExportAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+
+        if ident == "name" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(ExportAttr::Name(input.parse()?))
+        } else if ident == "namespace" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(ExportAttr::Namespace(input.parse()?))
+        } else if ident == "lazy" {
+            Ok(ExportAttr::Lazy(ident))
+        } else if ident == "task" {
+            Ok(ExportAttr::Task(ident))
+        } else if ident == "json" {
+            Ok(ExportAttr::Json(ident))
+        } else if ident == "requires" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(ExportAttr::Requires(input.parse()?))
+        } else if ident == "when" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(ExportAttr::When(input.parse()?))
+        } else if ident == "stub" {
+            Ok(ExportAttr::Stub(ident))
+        } else if ident == "init" {
+            Ok(ExportAttr::Init(ident))
+        } else if ident == "before_init" {
+            Ok(ExportAttr::BeforeInit(ident))
+        } else {
+            Err(syn::Error::new(ident.span(), "unknown export option"))
+        }
+    }
+}
+
+// How a single parameter of the exported function is supplied.
+pub(super) enum Param {
+    // `cx: &mut FunctionContext` or `cx: &mut Cx`
+    Context,
+    // `this`, extracted from the receiver of the call.
+    This,
+    // Extracted from the argument at the given index. The name of the
+    // parameter, if any, is used in error messages.
+    Argument(usize, String),
+    // `#[neon(default = expr)]`, evaluating `expr` if the argument is missing
+    // or `undefined`.
+    Default(usize, String, Box<syn::Expr>),
+    // `#[neon(rest)]`, collecting every argument from the given index onward.
+    Rest(usize, String),
+    // `#[neon(arc)]`, cloning the `Arc` out of a `JsBox<Arc<T>>` argument at
+    // the given index.
+    Arc(usize, String),
+}
+
+fn export_fn(attrs: &ExportAttrs, input: &mut syn::ItemFn) -> syn::Result<TokenStream> {
+    let sig = &mut input.sig;
+
+    if sig.generics.type_params().next().is_some() || sig.generics.const_params().next().is_some() {
+        return Err(syn::Error::new(
+            sig.generics.span(),
+            "exported functions cannot be generic over types",
+        ));
+    }
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "exported functions cannot be `async`",
+        ));
+    }
+
+    let params = parse_params(sig, false)?;
+    let name = sig.ident.clone();
+
+    if attrs.lazy && params.iter().any(|param| !matches!(param, Param::Context)) {
+        return Err(syn::Error::new(
+            sig.inputs.span(),
+            "lazy exports can only take the context as a parameter",
+        ));
+    }
+
+    // The body of a task runs on another thread, where neither the context
+    // nor any JavaScript value can be used.
+    if attrs.task {
+        if let Some(param) = params
+            .iter()
+            .zip(&sig.inputs)
+            .find(|(param, _)| matches!(param, Param::Context | Param::This))
+            .map(|(_, input)| input)
+        {
+            return Err(syn::Error::new(
+                param.span(),
+                "task exports cannot take the context or `this`",
+            ));
+        }
+    }
+
+    if attrs.json {
+        if let Some(param) = params
+            .iter()
+            .zip(&sig.inputs)
+            .find(|(param, _)| matches!(param, Param::Rest(..) | Param::Arc(..)))
+        {
+            let message = match param {
+                (Param::Rest(..), _) => "`#[neon(rest)]` cannot be used with `json`",
+                _ => "`#[neon(arc)]` cannot be used with `json`",
+            };
+
+            return Err(syn::Error::new(param.1.span(), message));
+        }
+    }
+
+    // An explicit name is used verbatim; otherwise the Rust name may be
+    // converted to camelCase by the module-wide option.
+    let export_name = match &attrs.name {
+        Some(name) => quote!(#name),
+        None => {
+            let name = name.to_string();
+
+            quote!(&::neon::macro_internal::export_name(#name))
+        }
+    };
+
+    let namespace = match &attrs.namespace {
+        Some(namespace) => quote!(#namespace),
+        None => quote!(""),
+    };
+
+    let (extract, args) = extract_params(&params, attrs.json);
+
+    // A lazy export installs the wrapper as a getter, which replaces itself
+    // with the value the first time it is called.
+    let install = |wrapper: TokenStream| {
+        if attrs.lazy {
+            quote!(::neon::macro_internal::export_lazy(
+                cx,
+                #namespace,
+                #export_name,
+                #wrapper,
+            ))
+        } else {
+            quote!(::neon::macro_internal::export_function(
+                cx,
+                #namespace,
+                #export_name,
+                #wrapper,
+            ))
+        }
+    };
+
+    // The instance is only marked as initialized if `init` succeeds, so that
+    // it can be called again after an error
+    let result = if attrs.lazy {
+        quote!(::neon::macro_internal::replace_lazy(&mut cx, #export_name, result))
+    } else if attrs.init {
+        quote!(
+            ::neon::macro_internal::finish_init(&mut cx);
+
+            Ok(result)
+        )
+    } else {
+        quote!(Ok(result))
+    };
+
+    let guard = if attrs.init {
+        quote!(::neon::macro_internal::begin_init(&mut cx)?;)
+    } else if attrs.before_init {
+        quote!()
+    } else {
+        // Called on every call, so the name is only built if it throws
+        let (name, convert) = match &attrs.name {
+            Some(name) => (name.value(), false),
+            None => (name.to_string(), true),
+        };
+
+        quote!(::neon::macro_internal::check_initialized(&mut cx, #name, #convert)?;)
+    };
+
+    // With `requires` or `when`, the conditions are checked when the module
+    // is loaded. If they do not hold, the export is left out, or with `stub`,
+    // replaced by a wrapper that throws.
+    let (export, stub) = if attrs.requires != 0 || attrs.when.is_some() {
+        let requires = attrs.requires;
+        let when = match &attrs.when {
+            Some(when) => quote!(Some(#when as fn() -> bool)),
+            None => quote!(None),
+        };
+        let unavailable = quote!(::neon::macro_internal::export_unavailable(#requires, #when));
+        let export = install(quote!(__neon_export_wrapper));
+
+        let (fallback, stub) = if attrs.stub.is_some() {
+            (
+                install(quote!(__neon_export_stub)),
+                quote!(
+                    fn __neon_export_stub(
+                        mut cx: ::neon::context::FunctionContext,
+                    ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                        let reason = #unavailable.unwrap_or_default();
+
+                        ::neon::macro_internal::throw_unavailable(&mut cx, #export_name, &reason)
+                    }
+                ),
+            )
+        } else {
+            (quote!(Ok(())), quote!())
+        };
+
+        (
+            quote!(
+                if #unavailable.is_some() {
+                    return #fallback;
+                }
+
+                #export
+            ),
+            stub,
+        )
+    } else {
+        (install(quote!(__neon_export_wrapper)), quote!())
+    };
+
+    let export = if attrs.init {
+        let name = attrs.name.as_ref();
+
+        quote!(
+            static __NEON_INIT: ::neon::macro_internal::InitRegistration =
+                ::neon::macro_internal::InitRegistration::new(#name);
+
+            ::neon::macro_internal::register_init(&__NEON_INIT);
+
+            #export
+        )
+    } else {
+        export
+    };
+
+    // A task moves the extracted arguments to the worker pool and resolves
+    // the returned promise with the converted result.
+    let body = if attrs.task {
+        quote!(::neon::macro_internal::spawn_task(&mut cx, move || #name(#(#args),*)))
+    } else if attrs.json {
+        // Only the `Ok` value of a `Result` is serialized; errors are thrown
+        // as they would be without `json`.
+        let unwrap = if returns_result(&sig.output) {
+            quote!(
+                let result = match result {
+                    Ok(result) => result,
+                    Err(err) => {
+                        return ::neon::macro_internal::into_js_value(&mut cx, Err::<(), _>(err));
+                    }
+                };
+            )
+        } else {
+            quote!()
+        };
+
+        quote!(
+            let result = #name(#(#args),*);
+            #unwrap
+            let result = ::neon::macro_internal::serde_json::to_string(&result)
+                .map_err(|err| err.to_string());
+            let result = ::neon::macro_internal::into_js_json(&mut cx, result)?;
+
+            #result
+        )
+    } else {
+        quote!(
+            let result = #name(#(#args),*);
+            let result = ::neon::macro_internal::into_js_value(&mut cx, result)?;
+
+            #result
+        )
+    };
+
+    let function = name.to_string();
+    let metadata = metadata(
+        &function,
+        attrs.name.as_ref().map(|name| name.value()),
+        attrs
+            .namespace
+            .as_ref()
+            .map(|namespace| namespace.value())
+            .unwrap_or_default(),
+        if attrs.lazy {
+            quote!(Lazy)
+        } else if attrs.task {
+            quote!(Task)
+        } else {
+            quote!(Function)
+        },
+        params_metadata(sig, &params),
+        match &sig.output {
+            syn::ReturnType::Type(_, ty) => type_metadata(ty),
+            syn::ReturnType::Default => quote!(::neon::registry::Type::new("()", "void")),
+        },
+    );
+    let kind = if attrs.lazy {
+        Kind::Lazy
+    } else if attrs.task {
+        Kind::Task
+    } else {
+        Kind::Function
+    };
+
+    let entry = Entry::new(
+        attrs
+            .namespace
+            .as_ref()
+            .map(|namespace| namespace.value())
+            .unwrap_or_default(),
+        attrs
+            .name
+            .as_ref()
+            .map(|name| name.value())
+            .unwrap_or_else(|| function.clone()),
+        attrs.name.is_some(),
+        kind,
+        sig,
+        &params,
+    );
+
+    let registration = registration(
+        export,
+        Some(metadata),
+        Some(quote!(__neon_export_wrapper)),
+        Some(entry.descriptor()),
+    );
+
+    let export = quote!(
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        const _: () = {
+            fn __neon_export_wrapper(
+                mut cx: ::neon::context::FunctionContext,
+            ) -> ::neon::result::JsResult<::neon::types::JsValue> {
+                let _span = ::neon::macro_internal::enter_export(#function);
+
+                #guard
+                #(#extract)*
+
+                #body
+            }
+
+            #stub
+
+            #registration
+        };
+    );
+
+    // Without `napi-6`, `neon` replaces the export of `init` with an error
+    let export = if attrs.init {
+        quote!(::neon::macro_internal::init_requires_napi_6! { #export })
+    } else {
+        export
+    };
+
+    Ok(quote!(
+        #input
+
+        #export
+    ))
+}
+
+// Generates statements that extract each parameter from the `FunctionContext`
+// named `cx` into a local, and the expressions that pass them to the function.
+pub(super) fn extract_params(params: &[Param], json: bool) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    // With `json`, values cross the boundary as JSON text that is converted
+    // by `serde_json`, which Neon re-exports with the `serde` feature.
+    let from_json = quote!(|json: &str| {
+        ::neon::macro_internal::serde_json::from_str(json).map_err(|err| err.to_string())
+    });
+
+    // Arguments are extracted into locals before the call so that the context
+    // is not borrowed while extraction is still using it.
+    let mut extract = Vec::with_capacity(params.len());
+    let mut args = Vec::with_capacity(params.len());
+
+    for (i, param) in params.iter().enumerate() {
+        let arg = format_ident!("__arg{}", i);
+        let value = match param {
+            Param::Context => {
+                args.push(quote!(&mut cx));
+                continue;
+            }
+            Param::This => quote!(::neon::macro_internal::extract_this(&mut cx)?),
+            Param::Argument(index, name) if json => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_json(&mut cx, #index, #name, #from_json)?)
+            }
+            Param::Default(index, name, default) if json => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_json_or(
+                    &mut cx,
+                    #index,
+                    #name,
+                    || #default,
+                    #from_json,
+                )?)
+            }
+            Param::Argument(index, name) => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_argument(&mut cx, #index, #name)?)
+            }
+            Param::Default(index, name, default) => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_argument_or(
+                    &mut cx,
+                    #index,
+                    #name,
+                    || #default,
+                )?)
+            }
+            Param::Rest(index, name) => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_rest(&mut cx, #index, #name)?)
+            }
+            Param::Arc(index, name) => {
+                let index = *index as i32;
+
+                quote!(::neon::macro_internal::extract_arc(&mut cx, #index, #name)?)
+            }
+        };
+
+        extract.push(quote!(let #arg = #value;));
+        args.push(quote!(#arg));
+    }
+
+    (extract, args)
+}
+
+// Generates the items that register `export`, the body of a function taking
+// `cx: &mut ModuleContext`, to be run when the module is loaded. Items with
+// `metadata` are listed by `neon::registered`, a `function` wrapper can be
+// called by `neon::standalone`, and items with a `loader` description are
+// wrapped by the loader of `neon_build::Loader`.
+pub(super) fn registration(
+    export: TokenStream,
+    metadata: Option<TokenStream>,
+    function: Option<TokenStream>,
+    loader: Option<String>,
+) -> TokenStream {
+    let constructor = constructor(quote!(::neon::macro_internal::register(&__NEON_REGISTRATION);));
+    let function = function.map(|function| quote!(.with_function(#function)));
+    let loader = loader.map(|loader| quote!(.with_loader(#loader)));
+    let new = match metadata {
+        Some(metadata) => quote!(
+            static __NEON_METADATA: ::neon::registry::Export = #metadata;
+
+            ::neon::macro_internal::Registration::with_metadata(__neon_export, &__NEON_METADATA)
+                #function
+                #loader
+        ),
+        None => quote!(::neon::macro_internal::Registration::new(__neon_export) #function #loader),
+    };
+
+    quote!(
+        fn __neon_export(
+            cx: &mut ::neon::context::ModuleContext,
+        ) -> ::neon::result::NeonResult<()> {
+            #export
+        }
+
+        static __NEON_REGISTRATION: ::neon::macro_internal::Registration = {
+            #new
+        };
+
+        #constructor
+    )
+}
+
+// Generates a `neon::registry::Export` describing an exported item. `kind` is
+// the name of a variant of `ExportKind`.
+pub(super) fn metadata(
+    rust_name: &str,
+    name: Option<String>,
+    namespace: String,
+    kind: TokenStream,
+    params: Vec<TokenStream>,
+    output: TokenStream,
+) -> TokenStream {
+    let name = match name {
+        Some(name) => quote!(Some(#name)),
+        None => quote!(None),
+    };
+
+    quote!(::neon::registry::Export::new(
+        #rust_name,
+        #name,
+        #namespace,
+        ::neon::registry::ExportKind::#kind,
+        &[#(#params),*],
+        #output,
+    ))
+}
+
+// Generates a `neon::registry::Param` for each parameter taken from the
+// arguments of a call
+fn params_metadata(sig: &syn::Signature, params: &[Param]) -> Vec<TokenStream> {
+    params
+        .iter()
+        .zip(&sig.inputs)
+        .filter_map(|(param, input)| {
+            let ty = match input {
+                syn::FnArg::Typed(input) => &*input.ty,
+                syn::FnArg::Receiver(_) => return None,
+            };
+
+            let (name, optional, rest) = match param {
+                Param::Context | Param::This => return None,
+                Param::Argument(_, name) => (name, is_option(ty), false),
+                Param::Default(_, name, _) => (name, true, false),
+                Param::Rest(_, name) => (name, false, true),
+                Param::Arc(_, name) => (name, false, false),
+            };
+
+            let ty = type_metadata(ty);
+
+            Some(quote!(::neon::registry::Param::new(#name, #ty, #optional, #rest)))
+        })
+        .collect()
+}
+
+// Generates a `neon::registry::Type` for `ty`
+pub(super) fn type_metadata(ty: &syn::Type) -> TokenStream {
+    let rust = rust_type(ty);
+    let typescript = loader::ts_type(ty);
+
+    quote!(::neon::registry::Type::new(#rust, #typescript))
+}
+
+// The source of a type without the spaces `quote` puts between tokens, e.g.,
+// `Option<Vec<f64>>` instead of `Option < Vec < f64 > >`
+pub(super) fn rust_type(ty: &syn::Type) -> String {
+    let tokens = quote!(#ty).to_string();
+    let chars = tokens.chars().collect::<Vec<_>>();
+    let word = |c: char| c.is_alphanumeric() || c == '_' || c == '\'';
+    let mut result = String::with_capacity(tokens.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c != ' ' {
+            result.push(c);
+            continue;
+        }
+
+        let prev = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1).copied();
+
+        // Keep the spaces that separate words, e.g., `dyn Trait`, and follow
+        // commas
+        match (prev, next) {
+            (Some(prev), Some(next)) if word(prev) && word(next) => result.push(' '),
+            (Some(','), _) => result.push(' '),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+// Checks syntactically for `Option<T>`
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+// Generates a global constructor that runs `register` when the library is
+// loaded. Global constructors are not run on `wasm32`, where the item would
+// silently never be registered, so it is a compile error instead.
+pub(super) fn constructor(register: TokenStream) -> TokenStream {
+    quote!(
+        #[cfg(target_arch = "wasm32")]
+        ::core::compile_error!(
+            "items registered when the module is loaded, e.g., with `#[neon::export]`, \
+             are not supported on `wasm32`; export them from `#[neon::main]` instead"
+        );
+
+        // Mark this function as a global constructor (like C++).
+        #[cfg_attr(target_os = "linux", link_section = ".init_array")]
+        #[cfg_attr(target_os = "android", link_section = ".init_array")]
+        #[cfg_attr(target_os = "macos", link_section = "__DATA,__mod_init_func")]
+        #[cfg_attr(target_os = "ios", link_section = "__DATA,__mod_init_func")]
+        #[cfg_attr(target_os = "windows", link_section = ".CRT$XCU")]
+        #[used]
+        static __NEON_REGISTER: extern "C" fn() = {
+            extern "C" fn __neon_register() {
+                #register
+            }
+
+            __neon_register
+        };
+    )
+}
+
+// Classifies each parameter and strips the `#[neon(...)]` attributes, which
+// are not valid once the function is emitted again. The receiver of a
+// `method` is not a parameter and is skipped.
+pub(super) fn parse_params(sig: &mut syn::Signature, method: bool) -> syn::Result<Vec<Param>> {
+    let skip = match sig.inputs.first() {
+        Some(syn::FnArg::Receiver(_)) if method => 1,
+        _ => 0,
+    };
+    let len = sig.inputs.len() - skip;
+    let mut params = Vec::with_capacity(len);
+    let mut index = 0;
+
+    for (i, input) in sig.inputs.iter_mut().skip(skip).enumerate() {
+        let input = match input {
+            syn::FnArg::Typed(input) => input,
+            syn::FnArg::Receiver(receiver) => {
+                return Err(syn::Error::new(
+                    receiver.span(),
+                    "exported functions cannot take `self`",
+                ))
+            }
+        };
+
+        let attrs = ParamAttrs::take(&mut input.attrs)?;
+
+        if i == 0 && is_context(&input.ty) {
+            if attrs.rest || attrs.this || attrs.arc || attrs.default.is_some() {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`#[neon(...)]` cannot be used on the context",
+                ));
+            }
+
+            params.push(Param::Context);
+            continue;
+        }
+
+        if attrs.this || is_this(&input.pat) {
+            let first = params.iter().all(|param| matches!(param, Param::Context));
+
+            if !first || attrs.rest || attrs.arc || attrs.default.is_some() {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`this` must be the first parameter after the context",
+                ));
+            }
+
+            params.push(Param::This);
+            continue;
+        }
+
+        if attrs.arc {
+            if attrs.rest || attrs.default.is_some() {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`#[neon(arc)]` cannot be used with `rest` or `default`",
+                ));
+            }
+
+            params.push(Param::Arc(index, param_name(&input.pat)));
+        } else if let Some(default) = attrs.default {
+            if attrs.rest {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`#[neon(rest)]` cannot have a default",
+                ));
+            }
+
+            params.push(Param::Default(index, param_name(&input.pat), default));
+        } else if attrs.rest {
+            if i + 1 != len {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "`#[neon(rest)]` must be used on the last parameter",
+                ));
+            }
+
+            params.push(Param::Rest(index, param_name(&input.pat)));
+        } else {
+            params.push(Param::Argument(index, param_name(&input.pat)));
+        }
+
+        index += 1;
+    }
+
+    Ok(params)
+}
+
+// The name of a parameter for error messages, without a leading underscore,
+// or an empty string if it is a pattern
+fn param_name(pat: &syn::Pat) -> String {
+    match pat {
+        syn::Pat::Ident(pat) => pat
+            .ident
+            .unraw()
+            .to_string()
+            .trim_start_matches('_')
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+// Attributes accepted on the parameters of an exported function.
+#[derive(Default)]
+struct ParamAttrs {
+    rest: bool,
+    this: bool,
+    arc: bool,
+    default: Option<Box<syn::Expr>>,
+}
+
+impl ParamAttrs {
+    fn take(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Self> {
+        let mut result = ParamAttrs::default();
+        let mut err = Ok(());
+
+        attrs.retain(|attr| {
+            if !attr.path.is_ident("neon") {
+                return true;
+            }
+
+            if let Err(e) = result.parse(attr) {
+                err = Err(e);
+            }
+
+            false
+        });
+
+        err.map(|_| result)
+    }
+
+    fn parse(&mut self, attr: &syn::Attribute) -> syn::Result<()> {
+        let list = attr.parse_args_with(
+            syn::punctuated::Punctuated::<ParamAttr, syn::Token![,]>::parse_terminated,
+        )?;
+
+        for item in list {
+            match item {
+                ParamAttr::Rest => self.rest = true,
+                ParamAttr::This => self.this = true,
+                ParamAttr::Arc => self.arc = true,
+                ParamAttr::Default(expr) => self.default = Some(expr),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+enum ParamAttr {
+    Rest,
+    This,
+    Arc,
+    Default(Box<syn::Expr>),
+}
+
+impl syn::parse::Parse for ParamAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+
+        if ident == "rest" {
+            Ok(ParamAttr::Rest)
+        } else if ident == "this" {
+            Ok(ParamAttr::This)
+        } else if ident == "arc" {
+            Ok(ParamAttr::Arc)
+        } else if ident == "default" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(ParamAttr::Default(input.parse()?))
+        } else {
+            Err(syn::Error::new(ident.span(), "unknown parameter attribute"))
+        }
+    }
+}
+
+// Checks for `&mut FunctionContext` or `&mut Cx`, the contexts that can be
+// passed to an exported function.
+pub(super) fn is_context(ty: &syn::Type) -> bool {
+    let ty = match ty {
+        syn::Type::Reference(ty) if ty.mutability.is_some() => &*ty.elem,
+        _ => return false,
+    };
+
+    match ty {
+        syn::Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "FunctionContext" || segment.ident == "Cx")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+// Checks syntactically for a `Result` return type, including aliases such as
+// `io::Result`.
+pub(super) fn returns_result(output: &syn::ReturnType) -> bool {
+    match output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(ty) => ty
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "Result")
+                .unwrap_or(false),
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+// A parameter named `this` receives the `this` binding of the call.
+fn is_this(pat: &syn::Pat) -> bool {
+    match pat {
+        syn::Pat::Ident(pat) => pat.ident == "this",
+        _ => false,
+    }
+}
@@ -0,0 +1,245 @@
+// Descriptions of exports for the JavaScript loader and TypeScript
+// declarations generated by `neon_build::Loader`.
+//
+// Each `#[neon::export]` function embeds a description of itself in the
+// compiled library, as a string referenced by its registration so that the
+// linker keeps it. `neon_build::Loader` finds the descriptions by their
+// prefix after the library is built; expanding the macros has no side
+// effects.
+
+use std::fmt::Write as _;
+
+use super::export::Param;
+
+// Starts the description of an export, which ends with a NUL
+const EXPORT_PREFIX: &str = "\0neon-export:1\n";
+
+// Embedded by `#[neon::main(camel_case)]`
+pub(super) const CAMEL_CASE: &str = "\0neon-main:camel_case\0";
+
+// Description of an export recorded for the loader.
+pub(super) struct Entry {
+    namespace: String,
+    name: String,
+    // An explicit `name` is not converted to camelCase
+    explicit: bool,
+    kind: Kind,
+    params: Vec<LoaderParam>,
+    output: String,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Kind {
+    Function,
+    Lazy,
+    Task,
+}
+
+pub(super) struct LoaderParam {
+    name: String,
+    ty: String,
+    optional: bool,
+    rest: bool,
+}
+
+impl Entry {
+    pub(super) fn new(
+        namespace: String,
+        name: String,
+        explicit: bool,
+        kind: Kind,
+        sig: &syn::Signature,
+        params: &[Param],
+    ) -> Self {
+        let params = params
+            .iter()
+            .zip(&sig.inputs)
+            .filter_map(|(param, input)| {
+                let ty = match input {
+                    syn::FnArg::Typed(input) => &*input.ty,
+                    syn::FnArg::Receiver(_) => return None,
+                };
+
+                let (name, ty, optional, rest) = match param {
+                    Param::Context | Param::This => return None,
+                    Param::Argument(_, name) => match option_inner(ty) {
+                        Some(inner) => (name, ts_type(inner), true, false),
+                        None => (name, ts_type(ty), false, false),
+                    },
+                    Param::Default(_, name, _) => (name, ts_type(ty), true, false),
+                    Param::Arc(_, name) => (name, ts_type(ty), false, false),
+                    Param::Rest(_, name) => {
+                        let elem = generic_args(ty, "Vec").and_then(|args| args.first().copied());
+                        let ty = elem
+                            .map(ts_element)
+                            .unwrap_or_else(|| "unknown".to_string());
+
+                        (name, ty, false, true)
+                    }
+                };
+
+                Some(LoaderParam {
+                    name: name.clone(),
+                    ty,
+                    optional,
+                    rest,
+                })
+            })
+            .collect();
+
+        let output = match &sig.output {
+            syn::ReturnType::Type(_, ty) => ts_type(ty),
+            syn::ReturnType::Default => "void".to_string(),
+        };
+
+        Self {
+            namespace,
+            name,
+            explicit,
+            kind,
+            params,
+            output,
+        }
+    }
+
+    // The description embedded in the library, read by `neon_build::Loader`
+    pub(super) fn descriptor(&self) -> String {
+        let kind = match self.kind {
+            Kind::Function => "function",
+            Kind::Lazy => "lazy",
+            Kind::Task => "task",
+        };
+
+        let mut text = format!(
+            "{}{}\t{}\t{}\t{}\t{}\n",
+            EXPORT_PREFIX,
+            clean(&self.namespace),
+            clean(&self.name),
+            self.explicit,
+            kind,
+            self.output,
+        );
+
+        for param in &self.params {
+            let _ = writeln!(
+                text,
+                "{}\t{}\t{}\t{}",
+                param.name, param.ty, param.optional, param.rest
+            );
+        }
+
+        text.push('\0');
+        text
+    }
+}
+
+// Tabs, newlines and NULs separate the fields and descriptions
+fn clean(s: &str) -> String {
+    s.replace(['\t', '\n', '\r', '\0'], " ")
+}
+
+// The inner type of `Option<T>`
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    generic_args(ty, "Option").and_then(|args| args.first().copied())
+}
+
+// The type arguments of a path type whose last segment is `name`
+fn generic_args<'a>(ty: &'a syn::Type, name: &str) -> Option<Vec<&'a syn::Type>> {
+    let segment = match ty {
+        syn::Type::Path(ty) => ty.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => Some(
+            args.args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => Some(Vec::new()),
+    }
+}
+
+// The TypeScript type of a Rust type, by its name. Types that are not
+// recognized are `unknown`.
+pub(super) fn ts_type(ty: &syn::Type) -> String {
+    let ty = match ty {
+        syn::Type::Reference(ty) => &*ty.elem,
+        syn::Type::Paren(ty) => &*ty.elem,
+        syn::Type::Tuple(ty) if ty.elems.is_empty() => return "void".to_string(),
+        ty => ty,
+    };
+
+    let segment = match ty {
+        syn::Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment,
+            None => return "unknown".to_string(),
+        },
+        syn::Type::Slice(slice) => return format!("{}[]", ts_element(&slice.elem)),
+        _ => return "unknown".to_string(),
+    };
+
+    let name = segment.ident.to_string();
+    let args = generic_args(ty, &name).unwrap_or_default();
+    let first = || {
+        args.first()
+            .map(|ty| ts_type(ty))
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    match name.as_str() {
+        "f32" | "f64" | "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64"
+        | "usize" | "JsNumber" => "number",
+        "bool" | "JsBoolean" => "boolean",
+        "str" | "String" | "JsString" => "string",
+        "JsUndefined" => "undefined",
+        "JsNull" => "null",
+        "JsArray" => "unknown[]",
+        "JsObject" => "object",
+        "JsFunction" => "(...args: unknown[]) => unknown",
+        "JsBuffer" => "Buffer",
+        "JsArrayBuffer" => "ArrayBuffer",
+        "JsDate" => "Date",
+        "JsError" => "Error",
+        "JsPromise" => "Promise<unknown>",
+        "Option" => return format!("{} | undefined", first()),
+        "Progress" => return format!("(progress: {}) => void", first()),
+        "Vec" => {
+            return args
+                .first()
+                .map(|ty| format!("{}[]", ts_element(ty)))
+                .unwrap_or_else(|| "unknown[]".to_string())
+        }
+        "HashMap" | "BTreeMap" => {
+            return format!(
+                "Record<string, {}>",
+                args.get(1)
+                    .map(|ty| ts_type(ty))
+                    .unwrap_or_else(|| "unknown".to_string())
+            )
+        }
+        // Wrappers whose first type argument is the JavaScript type
+        "Result" | "NeonResult" | "JsResult" | "Handle" | "Box" | "Arc" | "Rc" => return first(),
+        _ => "unknown",
+    }
+    .to_string()
+}
+
+// An array element type, parenthesized if it is a union or function
+fn ts_element(ty: &syn::Type) -> String {
+    let ty = ts_type(ty);
+
+    if ty.contains(' ') {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
@@ -0,0 +1,147 @@
+pub(crate) use self::class::class;
+pub(crate) use self::export::export;
+pub(crate) use self::test::test;
+
+mod class;
+mod export;
+mod loader;
+mod test;
+
+pub(crate) fn main(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let options = syn::parse_macro_input!(attr with parse_main_options);
+    let input = syn::parse_macro_input!(item as syn_mid::ItemFn);
+
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let sig = &input.sig;
+    let block = &input.block;
+    let name = &sig.ident;
+
+    // A function with `order` only adds itself to the functions run when the
+    // module is loaded; the entry point is defined by the one without it.
+    if let Some(order) = &options.order {
+        let path = name.to_string();
+        let constructor = export::constructor(quote::quote!(
+            ::neon::macro_internal::register_main(&__NEON_MAIN);
+        ));
+
+        return quote::quote!(
+            #input
+
+            #[doc(hidden)]
+            const _: () = {
+                static __NEON_MAIN: ::neon::macro_internal::MainRegistration =
+                    ::neon::macro_internal::MainRegistration::new(
+                        #name,
+                        #order,
+                        concat!(module_path!(), "::", #path),
+                    );
+
+                #constructor
+            };
+        )
+        .into();
+    }
+
+    // The marker tells `neon_build::Loader` that names are converted
+    let camel_case = if options.camel_case {
+        let marker = loader::CAMEL_CASE;
+
+        quote::quote!(::neon::macro_internal::set_export_camel_case(#marker);)
+    } else {
+        quote::quote!()
+    };
+
+    quote::quote!(
+        #(#attrs) *
+        #vis #sig {
+            #[no_mangle]
+            unsafe extern "C" fn napi_register_module_v1(
+                env: ::neon::macro_internal::runtime::raw::Env,
+                m: ::neon::macro_internal::runtime::raw::Local,
+            ) -> ::neon::macro_internal::runtime::raw::Local {
+                #camel_case
+
+                ::neon::macro_internal::initialize_module(env, m, #name);
+
+                m
+            }
+
+            // Entry point used by emnapi when the module is built for WebAssembly
+            #[cfg(target_arch = "wasm32")]
+            #[no_mangle]
+            unsafe extern "C" fn napi_register_wasm_v1(
+                env: ::neon::macro_internal::runtime::raw::Env,
+                m: ::neon::macro_internal::runtime::raw::Local,
+            ) -> ::neon::macro_internal::runtime::raw::Local {
+                napi_register_module_v1(env, m)
+            }
+
+            #block
+        }
+    )
+    .into()
+}
+
+// Options accepted by `#[neon::main(...)]`.
+#[derive(Default)]
+struct MainOptions {
+    camel_case: bool,
+    order: Option<Box<syn::Expr>>,
+}
+
+enum MainOption {
+    CamelCase(syn::Ident),
+    Order(syn::Ident, Box<syn::Expr>),
+}
+
+impl syn::parse::Parse for MainOption {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+
+        if ident == "camel_case" {
+            Ok(MainOption::CamelCase(ident))
+        } else if ident == "order" {
+            input.parse::<syn::Token![=]>()?;
+
+            Ok(MainOption::Order(ident, input.parse()?))
+        } else {
+            Err(syn::Error::new(ident.span(), "unknown module option"))
+        }
+    }
+}
+
+fn parse_main_options(input: syn::parse::ParseStream) -> syn::Result<MainOptions> {
+    let mut options = MainOptions::default();
+    let list = syn::punctuated::Punctuated::<MainOption, syn::Token![,]>::parse_terminated(input)?;
+    let mut camel_case = None;
+
+    for option in list {
+        match option {
+            MainOption::CamelCase(ident) => {
+                options.camel_case = true;
+                camel_case = Some(ident);
+            }
+            MainOption::Order(ident, order) => {
+                if options.order.is_some() {
+                    return Err(syn::Error::new(ident.span(), "duplicate `order`"));
+                }
+
+                options.order = Some(order);
+            }
+        }
+    }
+
+    // `camel_case` applies to the whole module, so it belongs on the entry point
+    if let (Some(ident), Some(_)) = (camel_case, &options.order) {
+        return Err(syn::Error::new(
+            ident.span(),
+            "`camel_case` cannot be combined with `order`",
+        ));
+    }
+
+    Ok(options)
+}
@@ -0,0 +1,77 @@
+use quote::quote;
+use syn::spanned::Spanned;
+
+pub(crate) fn test(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+
+    if !attr.is_empty() {
+        return syn::Error::new(attr.span(), "`#[neon::test]` does not take any options")
+            .to_compile_error()
+            .into();
+    }
+
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    match test_fn(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn test_fn(input: &syn::ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &input.sig;
+    let name = &sig.ident;
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "`#[neon::test]` cannot be used on an `async` function",
+        ));
+    }
+
+    // A test may take the context of the call to `__neon_run_tests`
+    let call = match sig.inputs.len() {
+        0 => quote!(#name()),
+        1 => quote!(#name(cx)),
+        _ => {
+            return Err(syn::Error::new(
+                sig.inputs.span(),
+                "a test takes no arguments or `cx: &mut FunctionContext`",
+            ))
+        }
+    };
+
+    let test_name = name.to_string();
+    let registration = super::export::registration(
+        quote!(::neon::macro_internal::export_test(
+            cx,
+            #test_name,
+            __neon_test
+        )),
+        None,
+        None,
+        None,
+    );
+
+    // Tests are left out of release builds, like `#[cfg(test)]` code
+    Ok(quote!(
+        #[cfg(debug_assertions)]
+        #input
+
+        #[cfg(debug_assertions)]
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        const _: () = {
+            fn __neon_test(
+                cx: &mut ::neon::context::FunctionContext,
+            ) -> ::neon::result::NeonResult<()> {
+                ::neon::macro_internal::TestOutput::into_result(#call)
+            }
+
+            #registration
+        };
+    ))
+}
@@ -24,3 +24,40 @@ pub unsafe fn len(env: Env, array: Local) -> u32 {
     );
     len
 }
+
+/// Reads the elements of `array` from `start` to `end`, exclusive, into `out`.
+/// Returns `false` if reading an element throws, leaving the elements read so
+/// far in `out`.
+pub unsafe fn get_range(
+    env: Env,
+    array: Local,
+    start: u32,
+    end: u32,
+    out: &mut Vec<Local>,
+) -> bool {
+    out.reserve(end.saturating_sub(start) as usize);
+
+    for index in start..end {
+        let mut element = std::mem::MaybeUninit::uninit();
+
+        if napi::get_element(env, array, index, element.as_mut_ptr()) != napi::Status::Ok {
+            return false;
+        }
+
+        out.push(element.assume_init());
+    }
+
+    true
+}
+
+/// Writes `values` to the elements of `array`, starting at `start`. Returns
+/// `false` if writing an element throws.
+pub unsafe fn set_range(env: Env, array: Local, start: u32, values: &[Local]) -> bool {
+    for (index, value) in (start..).zip(values) {
+        if napi::set_element(env, array, index, *value) != napi::Status::Ok {
+            return false;
+        }
+    }
+
+    true
+}
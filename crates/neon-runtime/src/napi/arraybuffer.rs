@@ -2,6 +2,7 @@ use crate::raw::{Env, Local};
 use std::mem::MaybeUninit;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU8, Ordering};
 
 use crate::napi::bindings as napi;
 
@@ -26,25 +27,142 @@ where
 {
     // Safety: Boxing could move the data; must box before grabbing a raw pointer
     let mut data = Box::new(data);
-    let buf = data.as_mut().as_mut();
-    let length = buf.len();
+    let length = data.as_mut().as_mut().len();
+    let bytes = data.as_mut().as_mut().as_mut_ptr();
+    let hint = Box::into_raw(data);
+
+    if !external_maybe_supported() {
+        let result = new_copy(env, bytes, length);
+
+        drop(Box::from_raw(hint));
+
+        return result;
+    }
+
+    let data = bytes as *mut c_void;
+    let mut result = MaybeUninit::uninit();
+    let status = napi::create_external_arraybuffer(
+        env,
+        data,
+        length,
+        Some(drop_external::<T>),
+        hint as *mut _,
+        result.as_mut_ptr(),
+    );
+
+    record_external_support(status);
+
+    // Hosts with the V8 memory cage enabled (e.g., Electron >= 21) do not allow
+    // external buffers. Ownership is not transferred, so the data is copied instead.
+    if status == napi::Status::NoExternalBuffersAllowed {
+        let result = new_copy(env, bytes, length);
+
+        drop(Box::from_raw(hint));
+
+        return result;
+    }
+
+    assert_eq!(status, napi::Status::Ok);
+
+    #[cfg(feature = "external-memory")]
+    crate::napi::mem::adjust_external_memory(env, length as i64);
+
+    result.assume_init()
+}
+
+// Whether the host allows external buffers, which is the same for every
+// environment in the process
+static EXTERNAL_SUPPORT: AtomicU8 = AtomicU8::new(EXTERNAL_UNKNOWN);
+
+const EXTERNAL_UNKNOWN: u8 = 0;
+const EXTERNAL_SUPPORTED: u8 = 1;
+const EXTERNAL_UNSUPPORTED: u8 = 2;
+
+/// Returns `true` if the host allows external buffers and array buffers, which
+/// hosts with the V8 memory cage enabled (e.g., Electron >= 21) do not.
+pub unsafe fn supports_external(env: Env) -> bool {
+    match EXTERNAL_SUPPORT.load(Ordering::Relaxed) {
+        EXTERNAL_SUPPORTED => return true,
+        EXTERNAL_UNSUPPORTED => return false,
+        _ => {}
+    }
+
+    // Probe with an empty array buffer, which has nothing to free
+    static PROBE: u8 = 0;
+
+    let mut result = MaybeUninit::uninit();
+    let status = napi::create_external_arraybuffer(
+        env,
+        &PROBE as *const u8 as *mut c_void,
+        0,
+        None,
+        null_mut(),
+        result.as_mut_ptr(),
+    );
+
+    record_external_support(status);
+
+    status == napi::Status::Ok
+}
+
+// Returns `false` if the host is known not to allow external buffers, so
+// constructors can copy without first attempting to create one
+pub(crate) fn external_maybe_supported() -> bool {
+    EXTERNAL_SUPPORT.load(Ordering::Relaxed) != EXTERNAL_UNSUPPORTED
+}
+
+pub(crate) fn record_external_support(status: napi::Status) {
+    let support = match status {
+        napi::Status::Ok => EXTERNAL_SUPPORTED,
+        napi::Status::NoExternalBuffersAllowed => EXTERNAL_UNSUPPORTED,
+        _ => return,
+    };
+
+    EXTERNAL_SUPPORT.store(support, Ordering::Relaxed);
+}
+
+unsafe fn new_copy(env: Env, bytes: *const u8, length: usize) -> Local {
+    let mut out = null_mut();
     let mut result = MaybeUninit::uninit();
 
     assert_eq!(
-        napi::create_external_arraybuffer(
-            env,
-            buf.as_mut_ptr() as *mut _,
-            length,
-            Some(drop_external::<T>),
-            Box::into_raw(data) as *mut _,
-            result.as_mut_ptr(),
-        ),
+        napi::create_arraybuffer(env, length, &mut out as *mut _, result.as_mut_ptr()),
         napi::Status::Ok,
     );
 
+    if length > 0 {
+        std::ptr::copy_nonoverlapping(bytes, out as *mut u8, length);
+    }
+
     result.assume_init()
 }
 
-unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void) {
-    Box::<T>::from_raw(hint as *mut _);
+unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void)
+where
+    T: AsMut<[u8]>,
+{
+    #[allow(unused_mut)]
+    let mut data = Box::<T>::from_raw(hint as *mut _);
+
+    #[cfg(feature = "external-memory")]
+    crate::napi::mem::adjust_external_memory(_env, -(data.as_mut().as_mut().len() as i64));
+
+    drop(data);
+}
+
+#[cfg(feature = "napi-7")]
+pub unsafe fn detach(env: Env, buf: Local) -> bool {
+    napi::detach_arraybuffer(env, buf) == napi::Status::Ok
+}
+
+#[cfg(feature = "napi-7")]
+pub unsafe fn is_detached(env: Env, buf: Local) -> bool {
+    let mut result = false;
+
+    assert_eq!(
+        napi::is_detached_arraybuffer(env, buf, &mut result as *mut _),
+        napi::Status::Ok,
+    );
+
+    result
 }
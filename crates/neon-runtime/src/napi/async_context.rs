@@ -0,0 +1,101 @@
+//! Rust wrappers for Node-API async context tracking
+//!
+//! Callbacks that are not called directly by JavaScript must run in an async
+//! context so that `async_hooks` can associate them with the resource that
+//! scheduled them.
+//!
+//! See: [Custom asynchronous operations](https://nodejs.org/api/n-api.html#n_api_custom_asynchronous_operations)
+
+use std::mem::MaybeUninit;
+
+use crate::napi::bindings as napi;
+use crate::raw::{Env, Local};
+
+/// Create an async context for `resource`, reported to `async_hooks` with the
+/// type `name`
+///
+/// Hosts without `async_hooks` may fail to create a context. The context is
+/// null in that case and the other functions in this module accept it.
+///
+/// # Safety
+/// * `env` must be a valid `napi_env` for the current thread
+/// * `resource` must be a valid object
+pub unsafe fn init(env: Env, resource: Local, name: &str) -> napi::AsyncContext {
+    let mut resource_name = MaybeUninit::uninit();
+    let mut result = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::create_string_utf8(
+            env,
+            name.as_ptr() as *const _,
+            name.len(),
+            resource_name.as_mut_ptr(),
+        ),
+        napi::Status::Ok,
+    );
+
+    let status = napi::async_init(
+        env,
+        resource,
+        resource_name.assume_init(),
+        result.as_mut_ptr(),
+    );
+
+    if status != napi::Status::Ok {
+        return std::ptr::null_mut();
+    }
+
+    result.assume_init()
+}
+
+/// Destroy an async context created with [`init`]
+///
+/// # Safety
+/// * `env` must be a valid `napi_env` for the current thread
+/// * `context` must not be used after it is destroyed
+pub unsafe fn destroy(env: Env, context: napi::AsyncContext) {
+    if context.is_null() {
+        return;
+    }
+
+    assert_eq!(napi::async_destroy(env, context), napi::Status::Ok);
+}
+
+#[cfg(feature = "napi-3")]
+/// Execute `f` inside a callback scope for `context`. When the outermost
+/// scope is closed, Node processes the `process.nextTick` and microtask queues.
+///
+/// # Safety
+/// * `env` must be a valid `napi_env` for the current thread
+/// * `resource` must be the object `context` was created with
+pub unsafe fn with_callback_scope<T, F: FnOnce() -> T>(
+    env: Env,
+    resource: Local,
+    context: napi::AsyncContext,
+    f: F,
+) -> T {
+    // Closes the scope even if `f` panics
+    struct Scope(Env, napi::CallbackScope);
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            unsafe {
+                napi::close_callback_scope(self.0, self.1);
+            }
+        }
+    }
+
+    let mut scope = MaybeUninit::uninit();
+
+    // Without an async context or callback scopes, `f` runs like any other
+    // call from native code
+    if context.is_null()
+        || napi::open_callback_scope(env, resource, context, scope.as_mut_ptr()) != napi::Status::Ok
+    {
+        return f();
+    }
+
+    let _scope = Scope(env, scope.assume_init());
+
+    f()
+}
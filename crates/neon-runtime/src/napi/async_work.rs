@@ -0,0 +1,249 @@
+//! Rust wrappers for Node-API simple asynchronous operations
+//!
+//! Async work is scheduled on the libuv threadpool. The `execute` step runs on
+//! a worker thread and the `complete` step runs on the JavaScript main thread.
+//!
+//! See: [Async Work in Node-API](https://nodejs.org/api/n-api.html#n_api_simple_asynchronous_operations)
+
+use std::ffi::c_void;
+use std::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::napi::bindings as napi;
+use crate::raw::Env;
+
+type Execute<I, O> = fn(input: I) -> O;
+type Complete<O, D> = fn(env: Env, output: thread::Result<O>, data: D);
+type Cancelled<D> = fn(env: Env, data: D);
+
+/// Handle for cancelling work scheduled with [`schedule_cancellable`]
+#[derive(Clone)]
+pub struct WorkHandle(Arc<AtomicPtr<napi::AsyncWork__>>);
+
+impl WorkHandle {
+    /// Cancel the work if it has not started executing. Returns `true` if
+    /// the work was cancelled, in which case the `cancelled` callback will be
+    /// called instead of `complete`.
+    ///
+    /// # Safety
+    /// * `env` must be a valid `napi_env` for the thread that scheduled the work
+    pub unsafe fn cancel(&self, env: Env) -> bool {
+        // The pointer is cleared before the work is deleted, on this thread
+        let work = self.0.load(Ordering::Acquire);
+
+        !work.is_null() && napi::cancel_async_work(env, work) == napi::Status::Ok
+    }
+}
+
+/// Schedule work to execute on the libuv threadpool
+///
+/// # Safety
+/// * `env` must be a valid `napi_env` for the current thread
+pub unsafe fn schedule<I, O, D>(
+    env: Env,
+    input: I,
+    data: D,
+    execute: Execute<I, O>,
+    complete: Complete<O, D>,
+) where
+    I: Send + 'static,
+    O: Send + 'static,
+    D: 'static,
+{
+    schedule_work(env, input, data, execute, complete, None);
+}
+
+/// Schedule work to execute on the libuv threadpool, returning a handle that
+/// can cancel it before it starts. If cancelled, `cancelled` is called with
+/// `data` on the JavaScript main thread instead of `complete`.
+///
+/// # Safety
+/// * `env` must be a valid `napi_env` for the current thread
+pub unsafe fn schedule_cancellable<I, O, D>(
+    env: Env,
+    input: I,
+    data: D,
+    execute: Execute<I, O>,
+    complete: Complete<O, D>,
+    cancelled: Cancelled<D>,
+) -> WorkHandle
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    D: 'static,
+{
+    let handle = WorkHandle(Arc::new(AtomicPtr::new(ptr::null_mut())));
+    let work = schedule_work(
+        env,
+        input,
+        data,
+        execute,
+        complete,
+        Some((cancelled, handle.clone())),
+    );
+
+    handle.0.store(work, Ordering::Release);
+    handle
+}
+
+unsafe fn schedule_work<I, O, D>(
+    env: Env,
+    input: I,
+    data: D,
+    execute: Execute<I, O>,
+    complete: Complete<O, D>,
+    cancelled: Option<(Cancelled<D>, WorkHandle)>,
+) -> napi::AsyncWork
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    D: 'static,
+{
+    let mut data = Box::new(Data {
+        state: State::Input(input),
+        execute,
+        complete,
+        cancelled,
+        data,
+        // Work is initialized as a null pointer, but set by `create_async_work`.
+        // `data` must not be used until this value has been set.
+        work: ptr::null_mut(),
+    });
+
+    // Store a pointer to `work` before ownership is transferred to `Box::into_raw`
+    let work = &mut data.work as *mut _;
+    let name = "neon_async_work";
+    let mut resource_name = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::create_string_utf8(
+            env,
+            name.as_ptr() as *const _,
+            name.len(),
+            resource_name.as_mut_ptr(),
+        ),
+        napi::Status::Ok,
+    );
+
+    // Create the `async_work`
+    assert_eq!(
+        napi::create_async_work(
+            env,
+            ptr::null_mut(),
+            resource_name.assume_init(),
+            Some(call_execute::<I, O, D>),
+            Some(call_complete::<I, O, D>),
+            Box::into_raw(data).cast(),
+            work,
+        ),
+        napi::Status::Ok,
+    );
+
+    // Queue the work
+    match napi::queue_async_work(env, *work) {
+        napi::Status::Ok => {}
+        status => {
+            // If queueing failed, delete the work to prevent a leak
+            napi::delete_async_work(env, *work);
+            assert_eq!(status, napi::Status::Ok);
+        }
+    }
+
+    *work
+}
+
+/// A pointer to data is passed to the `execute` and `complete` callbacks
+struct Data<I, O, D> {
+    state: State<I, O>,
+    execute: Execute<I, O>,
+    complete: Complete<O, D>,
+    cancelled: Option<(Cancelled<D>, WorkHandle)>,
+    data: D,
+    work: napi::AsyncWork,
+}
+
+/// State of the task that is transitioned by `execute` and `complete`
+enum State<I, O> {
+    /// Initial data input passed to `execute`
+    Input(I),
+    /// Transient state while `execute` is running
+    Executing,
+    /// Return data of `execute` passed to `complete`
+    Output(thread::Result<O>),
+}
+
+impl<I, O> State<I, O> {
+    /// Return the input if `State::Input`, replacing with `State::Executing`
+    fn take_execute_input(&mut self) -> Option<I> {
+        match std::mem::replace(self, Self::Executing) {
+            Self::Input(input) => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Return the output if `State::Output`, replacing with `State::Executing`
+    fn into_output(self) -> Option<thread::Result<O>> {
+        match self {
+            Self::Output(output) => Some(output),
+            _ => None,
+        }
+    }
+}
+
+/// Callback executed on the libuv threadpool
+///
+/// # Safety
+/// * `Env` should not be used because it could attempt to call JavaScript
+/// * `data` is expected to be a pointer to `Data<I, O, D>`
+unsafe extern "C" fn call_execute<I, O, D>(_: Env, data: *mut c_void) {
+    let data = &mut *data.cast::<Data<I, O, D>>();
+
+    // This is unwind safe because unwinding will resume on the other side
+    let output = catch_unwind(AssertUnwindSafe(|| {
+        // `unwrap` is ok because `call_execute` should be called exactly once
+        // after initialization
+        let input = data.state.take_execute_input().unwrap();
+
+        (data.execute)(input)
+    }));
+
+    data.state = State::Output(output);
+}
+
+/// Callback executed on the JavaScript main thread
+///
+/// # Safety
+/// * `data` is expected to be a pointer to `Data<I, O, D>`
+unsafe extern "C" fn call_complete<I, O, D>(env: Env, status: napi::Status, data: *mut c_void) {
+    let Data {
+        state,
+        complete,
+        cancelled,
+        data,
+        work,
+        ..
+    } = *Box::<Data<I, O, D>>::from_raw(data.cast());
+
+    // Prevent cancelling work that has been deleted
+    if let Some((_, handle)) = &cancelled {
+        handle.0.store(ptr::null_mut(), Ordering::Release);
+    }
+
+    napi::delete_async_work(env, work);
+
+    match status {
+        // `unwrap` is okay because `call_complete` should be called exactly once
+        // if and only if `call_execute` has completed successfully
+        napi::Status::Ok => complete(env, state.into_output().unwrap(), data),
+        napi::Status::Cancelled => {
+            if let Some((cancelled, _)) = cancelled {
+                cancelled(env, data);
+            }
+        }
+        _ => assert_eq!(status, napi::Status::Ok),
+    }
+}
@@ -40,6 +40,13 @@ mod napi1 {
 
             fn is_exception_pending(env: Env, result: *mut bool) -> Status;
 
+            fn fatal_error(
+                location: *const c_char,
+                location_len: usize,
+                message: *const c_char,
+                message_len: usize,
+            ) -> ();
+
             fn get_value_external(env: Env, value: Value, result: *mut *mut c_void) -> Status;
 
             fn typeof_value(env: Env, value: Value, result: *mut ValueType) -> Status;
@@ -54,6 +61,7 @@ mod napi1 {
 
             fn is_arraybuffer(env: Env, value: Value, result: *mut bool) -> Status;
             fn is_buffer(env: Env, value: Value, result: *mut bool) -> Status;
+            fn is_typedarray(env: Env, value: Value, result: *mut bool) -> Status;
             fn is_error(env: Env, value: Value, result: *mut bool) -> Status;
             fn is_array(env: Env, value: Value, result: *mut bool) -> Status;
 
@@ -104,6 +112,16 @@ mod napi1 {
                 length: *mut usize,
             ) -> Status;
 
+            fn get_typedarray_info(
+                env: Env,
+                typedarray: Value,
+                typ: *mut TypedArrayType,
+                length: *mut usize,
+                data: *mut *mut c_void,
+                arraybuffer: *mut Value,
+                byte_offset: *mut usize,
+            ) -> Status;
+
             fn get_cb_info(
                 env: Env,
                 cbinfo: CallbackInfo,
@@ -149,8 +167,43 @@ mod napi1 {
 
             fn set_property(env: Env, object: Value, key: Value, value: Value) -> Status;
 
+            fn define_properties(
+                env: Env,
+                object: Value,
+                property_count: usize,
+                properties: *const PropertyDescriptor,
+            ) -> Status;
+
+            fn define_class(
+                env: Env,
+                utf8name: *const c_char,
+                length: usize,
+                constructor: Callback,
+                data: *mut c_void,
+                property_count: usize,
+                properties: *const PropertyDescriptor,
+                result: *mut Value,
+            ) -> Status;
+
+            fn wrap(
+                env: Env,
+                js_object: Value,
+                native_object: *mut c_void,
+                finalize_cb: Finalize,
+                finalize_hint: *mut c_void,
+                result: *mut Ref,
+            ) -> Status;
+
+            fn unwrap(env: Env, js_object: Value, result: *mut *mut c_void) -> Status;
+
             fn get_property(env: Env, object: Value, key: Value, result: *mut Value) -> Status;
 
+            fn has_property(env: Env, object: Value, key: Value, result: *mut bool) -> Status;
+
+            fn has_own_property(env: Env, object: Value, key: Value, result: *mut bool) -> Status;
+
+            fn delete_property(env: Env, object: Value, key: Value, result: *mut bool) -> Status;
+
             fn set_element(env: Env, object: Value, index: u32, value: Value) -> Status;
 
             fn get_element(env: Env, object: Value, index: u32, result: *mut Value) -> Status;
@@ -179,6 +232,15 @@ mod napi1 {
 
             fn strict_equals(env: Env, lhs: Value, rhs: Value, result: *mut bool) -> Status;
 
+            fn instanceof(env: Env, object: Value, constructor: Value, result: *mut bool)
+                -> Status;
+
+            fn adjust_external_memory(
+                env: Env,
+                change_in_bytes: i64,
+                adjusted_value: *mut i64,
+            ) -> Status;
+
             fn create_external_arraybuffer(
                 env: Env,
                 data: *mut c_void,
@@ -198,6 +260,70 @@ mod napi1 {
             ) -> Status;
 
             fn run_script(env: Env, script: Value, result: *mut Value) -> Status;
+
+            fn create_promise(env: Env, deferred: *mut Deferred, promise: *mut Value) -> Status;
+
+            fn resolve_deferred(env: Env, deferred: Deferred, resolution: Value) -> Status;
+
+            fn reject_deferred(env: Env, deferred: Deferred, rejection: Value) -> Status;
+
+            fn is_promise(env: Env, value: Value, is_promise: *mut bool) -> Status;
+
+            fn create_async_work(
+                env: Env,
+                async_resource: Value,
+                async_resource_name: Value,
+                execute: AsyncExecuteCallback,
+                complete: AsyncCompleteCallback,
+                data: *mut c_void,
+                result: *mut AsyncWork,
+            ) -> Status;
+
+            fn delete_async_work(env: Env, work: AsyncWork) -> Status;
+
+            fn queue_async_work(env: Env, work: AsyncWork) -> Status;
+
+            fn cancel_async_work(env: Env, work: AsyncWork) -> Status;
+
+            fn async_init(
+                env: Env,
+                async_resource: Value,
+                async_resource_name: Value,
+                result: *mut AsyncContext,
+            ) -> Status;
+
+            fn async_destroy(env: Env, async_context: AsyncContext) -> Status;
+        }
+    );
+}
+
+#[cfg(feature = "napi-2")]
+mod napi2 {
+    use super::super::types::*;
+
+    generate!(
+        extern "C" {
+            fn get_uv_event_loop(env: Env, loop_: *mut UvLoop) -> Status;
+        }
+    );
+}
+
+#[cfg(feature = "napi-3")]
+mod napi3 {
+    use super::super::types::*;
+
+    generate!(
+        extern "C" {
+            fn open_callback_scope(
+                env: Env,
+                resource_object: Value,
+                context: AsyncContext,
+                result: *mut CallbackScope,
+            ) -> Status;
+
+            fn close_callback_scope(env: Env, scope: CallbackScope) -> Status;
+
+            fn fatal_exception(env: Env, err: Value) -> Status;
         }
     );
 }
@@ -234,6 +360,8 @@ mod napi4 {
                 mode: ThreadsafeFunctionReleaseMode,
             ) -> Status;
 
+            fn acquire_threadsafe_function(func: ThreadsafeFunction) -> Status;
+
             fn ref_threadsafe_function(env: Env, func: ThreadsafeFunction) -> Status;
 
             fn unref_threadsafe_function(env: Env, func: ThreadsafeFunction) -> Status;
@@ -284,17 +412,108 @@ mod napi6 {
     );
 }
 
+#[cfg(feature = "napi-7")]
+mod napi7 {
+    use super::super::types::*;
+
+    generate!(
+        extern "C" {
+            fn detach_arraybuffer(env: Env, arraybuffer: Value) -> Status;
+
+            fn is_detached_arraybuffer(env: Env, value: Value, result: *mut bool) -> Status;
+        }
+    );
+}
+
+#[cfg(feature = "napi-8")]
+mod napi8 {
+    use super::super::types::*;
+
+    generate!(
+        extern "C" {
+            fn type_tag_object(env: Env, value: Value, type_tag: *const TypeTag) -> Status;
+
+            fn check_object_type_tag(
+                env: Env,
+                value: Value,
+                type_tag: *const TypeTag,
+                result: *mut bool,
+            ) -> Status;
+
+            fn object_freeze(env: Env, object: Value) -> Status;
+
+            fn object_seal(env: Env, object: Value) -> Status;
+        }
+    );
+}
+
+#[cfg(feature = "napi-9")]
+mod napi9 {
+    use super::super::types::*;
+    use std::os::raw::c_char;
+
+    generate!(
+        extern "C" {
+            fn symbol_for(
+                env: Env,
+                description: *const c_char,
+                length: usize,
+                result: *mut Value,
+            ) -> Status;
+
+            fn get_module_file_name(env: Env, result: *mut *const c_char) -> Status;
+
+            fn create_syntax_error(env: Env, code: Value, msg: Value, result: *mut Value)
+                -> Status;
+        }
+    );
+}
+
+#[cfg(feature = "napi-10")]
+mod napi10 {
+    use super::super::types::*;
+    use std::os::raw::c_char;
+
+    generate!(
+        extern "C" {
+            fn create_property_key_utf8(
+                env: Env,
+                str: *const c_char,
+                length: usize,
+                result: *mut Value,
+            ) -> Status;
+        }
+    );
+}
+
 pub(crate) use napi1::*;
+#[cfg(feature = "napi-10")]
+pub(crate) use napi10::*;
+#[cfg(feature = "napi-2")]
+pub(crate) use napi2::*;
+#[cfg(feature = "napi-3")]
+pub(crate) use napi3::*;
 #[cfg(feature = "napi-4")]
 pub(crate) use napi4::*;
 #[cfg(feature = "napi-5")]
 pub(crate) use napi5::*;
 #[cfg(feature = "napi-6")]
 pub(crate) use napi6::*;
+#[cfg(feature = "napi-7")]
+pub(crate) use napi7::*;
+#[cfg(feature = "napi-8")]
+pub(crate) use napi8::*;
+#[cfg(feature = "napi-9")]
+pub(crate) use napi9::*;
+
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use super::{Env, Status};
 
+pub(super) static VERSION: AtomicU32 = AtomicU32::new(0);
+
 // This symbol is loaded separately because it is a prerequisite
+#[cfg(not(target_arch = "wasm32"))]
 unsafe fn get_version(host: &libloading::Library, env: Env) -> Result<u32, libloading::Error> {
     let get_version = host.get::<fn(Env, *mut u32) -> Status>(b"napi_get_version")?;
     let mut version = 0;
@@ -304,6 +523,7 @@ unsafe fn get_version(host: &libloading::Library, env: Env) -> Result<u32, liblo
     Ok(version)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub(crate) unsafe fn load(env: Env) -> Result<(), libloading::Error> {
     #[cfg(not(windows))]
     let host = libloading::os::unix::Library::this().into();
@@ -314,8 +534,16 @@ pub(crate) unsafe fn load(env: Env) -> Result<(), libloading::Error> {
     // with `Error: Module did not self-register` if N-API does not exist.
     let version = get_version(&host, env).expect("Failed to find N-API version");
 
+    VERSION.store(version, Ordering::Relaxed);
+
     napi1::load(&host, version, 1)?;
 
+    #[cfg(feature = "napi-2")]
+    napi2::load(&host, version, 2)?;
+
+    #[cfg(feature = "napi-3")]
+    napi3::load(&host, version, 3)?;
+
     #[cfg(feature = "napi-4")]
     napi4::load(&host, version, 4)?;
 
@@ -325,5 +553,33 @@ pub(crate) unsafe fn load(env: Env) -> Result<(), libloading::Error> {
     #[cfg(feature = "napi-6")]
     napi6::load(&host, version, 6)?;
 
+    #[cfg(feature = "napi-7")]
+    napi7::load(&host, version, 7)?;
+
+    #[cfg(feature = "napi-8")]
+    napi8::load(&host, version, 8)?;
+
+    #[cfg(feature = "napi-9")]
+    napi9::load(&host, version, 9)?;
+
+    #[cfg(feature = "napi-10")]
+    napi10::load(&host, version, 10)?;
+
+    Ok(())
+}
+
+// Symbols are imported by the WebAssembly module and do not need to be loaded
+#[cfg(target_arch = "wasm32")]
+pub(crate) unsafe fn load(env: Env) -> Result<(), std::convert::Infallible> {
+    extern "C" {
+        fn napi_get_version(env: Env, result: *mut u32) -> Status;
+    }
+
+    let mut version = 0;
+
+    assert_eq!(napi_get_version(env, &mut version as *mut _), Status::Ok);
+
+    VERSION.store(version, Ordering::Relaxed);
+
     Ok(())
 }
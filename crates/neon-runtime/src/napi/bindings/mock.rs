@@ -0,0 +1,89 @@
+//! # Routing N-API calls to a mock implementation
+//!
+//! `neon::test` runs Neon code without Node.js by implementing N-API itself.
+//! The implementation is installed once with [`install`], which provides each
+//! function by its symbol name, like the host process does for [`super::setup`].
+//! While a [`Guard`] returned by [`enter`] is alive, every N-API call made on
+//! its thread goes to the mock instead of the host, so a mock can be used in
+//! a process that has also loaded the real bindings, e.g., in a test running
+//! under Node.js.
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::sync::OnceLock;
+
+use super::Status;
+
+/// Returns the mock implementation of the N-API function with the symbol
+/// `name`, e.g., `"napi_get_undefined"`, or `None` if it is not implemented.
+/// The pointer must be an `extern "C"` function with the signature of the
+/// symbol.
+pub type Resolve = fn(name: &str) -> Option<*const c_void>;
+
+static RESOLVE: OnceLock<Resolve> = OnceLock::new();
+
+thread_local! {
+    // The number of guards alive on the thread
+    static ENTERED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Installs the mock implementation. Functions are resolved once, when they
+/// are first called with a guard, so only the first call has an effect.
+pub fn install(resolve: Resolve) {
+    let _ = RESOLVE.set(resolve);
+}
+
+/// Routes the N-API calls of the current thread to the mock until the guard
+/// is dropped. Guards may be nested.
+///
+/// # Safety
+/// Until the guard is dropped, N-API calls on the thread must only be passed
+/// values created by the mock, e.g., an `Env` of the mock instead of one from
+/// Node.js.
+pub unsafe fn enter() -> Guard {
+    ENTERED.with(|entered| entered.set(entered.get() + 1));
+
+    Guard(PhantomData)
+}
+
+/// Keeps N-API calls on the current thread routed to the mock. See [`enter`].
+pub struct Guard(PhantomData<*const ()>);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.set(entered.get() - 1));
+    }
+}
+
+pub(crate) fn is_entered() -> bool {
+    ENTERED.with(|entered| entered.get() > 0)
+}
+
+pub(crate) fn resolve(name: &str) -> Option<*const c_void> {
+    RESOLVE.get().and_then(|resolve| resolve(name))
+}
+
+/// The result of a function the mock does not implement. Unwinding out of an
+/// `extern "C"` function aborts, so instead of panicking, the function fails
+/// after printing which symbol was called.
+pub(crate) trait Unsupported {
+    fn unsupported(name: &str) -> Self;
+}
+
+impl Unsupported for Status {
+    fn unsupported(name: &str) -> Self {
+        eprintln!("`{}` is not supported without Node.js", name);
+
+        Status::GenericFailure
+    }
+}
+
+// `napi_fatal_error` does not return
+impl Unsupported for () {
+    fn unsupported(name: &str) -> Self {
+        eprintln!("`{}` is not supported without Node.js", name);
+
+        std::process::abort()
+    }
+}
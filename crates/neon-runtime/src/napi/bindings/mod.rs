@@ -20,6 +20,19 @@ macro_rules! napi_name {
     (typeof_value) => {
         "napi_typeof"
     };
+    // Symbols added after N-API 8 use the `node_api_` prefix
+    (symbol_for) => {
+        "node_api_symbol_for"
+    };
+    (get_module_file_name) => {
+        "node_api_get_module_file_name"
+    };
+    (create_syntax_error) => {
+        "node_api_create_syntax_error"
+    };
+    (create_property_key_utf8) => {
+        "node_api_create_property_key_utf8"
+    };
     // Default case: Stringify the identifier and prefix with `napi_`
     ($name:ident) => {
         concat!("napi_", stringify!($name))
@@ -35,6 +48,11 @@ macro_rules! napi_name {
 ///   host process and replaces the global struct with real implementations
 /// * `load` should be called exactly once before using any N-API functions
 /// * Wrapper functions are generated to delegate to fields in the `Napi` struct
+/// * With the `mock` feature, wrappers delegate to a second `Napi` struct
+///   holding the functions of the mock instead, while the thread has entered
+///   it (see the `mock` module)
+/// * On `wasm32`, symbols are imported by the module instead of loaded and the
+///   wrapper functions call the imports directly
 ///
 /// Sample input:
 ///
@@ -82,12 +100,10 @@ macro_rules! napi_name {
 ///     actual_napi_version: u32,
 ///     expected_napi_version: u32,
 /// ) -> Result<(), libloading::Error> {
-///     assert!(
-///         actual_napi_version >= expected_napi_version,
-///         "Minimum required N-API version {}, found {}.",
-///         expected_napi_version,
-///         actual_napi_version,
-///     );
+///     // Leave the symbols unloaded if the host is too old
+///     if actual_napi_version < expected_napi_version {
+///         return Ok(());
+///     }
 ///
 ///     NAPI = Napi {
 ///         // Load each N-API symbol
@@ -109,6 +125,7 @@ macro_rules! generate {
     (extern "C" {
         $(fn $name:ident($($param:ident: $ptype:ty$(,)?)*) -> $rtype:ty;)+
     }) => {
+        #[cfg(not(target_arch = "wasm32"))]
         pub(crate) struct Napi {
             $(
                 $name: unsafe extern "C" fn(
@@ -117,11 +134,17 @@ macro_rules! generate {
             )*
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
         #[inline(never)]
         fn panic_load<T>() -> T {
-            panic!("Must load N-API bindings")
+            panic!(
+                "Must load N-API bindings. The function may require a newer N-API version \
+                than {}.",
+                $crate::napi::bindings::version(),
+            )
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
         static mut NAPI: Napi = {
             $(
                 unsafe extern "C" fn $name($(_: $ptype,)*) -> $rtype {
@@ -136,17 +159,17 @@ macro_rules! generate {
             }
         };
 
+        #[cfg(not(target_arch = "wasm32"))]
         pub(crate) unsafe fn load(
             host: &libloading::Library,
             actual_napi_version: u32,
             expected_napi_version: u32,
         ) -> Result<(), libloading::Error> {
-            assert!(
-                actual_napi_version >= expected_napi_version,
-                "Minimum required N-API version {}, found {}.",
-                expected_napi_version,
-                actual_napi_version,
-            );
+            // Symbols from newer versions are left unloaded so that modules can
+            // check the version at runtime and degrade gracefully
+            if actual_napi_version < expected_napi_version {
+                return Ok(());
+            }
 
             NAPI = Napi {
                 $(
@@ -157,25 +180,99 @@ macro_rules! generate {
             Ok(())
         }
 
+        // The functions of the mock installed with `mock::install`, resolved
+        // the first time one of them is called
+        #[cfg(all(feature = "mock", not(target_arch = "wasm32")))]
+        fn mock() -> &'static Napi {
+            static MOCK: std::sync::OnceLock<Napi> = std::sync::OnceLock::new();
+
+            MOCK.get_or_init(|| {
+                $(
+                    unsafe extern "C" fn $name($(_: $ptype,)*) -> $rtype {
+                        <$rtype as $crate::napi::bindings::mock::Unsupported>::unsupported(
+                            napi_name!($name),
+                        )
+                    }
+                )*
+
+                Napi {
+                    $(
+                        $name: match $crate::napi::bindings::mock::resolve(napi_name!($name)) {
+                            // Safety: The resolver returns functions with the
+                            // signature of the symbol
+                            Some(f) => unsafe {
+                                std::mem::transmute::<
+                                    *const std::os::raw::c_void,
+                                    unsafe extern "C" fn($($ptype,)*) -> $rtype,
+                                >(f)
+                            },
+                            None => $name,
+                        },
+                    )*
+                }
+            })
+        }
+
         $(
+            #[cfg(not(target_arch = "wasm32"))]
             #[inline]
             pub(crate) unsafe fn $name($($param: $ptype,)*) -> $rtype {
+                #[cfg(feature = "mock")]
+                if $crate::napi::bindings::mock::is_entered() {
+                    return (mock().$name)($($param,)*);
+                }
+
                 (NAPI.$name)($($param,)*)
             }
         )*
+
+        // WebAssembly modules cannot load symbols dynamically. Instead, the
+        // N-API implementation (e.g., emnapi) provides them as imports.
+        #[cfg(target_arch = "wasm32")]
+        mod imports {
+            use super::*;
+
+            extern "C" {
+                $(
+                    #[link_name = napi_name!($name)]
+                    pub(super) fn $name($($param: $ptype,)*) -> $rtype;
+                )*
+            }
+        }
+
+        $(
+            #[cfg(target_arch = "wasm32")]
+            #[inline]
+            pub(crate) unsafe fn $name($($param: $ptype,)*) -> $rtype {
+                imports::$name($($param,)*)
+            }
+        )*
     };
 }
 
 use std::sync::Once;
 
 pub(crate) use functions::*;
+#[cfg(feature = "napi-8")]
+pub use types::TypeTag;
 pub(crate) use types::*;
+pub use types::{AsyncContext, Deferred, Ref, Status, TypedArrayType};
+#[cfg(feature = "napi-4")]
+pub use types::{ThreadsafeFunctionCallMode, ThreadsafeFunctionReleaseMode};
 
 mod functions;
+#[cfg(feature = "mock")]
+pub mod mock;
 mod types;
 
 static SETUP: Once = Once::new();
 
+/// Returns the N-API version of the host process, or `0` if the bindings have
+/// not been loaded.
+pub fn version() -> u32 {
+    functions::VERSION.load(std::sync::atomic::Ordering::Relaxed)
+}
+
 /// Loads N-API symbols from host process.
 /// Must be called at least once before using any functions in `neon-runtime` or
 /// they will panic.
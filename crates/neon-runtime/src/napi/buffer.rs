@@ -3,6 +3,7 @@ use std::mem::MaybeUninit;
 use std::os::raw::c_void;
 use std::ptr::null_mut;
 
+use crate::napi::arraybuffer;
 use crate::napi::bindings as napi;
 
 pub unsafe fn new(env: Env, out: &mut Local, size: u32) -> bool {
@@ -30,22 +31,62 @@ where
 {
     // Safety: Boxing could move the data; must box before grabbing a raw pointer
     let mut data = Box::new(data);
-    let buf = data.as_mut().as_mut();
-    let length = buf.len();
+    let length = data.as_mut().as_mut().len();
+    let bytes = data.as_mut().as_mut().as_mut_ptr();
+    let hint = Box::into_raw(data);
+
+    if !arraybuffer::external_maybe_supported() {
+        let result = new_copy(env, bytes, length);
+
+        drop(Box::from_raw(hint));
+
+        return result;
+    }
+
+    let data = bytes as *mut c_void;
+    let mut result = MaybeUninit::uninit();
+    let status = napi::create_external_buffer(
+        env,
+        length,
+        data,
+        Some(drop_external::<T>),
+        hint as *mut _,
+        result.as_mut_ptr(),
+    );
+
+    arraybuffer::record_external_support(status);
+
+    // Hosts with the V8 memory cage enabled (e.g., Electron >= 21) do not allow
+    // external buffers. Ownership is not transferred, so the data is copied instead.
+    if status == napi::Status::NoExternalBuffersAllowed {
+        let result = new_copy(env, bytes, length);
+
+        drop(Box::from_raw(hint));
+
+        return result;
+    }
+
+    assert_eq!(status, napi::Status::Ok);
+
+    #[cfg(feature = "external-memory")]
+    crate::napi::mem::adjust_external_memory(env, length as i64);
+
+    result.assume_init()
+}
+
+unsafe fn new_copy(env: Env, bytes: *const u8, length: usize) -> Local {
+    let mut out = null_mut();
     let mut result = MaybeUninit::uninit();
 
     assert_eq!(
-        napi::create_external_buffer(
-            env,
-            length,
-            buf.as_mut_ptr() as *mut _,
-            Some(drop_external::<T>),
-            Box::into_raw(data) as *mut _,
-            result.as_mut_ptr(),
-        ),
+        napi::create_buffer(env, length, &mut out as *mut _, result.as_mut_ptr()),
         napi::Status::Ok,
     );
 
+    if length > 0 {
+        std::ptr::copy_nonoverlapping(bytes, out as *mut u8, length);
+    }
+
     result.assume_init()
 }
 
@@ -58,6 +99,15 @@ pub unsafe fn data(env: Env, base_out: &mut *mut c_void, obj: Local) -> usize {
     size
 }
 
-unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void) {
-    Box::<T>::from_raw(hint as *mut _);
+unsafe extern "C" fn drop_external<T>(_env: Env, _data: *mut c_void, hint: *mut c_void)
+where
+    T: AsMut<[u8]>,
+{
+    #[allow(unused_mut)]
+    let mut data = Box::<T>::from_raw(hint as *mut _);
+
+    #[cfg(feature = "external-memory")]
+    crate::napi::mem::adjust_external_memory(_env, -(data.as_mut().as_mut().len() as i64));
+
+    drop(data);
 }
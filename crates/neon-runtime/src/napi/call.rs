@@ -40,6 +40,12 @@ impl Arguments {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    #[inline]
+    /// Get every argument, in order
+    pub fn as_slice(&self) -> &[Local] {
+        &self.0
+    }
 }
 
 #[repr(C)]
@@ -57,7 +63,9 @@ impl Default for CCallback {
     }
 }
 
-pub unsafe fn is_construct(env: Env, info: FunctionCallbackInfo) -> bool {
+/// Returns `new.target`, the constructor that was invoked with `new`, or
+/// `None` if the function was called without `new`.
+pub unsafe fn new_target(env: Env, info: FunctionCallbackInfo) -> Option<Local> {
     let mut target: MaybeUninit<Local> = MaybeUninit::zeroed();
 
     let status = napi::get_new_target(env, info, target.as_mut_ptr());
@@ -70,7 +78,15 @@ pub unsafe fn is_construct(env: Env, info: FunctionCallbackInfo) -> bool {
     // By the get_new_target contract, target will either be NULL if the current
     // function was called without `new`, or a valid napi_value handle if the current
     // function was called with `new`.
-    !target.is_null()
+    if target.is_null() {
+        None
+    } else {
+        Some(target)
+    }
+}
+
+pub unsafe fn is_construct(env: Env, info: FunctionCallbackInfo) -> bool {
+    new_target(env, info).is_some()
 }
 
 pub unsafe fn this(env: Env, info: FunctionCallbackInfo, out: &mut Local) {
@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::mem::MaybeUninit;
+use std::os::raw::c_void;
+use std::ptr;
+
+use crate::call::CCallback;
+use crate::napi::bindings as napi;
+use crate::napi::object::{descriptor, napi_callback, Property};
+use crate::raw::{Env, Local};
+
+/// The value stored in a wrapped object
+pub type Wrapped = Box<dyn Any + Send>;
+
+/// Creates a class named `name` whose constructor invokes `constructor`, with
+/// each of `properties` on the prototype and each of `statics` on the
+/// constructor. Like class members defined in JavaScript, the properties are
+/// writable and configurable but not enumerable.
+pub unsafe fn define(
+    env: Env,
+    name: &str,
+    constructor: CCallback,
+    properties: &[(Local, Property)],
+    statics: &[(Local, Property)],
+) -> Option<Local> {
+    let instance = properties.iter().map(|(name, property)| {
+        descriptor(*name, property, napi::PropertyAttributes::CONFIGURABLE)
+    });
+    let statics = statics.iter().map(|(name, property)| {
+        descriptor(
+            *name,
+            property,
+            napi::PropertyAttributes::CONFIGURABLE | napi::PropertyAttributes::STATIC,
+        )
+    });
+    let descriptors = instance.chain(statics).collect::<Vec<_>>();
+    let mut result = MaybeUninit::uninit();
+    let status = napi::define_class(
+        env,
+        name.as_ptr().cast(),
+        name.len(),
+        napi_callback(&constructor),
+        constructor.dynamic_callback,
+        descriptors.len(),
+        descriptors.as_ptr(),
+        result.as_mut_ptr(),
+    );
+
+    if status == napi::Status::Ok {
+        Some(result.assume_init())
+    } else {
+        None
+    }
+}
+
+extern "C" fn finalize_wrapped(_env: Env, data: *mut c_void, _hint: *mut c_void) {
+    unsafe {
+        drop(Box::<Wrapped>::from_raw(data.cast()));
+    }
+}
+
+/// Associates `value` with `object`. It is dropped when `object` is garbage
+/// collected. Returns `false` if `object` is already wrapped.
+pub unsafe fn wrap(env: Env, object: Local, value: Wrapped) -> bool {
+    let data = Box::into_raw(Box::new(value));
+    let status = napi::wrap(
+        env,
+        object,
+        data.cast(),
+        Some(finalize_wrapped),
+        ptr::null_mut(),
+        ptr::null_mut(),
+    );
+
+    if status != napi::Status::Ok {
+        drop(Box::from_raw(data));
+        return false;
+    }
+
+    true
+}
+
+/// Returns the value associated with `object` by [`wrap`], if any.
+///
+/// Safety: Like [`external::deref`](crate::external::deref), `object` must not
+/// have been wrapped by another native module.
+pub unsafe fn unwrap<'a>(env: Env, object: Local) -> Option<&'a Wrapped> {
+    let mut result = MaybeUninit::uninit();
+
+    if napi::unwrap(env, object, result.as_mut_ptr()) != napi::Status::Ok {
+        return None;
+    }
+
+    (result.assume_init() as *const Wrapped).as_ref()
+}
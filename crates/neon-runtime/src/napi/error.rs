@@ -42,6 +42,35 @@ pub unsafe fn clear_exception(env: Env) {
     assert_eq!(status, napi::Status::Ok);
 }
 
+/// Reports a pending exception as an uncaught exception, e.g., to the
+/// `uncaughtException` handlers of the process, and clears it.
+#[cfg(feature = "napi-3")]
+pub unsafe fn report_pending(env: Env) {
+    let mut error = MaybeUninit::uninit();
+
+    if !catch_error(env, error.as_mut_ptr()) {
+        return;
+    }
+
+    let status = napi::fatal_exception(env, error.assume_init());
+
+    assert_eq!(status, napi::Status::Ok);
+}
+
+/// Aborts the process with a fatal error report, which includes the location
+/// and message.
+pub unsafe fn fatal_error(location: &str, message: &str) -> ! {
+    napi::fatal_error(
+        location.as_ptr() as *const _,
+        location.len(),
+        message.as_ptr() as *const _,
+        message.len(),
+    );
+
+    // `napi_fatal_error` does not return
+    std::process::abort()
+}
+
 pub unsafe fn throw(env: Env, val: Local) {
     let status = napi::throw(env, val);
 
@@ -88,3 +117,13 @@ pub unsafe fn throw_error_from_utf8(env: Env, msg: *const u8, len: i32) {
 
     throw(env, err.assume_init());
 }
+
+#[cfg(feature = "napi-9")]
+pub unsafe fn new_syntax_error(env: Env, out: &mut Local, msg: Local) {
+    let mut result = MaybeUninit::uninit();
+    let status = napi::create_syntax_error(env, ptr::null_mut(), msg, result.as_mut_ptr());
+
+    assert_eq!(status, napi::Status::Ok);
+
+    *out = result.assume_init();
+}
@@ -47,3 +47,21 @@ pub unsafe fn get_instance_data<T: Send + 'static>(env: Env) -> *mut T {
 unsafe extern "C" fn drop_box<T>(_env: Env, data: *mut c_void, _hint: *mut c_void) {
     Box::<T>::from_raw(data.cast());
 }
+
+/// Returns the URL of the file the module was loaded from, e.g.,
+/// `file:///path/to/index.node`.
+///
+/// # Safety
+/// * `env` must point to a valid `napi_env` for this thread
+/// * The returned string is owned by the environment and must not outlive it
+#[cfg(feature = "napi-9")]
+pub unsafe fn module_file_name(env: Env) -> *const std::os::raw::c_char {
+    let mut name = ptr::null();
+
+    assert_eq!(
+        napi::get_module_file_name(env, &mut name as *mut _),
+        napi::Status::Ok,
+    );
+
+    name
+}
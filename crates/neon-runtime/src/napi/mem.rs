@@ -9,3 +9,14 @@ pub unsafe fn strict_equals(env: Env, lhs: Local, rhs: Local) -> bool {
     );
     result
 }
+
+/// Reports a change in the memory held by native values that are kept alive by
+/// JavaScript objects. Returns the total memory reported for the environment.
+pub unsafe fn adjust_external_memory(env: Env, change_in_bytes: i64) -> i64 {
+    let mut result = 0;
+    assert_eq!(
+        napi::adjust_external_memory(env, change_in_bytes, &mut result as *mut _),
+        napi::Status::Ok
+    );
+    result
+}
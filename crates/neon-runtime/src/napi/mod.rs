@@ -1,7 +1,10 @@
 pub mod array;
 pub mod arraybuffer;
+pub mod async_context;
+pub mod async_work;
 pub mod buffer;
 pub mod call;
+pub mod class;
 pub mod convert;
 #[cfg(feature = "napi-5")]
 pub mod date;
@@ -13,6 +16,7 @@ pub mod lifecycle;
 pub mod mem;
 pub mod object;
 pub mod primitive;
+pub mod promise;
 pub mod raw;
 pub mod reference;
 pub mod scope;
@@ -20,6 +24,9 @@ pub mod string;
 pub mod tag;
 #[cfg(feature = "napi-4")]
 pub mod tsfn;
+pub mod typedarray;
+#[cfg(feature = "napi-2")]
+pub mod uv;
 
 mod bindings;
 pub use bindings::*;
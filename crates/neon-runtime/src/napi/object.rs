@@ -1,5 +1,7 @@
 use std::mem::MaybeUninit;
+use std::os::raw::c_void;
 
+use crate::call::CCallback;
 use crate::napi::bindings as napi;
 use crate::raw::{Env, Local};
 
@@ -12,13 +14,25 @@ pub unsafe fn new(out: &mut Local, env: Env) {
 /// Mutates the `out` argument to refer to a `napi_value` containing the own property names of the
 /// `object` as a JavaScript Array.
 pub unsafe fn get_own_property_names(out: &mut Local, env: Env, object: Local) -> bool {
+    get_own_keys(out, env, object, napi::KeyFilter::ALL_PROPERTIES)
+}
+
+#[cfg(feature = "napi-6")]
+/// Mutates the `out` argument to refer to a `napi_value` containing the own enumerable property
+/// names of the `object`, as returned by `Object.keys`, as a JavaScript Array.
+pub unsafe fn get_own_enumerable_property_names(out: &mut Local, env: Env, object: Local) -> bool {
+    get_own_keys(out, env, object, napi::KeyFilter::ENUMERABLE)
+}
+
+#[cfg(feature = "napi-6")]
+unsafe fn get_own_keys(out: &mut Local, env: Env, object: Local, filter: napi::KeyFilter) -> bool {
     let mut property_names = MaybeUninit::uninit();
 
     if napi::get_all_property_names(
         env,
         object,
         napi::KeyCollectionMode::OwnOnly,
-        napi::KeyFilter::ALL_PROPERTIES | napi::KeyFilter::SKIP_SYMBOLS,
+        filter | napi::KeyFilter::SKIP_SYMBOLS,
         napi::KeyConversion::NumbersToStrings,
         property_names.as_mut_ptr(),
     ) != napi::Status::Ok
@@ -131,3 +145,137 @@ pub unsafe fn set(out: &mut bool, env: Env, object: Local, key: Local, val: Loca
 
     *out
 }
+
+/// Mutates `out` to `true` if `object` or its prototype chain has a property named by `key`.
+/// Returns `false` if the check failed.
+pub unsafe fn has(out: &mut bool, env: Env, object: Local, key: Local) -> bool {
+    napi::has_property(env, object, key, out as *mut _) == napi::Status::Ok
+}
+
+/// Mutates `out` to `true` if `object` has an own property named by `key`. Returns `false` if the
+/// check failed.
+pub unsafe fn has_own(out: &mut bool, env: Env, object: Local, key: Local) -> bool {
+    match napi::has_own_property(env, object, key, out as *mut _) {
+        napi::Status::Ok => true,
+        // N-API only accepts strings and symbols; convert other keys as JavaScript would
+        napi::Status::NameExpected => {
+            let mut key_str = MaybeUninit::uninit();
+
+            napi::coerce_to_string(env, key, key_str.as_mut_ptr()) == napi::Status::Ok
+                && napi::has_own_property(env, object, key_str.assume_init(), out as *mut _)
+                    == napi::Status::Ok
+        }
+        _ => false,
+    }
+}
+
+/// Deletes the property of `object` named by `key`, mutating `out` to `false` if the property
+/// could not be deleted. Returns `false` if an exception is pending.
+pub unsafe fn delete(out: &mut bool, env: Env, object: Local, key: Local) -> bool {
+    napi::delete_property(env, object, key, out as *mut _) == napi::Status::Ok
+}
+
+#[cfg(feature = "napi-8")]
+/// Freezes `object`, as with `Object.freeze`. Returns `false` if an exception is pending.
+pub unsafe fn freeze(env: Env, object: Local) -> bool {
+    napi::object_freeze(env, object) == napi::Status::Ok
+}
+
+#[cfg(feature = "napi-8")]
+/// Seals `object`, as with `Object.seal`. Returns `false` if an exception is pending.
+pub unsafe fn seal(env: Env, object: Local) -> bool {
+    napi::object_seal(env, object) == napi::Status::Ok
+}
+
+/// The value of a property defined with [`define_properties`].
+pub enum Property {
+    /// A writable data property
+    Value(Local),
+    /// A writable data property holding a new function that invokes the
+    /// callback
+    Method(CCallback),
+    /// A read-only accessor property whose getter invokes the callback
+    Getter(CCallback),
+    /// An accessor property whose getter, setter or both invoke the callback.
+    /// The setter is called with the new value as its only argument.
+    Accessor {
+        callback: CCallback,
+        get: bool,
+        set: bool,
+    },
+}
+
+/// Defines each of `properties`, keyed by a string or symbol, on `object` in a
+/// single call. Every property is enumerable and configurable. Returns `true`
+/// if the properties were defined.
+pub unsafe fn define_properties(env: Env, object: Local, properties: &[(Local, Property)]) -> bool {
+    let descriptors = properties
+        .iter()
+        .map(|(name, property)| {
+            descriptor(
+                *name,
+                property,
+                napi::PropertyAttributes::ENUMERABLE | napi::PropertyAttributes::CONFIGURABLE,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    napi::define_properties(env, object, descriptors.len(), descriptors.as_ptr())
+        == napi::Status::Ok
+}
+
+// Describes a property with the given attributes. Data properties and methods
+// are also writable.
+pub(crate) unsafe fn descriptor(
+    name: Local,
+    property: &Property,
+    attributes: napi::PropertyAttributes,
+) -> napi::PropertyDescriptor {
+    let mut descriptor = napi::PropertyDescriptor {
+        utf8name: std::ptr::null(),
+        name,
+        method: None,
+        getter: None,
+        setter: None,
+        value: std::ptr::null_mut(),
+        attributes,
+        data: std::ptr::null_mut(),
+    };
+
+    match property {
+        Property::Value(value) => {
+            descriptor.value = *value;
+            descriptor.attributes = descriptor.attributes | napi::PropertyAttributes::WRITABLE;
+        }
+        Property::Method(callback) => {
+            descriptor.method = napi_callback(callback);
+            descriptor.data = callback.dynamic_callback;
+            descriptor.attributes = descriptor.attributes | napi::PropertyAttributes::WRITABLE;
+        }
+        Property::Getter(callback) => {
+            descriptor.getter = napi_callback(callback);
+            descriptor.data = callback.dynamic_callback;
+        }
+        Property::Accessor { callback, get, set } => {
+            if *get {
+                descriptor.getter = napi_callback(callback);
+            }
+
+            if *set {
+                descriptor.setter = napi_callback(callback);
+            }
+
+            descriptor.data = callback.dynamic_callback;
+        }
+    }
+
+    descriptor
+}
+
+pub(crate) unsafe fn napi_callback(callback: &CCallback) -> napi::Callback {
+    type Callback = unsafe extern "C" fn(napi::Env, napi::CallbackInfo) -> napi::Value;
+
+    Some(std::mem::transmute::<*mut c_void, Callback>(
+        callback.static_callback,
+    ))
+}
@@ -0,0 +1,51 @@
+//! JavaScript Promise and Deferred handle
+//!
+//! See: [Promises in Node-API](https://nodejs.org/api/n-api.html#n_api_promises)
+
+use std::mem::MaybeUninit;
+
+use crate::napi::bindings as napi;
+use crate::raw::{Env, Local};
+
+/// Create a `Promise` and a `napi::Deferred` handle for resolving it
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * The returned `napi::Deferred` must be resolved or rejected exactly once
+pub unsafe fn create(env: Env) -> (napi::Deferred, Local) {
+    let mut deferred = MaybeUninit::uninit();
+    let mut promise = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::create_promise(env, deferred.as_mut_ptr(), promise.as_mut_ptr()),
+        napi::Status::Ok,
+    );
+
+    (deferred.assume_init(), promise.assume_init())
+}
+
+/// Resolve a promise from a `napi::Deferred` handle
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `resolution` is a valid `napi::Value`
+/// * `deferred` has not been resolved or rejected
+pub unsafe fn resolve(env: Env, deferred: napi::Deferred, resolution: Local) {
+    assert_eq!(
+        napi::resolve_deferred(env, deferred, resolution),
+        napi::Status::Ok,
+    );
+}
+
+/// Rejects a promise from a `napi::Deferred` handle
+///
+/// # Safety
+/// * `env` is a valid `napi_env` for the current thread
+/// * `rejection` is a valid `napi::Value`
+/// * `deferred` has not been resolved or rejected
+pub unsafe fn reject(env: Env, deferred: napi::Deferred, rejection: Local) {
+    assert_eq!(
+        napi::reject_deferred(env, deferred, rejection),
+        napi::Status::Ok,
+    );
+}
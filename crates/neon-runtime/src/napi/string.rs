@@ -34,3 +34,21 @@ pub unsafe fn run_script(out: &mut Local, env: Env, value: Local) -> bool {
 
     status == napi::Status::Ok
 }
+
+/// Creates a string that is optimized for use as a property key, such as a
+/// string that is used repeatedly to access objects.
+#[cfg(feature = "napi-10")]
+pub unsafe fn new_property_key(out: &mut Local, env: Env, data: *const u8, len: i32) -> bool {
+    let status = napi::create_property_key_utf8(env, data as *const _, len as usize, out);
+
+    status == napi::Status::Ok
+}
+
+/// Returns the symbol registered under `description` in the global symbol
+/// registry, as with `Symbol.for`, creating it if necessary.
+#[cfg(feature = "napi-9")]
+pub unsafe fn symbol_for(out: &mut Local, env: Env, data: *const u8, len: i32) -> bool {
+    let status = napi::symbol_for(env, data as *const _, len as usize, out);
+
+    status == napi::Status::Ok
+}
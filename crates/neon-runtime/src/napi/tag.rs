@@ -1,5 +1,6 @@
 use crate::napi::bindings as napi;
 use crate::raw::{Env, Local};
+use std::mem::MaybeUninit;
 
 /// Return true if an `napi_value` `val` has the expected value type.
 unsafe fn is_type(env: Env, val: Local, expect: napi::ValueType) -> bool {
@@ -11,6 +12,29 @@ unsafe fn is_type(env: Env, val: Local, expect: napi::ValueType) -> bool {
     actual == expect
 }
 
+/// Describes the type of `val` for error messages: the result of `typeof`,
+/// except that `null` is `"null"` and arrays are `"Array"`.
+pub unsafe fn type_name(env: Env, val: Local) -> &'static str {
+    let mut actual = napi::ValueType::Undefined;
+    assert_eq!(
+        napi::typeof_value(env, val, &mut actual as *mut _),
+        napi::Status::Ok
+    );
+
+    match actual {
+        napi::ValueType::Undefined => "undefined",
+        napi::ValueType::Null => "null",
+        napi::ValueType::Boolean => "boolean",
+        napi::ValueType::Number => "number",
+        napi::ValueType::String => "string",
+        napi::ValueType::Symbol => "symbol",
+        napi::ValueType::Object if is_array(env, val) => "Array",
+        napi::ValueType::Object | napi::ValueType::External => "object",
+        napi::ValueType::Function => "function",
+        napi::ValueType::BigInt => "bigint",
+    }
+}
+
 pub unsafe fn is_undefined(env: Env, val: Local) -> bool {
     is_type(env, val, napi::ValueType::Undefined)
 }
@@ -80,6 +104,79 @@ pub unsafe fn is_arraybuffer(env: Env, val: Local) -> bool {
     result
 }
 
+/// Is `val` a TypedArray instance?
+pub unsafe fn is_typedarray(env: Env, val: Local) -> bool {
+    let mut result = false;
+    assert_eq!(
+        napi::is_typedarray(env, val, &mut result as *mut _),
+        napi::Status::Ok
+    );
+    result
+}
+
+pub unsafe fn is_promise(env: Env, val: Local) -> bool {
+    let mut result = false;
+    assert_eq!(
+        napi::is_promise(env, val, &mut result as *mut _),
+        napi::Status::Ok
+    );
+    result
+}
+
+/// Is `val` an instance of the global `RegExp` class of the current realm?
+pub unsafe fn is_regexp(env: Env, val: Local) -> bool {
+    is_instance_of_global(env, val, "RegExp")
+}
+
+/// Is `val` an instance of the global `Map` class of the current realm?
+pub unsafe fn is_map(env: Env, val: Local) -> bool {
+    is_instance_of_global(env, val, "Map")
+}
+
+/// Is `val` an instance of the global `WeakMap` class of the current realm?
+pub unsafe fn is_weak_map(env: Env, val: Local) -> bool {
+    is_instance_of_global(env, val, "WeakMap")
+}
+
+/// Is `val` an instance of the global `WeakSet` class of the current realm?
+pub unsafe fn is_weak_set(env: Env, val: Local) -> bool {
+    is_instance_of_global(env, val, "WeakSet")
+}
+
+/// Is `val` an instance of the global `SharedArrayBuffer` class of the
+/// current realm?
+pub unsafe fn is_shared_arraybuffer(env: Env, val: Local) -> bool {
+    is_instance_of_global(env, val, "SharedArrayBuffer")
+}
+
+unsafe fn is_instance_of_global(env: Env, val: Local, class: &str) -> bool {
+    if !is_object(env, val) {
+        return false;
+    }
+
+    let mut global = MaybeUninit::uninit();
+    let mut name = MaybeUninit::uninit();
+    let mut constructor = MaybeUninit::uninit();
+    let mut result = false;
+
+    napi::get_global(env, global.as_mut_ptr()) == napi::Status::Ok
+        && napi::create_string_utf8(
+            env,
+            class.as_ptr() as *const _,
+            class.len(),
+            name.as_mut_ptr(),
+        ) == napi::Status::Ok
+        && napi::get_property(
+            env,
+            global.assume_init(),
+            name.assume_init(),
+            constructor.as_mut_ptr(),
+        ) == napi::Status::Ok
+        && napi::instanceof(env, val, constructor.assume_init(), &mut result as *mut _)
+            == napi::Status::Ok
+        && result
+}
+
 #[cfg(feature = "napi-5")]
 pub unsafe fn is_date(env: Env, val: Local) -> bool {
     let mut result = false;
@@ -89,3 +186,22 @@ pub unsafe fn is_date(env: Env, val: Local) -> bool {
     );
     result
 }
+
+/// Associates `tag` with `object`. Returns `false` if the object already has a tag.
+#[cfg(feature = "napi-8")]
+pub unsafe fn type_tag_object(env: Env, object: Local, tag: &napi::TypeTag) -> bool {
+    napi::type_tag_object(env, object, tag as *const _) == napi::Status::Ok
+}
+
+/// Is `object` tagged with `tag`?
+#[cfg(feature = "napi-8")]
+pub unsafe fn check_object_type_tag(env: Env, object: Local, tag: &napi::TypeTag) -> bool {
+    let mut result = false;
+
+    assert_eq!(
+        napi::check_object_type_tag(env, object, tag as *const _, &mut result as *mut _),
+        napi::Status::Ok
+    );
+
+    result
+}
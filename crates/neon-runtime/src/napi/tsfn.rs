@@ -36,12 +36,31 @@ unsafe impl Sync for Tsfn {}
 pub struct ThreadsafeFunction<T> {
     tsfn: Tsfn,
     is_finalized: Arc<Mutex<bool>>,
-    callback: fn(Option<Env>, T),
+    callback: Handler<T>,
+    // Created by the mock, which must also handle calls from other threads
+    #[cfg(feature = "mock")]
+    mock: bool,
 }
 
+// The function called with the data of each call, and with the JavaScript
+// function passed when creating the threadsafe function, if any
+#[derive(Debug)]
+enum Handler<T> {
+    Data(fn(Option<Env>, T)),
+    Function(fn(Option<(Env, Local)>, T)),
+}
+
+impl<T> Clone for Handler<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handler<T> {}
+
 #[derive(Debug)]
 struct Callback<T> {
-    callback: fn(Option<Env>, T),
+    callback: Handler<T>,
     data: T,
 }
 
@@ -77,13 +96,36 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
         max_queue_size: usize,
         callback: fn(Option<Env>, T),
     ) -> Self {
+        Self::create(
+            env,
+            std::ptr::null_mut(),
+            max_queue_size,
+            Handler::Data(callback),
+        )
+    }
+
+    /// Creates a N-API Threadsafe Function that holds a reference to the
+    /// JavaScript function `func`, which is passed to `callback` with the
+    /// data of each call. A `max_queue_size` of `0` is unbounded.
+    /// Safety: `Env` must be valid for the current thread and `func` must be
+    /// a function
+    pub unsafe fn with_function(
+        env: Env,
+        func: Local,
+        max_queue_size: usize,
+        callback: fn(Option<(Env, Local)>, T),
+    ) -> Self {
+        Self::create(env, func, max_queue_size, Handler::Function(callback))
+    }
+
+    unsafe fn create(env: Env, func: Local, max_queue_size: usize, callback: Handler<T>) -> Self {
         let mut result = MaybeUninit::uninit();
         let is_finalized = Arc::new(Mutex::new(false));
 
         assert_eq!(
             napi::create_threadsafe_function(
                 env,
-                std::ptr::null_mut(),
+                func,
                 std::ptr::null_mut(),
                 string(env, "neon threadsafe function"),
                 max_queue_size,
@@ -103,6 +145,8 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
             tsfn: Tsfn(result.assume_init()),
             is_finalized: is_finalized,
             callback,
+            #[cfg(feature = "mock")]
+            mock: napi::mock::is_entered(),
         }
     }
 
@@ -114,6 +158,9 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     ) -> Result<(), CallError<T>> {
         let is_blocking = is_blocking.unwrap_or(napi::ThreadsafeFunctionCallMode::Blocking);
 
+        #[cfg(feature = "mock")]
+        let _mock = self.enter_mock();
+
         let callback = Box::into_raw(Box::new(Callback {
             callback: self.callback,
             data,
@@ -152,21 +199,17 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     }
 
     /// References a threadsafe function to prevent exiting the event loop until it has been dropped. (Default)
+    /// Hosts that do not implement referencing leave the function referenced.
     /// Safety: `Env` must be valid for the current thread
     pub unsafe fn reference(&self, env: Env) {
-        assert_eq!(
-            napi::ref_threadsafe_function(env, self.tsfn.0,),
-            napi::Status::Ok,
-        );
+        let _ = napi::ref_threadsafe_function(env, self.tsfn.0);
     }
 
     /// Unreferences a threadsafe function to allow exiting the event loop before it has been dropped.
+    /// Hosts that do not implement unreferencing leave the function referenced.
     /// Safety: `Env` must be valid for the current thread
     pub unsafe fn unref(&self, env: Env) {
-        assert_eq!(
-            napi::unref_threadsafe_function(env, self.tsfn.0,),
-            napi::Status::Ok,
-        );
+        let _ = napi::unref_threadsafe_function(env, self.tsfn.0);
     }
 
     // Provides a C ABI wrapper for a napi callback notifying us about tsfn
@@ -180,7 +223,7 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
     // Provides a C ABI wrapper for invoking the user supplied function pointer
     unsafe extern "C" fn callback(
         env: Env,
-        _js_callback: napi::Value,
+        js_callback: napi::Value,
         _context: *mut c_void,
         data: *mut c_void,
     ) {
@@ -189,12 +232,68 @@ impl<T: Send + 'static> ThreadsafeFunction<T> {
         // Event loop has terminated
         let env = if env.is_null() { None } else { Some(env) };
 
-        callback(env, data);
+        match callback {
+            Handler::Data(callback) => callback(env, data),
+            Handler::Function(callback) => callback(env.map(|env| (env, js_callback)), data),
+        }
+    }
+}
+
+impl<T> ThreadsafeFunction<T> {
+    // Threadsafe functions may be called from any thread, so the calls of
+    // one created by the mock are routed to it explicitly
+    #[cfg(feature = "mock")]
+    fn enter_mock(&self) -> Option<napi::mock::Guard> {
+        if self.mock {
+            Some(unsafe { napi::mock::enter() })
+        } else {
+            None
+        }
+    }
+
+    /// Increments the number of threads using the threadsafe function, which
+    /// must be balanced by a call to `release`. Fails with `Closing` if the
+    /// function is being finalized.
+    pub fn acquire(&self) -> Result<(), napi::Status> {
+        #[cfg(feature = "mock")]
+        let _mock = self.enter_mock();
+
+        let is_finalized = self.is_finalized.lock().unwrap();
+
+        let status = if *is_finalized {
+            napi::Status::Closing
+        } else {
+            unsafe { napi::acquire_threadsafe_function(self.tsfn.0) }
+        };
+
+        match status {
+            napi::Status::Ok => Ok(()),
+            status => Err(status),
+        }
+    }
+
+    /// Decrements the number of threads using the threadsafe function, after
+    /// a call to `acquire`. With `Abort`, the queue is closed and later calls
+    /// fail with `Closing`.
+    pub fn release(&self, mode: napi::ThreadsafeFunctionReleaseMode) {
+        #[cfg(feature = "mock")]
+        let _mock = self.enter_mock();
+
+        let is_finalized = self.is_finalized.lock().unwrap();
+
+        if !*is_finalized {
+            unsafe {
+                napi::release_threadsafe_function(self.tsfn.0, mode);
+            }
+        }
     }
 }
 
 impl<T> Drop for ThreadsafeFunction<T> {
     fn drop(&mut self) {
+        #[cfg(feature = "mock")]
+        let _mock = self.enter_mock();
+
         let is_finalized = self.is_finalized.lock().unwrap();
 
         // tsfn was already finalized by `Environment::CleanupHandles()` in
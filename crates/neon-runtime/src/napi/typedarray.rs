@@ -0,0 +1,65 @@
+use crate::raw::{Env, Local};
+use std::mem::MaybeUninit;
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+
+use crate::napi::bindings as napi;
+
+pub use crate::napi::bindings::TypedArrayType;
+
+/// Information about the backing store of a `TypedArray`.
+pub struct TypedArrayInfo {
+    pub typ: TypedArrayType,
+    /// Number of elements in the array.
+    pub length: usize,
+    /// Pointer to the first element of the array.
+    pub data: *mut c_void,
+    pub arraybuffer: Local,
+    /// Offset in bytes of the first element within `arraybuffer`.
+    pub byte_offset: usize,
+}
+
+pub unsafe fn info(env: Env, obj: Local) -> TypedArrayInfo {
+    let mut typ = MaybeUninit::uninit();
+    let mut length = 0;
+    let mut data = null_mut();
+    let mut arraybuffer = MaybeUninit::uninit();
+    let mut byte_offset = 0;
+
+    assert_eq!(
+        napi::get_typedarray_info(
+            env,
+            obj,
+            typ.as_mut_ptr(),
+            &mut length as *mut _,
+            &mut data as *mut _,
+            arraybuffer.as_mut_ptr(),
+            &mut byte_offset as *mut _,
+        ),
+        napi::Status::Ok,
+    );
+
+    TypedArrayInfo {
+        typ: typ.assume_init(),
+        length,
+        data,
+        arraybuffer: arraybuffer.assume_init(),
+        byte_offset,
+    }
+}
+
+/// Size in bytes of a single element of a `TypedArray` of type `typ`.
+pub fn element_size(typ: TypedArrayType) -> usize {
+    match typ {
+        TypedArrayType::Int8Array
+        | TypedArrayType::Uint8Array
+        | TypedArrayType::Uint8ClampedArray => 1,
+        TypedArrayType::Int16Array | TypedArrayType::Uint16Array => 2,
+        TypedArrayType::Int32Array | TypedArrayType::Uint32Array | TypedArrayType::Float32Array => {
+            4
+        }
+        TypedArrayType::Float64Array
+        | TypedArrayType::BigInt64Array
+        | TypedArrayType::BigUint64Array => 8,
+    }
+}
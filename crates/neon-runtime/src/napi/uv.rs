@@ -0,0 +1,17 @@
+use std::mem::MaybeUninit;
+use std::os::raw::c_void;
+
+use crate::napi::bindings as napi;
+use crate::raw::Env;
+
+/// Returns the libuv event loop of the environment, a `uv_loop_t*`.
+pub unsafe fn event_loop(env: Env) -> *mut c_void {
+    let mut result = MaybeUninit::uninit();
+
+    assert_eq!(
+        napi::get_uv_event_loop(env, result.as_mut_ptr()),
+        napi::Status::Ok,
+    );
+
+    result.assume_init().cast()
+}
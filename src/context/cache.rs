@@ -0,0 +1,150 @@
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::context::{Context, ContextLocal};
+
+/// A map of values that are expensive to create, kept separately for each
+/// instance of a module.
+///
+/// An `EnvCache` is a [`ContextLocal`] holding a `HashMap`, for values that
+/// are built on demand and reused across calls, such as compiled regular
+/// expressions, prepared statements or [rooted](crate::handle::Root)
+/// constructors. Since each instance, e.g., each [worker thread][workers],
+/// has its own map, the values may be tied to the JavaScript environment that
+/// created them. They are kept until the instance is unloaded, so a cached
+/// `Root` keeps its object alive until then.
+///
+/// ```
+/// # #[cfg(feature = "napi-6")] {
+/// # use neon::prelude::*;
+/// use neon::context::EnvCache;
+///
+/// static CLASSES: EnvCache<String, Root<JsFunction>> = EnvCache::new();
+///
+/// fn construct(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let name = cx.argument::<JsString>(0)?.value(&mut cx);
+///     let class = CLASSES.get_or_try_init(&mut cx, name.clone(), |cx| {
+///         let class = cx.global().get(cx, name.as_str())?;
+///         let class = class.downcast_or_throw::<JsFunction, _>(cx)?;
+///
+///         NeonResult::Ok(class.root(cx))
+///     })?;
+///
+///     let class = class.to_inner(&mut cx);
+///
+///     class.construct(&mut cx, Vec::<Handle<JsValue>>::new())
+/// }
+/// # }
+/// ```
+///
+/// Values are never removed before the instance is unloaded, so that the
+/// references returned by the cache stay valid for the lifetime of the
+/// context. Caches with an unbounded set of keys should store values that can
+/// be cleared, e.g., `RefCell<Option<V>>`.
+///
+/// [workers]: https://nodejs.org/api/worker_threads.html
+pub struct EnvCache<K, V> {
+    local: ContextLocal<RefCell<HashMap<K, Box<V>>>>,
+}
+
+impl<K, V> EnvCache<K, V>
+where
+    K: Eq + Hash + Send + 'static,
+    V: Send + 'static,
+{
+    /// Creates a new cache, which is empty in every instance.
+    pub const fn new() -> Self {
+        Self {
+            local: ContextLocal::new(),
+        }
+    }
+
+    /// Returns the value for `key` in the current instance of the module, if
+    /// it has been initialized.
+    pub fn get<'cx, C, Q>(&self, cx: &mut C, key: &Q) -> Option<&'cx V>
+    where
+        C: Context<'cx>,
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let map = self.map(cx).borrow();
+
+        map.get(key).map(|value| extend(&**value))
+    }
+
+    /// Returns the value for `key` in the current instance of the module,
+    /// calling `f` to initialize it if necessary.
+    ///
+    /// If `f` initializes the same key, the value it stores is returned and
+    /// the value returned by `f` is dropped.
+    pub fn get_or_init<'cx, C, F>(&self, cx: &mut C, key: K, f: F) -> &'cx V
+    where
+        C: Context<'cx>,
+        F: FnOnce(&mut C) -> V,
+    {
+        match self.get_or_try_init(cx, key, |cx| Ok::<_, std::convert::Infallible>(f(cx))) {
+            Ok(value) => value,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Returns the value for `key` in the current instance of the module,
+    /// calling `f` to initialize it if necessary. If `f` fails, the key is
+    /// left uninitialized and the error is returned.
+    ///
+    /// If `f` initializes the same key, the value it stores is returned and
+    /// the value returned by `f` is dropped.
+    pub fn get_or_try_init<'cx, C, E, F>(&self, cx: &mut C, key: K, f: F) -> Result<&'cx V, E>
+    where
+        C: Context<'cx>,
+        F: FnOnce(&mut C) -> Result<V, E>,
+    {
+        if let Some(value) = self.get(cx, &key) {
+            return Ok(value);
+        }
+
+        // The map is not borrowed while `f` runs, so that it can use the cache
+        let value = f(cx)?;
+        let mut map = self.map(cx).borrow_mut();
+
+        Ok(extend(&**map.entry(key).or_insert_with(|| Box::new(value))))
+    }
+
+    /// Returns the number of values in the current instance of the module.
+    pub fn len<'cx, C: Context<'cx>>(&self, cx: &mut C) -> usize {
+        self.map(cx).borrow().len()
+    }
+
+    /// Returns `true` if the current instance of the module has no values.
+    pub fn is_empty<'cx, C: Context<'cx>>(&self, cx: &mut C) -> bool {
+        self.len(cx) == 0
+    }
+
+    fn map<'cx, C: Context<'cx>>(&self, cx: &mut C) -> &'cx RefCell<HashMap<K, Box<V>>> {
+        self.local.get_or_init(cx, Default::default)
+    }
+}
+
+impl<K, V> Default for EnvCache<K, V>
+where
+    K: Eq + Hash + Send + 'static,
+    V: Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> std::fmt::Debug for EnvCache<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("EnvCache").finish()
+    }
+}
+
+// Values are boxed so that their address is stable while the map grows, and
+// are only dropped with the instance, which outlives every context
+fn extend<'cx, V>(value: &V) -> &'cx V {
+    unsafe { &*(value as *const V) }
+}
@@ -1,5 +1,7 @@
 use super::ModuleContext;
 use crate::handle::Handle;
+#[cfg(feature = "napi-1")]
+use crate::handle::Managed;
 #[cfg(feature = "legacy-runtime")]
 use crate::object::class::ClassMap;
 use crate::result::NeonResult;
@@ -33,6 +35,45 @@ thread_local! {
     pub(crate) static IS_RUNNING: RefCell<bool> = RefCell::new(false);
 }
 
+#[cfg(all(feature = "napi-1", debug_assertions))]
+thread_local! {
+    // The environment of the innermost scope on this thread, which stamps
+    // each handle that is created
+    static CURRENT_ENV: Cell<raw::Env> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+/// Returns the environment of the innermost scope on this thread, or null if
+/// there is none.
+#[cfg(all(feature = "napi-1", debug_assertions))]
+pub(crate) fn current_env() -> raw::Env {
+    CURRENT_ENV.with(Cell::get)
+}
+
+// Makes an environment current until it is dropped, restoring the previous one
+#[cfg(all(feature = "napi-1", debug_assertions))]
+struct EnterEnv(raw::Env);
+
+#[cfg(all(feature = "napi-1", debug_assertions))]
+impl EnterEnv {
+    fn new(env: Env) -> Self {
+        Self(CURRENT_ENV.with(|current| current.replace(env.to_raw())))
+    }
+}
+
+#[cfg(all(feature = "napi-1", debug_assertions))]
+impl Drop for EnterEnv {
+    fn drop(&mut self) {
+        CURRENT_ENV.with(|current| current.set(self.0));
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl From<raw::Env> for Env {
+    fn from(env: raw::Env) -> Self {
+        Self(env)
+    }
+}
+
 #[cfg(feature = "legacy-runtime")]
 extern "C" fn drop_class_map(map: Box<ClassMap>) {
     std::mem::drop(map);
@@ -75,6 +116,57 @@ impl Env {
 pub struct ScopeMetadata {
     env: Env,
     active: Cell<bool>,
+    // Points to the `Arena` on the stack of `Scope::with`, which outlives every
+    // borrow of the scope
+    #[cfg(feature = "napi-1")]
+    arena: *const Arena,
+}
+
+/// Storage for strings borrowed from JavaScript for the duration of a scope.
+///
+/// Strings are copied into large chunks so that reading many short strings
+/// allocates rarely. A chunk is never reallocated and is only freed when the
+/// scope exits, so strings in the arena are valid for the lifetime of the
+/// scope.
+#[cfg(feature = "napi-1")]
+#[derive(Default)]
+pub struct Arena {
+    chunks: RefCell<Vec<Vec<u8>>>,
+}
+
+#[cfg(feature = "napi-1")]
+impl Arena {
+    pub(crate) const CHUNK_SIZE: usize = 4096;
+
+    /// Reserves `len` bytes and calls `fill` to write UTF-8 into them,
+    /// returning the number of bytes written.
+    ///
+    /// # Safety
+    /// `fill` must write valid UTF-8 and return a length no greater than `len`
+    pub(crate) unsafe fn alloc_str<F>(&self, len: usize, fill: F) -> &str
+    where
+        F: FnOnce(*mut u8, usize) -> usize,
+    {
+        let mut chunks = self.chunks.borrow_mut();
+        let has_space = match chunks.last() {
+            Some(chunk) => chunk.capacity() - chunk.len() >= len,
+            None => false,
+        };
+
+        if !has_space {
+            chunks.push(Vec::with_capacity(len.max(Self::CHUNK_SIZE)));
+        }
+
+        let chunk = chunks.last_mut().unwrap();
+        let start = chunk.len();
+        let ptr = chunk.as_mut_ptr().add(start);
+        let written = fill(ptr, len);
+
+        debug_assert!(written <= len);
+        chunk.set_len(start + written);
+
+        std::str::from_utf8_unchecked(std::slice::from_raw_parts(ptr, written))
+    }
 }
 
 pub struct Scope<'a, R: Root + 'static> {
@@ -83,7 +175,14 @@ pub struct Scope<'a, R: Root + 'static> {
 }
 
 impl<'a, R: Root + 'static> Scope<'a, R> {
+    #[cfg_attr(all(feature = "napi-1", feature = "diagnostics"), track_caller)]
     pub fn with<T, F: for<'b> FnOnce(Scope<'b, R>) -> T>(env: Env, f: F) -> T {
+        #[cfg(all(feature = "napi-1", feature = "diagnostics"))]
+        let _tracked = crate::diagnostics::Tracked::scope();
+        #[cfg(feature = "napi-1")]
+        let arena = Arena::default();
+        #[cfg(all(feature = "napi-1", debug_assertions))]
+        let _env = EnterEnv::new(env);
         let mut handle_scope: R = unsafe { R::allocate() };
         unsafe {
             handle_scope.enter(env.to_raw());
@@ -93,6 +192,8 @@ impl<'a, R: Root + 'static> Scope<'a, R> {
                 metadata: ScopeMetadata {
                     env,
                     active: Cell::new(true),
+                    #[cfg(feature = "napi-1")]
+                    arena: &arena,
                 },
                 handle_scope: &mut handle_scope,
             };
@@ -116,6 +217,13 @@ pub trait ContextInternal<'a>: Sized {
         self.scope_metadata().active.get()
     }
 
+    #[cfg(feature = "napi-1")]
+    fn arena(&self) -> &'a Arena {
+        // Safety: The arena is owned by `Scope::with`, which returns only
+        // after `'a` has ended
+        unsafe { &*self.scope_metadata().arena }
+    }
+
     fn check_active(&self) {
         if !self.is_active() {
             panic!("execution context is inactive");
@@ -239,7 +347,7 @@ pub fn initialize_module(exports: Handle<JsObject>, init: fn(ModuleContext) -> N
 #[cfg(feature = "napi-1")]
 pub fn initialize_module(
     env: raw::Env,
-    exports: Handle<JsObject>,
+    exports: raw::Local,
     init: fn(ModuleContext) -> NeonResult<()>,
 ) {
     unsafe {
@@ -250,7 +358,23 @@ pub fn initialize_module(
         *v.borrow_mut() = true;
     });
 
-    ModuleContext::with(Env(env), exports, |cx| {
-        let _ = init(cx);
+    let env = Env(env);
+    let exports = Handle::new_internal(JsObject::from_raw(env, exports));
+
+    let exported = ModuleContext::with(env, exports, |mut cx| {
+        crate::runtime::detect(&mut cx);
+        crate::macro_internal::export::export_all(&mut cx).is_ok()
     });
+
+    if !exported {
+        return;
+    }
+
+    // Each function marked with `#[neon::main]` gets its own context; an error
+    // stops the functions after it from running
+    for main in crate::macro_internal::export::mains(init) {
+        if ModuleContext::with(env, exports, main).is_err() {
+            break;
+        }
+    }
 }
@@ -0,0 +1,128 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::context::Context;
+use crate::lifecycle::InstanceData;
+
+// Ids are assigned lazily so that keys can be created in a `static`. Zero
+// marks a key that has not been assigned an id yet.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// A value that is unique to each instance of a module, such as the main
+/// thread and each [worker thread][workers] that loads the module.
+///
+/// A `static` that is initialized on first use (e.g., with `OnceCell`) is
+/// shared by every instance of a module. Values tied to a single JavaScript
+/// environment, such as a [`Root`](crate::handle::Root) or a
+/// [`Channel`](crate::event::Channel), may not be shared across instances.
+/// A `ContextLocal` is initialized separately for each instance and its value
+/// is dropped when that instance is unloaded.
+///
+/// ```
+/// # #[cfg(feature = "napi-6")] {
+/// # use neon::prelude::*;
+/// use neon::context::ContextLocal;
+///
+/// static COUNTER: ContextLocal<std::cell::Cell<u32>> = ContextLocal::new();
+///
+/// fn increment(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let counter = COUNTER.get_or_init(&mut cx, Default::default);
+///
+///     counter.set(counter.get() + 1);
+///
+///     Ok(cx.number(counter.get()))
+/// }
+/// # }
+/// ```
+///
+/// [workers]: https://nodejs.org/api/worker_threads.html
+pub struct ContextLocal<T> {
+    id: AtomicUsize,
+    _marker: PhantomData<T>,
+}
+
+// Values are only accessed on the JavaScript thread that owns them and are
+// required to be `Send` so that they can be dropped when the module unloads.
+unsafe impl<T> Sync for ContextLocal<T> {}
+
+impl<T: Any + Send + 'static> ContextLocal<T> {
+    /// Creates a new, uninitialized key.
+    pub const fn new() -> Self {
+        Self {
+            id: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the value for the current instance of the module, if it has
+    /// been initialized.
+    pub fn get<'cx, C: Context<'cx>>(&self, cx: &mut C) -> Option<&'cx T> {
+        InstanceData::local(cx, self.id())
+            .as_ref()
+            .map(|value| downcast(&**value))
+    }
+
+    /// Returns the value for the current instance of the module, calling `f`
+    /// to initialize it if necessary.
+    ///
+    /// If `f` initializes the same key, the value it stores is returned and
+    /// the value returned by `f` is dropped.
+    pub fn get_or_init<'cx, C, F>(&self, cx: &mut C, f: F) -> &'cx T
+    where
+        C: Context<'cx>,
+        F: FnOnce() -> T,
+    {
+        if let Some(value) = self.get(cx) {
+            return value;
+        }
+
+        let value = f();
+        // `f` may have initialized other keys and resized the table, so the
+        // slot is fetched again
+        let slot = InstanceData::local(cx, self.id());
+
+        downcast(&**slot.get_or_insert_with(|| Box::new(value)))
+    }
+
+    fn id(&self) -> usize {
+        let id = self.id.load(Ordering::Acquire);
+
+        if id != 0 {
+            return id;
+        }
+
+        let next = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        match self
+            .id
+            .compare_exchange(0, next, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => next,
+            // Another thread assigned an id first
+            Err(id) => id,
+        }
+    }
+}
+
+impl<T: Any + Send + 'static> Default for ContextLocal<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for ContextLocal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ContextLocal").finish()
+    }
+}
+
+// Values are boxed so that their address is stable while the table grows
+fn downcast<'cx, T: Any>(value: &(dyn Any + Send)) -> &'cx T {
+    let value = value
+        .downcast_ref::<T>()
+        // `unwrap` will not panic because each id is only used by one key
+        .unwrap();
+
+    unsafe { &*(value as *const T) }
+}
@@ -136,6 +136,11 @@
 //! [`Context::try_catch`](Context::try_catch) method, which catches any thrown
 //! exception and restores the context to non-throwing state.
 //!
+//! Cleanup code can check whether the thread is throwing with
+//! [`Context::is_throwing`](Context::is_throwing), and inspect the exception with
+//! [`Context::pending_exception`](Context::pending_exception), without catching it.
+//! Both may be called while the thread is throwing.
+//!
 //! ## See also
 //!
 //! 1. Ecma International. [Execution contexts](https://tc39.es/ecma262/#sec-execution-contexts), _ECMAScript Language Specification_.
@@ -146,41 +151,73 @@
 //! [iterator]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Iterators_and_Generators
 //! [question-mark]: https://doc.rust-lang.org/edition-guide/rust-2018/error-handling-and-panics/the-question-mark-operator-for-easier-error-handling.html
 
+#[cfg(feature = "napi-6")]
+mod cache;
 pub(crate) mod internal;
+#[cfg(feature = "napi-6")]
+mod local;
 
 use crate::borrow::internal::Ledger;
 use crate::borrow::{Borrow, BorrowMut, Ref, RefMut};
 use crate::context::internal::Env;
+#[cfg(feature = "napi-1")]
+use crate::event::schedule;
 #[cfg(all(feature = "napi-4", feature = "channel-api"))]
 use crate::event::Channel;
+#[cfg(all(feature = "napi-1", feature = "task-api"))]
+use crate::event::TaskBuilder;
 use crate::handle::{Handle, Managed};
 #[cfg(all(feature = "napi-6", feature = "channel-api"))]
 use crate::lifecycle::InstanceData;
+#[cfg(feature = "napi-1")]
+use crate::macro_internal::export::PendingExport;
+#[cfg(feature = "napi-1")]
+use crate::macro_internal::test::PendingTest;
 #[cfg(feature = "legacy-runtime")]
 use crate::object::class::Class;
 use crate::object::{Object, This};
 use crate::result::{JsResult, NeonResult, Throw};
+#[cfg(feature = "napi-1")]
+use crate::types::binary::{self, InvalidUtf8};
 use crate::types::binary::{JsArrayBuffer, JsBuffer};
 #[cfg(feature = "napi-1")]
 use crate::types::boxed::{Finalize, JsBox};
 #[cfg(feature = "napi-5")]
 use crate::types::date::{DateError, JsDate};
 use crate::types::error::JsError;
+#[cfg(all(feature = "napi-1", feature = "promise-api"))]
+use crate::types::{Deferred, JsPromise};
 use crate::types::{
     JsArray, JsBoolean, JsFunction, JsNull, JsNumber, JsObject, JsString, JsUndefined, JsValue,
     StringResult, Value,
 };
 use neon_runtime;
 use neon_runtime::raw;
+#[cfg(feature = "napi-1")]
+use smallvec::SmallVec;
 use std;
+#[cfg(feature = "napi-1")]
+use std::any::Any;
 use std::cell::RefCell;
 use std::convert::Into;
 use std::marker::PhantomData;
+#[cfg(feature = "napi-1")]
+use std::mem::MaybeUninit;
+#[cfg(feature = "napi-1")]
+use std::ops::{Deref, DerefMut};
 use std::os::raw::c_void;
 use std::panic::UnwindSafe;
 
 use self::internal::{ContextInternal, Scope, ScopeMetadata};
 
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::cache::EnvCache;
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::local::ContextLocal;
+
 #[repr(C)]
 pub(crate) struct CallbackInfo<'a> {
     info: raw::FunctionCallbackInfo,
@@ -188,6 +225,15 @@ pub(crate) struct CallbackInfo<'a> {
 }
 
 impl CallbackInfo<'_> {
+    /// Wraps the callback info of a call made by `neon::test::MockContext`.
+    #[cfg(feature = "mock-context")]
+    pub(crate) fn from_raw(info: raw::FunctionCallbackInfo) -> Self {
+        Self {
+            info,
+            _lifetime: PhantomData,
+        }
+    }
+
     pub fn data(&self, env: Env) -> *mut c_void {
         unsafe {
             let mut raw_data: *mut c_void = std::mem::zeroed();
@@ -204,6 +250,16 @@ impl CallbackInfo<'_> {
         CallContext::<T>::with(env, self, f)
     }
 
+    #[cfg(feature = "napi-1")]
+    pub unsafe fn with_cx_data<T: This, U, F: for<'a> FnOnce(CallContext<'a, T>) -> U>(
+        &self,
+        env: Env,
+        data: &(dyn Any + Send),
+        f: F,
+    ) -> U {
+        CallContext::<T>::with_data(env, self, Some(data), f)
+    }
+
     #[cfg(feature = "legacy-runtime")]
     pub fn set_return<T: Value>(&self, value: Handle<T>) {
         unsafe { neon_runtime::call::set_return(self.info, value.to_raw()) }
@@ -227,6 +283,14 @@ impl CallbackInfo<'_> {
         }
     }
 
+    #[cfg(feature = "napi-1")]
+    fn new_target<'b, C: Context<'b>>(&self, cx: &C) -> Option<Handle<'b, JsFunction>> {
+        let env = cx.env();
+
+        unsafe { neon_runtime::call::new_target(env.to_raw(), self.info) }
+            .map(|target| Handle::new_internal(JsFunction::from_raw(env, target)))
+    }
+
     pub fn len<'b, C: Context<'b>>(&self, cx: &C) -> i32 {
         unsafe { neon_runtime::call::len(cx.env().to_raw(), self.info) }
     }
@@ -258,6 +322,19 @@ impl CallbackInfo<'_> {
     }
 }
 
+/// Aborts the process with a fatal error, printing `location` and `message`.
+///
+/// This is a wrapper for [`napi_fatal_error`][fatal], which does not require
+/// a context, so it may be called from any thread. Within a Neon function,
+/// prefer [`Context::fatal`], which reports the location of the caller.
+///
+/// [fatal]: https://nodejs.org/api/n-api.html#n_api_napi_fatal_error
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub fn fatal_error(location: &str, message: &str) -> ! {
+    unsafe { neon_runtime::error::fatal_error(location, message) }
+}
+
 /// Indicates whether a function was called with `new`.
 #[derive(Clone, Copy, Debug)]
 pub enum CallKind {
@@ -367,6 +444,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     /// Handles created in the new scope are kept alive only for the duration of the computation and cannot escape.
     ///
     /// This method can be useful for limiting the life of temporary values created during long-running computations, to prevent leaks.
+    #[cfg_attr(all(feature = "napi-1", feature = "diagnostics"), track_caller)]
     fn execute_scoped<T, F>(&self, f: F) -> T
     where
         F: for<'b> FnOnce(ExecuteContext<'b>) -> T,
@@ -383,6 +461,7 @@ pub trait Context<'a>: ContextInternal<'a> {
     /// Handles created in the new scope are kept alive only for the duration of the computation and cannot escape, with the exception of the result value, which is rooted in the outer context.
     ///
     /// This method can be useful for limiting the life of temporary values created during long-running computations, to prevent leaks.
+    #[cfg_attr(all(feature = "napi-1", feature = "diagnostics"), track_caller)]
     fn compute_scoped<V, F>(&self, f: F) -> JsResult<'a, V>
     where
         V: Value,
@@ -475,6 +554,47 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsBuffer::new(self, size)
     }
 
+    /// Encodes a string as UTF-8 into a new `JsBuffer`, like
+    /// [`TextEncoder.encode()`][encode] but without calling into JavaScript.
+    ///
+    /// Lone surrogates are replaced with U+FFFD.
+    ///
+    /// [encode]: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder/encode
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    fn encode_utf8(&mut self, s: Handle<JsString>) -> JsResult<'a, JsBuffer> {
+        binary::encode_utf8(self, s)
+    }
+
+    /// Decodes the UTF-8 contents of a `JsBuffer` into a string, like
+    /// [`TextDecoder.decode()`][decode] but without calling into JavaScript.
+    ///
+    /// Unlike `TextDecoder`, a leading byte order mark is kept.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// use neon::types::InvalidUtf8;
+    ///
+    /// fn decode_strict(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let buffer = cx.argument::<JsBuffer>(0)?;
+    ///
+    ///     cx.decode_utf8(buffer, InvalidUtf8::Throw)
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [decode]: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/decode
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    fn decode_utf8(
+        &mut self,
+        buffer: Handle<JsBuffer>,
+        invalid: InvalidUtf8,
+    ) -> JsResult<'a, JsString> {
+        binary::decode_utf8(self, buffer, invalid)
+    }
+
     /// Convenience method for creating a `JsDate` value.
     #[cfg(feature = "napi-5")]
     #[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
@@ -489,8 +609,66 @@ pub trait Context<'a>: ContextInternal<'a> {
         })
     }
 
+    /// Returns `true` if a JavaScript exception is pending, i.e., an operation
+    /// has thrown and the exception has not been caught yet.
+    ///
+    /// Unlike [`try_catch`](Context::try_catch), this does not catch the
+    /// exception, so cleanup code can check for it and still let it propagate.
+    /// It may be called while the thread is throwing.
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    fn is_throwing(&mut self) -> bool {
+        unsafe { neon_runtime::error::is_throwing(self.env().to_raw()) }
+    }
+
+    /// Returns the pending JavaScript exception, if any, without clearing it.
+    ///
+    /// The exception is still pending afterwards; it continues to propagate
+    /// when the function returns `Err(Throw)`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn call_and_log(mut cx: FunctionContext) -> JsResult<JsValue> {
+    ///     let f = cx.argument::<JsFunction>(0)?;
+    ///     let this = cx.undefined();
+    ///     let args: Vec<Handle<JsValue>> = vec![];
+    ///     let result = f.call(&mut cx, this, args);
+    ///
+    ///     if let Some(err) = cx.pending_exception() {
+    ///         let message = err.to_string(&mut cx)?.value(&mut cx);
+    ///
+    ///         eprintln!("callback failed: {}", message);
+    ///     }
+    ///
+    ///     result
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    fn pending_exception(&mut self) -> Option<Handle<'a, JsValue>> {
+        let env = self.env().to_raw();
+        let mut local: MaybeUninit<raw::Local> = MaybeUninit::zeroed();
+
+        unsafe {
+            if !neon_runtime::error::catch_error(env, local.as_mut_ptr()) {
+                return None;
+            }
+
+            let local = local.assume_init();
+
+            // Throwing the same value again leaves the exception pending
+            neon_runtime::error::throw(env, local);
+
+            Some(Handle::new_internal(JsValue::from_raw(self.env(), local)))
+        }
+    }
+
     /// Throws a JS value.
     fn throw<T: Value, U>(&mut self, v: Handle<T>) -> NeonResult<U> {
+        v.check_env(self);
+
         unsafe {
             neon_runtime::error::throw(self.env().to_raw(), v.to_raw());
         }
@@ -512,6 +690,13 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsError::range_error(self, msg)
     }
 
+    /// Creates an instance of the [`SyntaxError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/SyntaxError) class.
+    #[cfg(feature = "napi-9")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-9")))]
+    fn syntax_error<S: AsRef<str>>(&mut self, msg: S) -> JsResult<'a, JsError> {
+        JsError::syntax_error(self, msg)
+    }
+
     /// Throws a direct instance of the [`Error`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/Error) class.
     fn throw_error<S: AsRef<str>, T>(&mut self, msg: S) -> NeonResult<T> {
         let err = JsError::error(self, msg)?;
@@ -530,6 +715,44 @@ pub trait Context<'a>: ContextInternal<'a> {
         self.throw(err)
     }
 
+    /// Throws an instance of the [`SyntaxError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/SyntaxError) class.
+    #[cfg(feature = "napi-9")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-9")))]
+    fn throw_syntax_error<S: AsRef<str>, T>(&mut self, msg: S) -> NeonResult<T> {
+        let err = JsError::syntax_error(self, msg)?;
+        self.throw(err)
+    }
+
+    /// Aborts the process with a fatal error, for invariants that cannot be
+    /// recovered from, e.g., when continuing would corrupt state.
+    ///
+    /// Unlike [`std::process::abort`], Node.js prints the message and the
+    /// location of the caller, and writes a diagnostic report if it is
+    /// enabled. See [`fatal_error`].
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn check_invariant(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let len = cx.argument::<JsArray>(0)?.len(&mut cx);
+    ///
+    ///     if len > 1024 {
+    ///         cx.fatal("the table is larger than its capacity");
+    ///     }
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    #[track_caller]
+    fn fatal<S: AsRef<str>>(&mut self, msg: S) -> ! {
+        let location = std::panic::Location::caller().to_string();
+
+        fatal_error(&location, msg.as_ref())
+    }
+
     #[cfg(feature = "napi-1")]
     /// Convenience method for wrapping a value in a `JsBox`.
     ///
@@ -551,6 +774,113 @@ pub trait Context<'a>: ContextInternal<'a> {
         JsBox::new(self, v)
     }
 
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Returns the N-API version supported by the host process, which may be
+    /// newer than the version the module was compiled for.
+    ///
+    /// See [`neon::feature`](crate::feature) to check for specific features.
+    fn napi_version(&mut self) -> u32 {
+        crate::feature::napi_version()
+    }
+
+    /// Returns the libuv event loop of the current environment, a raw
+    /// `uv_loop_t*`.
+    ///
+    /// This allows integrating libuv-based libraries (e.g., custom polls,
+    /// timers or file system events) with the event loop of the addon, by
+    /// casting the pointer to the `uv_loop_t` type of a libuv binding. The
+    /// loop must only be used on the JavaScript thread of the environment.
+    #[cfg(all(feature = "napi-2", feature = "sys"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "napi-2", feature = "sys"))))]
+    fn uv_loop(&mut self) -> *mut c_void {
+        unsafe { neon_runtime::uv::event_loop(self.env().to_raw()) }
+    }
+
+    /// Calls `f` with the raw `napi_env` of the context, e.g., to pass it to
+    /// another N-API wrapper or to hand-written C.
+    ///
+    /// Handles created by `f` in the current handle scope are released with
+    /// the context. If `f` leaves an exception pending, the context is
+    /// throwing when this returns and should return `Err(Throw)`.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "napi-1", feature = "sys"))] {
+    /// # use neon::prelude::*;
+    /// use neon::sys;
+    ///
+    /// # unsafe extern "C" fn native_init(_: sys::Env, _: sys::Value) {}
+    /// // A function of a C library that defines its exports with N-API, e.g.,
+    /// // `extern "C" { fn native_init(env: sys::Env, exports: sys::Value); }`
+    /// fn init(mut cx: FunctionContext) -> JsResult<JsObject> {
+    ///     let exports = cx.empty_object();
+    ///
+    ///     cx.with_raw_env(|env| unsafe { native_init(env, exports.to_raw()) });
+    ///
+    ///     Ok(exports)
+    /// }
+    /// # }
+    /// ```
+    #[cfg(all(feature = "napi-1", feature = "sys"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sys")))]
+    fn with_raw_env<T, F>(&mut self, f: F) -> T
+    where
+        F: FnOnce(crate::sys::Env) -> T,
+    {
+        f(self.env().to_raw())
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Reports a change of `delta` bytes in native memory that is kept alive by
+    /// JavaScript objects, so that the garbage collector can account for it when
+    /// deciding to collect. Returns the total reported for this environment.
+    ///
+    /// Every increase should be matched by a decrease once the memory is freed,
+    /// typically in [`Finalize::finalize`]. With the `external-memory` feature,
+    /// the size of each value in a [`JsBox`] and the length of each external
+    /// buffer are reported automatically.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// struct Image(Vec<u8>);
+    ///
+    /// impl Finalize for Image {
+    ///     fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+    ///         cx.adjust_external_memory(-(self.0.len() as i64));
+    ///     }
+    /// }
+    ///
+    /// fn load_image(mut cx: FunctionContext) -> JsResult<JsBox<Image>> {
+    ///     let image = Image(vec![0; 1 << 20]);
+    ///
+    ///     cx.adjust_external_memory(image.0.len() as i64);
+    ///
+    ///     Ok(cx.boxed(image))
+    /// }
+    /// # }
+    /// ```
+    fn adjust_external_memory(&mut self, delta: i64) -> i64 {
+        unsafe { neon_runtime::mem::adjust_external_memory(self.env().to_raw(), delta) }
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Returns `true` if the host allows buffers backed by memory owned by
+    /// Rust. Hosts with the V8 memory cage enabled, such as Electron 21 and
+    /// later, do not.
+    ///
+    /// [`JsBuffer::external`] and [`JsArrayBuffer::external`] copy the bytes
+    /// on these hosts instead, so the same binary works on either kind of
+    /// host. Check for support to choose a different strategy, e.g., to
+    /// create the buffer first and write into it, instead of copying.
+    fn supports_external_buffers(&mut self) -> bool {
+        unsafe { neon_runtime::arraybuffer::supports_external(self.env().to_raw()) }
+    }
+
     #[cfg(all(feature = "napi-4", feature = "channel-api"))]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "napi-4", feature = "channel-api"))))]
     /// Returns an unbounded channel for scheduling events to be executed on the JavaScript thread.
@@ -567,12 +897,196 @@ pub trait Context<'a>: ContextInternal<'a> {
         channel
     }
 
+    #[cfg(all(feature = "napi-4", feature = "channel-api"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "napi-4", feature = "channel-api"))))]
+    /// Drives `future` to completion on the JavaScript thread.
+    ///
+    /// The future does not need to be `Send`, so it may hold values that must
+    /// stay on the JavaScript thread, such as [`Root`](crate::handle::Root)s,
+    /// across `.await` points. It is polled by the event loop, first after the
+    /// current function returns and then each time it is woken, from any
+    /// thread. While it is polled, [`with_local_context`] provides a context
+    /// for working with JavaScript values. Node keeps running until the
+    /// future completes.
+    ///
+    /// [`with_local_context`]: crate::event::with_local_context
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+    /// # use neon::prelude::*;
+    /// # async fn fetch(_: String) -> String { todo!() }
+    /// use neon::event::with_local_context;
+    ///
+    /// fn fetch_and_call(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let url = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    ///
+    ///     cx.spawn_local(async move {
+    ///         let body = fetch(url).await;
+    ///
+    ///         let _ = with_local_context(|mut cx| -> NeonResult<()> {
+    ///             let callback = callback.into_inner(&mut cx);
+    ///             let this = cx.undefined();
+    ///             let body = cx.string(body);
+    ///
+    ///             callback.call(&mut cx, this, [body.upcast::<JsValue>()])?;
+    ///
+    ///             Ok(())
+    ///         });
+    ///     });
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// # }
+    /// ```
+    fn spawn_local<F>(&mut self, future: F)
+    where
+        F: std::future::Future<Output = ()> + 'static,
+    {
+        crate::event::local::spawn(self, future)
+    }
+
     #[cfg(all(feature = "napi-4", feature = "channel-api"))]
     #[deprecated(since = "0.9.0", note = "Please use the channel() method instead")]
     #[doc(hidden)]
     fn queue(&mut self) -> Channel {
         self.channel()
     }
+
+    #[cfg(all(feature = "napi-1", feature = "promise-api"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "promise-api")))]
+    /// Creates a [`JsPromise`] and the [`Deferred`] used to settle it.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "napi-1", feature = "promise-api"))] {
+    /// # use neon::prelude::*;
+    /// fn resolve_later(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let (deferred, promise) = cx.promise();
+    ///     let value = cx.number(42);
+    ///
+    ///     deferred.resolve(&mut cx, value);
+    ///
+    ///     Ok(promise)
+    /// }
+    /// # }
+    /// ```
+    fn promise(&mut self) -> (Deferred, Handle<'a, JsPromise>) {
+        JsPromise::new(self)
+    }
+
+    #[cfg(all(feature = "napi-1", feature = "task-api"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "task-api")))]
+    /// Creates a [`TaskBuilder`] for running `execute` on the Node worker pool.
+    ///
+    /// The task is not scheduled until it is given a completion step with
+    /// [`TaskBuilder::and_then`] or [`TaskBuilder::promise`].
+    fn task<'cx, O, E>(&'cx mut self, execute: E) -> TaskBuilder<'cx, Self, E>
+    where
+        'a: 'cx,
+        O: Send + 'static,
+        E: FnOnce() -> O + Send + 'static,
+    {
+        TaskBuilder::new(self, execute)
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Schedules `f` to run on the JavaScript thread as a [microtask][microtask],
+    /// after the currently executing JavaScript completes and before control
+    /// returns to the event loop.
+    ///
+    /// If `f` throws, the exception is reported as an uncaught exception.
+    ///
+    /// # Example:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn log_later(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let message = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///     cx.queue_microtask(move |mut cx| {
+    ///         let console = cx.global().get(&mut cx, "console")?;
+    ///         let console = console.downcast_or_throw::<JsObject, _>(&mut cx)?;
+    ///         let log = console.get(&mut cx, "log")?;
+    ///         let log = log.downcast_or_throw::<JsFunction, _>(&mut cx)?;
+    ///         let message = cx.string(message);
+    ///
+    ///         log.call(&mut cx, console, vec![message])?;
+    ///
+    ///         Ok(())
+    ///     })?;
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [microtask]: https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask
+    fn queue_microtask<F>(&mut self, f: F) -> NeonResult<()>
+    where
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        schedule::schedule(self, "queueMicrotask", None, f)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Schedules `f` to run on the JavaScript thread with `setImmediate`, after
+    /// pending I/O events in the current turn of the event loop.
+    ///
+    /// Returns the `Immediate` object, which may be passed to `clearImmediate`.
+    fn set_immediate<F>(&mut self, f: F) -> JsResult<'a, JsValue>
+    where
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        schedule::schedule(self, "setImmediate", None, f)
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Schedules `f` to run on the JavaScript thread with `setTimeout`, after at
+    /// least `ms` milliseconds.
+    ///
+    /// Returns the `Timeout` object, which may be passed to `clearTimeout`.
+    fn set_timeout<F>(&mut self, ms: u32, f: F) -> JsResult<'a, JsValue>
+    where
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        schedule::schedule(self, "setTimeout", Some(ms as f64), f)
+    }
+
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    /// Creates a deep copy of `value` with the [structured clone][clone]
+    /// algorithm, as with the global `structuredClone`. Values in `transfer`,
+    /// such as `ArrayBuffer`s, are moved to the copy and detached.
+    ///
+    /// A copy is useful to snapshot a mutable input, e.g., before keeping it
+    /// in a [`Root`](crate::handle::Root) after the call returns.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn snapshot(mut cx: FunctionContext) -> JsResult<JsValue> {
+    ///     let input = cx.argument::<JsObject>(0)?;
+    ///
+    ///     cx.structured_clone(input, &[])
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [clone]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm
+    fn structured_clone<'b, V: Value>(
+        &mut self,
+        value: Handle<'b, V>,
+        transfer: &[Handle<'b, JsValue>],
+    ) -> JsResult<'a, JsValue> {
+        crate::transfer::structured_clone(self, value, transfer)
+    }
 }
 
 /// An execution context of module initialization.
@@ -580,8 +1094,12 @@ pub struct ModuleContext<'a> {
     #[cfg(feature = "legacy-runtime")]
     scope: Scope<'a, raw::HandleScope>,
     #[cfg(feature = "napi-1")]
-    scope: Scope<'a, raw::InheritedHandleScope>,
+    cx: Cx<'a>,
     exports: Handle<'a, JsObject>,
+    #[cfg(feature = "napi-1")]
+    pending_exports: Vec<PendingExport<'a>>,
+    #[cfg(feature = "napi-1")]
+    pending_tests: Vec<PendingTest>,
 }
 
 impl<'a> UnwindSafe for ModuleContext<'a> {}
@@ -604,7 +1122,20 @@ impl<'a> ModuleContext<'a> {
                     <= std::mem::align_of::<raw::HandleScope>()
             );
         }
-        Scope::with(env, |scope| f(ModuleContext { scope, exports }))
+        #[cfg(feature = "legacy-runtime")]
+        let result = Scope::with(env, |scope| f(ModuleContext { scope, exports }));
+
+        #[cfg(feature = "napi-1")]
+        let result = Cx::with(env, |cx| {
+            f(ModuleContext {
+                cx,
+                exports,
+                pending_exports: Vec::new(),
+                pending_tests: Vec::new(),
+            })
+        });
+
+        result
     }
 
     /// Convenience method for exporting a Neon function from a module.
@@ -636,12 +1167,28 @@ impl<'a> ModuleContext<'a> {
     pub fn exports_object(&mut self) -> JsResult<'a, JsObject> {
         Ok(self.exports)
     }
+
+    #[cfg(feature = "napi-1")]
+    pub(crate) fn pending_exports(&mut self) -> &mut Vec<PendingExport<'a>> {
+        &mut self.pending_exports
+    }
+
+    #[cfg(feature = "napi-1")]
+    pub(crate) fn pending_tests(&mut self) -> &mut Vec<PendingTest> {
+        &mut self.pending_tests
+    }
 }
 
 impl<'a> ContextInternal<'a> for ModuleContext<'a> {
+    #[cfg(feature = "legacy-runtime")]
     fn scope_metadata(&self) -> &ScopeMetadata {
         &self.scope.metadata
     }
+
+    #[cfg(feature = "napi-1")]
+    fn scope_metadata(&self) -> &ScopeMetadata {
+        self.cx.scope_metadata()
+    }
 }
 
 impl<'a> Context<'a> for ModuleContext<'a> {}
@@ -652,6 +1199,7 @@ pub struct ExecuteContext<'a> {
 }
 
 impl<'a> ExecuteContext<'a> {
+    #[cfg_attr(all(feature = "napi-1", feature = "diagnostics"), track_caller)]
     pub(crate) fn with<T, C: Context<'a>, F: for<'b> FnOnce(ExecuteContext<'b>) -> T>(
         cx: &C,
         f: F,
@@ -676,6 +1224,7 @@ pub struct ComputeContext<'a, 'outer> {
 }
 
 impl<'a, 'b> ComputeContext<'a, 'b> {
+    #[cfg_attr(all(feature = "napi-1", feature = "diagnostics"), track_caller)]
     pub(crate) fn with<T, C: Context<'a>, F: for<'c, 'd> FnOnce(ComputeContext<'c, 'd>) -> T>(
         cx: &C,
         f: F,
@@ -705,10 +1254,12 @@ pub struct CallContext<'a, T: This> {
     #[cfg(feature = "legacy-runtime")]
     scope: Scope<'a, raw::HandleScope>,
     #[cfg(feature = "napi-1")]
-    scope: Scope<'a, raw::InheritedHandleScope>,
+    cx: Cx<'a>,
     info: &'a CallbackInfo<'a>,
     #[cfg(feature = "napi-1")]
-    arguments: Option<neon_runtime::call::Arguments>,
+    arguments: Option<SmallVec<[Handle<'a, JsValue>; 4]>>,
+    #[cfg(feature = "napi-1")]
+    data: Option<&'a (dyn Any + Send)>,
     phantom_type: PhantomData<T>,
 }
 
@@ -726,17 +1277,100 @@ impl<'a, T: This> CallContext<'a, T> {
         kind
     }
 
+    /// Produces `new.target`, the constructor that was invoked with `new`, or
+    /// `None` if the function was called without `new`.
+    ///
+    /// When a constructor is extended in JavaScript and called with `super()`,
+    /// `new.target` is the subclass rather than the function itself.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn person(mut cx: FunctionContext) -> JsResult<JsObject> {
+    ///     if cx.new_target().is_none() {
+    ///         return cx.throw_type_error("`Person` must be called with `new`");
+    ///     }
+    ///
+    ///     let this = cx.this();
+    ///     let name = cx.argument::<JsString>(0)?;
+    ///
+    ///     this.set(&mut cx, "name", name)?;
+    ///
+    ///     Ok(this)
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn new_target(&mut self) -> Option<Handle<'a, JsFunction>> {
+        self.info.new_target(self)
+    }
+
+    /// Produces the data attached to the function with
+    /// [`JsFunction::with_data`](crate::types::JsFunction::with_data), or
+    /// `None` if the function has no data or it is not a `D`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// struct Greeting(String);
+    ///
+    /// fn greet(mut cx: FunctionContext) -> JsResult<JsString> {
+    ///     let greeting = match cx.data::<Greeting>() {
+    ///         Some(greeting) => greeting.0.clone(),
+    ///         None => return cx.throw_type_error("missing greeting"),
+    ///     };
+    ///     let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///     Ok(cx.string(format!("{}, {}!", greeting, name)))
+    /// }
+    ///
+    /// fn greeter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    ///     let greeting = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///
+    ///     JsFunction::with_data(&mut cx, Greeting(greeting), greet)
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn data<D: Any>(&self) -> Option<&'a D> {
+        self.data?.downcast_ref()
+    }
+
     pub(crate) fn with<U, F: for<'b> FnOnce(CallContext<'b, T>) -> U>(
         env: Env,
         info: &'a CallbackInfo<'a>,
         f: F,
     ) -> U {
-        Scope::with(env, |scope| {
+        #[cfg(feature = "legacy-runtime")]
+        let result = Scope::with(env, |scope| {
             f(CallContext {
                 scope,
                 info,
-                #[cfg(feature = "napi-1")]
+                phantom_type: PhantomData,
+            })
+        });
+
+        #[cfg(feature = "napi-1")]
+        let result = Self::with_data(env, info, None, f);
+
+        result
+    }
+
+    #[cfg(feature = "napi-1")]
+    pub(crate) fn with_data<U, F: for<'b> FnOnce(CallContext<'b, T>) -> U>(
+        env: Env,
+        info: &'a CallbackInfo<'a>,
+        data: Option<&'a (dyn Any + Send)>,
+        f: F,
+    ) -> U {
+        Cx::with(env, |cx| {
+            f(CallContext {
+                cx,
+                info,
                 arguments: None,
+                data,
                 phantom_type: PhantomData,
             })
         })
@@ -761,18 +1395,50 @@ impl<'a, T: This> CallContext<'a, T> {
 
         #[cfg(feature = "napi-1")]
         {
-            let argv = if let Some(argv) = self.arguments.as_ref() {
-                argv
-            } else {
-                let argv = self.info.argv(self);
-                self.arguments.insert(argv)
-            };
-
-            argv.get(i as usize)
-                .map(|v| Handle::new_internal(JsValue::from_raw(self.env(), v)))
+            self.args().get(i as usize).copied()
         }
     }
 
+    /// Produces every argument that was passed to the function, in order.
+    ///
+    /// The arguments are fetched from the engine once per call, so this is
+    /// cheaper than calling [`argument`](CallContext::argument) for each of
+    /// many arguments. The handles are `Copy` and may be copied out of the
+    /// slice to keep using them with the context.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let args = cx.args().to_vec();
+    ///     let mut sum = 0.0;
+    ///
+    ///     for arg in args {
+    ///         sum += arg.downcast_or_throw::<JsNumber, _>(&mut cx)?.value(&mut cx);
+    ///     }
+    ///
+    ///     Ok(cx.number(sum))
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn args(&mut self) -> &[Handle<'a, JsValue>] {
+        if self.arguments.is_none() {
+            let env = self.env();
+            let argv = self.info.argv(self);
+            let args = argv
+                .as_slice()
+                .iter()
+                .map(|&arg| Handle::new_internal(JsValue::from_raw(env, arg)))
+                .collect();
+
+            self.arguments = Some(args);
+        }
+
+        self.arguments.as_ref().unwrap()
+    }
+
     /// Produces the `i`th argument and casts it to the type `V`, or throws an exception if `i` is greater than or equal to `self.len()` or cannot be cast to `V`.
     pub fn argument<V: Value>(&mut self, i: i32) -> JsResult<'a, V> {
         match self.argument_opt(i) {
@@ -793,9 +1459,15 @@ impl<'a, T: This> CallContext<'a, T> {
 }
 
 impl<'a, T: This> ContextInternal<'a> for CallContext<'a, T> {
+    #[cfg(feature = "legacy-runtime")]
     fn scope_metadata(&self) -> &ScopeMetadata {
         &self.scope.metadata
     }
+
+    #[cfg(feature = "napi-1")]
+    fn scope_metadata(&self) -> &ScopeMetadata {
+        self.cx.scope_metadata()
+    }
 }
 
 impl<'a, T: This> Context<'a> for CallContext<'a, T> {}
@@ -810,7 +1482,10 @@ pub type MethodContext<'a, T> = CallContext<'a, T>;
 pub struct TaskContext<'a> {
     /// We use an "inherited HandleScope" here because the C++ `neon::Task::complete`
     /// method sets up and tears down a `HandleScope` for us.
+    #[cfg(feature = "legacy-runtime")]
     scope: Scope<'a, raw::InheritedHandleScope>,
+    #[cfg(feature = "napi-1")]
+    cx: Cx<'a>,
 }
 
 impl<'a> TaskContext<'a> {
@@ -820,20 +1495,147 @@ impl<'a> TaskContext<'a> {
         Scope::with(env, |scope| f(TaskContext { scope }))
     }
 
-    #[cfg(all(feature = "napi-4", feature = "channel-api"))]
+    #[cfg(feature = "napi-1")]
     pub(crate) fn with_context<T, F: for<'b> FnOnce(TaskContext<'b>) -> T>(env: Env, f: F) -> T {
-        Scope::with(env, |scope| f(TaskContext { scope }))
+        Cx::with(env, |cx| f(TaskContext { cx }))
     }
 }
 
 impl<'a> ContextInternal<'a> for TaskContext<'a> {
+    #[cfg(feature = "legacy-runtime")]
     fn scope_metadata(&self) -> &ScopeMetadata {
         &self.scope.metadata
     }
+
+    #[cfg(feature = "napi-1")]
+    fn scope_metadata(&self) -> &ScopeMetadata {
+        self.cx.scope_metadata()
+    }
 }
 
 impl<'a> Context<'a> for TaskContext<'a> {}
 
+/// A generic execution context, shared by the contexts of the N-API backend.
+///
+/// [`FunctionContext`], [`ModuleContext`] and [`TaskContext`] dereference to a
+/// `Cx`, so helper functions can take `&mut Cx` rather than being generic over
+/// [`Context`]. Unlike a generic context parameter, a `Cx` can be stored in
+/// structs and used in trait objects.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// fn greeting<'cx>(cx: &mut Cx<'cx>, name: &str) -> JsResult<'cx, JsString> {
+///     Ok(cx.string(format!("Hello, {}!", name)))
+/// }
+///
+/// fn greet(mut cx: FunctionContext) -> JsResult<JsString> {
+///     let name = cx.argument::<JsString>(0)?.value(&mut cx);
+///
+///     greeting(&mut cx, &name)
+/// }
+/// # }
+/// ```
+#[cfg(feature = "napi-1")]
+pub struct Cx<'a> {
+    scope: Scope<'a, raw::InheritedHandleScope>,
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> Cx<'a> {
+    pub(crate) fn with<T, F: for<'b> FnOnce(Cx<'b>) -> T>(env: Env, f: F) -> T {
+        Scope::with(env, |scope| f(Cx { scope }))
+    }
+
+    /// Creates a context from the raw `napi_env` of foreign code, e.g., a
+    /// callback of another N-API wrapper, and calls `f` with it.
+    ///
+    /// The context opens a handle scope, which is closed when `f` returns, so
+    /// handles created by `f` do not leak into the caller.
+    ///
+    /// # Safety
+    ///
+    /// * `env` must be the environment of the current thread, and valid for
+    ///   the duration of the call, e.g., the `napi_env` passed to a callback.
+    /// * JavaScript must not be throwing, and the environment must be able to
+    ///   run JavaScript, i.e., not be in a finalizer or shutting down.
+    /// * No other Neon context of the same environment may be used while `f`
+    ///   runs.
+    #[cfg(feature = "sys")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sys")))]
+    pub unsafe fn from_raw<T, F>(env: crate::sys::Env, f: F) -> T
+    where
+        F: for<'b> FnOnce(Cx<'b>) -> T,
+    {
+        neon_runtime::setup(env);
+
+        let env: Env = std::mem::transmute(env);
+
+        Scope::<raw::HandleScope>::with(env, |_| Cx::with(env, f))
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> UnwindSafe for Cx<'a> {}
+
+#[cfg(feature = "napi-1")]
+impl<'a> ContextInternal<'a> for Cx<'a> {
+    fn scope_metadata(&self) -> &ScopeMetadata {
+        &self.scope.metadata
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> Context<'a> for Cx<'a> {}
+
+#[cfg(feature = "napi-1")]
+impl<'a> Deref for ModuleContext<'a> {
+    type Target = Cx<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cx
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> DerefMut for ModuleContext<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cx
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a, T: This> Deref for CallContext<'a, T> {
+    type Target = Cx<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cx
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a, T: This> DerefMut for CallContext<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cx
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> Deref for TaskContext<'a> {
+    type Target = Cx<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cx
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl<'a> DerefMut for TaskContext<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cx
+    }
+}
+
 /// A view of the JS engine in the context of a finalize method on garbage collection
 #[cfg(feature = "napi-1")]
 pub(crate) struct FinalizeContext<'a> {
@@ -0,0 +1,166 @@
+//! Diagnostics for finding leaked `Root`s and long-lived handle scopes.
+//!
+//! With the `diagnostics` feature enabled, Neon tracks every live
+//! [`Root`](crate::handle::Root) and every open handle scope along with the
+//! location in the source that created it. A [`report`] groups the live
+//! values by call site, so a count that keeps growing points at the code
+//! responsible for the leak.
+//!
+//! ```
+//! # #[cfg(feature = "diagnostics")] {
+//! # use neon::prelude::*;
+//! fn report_leaks(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     for site in neon::diagnostics::report().roots {
+//!         eprintln!("{} live roots created at {}", site.count, site.location);
+//!     }
+//!
+//!     Ok(cx.undefined())
+//! }
+//! # }
+//! ```
+//!
+//! If backtraces are enabled with the `RUST_BACKTRACE` environment variable,
+//! the backtrace of the first value created at each call site is included in
+//! the report.
+//!
+//! Tracking has a cost for every root and every call into Rust, and should
+//! not be enabled in release builds unless a leak is being investigated.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+static LIVE: Mutex<Option<HashMap<u64, Entry>>> = Mutex::new(None);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Root,
+    Scope,
+}
+
+struct Entry {
+    kind: Kind,
+    location: &'static Location<'static>,
+    backtrace: Backtrace,
+}
+
+/// The values tracked at the time [`report`] was called.
+#[derive(Debug)]
+pub struct Report {
+    /// Live `Root`s, grouped by the location that created them.
+    pub roots: Vec<CallSite>,
+    /// Open handle scopes, grouped by the location that opened them.
+    pub scopes: Vec<CallSite>,
+}
+
+/// A location in the source and the number of live values created there.
+#[derive(Debug)]
+pub struct CallSite {
+    /// Where the values were created.
+    pub location: &'static Location<'static>,
+    /// The number of values created at `location` that are still alive.
+    pub count: usize,
+    /// The backtrace of one of the values, if backtraces are enabled.
+    pub backtrace: Option<String>,
+}
+
+/// Returns the live `Root`s and open handle scopes in every environment,
+/// grouped by call site with the most values first.
+pub fn report() -> Report {
+    let live = LIVE.lock().unwrap_or_else(|err| err.into_inner());
+    let mut roots = HashMap::new();
+    let mut scopes = HashMap::new();
+
+    for entry in live.iter().flat_map(|live| live.values()) {
+        let sites = match entry.kind {
+            Kind::Root => &mut roots,
+            Kind::Scope => &mut scopes,
+        };
+
+        let site = sites
+            .entry(entry.location as *const _)
+            .or_insert_with(|| CallSite {
+                location: entry.location,
+                count: 0,
+                backtrace: None,
+            });
+
+        site.count += 1;
+
+        if site.backtrace.is_none() && entry.backtrace.status() == BacktraceStatus::Captured {
+            site.backtrace = Some(entry.backtrace.to_string());
+        }
+    }
+
+    Report {
+        roots: sorted(roots),
+        scopes: sorted(scopes),
+    }
+}
+
+fn sorted(sites: HashMap<*const Location<'static>, CallSite>) -> Vec<CallSite> {
+    let mut sites = sites.into_values().collect::<Vec<_>>();
+
+    sites.sort_by_key(|site| std::cmp::Reverse(site.count));
+    sites
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, sites) in [("roots", &self.roots), ("handle scopes", &self.scopes)].iter() {
+            let total = sites.iter().map(|site| site.count).sum::<usize>();
+
+            writeln!(f, "{} live {}", total, name)?;
+
+            for site in sites.iter() {
+                writeln!(f, "  {:>6} at {}", site.count, site.location)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Registers a value with the diagnostics report until it is dropped.
+pub(crate) struct Tracked(u64);
+
+impl Tracked {
+    #[track_caller]
+    pub(crate) fn root() -> Self {
+        Self::new(Kind::Root, Location::caller())
+    }
+
+    #[track_caller]
+    pub(crate) fn scope() -> Self {
+        Self::new(Kind::Scope, Location::caller())
+    }
+
+    fn new(kind: Kind, location: &'static Location<'static>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let entry = Entry {
+            kind,
+            location,
+            backtrace: Backtrace::capture(),
+        };
+
+        LIVE.lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .insert(id, entry);
+
+        Tracked(id)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        if let Some(live) = LIVE.lock().unwrap_or_else(|err| err.into_inner()).as_mut() {
+            live.remove(&self.0);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::context::Context;
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::boxed::Finalize;
+use crate::types::{JsArray, JsFunction, JsObject, JsValue};
+
+/// The store of an [`AsyncLocalStorage`][als] instance, captured so that it
+/// can be restored after work dispatched to Rust completes.
+///
+/// Callbacks executed through a [`Channel`](crate::event::Channel) or as the
+/// completion of a task run outside of the JavaScript code that scheduled
+/// them. A snapshot carries request-scoped data across that gap.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// # use neon::event::AsyncLocalSnapshot;
+/// fn process(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let storage = cx.argument::<JsObject>(0)?;
+///     let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+///     let snapshot = AsyncLocalSnapshot::capture(&mut cx, storage)?;
+///     let channel = cx.channel();
+///
+///     std::thread::spawn(move || {
+///         channel.send(move |mut cx| {
+///             // `storage.getStore()` returns the captured store while the
+///             // callback runs
+///             snapshot.run(&mut cx, |cx| {
+///                 let callback = callback.into_inner(cx);
+///                 let this = cx.undefined();
+///
+///                 callback.call(cx, this, Vec::<Handle<JsValue>>::new())?;
+///
+///                 Ok(())
+///             })
+///         });
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// [als]: https://nodejs.org/api/async_context.html#async_context_class_asynclocalstorage
+pub struct AsyncLocalSnapshot {
+    // `[storage, store]`, since the store may be a primitive
+    inner: Root<JsArray>,
+}
+
+impl AsyncLocalSnapshot {
+    /// Captures the current store of `storage`, an `AsyncLocalStorage`
+    /// instance.
+    pub fn capture<'a, C: Context<'a>>(cx: &mut C, storage: Handle<JsObject>) -> NeonResult<Self> {
+        let store = call_method(cx, storage, "getStore", &[])?;
+        let inner = JsArray::new(cx, 2);
+
+        inner.set(cx, 0, storage)?;
+        inner.set(cx, 1, store)?;
+
+        Ok(Self {
+            inner: inner.root(cx),
+        })
+    }
+
+    /// Executes `f` with the captured store entered, restoring the previous
+    /// store afterwards, even if `f` throws.
+    pub fn run<'a, C, T, F>(self, cx: &mut C, f: F) -> NeonResult<T>
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut C) -> NeonResult<T>,
+    {
+        let inner = self.inner.into_inner(cx);
+        let storage = inner.get(cx, 0)?.downcast_or_throw::<JsObject, _>(cx)?;
+        let store = inner.get(cx, 1)?;
+        let previous = call_method(cx, storage, "getStore", &[])?;
+
+        call_method(cx, storage, "enterWith", &[store])?;
+
+        let result = cx.try_catch_internal(f);
+
+        call_method(cx, storage, "enterWith", &[previous])?;
+
+        match result {
+            Ok(v) => Ok(v),
+            Err(err) => cx.throw(err),
+        }
+    }
+
+    /// Drops the snapshot without restoring it.
+    pub fn drop<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.inner.drop(cx);
+    }
+}
+
+fn call_method<'a, C: Context<'a>>(
+    cx: &mut C,
+    object: Handle<JsObject>,
+    name: &str,
+    args: &[Handle<'a, JsValue>],
+) -> JsResult<'a, JsValue> {
+    let method = object
+        .get(cx, name)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+
+    method.call(cx, object, args.to_vec())
+}
+
+impl Finalize for AsyncLocalSnapshot {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.drop(cx);
+    }
+}
+
+impl std::fmt::Debug for AsyncLocalSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AsyncLocalSnapshot")
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use smallvec::SmallVec;
+
+use crate::context::internal::ContextInternal;
+use crate::context::{Context, TaskContext};
+use crate::event::Channel;
+use crate::handle::{Handle, Root};
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::{JsFunction, JsValue, Value};
+
+type ArgFn = Box<dyn for<'b> FnOnce(&mut TaskContext<'b>) -> JsResult<'b, JsValue> + Send>;
+
+/// Builder for calling a rooted JavaScript function from any thread.
+///
+/// Arguments are Rust values that are converted with [`TryIntoJs`] on the
+/// JavaScript thread when the call runs. [`exec_on`](Call::exec_on) and
+/// [`apply_on`](Call::apply_on) schedule the call through a [`Channel`] and
+/// return a [`JoinHandle`] for waiting on its result.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// use neon::event::Call;
+///
+/// fn sum_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let add = cx.argument::<JsFunction>(0)?.root(&mut cx);
+///     let channel = cx.channel();
+///
+///     std::thread::spawn(move || {
+///         let sum = Call::new(add)
+///             .arg(1.0)
+///             .arg(2.0)
+///             .apply_on::<f64>(&channel)
+///             .join();
+///
+///         println!("{:?}", sum);
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// The function is held in an `Arc`, so a callback can be called many times
+/// by cloning the `Arc` instead of rooting it again. Scheduling a call clears
+/// `this` and the arguments, so a single builder can also be reused for
+/// every call in a loop. Up to four arguments are stored inline and the
+/// argument list keeps its capacity between calls, but each argument is still
+/// boxed and each scheduled call allocates its message on the channel:
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// # use neon::event::Call;
+/// fn report(callback: Root<JsFunction>, channel: Channel) {
+///     let mut call = Call::with_capacity(callback, 2);
+///
+///     for (i, line) in ["a", "b", "c"].iter().enumerate() {
+///         call.push_arg(i as f64).push_arg(line.to_string());
+///         call.exec_on(&channel);
+///     }
+/// }
+/// # }
+/// ```
+pub struct Call {
+    callee: Arc<Root<JsFunction>>,
+    this: Option<ArgFn>,
+    args: SmallVec<[ArgFn; 4]>,
+}
+
+impl Call {
+    /// Starts building a call to `callee`, with `this` set to `undefined` and
+    /// no arguments.
+    pub fn new(callee: impl Into<Arc<Root<JsFunction>>>) -> Self {
+        Self {
+            callee: callee.into(),
+            this: None,
+            args: SmallVec::new(),
+        }
+    }
+
+    /// Starts building a call like [`new`](Call::new), with room for
+    /// `capacity` arguments.
+    pub fn with_capacity(callee: impl Into<Arc<Root<JsFunction>>>, capacity: usize) -> Self {
+        Self {
+            callee: callee.into(),
+            this: None,
+            args: SmallVec::with_capacity(capacity),
+        }
+    }
+
+    /// Sets the receiver of the call.
+    pub fn this<T>(mut self, this: T) -> Self
+    where
+        T: for<'b> TryIntoJs<'b> + Send + 'static,
+    {
+        self.this = Some(arg_fn(this));
+        self
+    }
+
+    /// Adds an argument to the call.
+    pub fn arg<T>(mut self, arg: T) -> Self
+    where
+        T: for<'b> TryIntoJs<'b> + Send + 'static,
+    {
+        self.args.push(arg_fn(arg));
+        self
+    }
+
+    /// Adds an argument to the call without consuming the builder.
+    pub fn push_arg<T>(&mut self, arg: T) -> &mut Self
+    where
+        T: for<'b> TryIntoJs<'b> + Send + 'static,
+    {
+        self.args.push(arg_fn(arg));
+        self
+    }
+
+    /// Sets the receiver of the call without consuming the builder.
+    pub fn set_this<T>(&mut self, this: T) -> &mut Self
+    where
+        T: for<'b> TryIntoJs<'b> + Send + 'static,
+    {
+        self.this = Some(arg_fn(this));
+        self
+    }
+
+    /// Schedules the call on the JavaScript thread of `channel`, ignoring
+    /// the return value.
+    pub fn exec_on(&mut self, channel: &Channel) -> JoinHandle<()> {
+        self.schedule(channel, |_, _| Ok(()))
+    }
+
+    /// Schedules the call on the JavaScript thread of `channel` and extracts
+    /// the return value as `R`. Failing to extract the value is reported like
+    /// an exception thrown by the function.
+    pub fn apply_on<R>(&mut self, channel: &Channel) -> JoinHandle<R>
+    where
+        R: for<'b> TryFromJs<'b> + Send + 'static,
+    {
+        self.schedule(channel, |cx, value| R::from_js(cx, value))
+    }
+
+    fn schedule<R, F>(&mut self, channel: &Channel, complete: F) -> JoinHandle<R>
+    where
+        R: Send + 'static,
+        F: for<'b> FnOnce(&mut TaskContext<'b>, Handle<'b, JsValue>) -> NeonResult<R>
+            + Send
+            + 'static,
+    {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let callee = self.callee.clone();
+        let this = self.this.take();
+        // Up to four arguments are moved into the message inline, and the
+        // capacity of the builder is kept for the next call
+        let args = self.args.drain(..).collect::<SmallVec<[ArgFn; 4]>>();
+
+        // If the channel is closed, `tx` is dropped and `join` reports it
+        let _ = channel.try_send(move |mut cx| {
+            let result = cx.try_catch_internal(move |cx| {
+                let callee = callee.to_inner(cx);
+                let this = match this {
+                    Some(this) => this(cx)?,
+                    None => cx.undefined().upcast(),
+                };
+
+                let args = args
+                    .into_iter()
+                    .map(|arg| arg(cx))
+                    .collect::<NeonResult<SmallVec<[_; 4]>>>()?;
+
+                let value = callee.call(cx, this, args)?;
+
+                complete(cx, value)
+            });
+
+            let result = result.map_err(|err| JoinError::Threw(describe(&mut cx, err)));
+            let _ = tx.send(result);
+
+            Ok(())
+        });
+
+        JoinHandle { rx }
+    }
+}
+
+fn arg_fn<T>(value: T) -> ArgFn
+where
+    T: for<'b> TryIntoJs<'b> + Send + 'static,
+{
+    Box::new(move |cx| Ok(value.try_into_js(cx)?.upcast()))
+}
+
+// Converts an exception to a string for a thread without access to the
+// JavaScript value
+fn describe(cx: &mut TaskContext, err: Handle<JsValue>) -> String {
+    cx.try_catch_internal(|cx| Ok(err.to_string(cx)?.value(cx)))
+        .unwrap_or_else(|_| String::from("<exception could not be converted to a string>"))
+}
+
+/// Handle for waiting on the result of a [`Call`] from another thread.
+pub struct JoinHandle<T> {
+    rx: mpsc::Receiver<Result<T, JoinError>>,
+}
+
+impl<T> JoinHandle<T> {
+    /// Blocks the current thread until the call completes.
+    ///
+    /// Joining on the JavaScript thread that runs the call never returns,
+    /// since the call cannot run while the thread is blocked.
+    pub fn join(self) -> Result<T, JoinError> {
+        self.rx.recv().unwrap_or(Err(JoinError::NotExecuted))
+    }
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JoinHandle").finish()
+    }
+}
+
+/// Error returned by [`JoinHandle::join`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JoinError {
+    /// The call threw an exception, or its result could not be extracted.
+    /// Holds the exception converted to a string.
+    Threw(String),
+    /// The call did not run, because the channel was closed or the module
+    /// was unloaded.
+    NotExecuted,
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinError::Threw(msg) => write!(f, "the call threw: {}", msg),
+            JoinError::NotExecuted => f.write_str("the call was not executed"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
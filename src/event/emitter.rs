@@ -0,0 +1,311 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::context::internal::ContextInternal;
+use crate::context::{Context, FunctionContext, TaskContext};
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::boxed::{Finalize, JsBox};
+use crate::types::extract::TryFromJs;
+use crate::types::{JsBoolean, JsFunction, JsObject, JsUndefined, JsValue, Value};
+
+use super::schedule;
+
+/// A JavaScript [`EventEmitter`][emitter], or any object with compatible
+/// `emit`, `on` and `removeListener` methods.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::event::Emitter;
+///
+/// fn watch(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let socket = cx.argument::<JsObject>(0)?;
+///     let socket = Emitter::new(socket);
+///
+///     // The closure is called until the subscription is dropped
+///     let subscription = socket.on(&mut cx, "data", |_cx, (chunk,): (String,)| {
+///         println!("received {} bytes", chunk.len());
+///         Ok(())
+///     })?;
+///
+///     let mut subscription = Some(subscription);
+///
+///     socket
+///         .on(&mut cx, "close", move |mut cx, ()| match subscription.take() {
+///             Some(subscription) => subscription.unsubscribe(&mut cx),
+///             None => Ok(()),
+///         })?
+///         .detach(&mut cx);
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// [emitter]: https://nodejs.org/api/events.html#events_class_eventemitter
+#[derive(Clone, Copy)]
+pub struct Emitter<'a> {
+    emitter: Handle<'a, JsObject>,
+}
+
+impl<'a> Emitter<'a> {
+    /// Wraps an `EventEmitter` instance.
+    pub fn new(emitter: Handle<'a, JsObject>) -> Self {
+        Self { emitter }
+    }
+
+    /// Returns the wrapped `EventEmitter`.
+    pub fn as_object(&self) -> Handle<'a, JsObject> {
+        self.emitter
+    }
+
+    /// Synchronously calls each listener of `event` with `args`. Returns
+    /// `true` if the event had listeners.
+    pub fn emit<'b, C, A, AS>(&self, cx: &mut C, event: &str, args: AS) -> NeonResult<bool>
+    where
+        C: Context<'b>,
+        A: Value + 'b,
+        AS: IntoIterator<Item = Handle<'b, A>>,
+    {
+        let event = cx.string(event).upcast::<JsValue>();
+        let args = std::iter::once(event)
+            .chain(args.into_iter().map(|arg| arg.upcast()))
+            .collect::<Vec<_>>();
+
+        let result = self.method(cx, "emit")?.call(cx, self.emitter, args)?;
+
+        Ok(result
+            .downcast::<JsBoolean, _>(cx)
+            .map(|b| b.value(cx))
+            .unwrap_or(false))
+    }
+
+    /// Calls `f` each time `event` is emitted, with the event arguments
+    /// extracted as `A`.
+    ///
+    /// Arguments are extracted from the event in order: missing arguments
+    /// are treated as `undefined` and extra arguments are ignored. If an
+    /// argument cannot be extracted, the conversion error is thrown to the
+    /// code that emitted the event and `f` is not called.
+    ///
+    /// Dropping the returned [`Subscription`] stops `f` from being called.
+    pub fn on<'b, C, A, F>(&self, cx: &mut C, event: &str, f: F) -> NeonResult<Subscription>
+    where
+        C: Context<'b>,
+        A: for<'c> EventArgs<'c>,
+        F: FnMut(TaskContext, A) -> NeonResult<()> + Send + 'static,
+    {
+        let active = Arc::new(AtomicBool::new(true));
+        let state = Listener {
+            active: active.clone(),
+            callback: RefCell::new(Some(callback(f))),
+        };
+
+        let listener = schedule::bind(cx, listen, state)?;
+        let name = cx.string(event);
+
+        self.method(cx, "on")?.call(
+            cx,
+            self.emitter,
+            vec![name.upcast::<JsValue>(), listener.upcast()],
+        )?;
+
+        Ok(Subscription {
+            active: Active(Some(active)),
+            event: event.to_string(),
+            emitter: self.emitter.root(cx),
+            listener: listener.root(cx),
+        })
+    }
+
+    fn method<'b, C: Context<'b>>(&self, cx: &mut C, name: &str) -> JsResult<'b, JsFunction> {
+        self.emitter.get(cx, name)?.downcast_or_throw(cx)
+    }
+}
+
+/// A listener registered with [`Emitter::on`].
+///
+/// Dropping a `Subscription`, even from another thread, stops the closure
+/// from being called. The listener function itself remains registered until
+/// [`unsubscribe`](Subscription::unsubscribe) is called or the emitter is
+/// garbage collected. Call [`detach`](Subscription::detach) to keep a
+/// listener for the lifetime of the emitter.
+///
+/// Like a [`Root`], a `Subscription` must be explicitly unsubscribed when
+/// using N-API < 6.
+#[must_use = "dropping a `Subscription` stops the listener; use `detach` to keep it"]
+pub struct Subscription {
+    active: Active,
+    event: String,
+    emitter: Root<JsObject>,
+    listener: Root<JsFunction>,
+}
+
+impl Subscription {
+    /// Removes the listener from the emitter.
+    pub fn unsubscribe<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<()> {
+        let Subscription {
+            event,
+            emitter,
+            listener,
+            active,
+        } = self;
+
+        drop(active);
+
+        let emitter = emitter.into_inner(cx);
+        let listener = listener.into_inner(cx);
+        let event = cx.string(event);
+        let remove = emitter
+            .get(cx, "removeListener")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        remove.call(
+            cx,
+            emitter,
+            vec![event.upcast::<JsValue>(), listener.upcast()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the closure is still being called for new events.
+    pub fn is_active(&self) -> bool {
+        match &self.active.0 {
+            Some(active) => active.load(Ordering::SeqCst),
+            None => true,
+        }
+    }
+
+    /// Releases the subscription without deactivating the listener. The
+    /// closure is called for the lifetime of the emitter.
+    pub fn detach<'a, C: Context<'a>>(self, cx: &mut C) {
+        let Subscription {
+            mut active,
+            emitter,
+            listener,
+            ..
+        } = self;
+
+        active.0.take();
+        emitter.drop(cx);
+        listener.drop(cx);
+    }
+}
+
+// Deactivates the listener when the `Subscription` is dropped
+struct Active(Option<Arc<AtomicBool>>);
+
+impl Drop for Active {
+    fn drop(&mut self) {
+        if let Some(active) = self.0.take() {
+            active.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("event", &self.event)
+            .field("active", &self.is_active())
+            .finish()
+    }
+}
+
+/// Arguments of an event that can be extracted for an [`Emitter::on`]
+/// listener.
+///
+/// Implemented for `()` and tuples of up to six [`TryFromJs`] values.
+pub trait EventArgs<'a>: Sized {
+    /// Extracts the arguments, throwing if any cannot be converted.
+    fn from_args<C: Context<'a>>(cx: &mut C, args: &[Handle<'a, JsValue>]) -> NeonResult<Self>;
+}
+
+impl<'a> EventArgs<'a> for () {
+    fn from_args<C: Context<'a>>(_: &mut C, _: &[Handle<'a, JsValue>]) -> NeonResult<Self> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_event_args {
+    ($($i:expr => $t:ident),+) => {
+        impl<'a, $($t: TryFromJs<'a>),+> EventArgs<'a> for ($($t,)+) {
+            #[allow(non_snake_case)]
+            fn from_args<C: Context<'a>>(
+                cx: &mut C,
+                args: &[Handle<'a, JsValue>],
+            ) -> NeonResult<Self> {
+                $(
+                    let arg = match args.get($i) {
+                        Some(&arg) => arg,
+                        None => cx.undefined().upcast(),
+                    };
+                    let $t = $t::from_js(cx, arg)?;
+                )+
+
+                Ok(($($t,)+))
+            }
+        }
+    };
+}
+
+impl_event_args!(0 => T1);
+impl_event_args!(0 => T1, 1 => T2);
+impl_event_args!(0 => T1, 1 => T2, 2 => T3);
+impl_event_args!(0 => T1, 1 => T2, 2 => T3, 3 => T4);
+impl_event_args!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5);
+impl_event_args!(0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6);
+
+type Callback = Box<dyn for<'b> FnMut(&mut FunctionContext<'b>) -> NeonResult<()> + Send>;
+
+struct Listener {
+    active: Arc<AtomicBool>,
+    callback: RefCell<Option<Callback>>,
+}
+
+impl Finalize for Listener {}
+
+// Erases the argument type by extracting arguments before calling `f`
+fn callback<A, F>(mut f: F) -> Callback
+where
+    A: for<'c> EventArgs<'c>,
+    F: FnMut(TaskContext, A) -> NeonResult<()> + Send + 'static,
+{
+    Box::new(move |cx| {
+        // The first argument is the bound `Listener`
+        let args = (1..cx.len())
+            .map(|i| cx.argument::<JsValue>(i))
+            .collect::<NeonResult<Vec<_>>>()?;
+        let args = A::from_args(cx, &args)?;
+
+        TaskContext::with_context(cx.env(), |cx| f(cx, args))
+    })
+}
+
+fn listen(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let listener = cx.argument::<JsBox<Listener>>(0)?;
+
+    if !listener.active.load(Ordering::SeqCst) {
+        // Release the closure eagerly; it will never be called again
+        if let Ok(mut callback) = listener.callback.try_borrow_mut() {
+            callback.take();
+        }
+
+        return Ok(cx.undefined());
+    }
+
+    let mut callback = match listener.callback.try_borrow_mut() {
+        Ok(callback) => callback,
+        Err(_) => return cx.throw_error("Emitter listener was called recursively"),
+    };
+
+    if let Some(callback) = callback.as_mut() {
+        callback(&mut cx)?;
+    }
+
+    Ok(cx.undefined())
+}
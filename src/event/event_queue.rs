@@ -1,11 +1,17 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
 
-use neon_runtime::raw::Env;
+use neon_runtime::raw::{Env, Local};
 use neon_runtime::tsfn::ThreadsafeFunction;
+use neon_runtime::{async_context, error, napi, reference};
 
-use crate::context::{Context, TaskContext};
+use crate::context::{internal, Context, TaskContext};
+use crate::handle::Managed;
 use crate::result::NeonResult;
+use crate::trace::Queued;
+use crate::types::JsObject;
 
 type Callback = Box<dyn FnOnce(Env) + Send + 'static>;
 
@@ -54,7 +60,60 @@ type Callback = Box<dyn FnOnce(Env) + Send + 'static>;
 ///     Ok(cx.undefined())
 /// }
 /// ```
-
+///
+/// # Event loop
+///
+/// Node does not exit while a _referenced_ channel exists, so that closures
+/// sent from other threads still run. Channels are referenced when they are
+/// created; [`Channel::unref`] allows Node to exit while the channel exists
+/// and [`Channel::reference`] reverses it. Each clone is referenced or
+/// unreferenced separately: a clone starts out like the channel it was cloned
+/// from, and Node is kept running while any referenced clone exists. Dropping
+/// the last referenced clone allows Node to exit.
+///
+/// A [`WeakChannel`], created with [`Channel::downgrade`], never keeps Node
+/// running and does not keep the queue alive. It is useful for background
+/// threads that report progress while JavaScript is interested, but should not
+/// prevent the process from exiting.
+///
+/// # Ordering
+///
+/// Closures sent on a channel and its clones run one at a time, in the order
+/// they were sent. Channels created separately, e.g., with [`Channel::new`],
+/// have separate queues that may interleave.
+///
+/// A channel created with [`Channel::ordered`] also keeps that order if a
+/// closure re-enters the event loop, e.g., by synchronously waiting on
+/// JavaScript: the next closure does not start until the previous one has
+/// returned. This matters for event streams where the order is part of the
+/// data, such as log sequences or transaction events.
+///
+/// A channel created with [`Channel::high_priority`] has a dedicated queue
+/// for urgent closures, such as cancellation. They run before any closure
+/// that is still waiting in the queue of the channel it was created from.
+///
+/// # Backpressure
+///
+/// The queue of a channel is unbounded, so a producer that sends closures
+/// faster than JavaScript runs them grows it without limit. [`Channel::stats`]
+/// reports the depth of the queue and how long closures waited in it, and
+/// [`Channel::on_high_water_mark`] notifies the producer when the queue grows
+/// too deep, so that it can slow down or shed load.
+///
+/// # Async context
+///
+/// Closures are executed inside the async context of the channel, so
+/// `async_hooks` based tools, such as `AsyncLocalStorage` and APM agents,
+/// observe them as continuations of an async resource. The resource is
+/// reported with the type `"neon::Channel"`, unless the channel is created
+/// with [`Channel::with_resource_name`].
+///
+/// # Tracing
+///
+/// With the `tracing` feature enabled, each closure runs in a `DEBUG` span
+/// named `channel` with the target `neon`. The `queue_latency_us` field of
+/// the span is the time, in microseconds, between sending the closure and
+/// running it.
 pub struct Channel {
     state: Arc<ChannelState>,
     has_ref: bool,
@@ -70,8 +129,45 @@ impl Channel {
     /// Creates an unbounded channel for scheduling closures on the JavaScript
     /// main thread
     pub fn new<'a, C: Context<'a>>(cx: &mut C) -> Self {
+        Self::with_resource_name(cx, "neon::Channel")
+    }
+
+    /// Creates an unbounded channel whose closures are attributed by
+    /// `async_hooks` to an async resource of the type `name`
+    pub fn with_resource_name<'a, C: Context<'a>>(cx: &mut C, name: &str) -> Self {
+        let resource = Arc::new(AsyncResource::new(cx, name));
+
+        Self::with_state(ChannelState::new(cx, resource, Delivery::Queued))
+    }
+
+    /// Creates an unbounded channel whose closures run strictly in the order
+    /// they were sent on it and its clones, even if a closure re-enters the
+    /// event loop. See [Ordering](#ordering).
+    pub fn ordered<'a, C: Context<'a>>(cx: &mut C) -> Self {
+        let resource = Arc::new(AsyncResource::new(cx, "neon::Channel"));
+        let delivery = Delivery::Ordered(Default::default());
+
+        Self::with_state(ChannelState::new(cx, resource, delivery))
+    }
+
+    /// Creates a channel with a dedicated threadsafe function, whose closures
+    /// run before the closures still queued on this channel and its clones.
+    ///
+    /// The returned channel shares the async context of this channel, and its
+    /// clones share the dedicated queue. It is referenced separately from this
+    /// channel.
+    pub fn high_priority<'a, C: Context<'a>>(&self, cx: &mut C) -> Self {
+        let resource = Arc::clone(&self.state.resource);
+        let mut state = ChannelState::new(cx, resource, Delivery::Priority);
+
+        state.priority = Arc::clone(&self.state.priority);
+
+        Self::with_state(state)
+    }
+
+    fn with_state(state: ChannelState) -> Self {
         Self {
-            state: Arc::new(ChannelState::new(cx)),
+            state: Arc::new(state),
             has_ref: true,
         }
     }
@@ -119,17 +215,145 @@ impl Channel {
     where
         F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
     {
-        let callback = Box::new(move |env| {
-            let env = unsafe { std::mem::transmute(env) };
+        let queued = Queued::now();
+        let sent = Instant::now();
+        let stats = Arc::clone(&self.state.stats);
+
+        self.schedule(1, move |env| {
+            stats.delivered(sent);
+            run(env, queued, f);
+        })
+    }
 
-            // Note: It is sufficient to use `TaskContext`'s `InheritedHandleScope` because
-            // N-API creates a `HandleScope` before calling the callback.
-            TaskContext::with_context(env, move |cx| {
-                let _ = f(cx);
-            });
-        });
+    /// Schedules closures to execute, in order, on the JavaScript thread that
+    /// created this Channel
+    /// Panics if there is a libuv error
+    ///
+    /// The closures are delivered together, in a single call of the threadsafe
+    /// function, which is much cheaper than sending each one for emitters of
+    /// many small events, such as metrics. The closures share a handle scope,
+    /// so handles created by one are only freed after the last has run. An
+    /// exception thrown by one is reported as uncaught and the rest still run.
+    pub fn send_batch<I, F>(&self, fs: I)
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        self.try_send_batch(fs).unwrap()
+    }
+
+    /// Schedules closures to execute, in order, on the JavaScript thread that
+    /// created this Channel
+    /// Returns an `Error` if the closures could not be scheduled.
+    ///
+    /// See [`Channel::send_batch`] for details on how the closures run and
+    /// [`SendError`] for additional details on failure causes.
+    pub fn try_send_batch<I, F>(&self, fs: I) -> Result<(), SendError>
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        let fs = fs.into_iter().collect::<Vec<_>>();
+        let queued = Queued::now();
+        let sent = Instant::now();
+        let stats = Arc::clone(&self.state.stats);
 
-        self.state.tsfn.call(callback, None).map_err(|_| SendError)
+        if fs.is_empty() {
+            return Ok(());
+        }
+
+        self.schedule(fs.len(), move |env| {
+            for f in fs {
+                stats.delivered(sent);
+                run(env, queued, f);
+            }
+        })
+    }
+
+    /// Returns statistics of the queue of this channel, which is shared by
+    /// its clones.
+    pub fn stats(&self) -> ChannelStats {
+        self.state.stats.snapshot()
+    }
+
+    /// Calls `f` with the depth of the queue each time sending closures makes
+    /// it reach `mark` closures, from below. The queue is shared by the clones
+    /// of this channel, and replaces a callback set with any of them. A `mark`
+    /// of `0` removes the callback.
+    ///
+    /// `f` runs on the thread that sent the closures, before they are queued,
+    /// so that a producer can slow down before sending more. It must not send
+    /// on the channel itself.
+    pub fn on_high_water_mark<F>(&self, mark: usize, f: F)
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.state.stats.set_high_water_mark(mark, Arc::new(f));
+    }
+
+    // Queues a call of `f`, which runs `count` closures, in the async context
+    // of the channel, following the delivery of the channel
+    fn schedule<F>(&self, count: usize, f: F) -> Result<(), SendError>
+    where
+        F: FnOnce(Env) + Send + 'static,
+    {
+        let resource = Arc::clone(&self.state.resource);
+
+        // Note: It is sufficient to use `TaskContext`'s `InheritedHandleScope` because
+        // N-API creates a `HandleScope` before calling the callback.
+        let callback: Callback = Box::new(move |env| resource.enter(env, || f(env)));
+
+        let priority = Arc::clone(&self.state.priority);
+
+        // Counted before the closure can be delivered, which uncounts it, and
+        // uncounted below if the send fails
+        self.state.stats.queued(count);
+
+        // The closure is queued before the call, so that it is not missed by
+        // a delivery that runs before it would be queued, and is taken back
+        // if the call fails
+        let (callback, pending): (Callback, _) = match &self.state.delivery {
+            Delivery::Queued => (
+                Box::new(move |env| {
+                    priority.run(env);
+                    callback(env);
+                }),
+                None,
+            ),
+            Delivery::Ordered(queue) => {
+                let id = queue.push(callback);
+                let pending = (Arc::clone(queue), id);
+                let queue = Arc::clone(queue);
+
+                (
+                    Box::new(move |env| {
+                        priority.run(env);
+                        queue.run(env);
+                    }),
+                    Some(pending),
+                )
+            }
+            Delivery::Priority => {
+                let id = priority.push(callback);
+                let pending = (Arc::clone(&priority), id);
+
+                (Box::new(move |env| priority.run(env)), Some(pending))
+            }
+        };
+
+        if self.state.tsfn.call(callback, None).is_ok() {
+            return Ok(());
+        }
+
+        // A delivery of another call may have already taken the closure, in
+        // which case it runs and the send succeeded
+        match pending {
+            Some((queue, id)) if !queue.remove(id) => Ok(()),
+            _ => {
+                self.state.stats.dropped(count);
+                Err(SendError)
+            }
+        }
     }
 
     /// Returns a boolean indicating if this `Channel` will prevent the Node event
@@ -137,6 +361,29 @@ impl Channel {
     pub fn has_ref(&self) -> bool {
         self.has_ref
     }
+
+    /// Creates a [`WeakChannel`] that sends closures on the same queue, but
+    /// neither keeps the event loop running nor keeps the queue alive.
+    pub fn downgrade(&self) -> WeakChannel {
+        WeakChannel {
+            state: Arc::downgrade(&self.state),
+        }
+    }
+}
+
+// Runs a closure sent on a channel. An exception it throws is reported as
+// uncaught right away, because other closures may run in the same call of the
+// threadsafe function.
+fn run<F>(env: Env, queued: Queued, f: F)
+where
+    F: FnOnce(TaskContext) -> NeonResult<()>,
+{
+    TaskContext::with_context(internal::Env::from(env), move |cx| {
+        let _span = queued.channel();
+        let _ = f(cx);
+    });
+
+    unsafe { error::report_pending(env) };
 }
 
 impl Clone for Channel {
@@ -146,6 +393,9 @@ impl Clone for Channel {
     /// starvation if one of the threads posts significantly more callbacks on
     /// the channel than the other one.
     ///
+    /// The clone is referenced if the original is, and is referenced or
+    /// unreferenced separately from then on.
+    ///
     /// Cloned and referenced Channel instances might trigger additional
     /// event-loop tick when dropped. Channel can be wrapped into an Arc and
     /// shared between different threads/callers to avoid this.
@@ -202,6 +452,191 @@ impl Drop for Channel {
     }
 }
 
+/// A handle to the queue of a [`Channel`] that never keeps the event loop
+/// running.
+///
+/// Closures sent on a `WeakChannel` run like closures sent on the `Channel`
+/// it was created from, as long as Node has other reasons to keep running.
+/// Once every clone of that `Channel` has been dropped and the closures it
+/// queued have run, sending fails with a [`SendError`].
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// fn watch(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+///     let channel = cx.channel().downgrade();
+///
+///     std::thread::spawn(move || {
+///         // Does not prevent Node from exiting while the thread sleeps
+///         std::thread::sleep(std::time::Duration::from_secs(60));
+///
+///         let _ = channel.try_send(move |mut cx| {
+///             let this = cx.undefined();
+///
+///             callback.into_inner(&mut cx).call(&mut cx, this, [])?;
+///
+///             Ok(())
+///         });
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WeakChannel {
+    state: Weak<ChannelState>,
+}
+
+impl std::fmt::Debug for WeakChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WeakChannel")
+    }
+}
+
+impl WeakChannel {
+    /// Schedules a closure to execute on the JavaScript thread of the channel.
+    /// Returns an `Error` if every clone of the channel has been dropped or
+    /// the task could not be scheduled.
+    pub fn try_send<F>(&self, f: F) -> Result<(), SendError>
+    where
+        F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+    {
+        match self.upgrade() {
+            Some(channel) => channel.try_send(f),
+            None => Err(SendError),
+        }
+    }
+
+    /// Returns an unreferenced [`Channel`] for the same queue, or `None` if
+    /// every clone of the channel has been dropped.
+    pub fn upgrade(&self) -> Option<Channel> {
+        self.state.upgrade().map(|state| Channel {
+            state,
+            has_ref: false,
+        })
+    }
+}
+
+/// Statistics of the queue of a [`Channel`], returned by [`Channel::stats`].
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelStats {
+    queued: usize,
+    delivered: u64,
+    dropped: u64,
+    total_latency: Duration,
+    max_latency: Duration,
+}
+
+impl ChannelStats {
+    /// The number of closures that were sent and have not started running.
+    pub fn queued(&self) -> usize {
+        self.queued
+    }
+
+    /// The number of closures that started running.
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+
+    /// The number of closures that could not be sent, e.g., because
+    /// [`Channel::try_send`] failed.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// The mean time closures waited in the queue before running, or `None`
+    /// if no closure has run.
+    pub fn mean_latency(&self) -> Option<Duration> {
+        if self.delivered == 0 {
+            return None;
+        }
+
+        let mean = self.total_latency.as_micros() / u128::from(self.delivered);
+
+        Some(Duration::from_micros(mean as u64))
+    }
+
+    /// The longest time a closure waited in the queue before running.
+    pub fn max_latency(&self) -> Duration {
+        self.max_latency
+    }
+}
+
+type HighWaterMark = Arc<dyn Fn(usize) + Send + Sync>;
+
+// Counters of a queue, which are updated from any thread
+#[derive(Default)]
+struct Stats {
+    queued: AtomicUsize,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    total_latency_us: AtomicU64,
+    max_latency_us: AtomicU64,
+    // `0` if there is no callback
+    high_water_mark: AtomicUsize,
+    on_high_water_mark: Mutex<Option<HighWaterMark>>,
+}
+
+impl Stats {
+    fn queued(&self, count: usize) {
+        let before = self.queued.fetch_add(count, Ordering::Relaxed);
+        let mark = self.high_water_mark.load(Ordering::Relaxed);
+
+        if mark == 0 || before >= mark || before + count < mark {
+            return;
+        }
+
+        // The lock is released before calling back, so that the callback may
+        // be replaced from the callback
+        let f = self
+            .on_high_water_mark
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone();
+
+        if let Some(f) = f {
+            f(before + count);
+        }
+    }
+
+    fn dropped(&self, count: usize) {
+        self.queued.fetch_sub(count, Ordering::Relaxed);
+        self.dropped.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    fn delivered(&self, sent: Instant) {
+        let latency_us = sent.elapsed().as_micros() as u64;
+
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_us
+            .fetch_add(latency_us, Ordering::Relaxed);
+        self.max_latency_us.fetch_max(latency_us, Ordering::Relaxed);
+    }
+
+    fn set_high_water_mark(&self, mark: usize, f: HighWaterMark) {
+        let mut on_high_water_mark = self
+            .on_high_water_mark
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+
+        *on_high_water_mark = if mark == 0 { None } else { Some(f) };
+        self.high_water_mark.store(mark, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ChannelStats {
+        ChannelStats {
+            queued: self.queued.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            total_latency: Duration::from_micros(self.total_latency_us.load(Ordering::Relaxed)),
+            max_latency: Duration::from_micros(self.max_latency_us.load(Ordering::Relaxed)),
+        }
+    }
+}
+
 /// Error indicating that a closure was unable to be scheduled to execute on the event loop.
 ///
 /// The most likely cause of a failure is that Node is shutting down. This may occur if the
@@ -229,14 +664,42 @@ impl std::error::Error for SendError {}
 struct ChannelState {
     tsfn: ThreadsafeFunction<Callback>,
     ref_count: AtomicUsize,
+    resource: Arc<AsyncResource>,
+    // Closures sent on high priority channels, which are shared with the
+    // channel they were created from
+    priority: Arc<Pending>,
+    delivery: Delivery,
+    // Shared with closures that are still queued, which update it when they
+    // run
+    stats: Arc<Stats>,
+}
+
+// How the closures sent on a channel reach the threadsafe function
+enum Delivery {
+    // Each call of the threadsafe function runs one closure
+    Queued,
+    // Closures wait in a queue that is drained by calls of the threadsafe
+    // function, so that they never run nested
+    Ordered(Arc<Pending>),
+    // Closures wait in the priority queue of the channel the channel was
+    // created from, which is drained before each of its closures
+    Priority,
 }
 
 impl ChannelState {
-    fn new<'a, C: Context<'a>>(cx: &mut C) -> Self {
+    fn new<'a, C: Context<'a>>(
+        cx: &mut C,
+        resource: Arc<AsyncResource>,
+        delivery: Delivery,
+    ) -> Self {
         let tsfn = unsafe { ThreadsafeFunction::new(cx.env().to_raw(), Self::callback) };
         Self {
             tsfn,
             ref_count: AtomicUsize::new(1),
+            resource,
+            priority: Default::default(),
+            delivery,
+            stats: Default::default(),
         }
     }
 
@@ -275,3 +738,128 @@ impl ChannelState {
         }
     }
 }
+
+impl Drop for ChannelState {
+    fn drop(&mut self) {
+        // The async context can only be destroyed on the JavaScript thread.
+        // Closures that are still queued hold their own reference, so the
+        // context is destroyed after they have run. If Node is shutting down,
+        // the context is leaked.
+        let resource = Arc::clone(&self.resource);
+        let _ = self.tsfn.call(
+            Box::new(move |env| {
+                if let Ok(resource) = Arc::try_unwrap(resource) {
+                    unsafe { resource.destroy(env) };
+                }
+            }),
+            None,
+        );
+    }
+}
+
+// Closures waiting to run on the JavaScript thread
+#[derive(Default)]
+struct Pending {
+    closures: Mutex<VecDeque<(u64, Callback)>>,
+    next_id: AtomicU64,
+    running: AtomicBool,
+}
+
+impl Pending {
+    fn closures(&self) -> MutexGuard<'_, VecDeque<(u64, Callback)>> {
+        // The queue is always valid, even if a thread panicked while holding
+        // the lock
+        self.closures.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Queues a closure and returns an id to remove it with
+    fn push(&self, callback: Callback) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.closures().push_back((id, callback));
+        id
+    }
+
+    // Removes a closure that has not been taken by `run`. Returns `false` if
+    // it was already taken.
+    fn remove(&self, id: u64) -> bool {
+        let mut closures = self.closures();
+
+        match closures.iter().position(|(other, _)| *other == id) {
+            Some(index) => {
+                closures.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Runs closures until the queue is empty. If a closure re-enters the event
+    // loop, nested calls return immediately and the closures they would have
+    // run are left to the outer call.
+    fn run(&self, env: Env) {
+        if self.running.swap(true, Ordering::Acquire) {
+            return;
+        }
+
+        struct Running<'a>(&'a AtomicBool);
+
+        impl Drop for Running<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::Release);
+            }
+        }
+
+        let _running = Running(&self.running);
+
+        loop {
+            // The lock is released before the closure runs, so that it can
+            // send more closures
+            let callback = match self.closures().pop_front() {
+                Some((_, callback)) => callback,
+                None => break,
+            };
+
+            callback(env);
+        }
+    }
+}
+
+// An object and async context that closures sent on a channel are executed in
+struct AsyncResource {
+    object: napi::Ref,
+    context: napi::AsyncContext,
+}
+
+// # Safety
+// The object reference and the async context are only used on the JavaScript
+// thread, which is guaranteed by the `Env` required to enter them.
+unsafe impl Send for AsyncResource {}
+unsafe impl Sync for AsyncResource {}
+
+impl AsyncResource {
+    fn new<'a, C: Context<'a>>(cx: &mut C, name: &str) -> Self {
+        let env = cx.env().to_raw();
+        let object = JsObject::new(cx).to_raw();
+
+        unsafe {
+            Self {
+                object: reference::new(env, object),
+                context: async_context::init(env, object, name),
+            }
+        }
+    }
+
+    fn enter<T, F: FnOnce() -> T>(&self, env: Env, f: F) -> T {
+        unsafe {
+            let object: Local = reference::get(env, self.object);
+
+            async_context::with_callback_scope(env, object, self.context, f)
+        }
+    }
+
+    unsafe fn destroy(self, env: Env) {
+        async_context::destroy(env, self.context);
+        reference::unreference(env, self.object);
+    }
+}
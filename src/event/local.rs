@@ -0,0 +1,146 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{self, Wake, Waker};
+
+use neon_runtime::raw;
+
+use crate::context::internal::{ContextInternal, Env};
+use crate::context::{Context, TaskContext};
+use crate::event::{Channel, WeakChannel};
+
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+struct Task {
+    future: LocalFuture,
+    // Keeps the event loop running until the future completes
+    _channel: Channel,
+}
+
+thread_local! {
+    // Futures that have not completed, which never leave the JavaScript thread
+    // that spawned them
+    static TASKS: RefCell<HashMap<u64, Task>> = RefCell::new(HashMap::new());
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+    // The environment of the future that is being polled
+    static POLLING: Cell<Option<raw::Env>> = const { Cell::new(None) };
+}
+
+pub(crate) fn spawn<'a, C, F>(cx: &mut C, future: F)
+where
+    C: Context<'a>,
+    F: Future<Output = ()> + 'static,
+{
+    let channel = cx.channel();
+    let id = NEXT_ID.with(|next| {
+        let id = next.get();
+
+        next.set(id + 1);
+        id
+    });
+
+    let waker = Arc::new(TaskWaker {
+        id,
+        channel: channel.downgrade(),
+        scheduled: AtomicBool::new(false),
+    });
+
+    let task = Task {
+        future: Box::pin(future),
+        _channel: channel,
+    };
+
+    TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+
+    // The first poll is scheduled like any other, so that the future never
+    // runs in the middle of the function that spawned it
+    waker.wake();
+}
+
+/// Calls `f` with a context of the JavaScript thread, from a future spawned
+/// with [`Context::spawn_local`](crate::context::Context::spawn_local).
+///
+/// Handles created by `f` are only valid until it returns, so they cannot be
+/// held across an `.await`. Values that are needed after an `.await` should
+/// be kept as a [`Root`](crate::handle::Root) and turned back into a handle
+/// in the next call.
+///
+/// # Panics
+///
+/// Panics if the caller is not a future spawned with `spawn_local` that is
+/// being polled.
+pub fn with_local_context<T, F>(f: F) -> T
+where
+    F: for<'b> FnOnce(TaskContext<'b>) -> T,
+{
+    let env = POLLING
+        .with(|polling| polling.get())
+        .expect("`with_local_context` may only be called from a future spawned with `spawn_local`");
+
+    TaskContext::with_context(Env::from(env), f)
+}
+
+// Polls a future on its JavaScript thread, when it was woken
+fn poll(env: raw::Env, waker: Arc<TaskWaker>) {
+    let id = waker.id;
+    let mut task = match TASKS.with(|tasks| tasks.borrow_mut().remove(&id)) {
+        Some(task) => task,
+        None => return,
+    };
+
+    // Wakes while the future is polled schedule another poll
+    waker.scheduled.store(false, Ordering::Release);
+
+    let waker = Waker::from(waker);
+    let mut cx = task::Context::from_waker(&waker);
+    let _polling = Polling::enter(env);
+
+    if task.future.as_mut().poll(&mut cx).is_pending() {
+        TASKS.with(|tasks| tasks.borrow_mut().insert(id, task));
+    }
+}
+
+// Makes the environment available to `with_local_context` until dropped,
+// restoring the one of an outer poll
+struct Polling(Option<raw::Env>);
+
+impl Polling {
+    fn enter(env: raw::Env) -> Self {
+        Self(POLLING.with(|polling| polling.replace(Some(env))))
+    }
+}
+
+impl Drop for Polling {
+    fn drop(&mut self) {
+        POLLING.with(|polling| polling.set(self.0));
+    }
+}
+
+// Schedules a poll on the channel of the task, from any thread. Once the task
+// completes, the channel is gone and wakes do nothing.
+struct TaskWaker {
+    id: u64,
+    channel: WeakChannel,
+    scheduled: AtomicBool,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if self.scheduled.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let waker = Arc::clone(self);
+        let _ = self.channel.try_send(move |cx| {
+            poll(cx.env().to_raw(), waker);
+            Ok(())
+        });
+    }
+}
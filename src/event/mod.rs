@@ -127,7 +127,7 @@
 mod event_queue;
 
 #[cfg(all(feature = "napi-4", feature = "channel-api"))]
-pub use self::event_queue::{Channel, SendError};
+pub use self::event_queue::{Channel, ChannelStats, SendError, WeakChannel};
 
 #[cfg(all(feature = "napi-4", feature = "channel-api"))]
 #[deprecated(since = "0.9.0", note = "Please use the Channel type instead")]
@@ -139,6 +139,55 @@ pub type EventQueue = self::event_queue::Channel;
 #[doc(hidden)]
 pub type EventQueueError = self::event_queue::SendError;
 
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+mod tsfn;
+
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+pub use self::tsfn::{CallError, CallErrorKind, CallMode, ThreadsafeFunction};
+
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+mod call;
+
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+pub use self::call::{Call, JoinError, JoinHandle};
+
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+pub(crate) mod local;
+
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+pub use self::local::with_local_context;
+
+#[cfg(feature = "napi-1")]
+mod async_local;
+
+#[cfg(feature = "napi-1")]
+pub use self::async_local::AsyncLocalSnapshot;
+
+#[cfg(feature = "napi-1")]
+mod emitter;
+
+#[cfg(feature = "napi-1")]
+pub use self::emitter::{Emitter, EventArgs, Subscription};
+
+#[cfg(feature = "napi-1")]
+pub(crate) mod schedule;
+
+#[cfg(all(feature = "napi-6", feature = "promise-api"))]
+pub(crate) mod rejection;
+
+#[cfg(all(feature = "napi-6", feature = "promise-api"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "napi-6", feature = "promise-api"))))]
+pub use self::rejection::on_unhandled_rejection;
+
+#[cfg(all(feature = "napi-1", feature = "task-api"))]
+mod task;
+
+#[cfg(all(feature = "napi-1", feature = "task-api"))]
+pub use self::task::TaskBuilder;
+
+#[cfg(all(feature = "napi-1", feature = "task-api", feature = "promise-api"))]
+pub use self::task::CancellableTask;
+
 #[cfg(all(not(feature = "napi-1"), feature = "event-handler-api"))]
 mod event_handler;
 
@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+
+use crate::context::{Context, ContextLocal, Cx, FunctionContext};
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::{JsBoolean, JsFunction, JsNumber, JsObject, JsPromise, JsUndefined, JsValue};
+
+type Handler = Box<dyn for<'b> FnMut(&mut Cx<'b>, Handle<'b, JsValue>) -> NeonResult<()> + Send>;
+
+// Promises created by this instance of the module are added to a `WeakSet`
+// once a handler is installed, so that tracking does not keep them alive.
+struct Tracker {
+    promises: Root<JsObject>,
+    handler: RefCell<Handler>,
+}
+
+static TRACKER: ContextLocal<Tracker> = ContextLocal::new();
+
+/// Calls `f` with the reason of each promise created by this module, e.g.,
+/// with [`Context::promise`], that is rejected without a rejection handler.
+///
+/// Rejections that are never handled are otherwise easy to miss during
+/// development, since they are reported far from the native code that
+/// rejected them. Calling `on_unhandled_rejection` again replaces `f`.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-6", feature = "promise-api"))] {
+/// # use neon::prelude::*;
+/// use neon::event::on_unhandled_rejection;
+///
+/// fn log_rejections(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     on_unhandled_rejection(&mut cx, |cx, reason| {
+///         let reason = reason.to_string(cx)?.value(cx);
+///
+///         eprintln!("unhandled rejection: {}", reason);
+///
+///         Ok(())
+///     })?;
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// Only promises created after `f` is installed are tracked. The handler is
+/// called from a listener for the [`unhandledRejection`][event] event of
+/// `process`. Since Node.js only applies its default behavior when there are
+/// no listeners, the listener rethrows the reason of any other unhandled
+/// rejection if it is the only listener, as Node.js does by default.
+///
+/// [event]: https://nodejs.org/api/process.html#process_event_unhandledrejection
+pub fn on_unhandled_rejection<'a, C, F>(cx: &mut C, f: F) -> NeonResult<()>
+where
+    C: Context<'a>,
+    F: for<'b> FnMut(&mut Cx<'b>, Handle<'b, JsValue>) -> NeonResult<()> + Send + 'static,
+{
+    if let Some(tracker) = TRACKER.get(cx) {
+        *tracker.handler.borrow_mut() = Box::new(f);
+
+        return Ok(());
+    }
+
+    let promises = construct(cx, "WeakSet")?;
+    let listener = JsFunction::new(cx, listen)?;
+    let event = cx.string("unhandledRejection");
+
+    process(cx)?
+        .method(cx, "on")?
+        .arg(event)
+        .arg(listener)
+        .call(cx)?;
+
+    let tracker = Tracker {
+        promises: promises.root(cx),
+        handler: RefCell::new(Box::new(f)),
+    };
+
+    TRACKER.get_or_init(cx, move || tracker);
+
+    Ok(())
+}
+
+// Adds a promise created by this module to the tracked promises, if a handler
+// is installed.
+pub(crate) fn track<'a, C: Context<'a>>(
+    cx: &mut C,
+    promise: Handle<'a, JsPromise>,
+) -> NeonResult<()> {
+    if let Some(tracker) = TRACKER.get(cx) {
+        let promises = tracker.promises.to_inner(cx);
+
+        promises.method(cx, "add")?.arg(promise).call(cx)?;
+    }
+
+    Ok(())
+}
+
+fn listen(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let reason = cx.argument::<JsValue>(0)?;
+    let promise = cx.argument::<JsValue>(1)?;
+    let tracker = match TRACKER.get(&mut cx) {
+        Some(tracker) => tracker,
+        None => return Ok(cx.undefined()),
+    };
+
+    let promises = tracker.promises.to_inner(&mut cx);
+    let tracked = promises
+        .method(&mut cx, "has")?
+        .arg(promise)
+        .call(&mut cx)?
+        .downcast_or_throw::<JsBoolean, _>(&mut cx)?
+        .value(&mut cx);
+
+    if tracked {
+        let mut handler = match tracker.handler.try_borrow_mut() {
+            Ok(handler) => handler,
+            Err(_) => return cx.throw_error("unhandled rejection handler was called recursively"),
+        };
+
+        handler(&mut cx, reason)?;
+
+        return Ok(cx.undefined());
+    }
+
+    let event = cx.string("unhandledRejection");
+    let listeners = process(&mut cx)?
+        .method(&mut cx, "listenerCount")?
+        .arg(event)
+        .call(&mut cx)?
+        .downcast_or_throw::<JsNumber, _>(&mut cx)?
+        .value(&mut cx);
+
+    if listeners <= 1.0 {
+        return cx.throw(reason);
+    }
+
+    Ok(cx.undefined())
+}
+
+fn process<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+    cx.global().get(cx, "process")?.downcast_or_throw(cx)
+}
+
+fn construct<'a, C: Context<'a>>(cx: &mut C, class: &str) -> JsResult<'a, JsObject> {
+    let class = cx
+        .global()
+        .get(cx, class)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+
+    class.construct(cx, Vec::<Handle<JsValue>>::new())
+}
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+
+use crate::context::internal::ContextInternal;
+use crate::context::{Context, FunctionContext, TaskContext};
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::boxed::{Finalize, JsBox};
+use crate::types::{JsFunction, JsUndefined, JsValue, Value};
+
+type Callback = Box<dyn FnOnce(TaskContext) -> NeonResult<()> + Send + 'static>;
+
+// A closure waiting to be called by a JavaScript scheduling function. The
+// closure is taken on the first call, since `FnOnce` may only be called once.
+struct Scheduled(RefCell<Option<Callback>>);
+
+impl Finalize for Scheduled {}
+
+/// Schedules `f` by calling the global function `scheduler` with a callback
+/// and, optionally, a delay in milliseconds. Returns the value returned by the
+/// scheduler, e.g., a `Timeout` for `setTimeout`.
+pub(crate) fn schedule<'a, C, F>(
+    cx: &mut C,
+    scheduler: &str,
+    delay: Option<f64>,
+    f: F,
+) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+    F: FnOnce(TaskContext) -> NeonResult<()> + Send + 'static,
+{
+    let scheduled = Scheduled(RefCell::new(Some(Box::new(f))));
+    let callback = bind(cx, trampoline, scheduled)?;
+    let global = cx.global();
+    let scheduler = global
+        .get(cx, scheduler)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+    let mut args = vec![callback.upcast::<JsValue>()];
+
+    if let Some(delay) = delay {
+        args.push(cx.number(delay).upcast());
+    }
+
+    scheduler.call(cx, global, args)
+}
+
+/// Creates a JavaScript function that calls `f` with `data` boxed and bound
+/// as the first argument, followed by any arguments of the call.
+pub(crate) fn bind<'a, C, U, R>(
+    cx: &mut C,
+    f: fn(FunctionContext) -> JsResult<R>,
+    data: U,
+) -> JsResult<'a, JsFunction>
+where
+    C: Context<'a>,
+    U: Finalize + Send + 'static,
+    R: Value,
+{
+    let data = cx.boxed(data).upcast();
+
+    bind_value(cx, f, data)
+}
+
+/// Creates a JavaScript function that calls `f` with `data` bound as the
+/// first argument. Unlike [`bind`], `data` may be shared by several functions.
+pub(crate) fn bind_value<'a, C, R>(
+    cx: &mut C,
+    f: fn(FunctionContext) -> JsResult<R>,
+    data: Handle<'a, JsValue>,
+) -> JsResult<'a, JsFunction>
+where
+    C: Context<'a>,
+    R: Value,
+{
+    let f = JsFunction::new(cx, f)?;
+    let this = cx.undefined();
+
+    f.method(cx, "bind")?
+        .arg(this)
+        .arg(data)
+        .call(cx)?
+        .downcast_or_throw(cx)
+}
+
+fn trampoline(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let scheduled = cx.argument::<JsBox<Scheduled>>(0)?;
+    let f = scheduled.0.borrow_mut().take();
+
+    if let Some(f) = f {
+        TaskContext::with_context(cx.env(), f)?;
+    }
+
+    Ok(cx.undefined())
+}
@@ -0,0 +1,419 @@
+use std::panic::{resume_unwind, AssertUnwindSafe};
+#[cfg(feature = "promise-api")]
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use neon_runtime::{async_work, raw};
+
+use crate::context::internal::Env;
+use crate::context::{Context, TaskContext};
+#[cfg(feature = "promise-api")]
+use crate::result::JsResult;
+use crate::result::NeonResult;
+use crate::trace::Queued;
+use crate::types::error::convert_panics;
+#[cfg(feature = "promise-api")]
+use crate::{
+    context::FunctionContext,
+    handle::{Handle, Root},
+    object::Object,
+    types::{
+        Deferred, JsBoolean, JsError, JsFunction, JsObject, JsPromise, JsUndefined, JsValue, Value,
+    },
+};
+
+/// Node asynchronous task builder
+///
+/// A task runs a closure on the Node worker pool and then runs a completion
+/// step with the result on the JavaScript main thread. Tasks are created with
+/// [`Context::task`](crate::context::Context::task).
+///
+/// ```
+/// # #[cfg(all(feature = "napi-1", feature = "promise-api", feature = "task-api"))] {
+/// # use neon::prelude::*;
+/// fn greet(mut cx: FunctionContext) -> JsResult<JsPromise> {
+///     let name = cx.argument::<JsString>(0)?.value(&mut cx);
+///
+///     let promise = cx
+///         .task(move || format!("Hello, {}!", name))
+///         .promise(|mut cx, greeting| Ok(cx.string(greeting)));
+///
+///     Ok(promise)
+/// }
+/// # }
+/// ```
+///
+/// With the `tracing` feature enabled, the `execute` callback runs in a
+/// `DEBUG` span named `task` with the target `neon`, which records the time
+/// the task waited for a worker in the `queue_latency_us` field.
+#[cfg_attr(docsrs, doc(cfg(feature = "task-api")))]
+pub struct TaskBuilder<'cx, C, E> {
+    cx: &'cx mut C,
+    execute: E,
+}
+
+impl<'a: 'cx, 'cx, C, O, E> TaskBuilder<'cx, C, E>
+where
+    C: Context<'a>,
+    O: Send + 'static,
+    E: FnOnce() -> O + Send + 'static,
+{
+    /// Construct a new task builder from an `execute` callback that can be
+    /// scheduled to execute on the Node worker pool
+    pub fn new(cx: &'cx mut C, execute: E) -> Self {
+        Self { cx, execute }
+    }
+
+    /// Schedules a task to execute on the Node worker pool, executing the
+    /// `complete` callback on the JavaScript main thread with the result
+    /// of the `execute` callback
+    ///
+    /// If `execute` panics, the panic is converted to an exception and
+    /// `complete` is not called.
+    pub fn and_then<F>(self, complete: F)
+    where
+        F: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
+    {
+        let env = self.cx.env();
+        let execute = self.execute;
+
+        schedule(env, execute, complete);
+    }
+
+    #[cfg(feature = "promise-api")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "promise-api", feature = "task-api"))))]
+    /// Schedules a task to execute on the Node worker pool and returns a
+    /// promise that is resolved with the value from the `complete` callback.
+    ///
+    /// The promise is rejected if `complete` throws or if either callback
+    /// panics.
+    pub fn promise<V, F>(self, complete: F) -> Handle<'a, JsPromise>
+    where
+        V: Value,
+        F: for<'b> FnOnce(TaskContext<'b>, O) -> JsResult<'b, V> + Send + 'static,
+    {
+        let env = self.cx.env();
+        let (deferred, promise) = JsPromise::new(self.cx);
+
+        schedule_promise(env, self.execute, complete, deferred);
+
+        promise
+    }
+
+    #[cfg(feature = "promise-api")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "promise-api", feature = "task-api"))))]
+    /// Schedules a task like [`promise`](TaskBuilder::promise) and also
+    /// returns a [`CancellableTask`] that can cancel it while it is waiting
+    /// for a worker. A cancelled task does not run and its promise is
+    /// rejected with an `AbortError`.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "napi-1", feature = "promise-api", feature = "task-api"))] {
+    /// # use neon::prelude::*;
+    /// fn checksum(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    ///     let signal = cx.argument::<JsObject>(1)?;
+    ///
+    ///     let (task, promise) = cx
+    ///         .task(move || data.bytes().map(f64::from).sum::<f64>())
+    ///         .cancellable_promise(|mut cx, sum| Ok(cx.number(sum)));
+    ///
+    ///     task.abort_on(&mut cx, signal)?;
+    ///
+    ///     Ok(promise)
+    /// }
+    /// # }
+    /// ```
+    pub fn cancellable_promise<V, F>(self, complete: F) -> (CancellableTask, Handle<'a, JsPromise>)
+    where
+        V: Value,
+        F: for<'b> FnOnce(TaskContext<'b>, O) -> JsResult<'b, V> + Send + 'static,
+    {
+        let env = self.cx.env();
+        let (deferred, promise) = JsPromise::new(self.cx);
+        let input = queued(self.execute);
+        let listeners = AbortListeners::default();
+
+        let work = unsafe {
+            async_work::schedule_cancellable(
+                env.to_raw(),
+                input,
+                (complete, deferred, listeners.clone()),
+                execute,
+                complete_cancellable::<O, F, V>,
+                cancel_promise::<F>,
+            )
+        };
+
+        (CancellableTask { work, listeners }, promise)
+    }
+}
+
+#[cfg(feature = "promise-api")]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "promise-api", feature = "task-api"))))]
+/// Handle to a task scheduled with
+/// [`TaskBuilder::cancellable_promise`](TaskBuilder::cancellable_promise).
+///
+/// Only a task that has not started executing can be cancelled; once a
+/// worker has picked it up, it runs to completion and its promise settles as
+/// usual.
+#[derive(Clone)]
+pub struct CancellableTask {
+    work: async_work::WorkHandle,
+    listeners: AbortListeners,
+}
+
+#[cfg(feature = "promise-api")]
+// The `abort` listeners added by `abort_on`, removed once the task completes
+// or is cancelled. `None` after that, so that later listeners are not added.
+#[derive(Clone)]
+struct AbortListeners(Arc<Mutex<Option<Vec<AbortListener>>>>);
+
+#[cfg(feature = "promise-api")]
+// An `AbortSignal` and the listener added to it
+type AbortListener = (Root<JsObject>, Root<JsFunction>);
+
+#[cfg(feature = "promise-api")]
+impl Default for AbortListeners {
+    fn default() -> Self {
+        Self(Arc::new(Mutex::new(Some(Vec::new()))))
+    }
+}
+
+#[cfg(feature = "promise-api")]
+impl AbortListeners {
+    // Keeps a listener for removal, or returns it if the task has already
+    // completed or been cancelled
+    fn push(&self, listener: AbortListener) -> Option<AbortListener> {
+        match self.0.lock() {
+            Ok(mut listeners) => match listeners.as_mut() {
+                Some(listeners) => {
+                    listeners.push(listener);
+                    None
+                }
+                None => Some(listener),
+            },
+            Err(_) => Some(listener),
+        }
+    }
+
+    // Removes every listener and stops keeping new ones
+    fn remove<'a, C: Context<'a>>(&self, cx: &mut C) {
+        let listeners = match self.0.lock() {
+            Ok(mut listeners) => listeners.take().unwrap_or_default(),
+            Err(_) => return,
+        };
+
+        for listener in listeners {
+            remove_abort_listener(cx, listener);
+        }
+    }
+}
+
+#[cfg(feature = "promise-api")]
+// Errors thrown by `removeEventListener` are ignored, since the task has
+// already settled
+fn remove_abort_listener<'a, C: Context<'a>>(cx: &mut C, (signal, listener): AbortListener) {
+    let signal = signal.into_inner(cx);
+    let listener = listener.into_inner(cx);
+
+    let _ = cx.try_catch_internal(|cx| {
+        let remove_event_listener = signal
+            .get(cx, "removeEventListener")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+        let event = cx.string("abort").upcast::<JsValue>();
+
+        remove_event_listener.call(cx, signal, [event, listener.upcast()])?;
+
+        Ok(())
+    });
+}
+
+#[cfg(feature = "promise-api")]
+impl CancellableTask {
+    /// Cancels the task if it has not started executing, rejecting its
+    /// promise with an `AbortError`. Returns `true` if the task was
+    /// cancelled.
+    pub fn cancel<'a, C: Context<'a>>(&self, cx: &mut C) -> bool {
+        unsafe { self.work.cancel(cx.env().to_raw()) }
+    }
+
+    /// Cancels the task when the JavaScript `AbortSignal` `signal` is
+    /// aborted, or immediately if it already is. The listener is removed from
+    /// `signal` when the task completes or is cancelled.
+    pub fn abort_on<'a, C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        signal: Handle<JsObject>,
+    ) -> NeonResult<()> {
+        let aborted = signal.get(cx, "aborted")?;
+
+        if matches!(aborted.downcast::<JsBoolean, _>(cx), Ok(b) if b.value(cx)) {
+            self.cancel(cx);
+            return Ok(());
+        }
+
+        let add_event_listener = signal
+            .get(cx, "addEventListener")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        let event = cx.string("abort").upcast::<JsValue>();
+        let listener = JsFunction::with_data(cx, self.clone(), abort_listener)?;
+        let options = cx.empty_object();
+        let once = cx.boolean(true);
+
+        options.set(cx, "once", once)?;
+        add_event_listener.call(cx, signal, [event, listener.upcast(), options.upcast()])?;
+
+        // The task had already completed or been cancelled
+        if let Some(listener) = self.listeners.push((signal.root(cx), listener.root(cx))) {
+            remove_abort_listener(cx, listener);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "promise-api")]
+fn abort_listener(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    if let Some(task) = cx.data::<CancellableTask>() {
+        task.clone().cancel(&mut cx);
+    }
+
+    Ok(cx.undefined())
+}
+
+fn schedule<I, O, D>(env: Env, input: I, data: D)
+where
+    I: FnOnce() -> O + Send + 'static,
+    O: Send + 'static,
+    D: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
+{
+    let input = queued(input);
+
+    unsafe {
+        async_work::schedule(env.to_raw(), input, data, execute, complete::<O, D>);
+    }
+}
+
+// Runs `input` in a span that records how long it waited for a worker
+fn queued<I, O>(input: I) -> impl FnOnce() -> O + Send + 'static
+where
+    I: FnOnce() -> O + Send + 'static,
+    O: Send + 'static,
+{
+    let queued = Queued::now();
+
+    move || {
+        let _span = queued.task();
+
+        input()
+    }
+}
+
+fn execute<I, O>(input: I) -> O
+where
+    I: FnOnce() -> O + Send + 'static,
+    O: Send + 'static,
+{
+    input()
+}
+
+fn complete<O, D>(env: raw::Env, output: thread::Result<O>, callback: D)
+where
+    O: Send + 'static,
+    D: FnOnce(TaskContext, O) -> NeonResult<()> + Send + 'static,
+{
+    let env: Env = unsafe { std::mem::transmute(env) };
+
+    // Note: It is sufficient to use `TaskContext`'s `InheritedHandleScope` because
+    // N-API creates a `HandleScope` before calling the callback.
+    TaskContext::with_context(env, move |cx| {
+        let _ = convert_panics(
+            env,
+            AssertUnwindSafe(move || match output {
+                Ok(output) => callback(cx, output),
+                Err(panic) => resume_unwind(panic),
+            }),
+        );
+    });
+}
+
+#[cfg(feature = "promise-api")]
+fn schedule_promise<I, O, D, V>(env: Env, input: I, complete: D, deferred: Deferred)
+where
+    I: FnOnce() -> O + Send + 'static,
+    O: Send + 'static,
+    D: for<'b> FnOnce(TaskContext<'b>, O) -> JsResult<'b, V> + Send + 'static,
+    V: Value,
+{
+    let input = queued(input);
+
+    unsafe {
+        async_work::schedule(
+            env.to_raw(),
+            input,
+            (complete, deferred),
+            execute,
+            complete_promise::<O, D, V>,
+        );
+    }
+}
+
+#[cfg(feature = "promise-api")]
+fn cancel_promise<D>(env: raw::Env, (_, deferred, listeners): (D, Deferred, AbortListeners)) {
+    let env: Env = unsafe { std::mem::transmute(env) };
+
+    deferred.settle(env, move |mut cx| -> JsResult<JsUndefined> {
+        listeners.remove(&mut cx);
+
+        let err = JsError::error(&mut cx, "The operation was aborted")?;
+        let name = cx.string("AbortError");
+        let code = cx.string("ABORT_ERR");
+
+        err.set(&mut cx, "name", name)?;
+        err.set(&mut cx, "code", code)?;
+
+        cx.throw(err)
+    });
+}
+
+#[cfg(feature = "promise-api")]
+fn complete_cancellable<O, D, V>(
+    env: raw::Env,
+    output: thread::Result<O>,
+    (complete, deferred, listeners): (D, Deferred, AbortListeners),
+) where
+    O: Send + 'static,
+    D: for<'b> FnOnce(TaskContext<'b>, O) -> JsResult<'b, V> + Send + 'static,
+    V: Value,
+{
+    let env: Env = unsafe { std::mem::transmute(env) };
+
+    deferred.settle(env, move |mut cx| {
+        listeners.remove(&mut cx);
+
+        match output {
+            Ok(output) => complete(cx, output),
+            Err(panic) => resume_unwind(panic),
+        }
+    });
+}
+
+#[cfg(feature = "promise-api")]
+fn complete_promise<O, D, V>(
+    env: raw::Env,
+    output: thread::Result<O>,
+    (complete, deferred): (D, Deferred),
+) where
+    O: Send + 'static,
+    D: for<'b> FnOnce(TaskContext<'b>, O) -> JsResult<'b, V> + Send + 'static,
+    V: Value,
+{
+    let env: Env = unsafe { std::mem::transmute(env) };
+
+    deferred.settle(env, move |cx| match output {
+        Ok(output) => complete(cx, output),
+        Err(panic) => resume_unwind(panic),
+    });
+}
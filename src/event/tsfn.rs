@@ -0,0 +1,272 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use neon_runtime::napi::{Status, ThreadsafeFunctionCallMode, ThreadsafeFunctionReleaseMode};
+use neon_runtime::raw;
+use neon_runtime::tsfn;
+
+use crate::context::internal::Env;
+use crate::context::{Context, TaskContext};
+use crate::handle::{Handle, Managed};
+use crate::result::NeonResult;
+use crate::types::extract::TryIntoJs;
+use crate::types::{JsFunction, JsValue};
+
+/// A JavaScript function that can be called from any thread, with explicit
+/// control over its queue.
+///
+/// A `ThreadsafeFunction` is a thin wrapper over the Node-API
+/// [thread-safe function][napi-tsfn] that [`Channel`](crate::event::Channel)
+/// is built on. Each call queues `Args`, which are converted with
+/// [`TryIntoJs`] on the JavaScript thread and passed to the function as its
+/// only argument. Unlike a `Channel`:
+///
+/// * The queue may be bounded with [`ThreadsafeFunction::with_capacity`], and
+///   each [`call`](ThreadsafeFunction::call) chooses whether to wait for room
+///   ([`CallMode::Blocking`]) or to fail ([`CallMode::NonBlocking`]).
+/// * Each handle holds one count of the threads using the function, taken
+///   with [`acquire`](ThreadsafeFunction::acquire) and given back when the
+///   handle is released or dropped. The function is finalized when every
+///   handle is gone, or when one of them [aborts](ThreadsafeFunction::abort).
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// use neon::event::{CallMode, ThreadsafeFunction};
+///
+/// fn count_to(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+///     let callback = cx.argument::<JsFunction>(1)?;
+///     let tsfn = ThreadsafeFunction::<u32>::with_capacity(&mut cx, callback, 8)?;
+///
+///     std::thread::spawn(move || {
+///         for i in 1..=n {
+///             // Waits while eight calls are queued
+///             if tsfn.call(i, CallMode::Blocking).is_err() {
+///                 break;
+///             }
+///         }
+///     });
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// [napi-tsfn]: https://nodejs.org/api/n-api.html#asynchronous-thread-safe-function-calls
+pub struct ThreadsafeFunction<Args> {
+    tsfn: Arc<tsfn::ThreadsafeFunction<Args>>,
+    // Set if this handle holds a count taken with `acquire`; the first handle
+    // shares the count the function was created with
+    acquired: bool,
+    _args: PhantomData<fn(Args)>,
+}
+
+impl<Args> ThreadsafeFunction<Args>
+where
+    Args: for<'a> TryIntoJs<'a> + Send + 'static,
+{
+    /// Wraps `callback` with an unbounded queue.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, callback: Handle<JsFunction>) -> NeonResult<Self> {
+        Self::with_capacity(cx, callback, 0)
+    }
+
+    /// Wraps `callback` with a queue of at most `max_queue_size` calls, or an
+    /// unbounded queue if `max_queue_size` is `0`.
+    pub fn with_capacity<'a, C: Context<'a>>(
+        cx: &mut C,
+        callback: Handle<JsFunction>,
+        max_queue_size: usize,
+    ) -> NeonResult<Self> {
+        let tsfn = unsafe {
+            tsfn::ThreadsafeFunction::with_function(
+                cx.env().to_raw(),
+                callback.to_raw(),
+                max_queue_size,
+                call_js::<Args>,
+            )
+        };
+
+        Ok(Self {
+            tsfn: Arc::new(tsfn),
+            acquired: false,
+            _args: PhantomData,
+        })
+    }
+
+    /// Queues a call with `args`.
+    ///
+    /// With [`CallMode::Blocking`], waits while the queue is full. This must
+    /// not be used on the JavaScript thread with a bounded queue, since the
+    /// queue can only drain once the thread is free. With
+    /// [`CallMode::NonBlocking`], fails with [`CallErrorKind::QueueFull`]
+    /// instead.
+    ///
+    /// On failure, `args` can be recovered from the error to try again.
+    pub fn call(&self, args: Args, mode: CallMode) -> Result<(), CallError<Args>> {
+        let mode = match mode {
+            CallMode::Blocking => ThreadsafeFunctionCallMode::Blocking,
+            CallMode::NonBlocking => ThreadsafeFunctionCallMode::NonBlocking,
+        };
+
+        self.tsfn
+            .call(args, Some(mode))
+            .map_err(|err| CallError::new(err.kind(), err.into_inner()))
+    }
+
+    /// Returns a new handle to the function, adding one to the count of
+    /// threads using it. Fails with [`CallErrorKind::Closing`] if the function
+    /// was aborted or is being finalized.
+    pub fn acquire(&self) -> Result<Self, CallError<()>> {
+        self.tsfn
+            .acquire()
+            .map_err(|status| CallError::new(status, ()))?;
+
+        Ok(Self {
+            tsfn: self.tsfn.clone(),
+            acquired: true,
+            _args: PhantomData,
+        })
+    }
+
+    /// Releases this handle, subtracting one from the count of threads using
+    /// the function. Equivalent to dropping it.
+    pub fn release(self) {}
+
+    /// Closes the queue of the function. Calls from every handle fail with
+    /// [`CallErrorKind::Closing`] and calls that are still queued are
+    /// dropped without running.
+    pub fn abort(self) {
+        // A count is taken for the abort, so that the count held by this
+        // handle is still released when it is dropped
+        if self.tsfn.acquire().is_ok() {
+            self.tsfn.release(ThreadsafeFunctionReleaseMode::Abort);
+        }
+    }
+
+    /// Prevents the event loop from exiting while the function exists.
+    /// Applies to every handle. (Default)
+    pub fn reference<'a, C: Context<'a>>(&self, cx: &mut C) {
+        unsafe { self.tsfn.reference(cx.env().to_raw()) }
+    }
+
+    /// Allows the event loop to exit while the function exists. Applies to
+    /// every handle.
+    pub fn unref<'a, C: Context<'a>>(&self, cx: &mut C) {
+        unsafe { self.tsfn.unref(cx.env().to_raw()) }
+    }
+}
+
+impl<Args> Drop for ThreadsafeFunction<Args> {
+    fn drop(&mut self) {
+        if self.acquired {
+            self.tsfn.release(ThreadsafeFunctionReleaseMode::Release);
+        }
+    }
+}
+
+impl<Args> fmt::Debug for ThreadsafeFunction<Args> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ThreadsafeFunction")
+    }
+}
+
+// Calls the JavaScript function with the converted arguments. If the
+// environment was torn down, the arguments are dropped.
+fn call_js<Args>(env: Option<(raw::Env, raw::Local)>, args: Args)
+where
+    Args: for<'a> TryIntoJs<'a>,
+{
+    let (env, callback) = match env {
+        Some(env) => env,
+        None => return,
+    };
+
+    let env = Env::from(env);
+
+    // An exception thrown by the conversion or the function is reported as
+    // uncaught, like one thrown by a closure sent on a `Channel`
+    TaskContext::with_context(env, move |mut cx| {
+        let callback = Handle::<JsFunction>::new_internal(JsFunction::from_raw(env, callback));
+        let _ = (|| -> NeonResult<()> {
+            let arg = args.try_into_js(&mut cx)?.upcast::<JsValue>();
+            let this = cx.undefined();
+
+            callback.call(&mut cx, this, [arg])?;
+
+            Ok(())
+        })();
+    });
+}
+
+/// How [`ThreadsafeFunction::call`] handles a full queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallMode {
+    /// Wait until there is room in the queue
+    Blocking,
+    /// Fail with [`CallErrorKind::QueueFull`]
+    NonBlocking,
+}
+
+/// Why a call to a [`ThreadsafeFunction`] failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CallErrorKind {
+    /// The queue is full and the call did not wait
+    QueueFull,
+    /// The function was aborted or is being finalized, e.g., because Node is
+    /// shutting down
+    Closing,
+    /// Node-API failed for another reason
+    Failed,
+}
+
+/// Error returned when a [`ThreadsafeFunction`] could not be called, holding
+/// the arguments of the call.
+pub struct CallError<T> {
+    kind: CallErrorKind,
+    data: T,
+}
+
+impl<T> CallError<T> {
+    fn new(status: Status, data: T) -> Self {
+        let kind = match status {
+            Status::QueueFull => CallErrorKind::QueueFull,
+            Status::Closing => CallErrorKind::Closing,
+            _ => CallErrorKind::Failed,
+        };
+
+        Self { kind, data }
+    }
+
+    /// Why the call failed.
+    pub fn kind(&self) -> CallErrorKind {
+        self.kind
+    }
+
+    /// Returns the arguments of the call, e.g., to try again.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T> fmt::Display for CallError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            CallErrorKind::QueueFull => f.write_str("queue is full"),
+            CallErrorKind::Closing => f.write_str("function is closing"),
+            CallErrorKind::Failed => f.write_str("call failed"),
+        }
+    }
+}
+
+impl<T> fmt::Debug for CallError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CallError")
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl<T> std::error::Error for CallError<T> {}
@@ -0,0 +1,135 @@
+//! Runtime detection of the Node-API features supported by the host.
+//!
+//! A module compiled for a N-API version may still be loaded by an older
+//! version of Node.js or Electron. Symbols that the host does not provide are
+//! left unloaded and calling a function that requires them panics. Checking
+//! [`supports`] first allows a module to fall back to an alternative instead:
+//!
+//! ```
+//! # #[cfg(feature = "napi-5")] {
+//! # use neon::prelude::*;
+//! use neon::feature::{self, Feature};
+//!
+//! fn now(mut cx: FunctionContext) -> JsResult<JsValue> {
+//!     let ms = 1_600_000_000_000.0;
+//!
+//!     if feature::supports(Feature::Date) {
+//!         Ok(cx.date(ms).or_throw(&mut cx)?.upcast())
+//!     } else {
+//!         Ok(cx.number(ms).upcast())
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! Some Neon APIs depend on the N-API version a module is compiled for rather
+//! than on a single feature. For example, with `napi-6`, every
+//! [`Root`](crate::handle::Root) uses instance data. A module should be
+//! compiled for the oldest N-API version it needs to load in.
+//!
+//! Neon APIs that require a newer N-API version are only available with the
+//! matching `napi-*` feature, so using them without it fails to compile
+//! instead of failing to load.
+
+use std::fmt;
+use std::ops::BitOr;
+
+/// The N-API version of the host process, or `0` if it is not known yet
+/// because the module has not been initialized.
+pub fn napi_version() -> u32 {
+    neon_runtime::napi::version()
+}
+
+/// Returns `true` if the host supports every feature in `features`.
+///
+/// Features may be combined with `|`, e.g., `Feature::Date | Feature::BigInt`.
+pub fn supports<F: Into<Features>>(features: F) -> bool {
+    features.into().napi_version() <= napi_version()
+}
+
+/// A capability of the host that depends on its N-API version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Feature {
+    /// Callback scopes for calling into JavaScript from native async work
+    CallbackScope,
+    /// Thread-safe functions, used by [`Channel`](crate::event::Channel)
+    ThreadsafeFunction,
+    /// Creating and reading `Date` objects
+    Date,
+    /// Creating and reading `BigInt` values
+    BigInt,
+    /// Data associated with each instance of a module
+    InstanceData,
+    /// Listing property names with filters
+    KeyCollection,
+    /// Detaching an `ArrayBuffer`
+    DetachArrayBuffer,
+    /// Tagging objects with a type for safe unwrapping
+    TypeTag,
+    /// Creating `SyntaxError` objects
+    SyntaxError,
+    /// Optimized strings for property keys
+    PropertyKey,
+}
+
+impl Feature {
+    /// The minimum N-API version that provides the feature.
+    pub fn napi_version(self) -> u32 {
+        match self {
+            Feature::CallbackScope => 3,
+            Feature::ThreadsafeFunction => 4,
+            Feature::Date => 5,
+            Feature::BigInt | Feature::InstanceData | Feature::KeyCollection => 6,
+            Feature::DetachArrayBuffer => 7,
+            Feature::TypeTag => 8,
+            Feature::SyntaxError => 9,
+            Feature::PropertyKey => 10,
+        }
+    }
+}
+
+/// A set of [`Feature`]s, created by combining features with `|`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Features {
+    napi_version: u32,
+}
+
+impl Features {
+    /// The minimum N-API version that provides every feature in the set.
+    pub fn napi_version(self) -> u32 {
+        self.napi_version
+    }
+}
+
+impl From<Feature> for Features {
+    fn from(feature: Feature) -> Self {
+        Features {
+            napi_version: feature.napi_version(),
+        }
+    }
+}
+
+impl<F: Into<Features>> BitOr<F> for Feature {
+    type Output = Features;
+
+    fn bitor(self, rhs: F) -> Features {
+        Features::from(self) | rhs
+    }
+}
+
+impl<F: Into<Features>> BitOr<F> for Features {
+    type Output = Features;
+
+    fn bitor(self, rhs: F) -> Features {
+        Features {
+            napi_version: self.napi_version.max(rhs.into().napi_version),
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} (N-API {})", self, self.napi_version())
+    }
+}
@@ -62,6 +62,13 @@ pub(crate) mod root;
 #[cfg(feature = "napi-1")]
 pub use self::root::Root;
 
+#[cfg(feature = "napi-6")]
+mod shared;
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::shared::SharedRoot;
+
 use self::internal::SuperType;
 use crate::context::internal::Env;
 use crate::context::Context;
@@ -82,11 +89,19 @@ pub trait Managed: Copy {
 }
 
 /// A handle to a JavaScript value that is owned by the JavaScript engine.
+///
+/// With the N-API backend and debug assertions enabled, the `Debug` output of
+/// a handle used on the thread of its scope describes the JavaScript value
+/// like [`Value::to_debug_string`](crate::types::Value::to_debug_string).
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy)]
 pub struct Handle<'a, T: Managed + 'a> {
     value: T,
     phantom: PhantomData<&'a T>,
+    // The environment that created the handle, checked when it is used with a
+    // context
+    #[cfg(all(feature = "napi-1", debug_assertions))]
+    env: raw::Env,
 }
 
 #[cfg(feature = "legacy-runtime")]
@@ -104,15 +119,80 @@ impl<'a, T: Managed + 'a> Handle<'a, T> {
         Handle {
             value,
             phantom: PhantomData,
+            #[cfg(all(feature = "napi-1", debug_assertions))]
+            env: crate::context::internal::current_env(),
+        }
+    }
+
+    /// Panics if debug assertions are enabled and the handle was created in a
+    /// different environment than that of `cx`, e.g., by another instance of
+    /// the module.
+    #[cfg_attr(all(feature = "napi-1", debug_assertions), track_caller)]
+    pub(crate) fn check_env<'b, C: Context<'b>>(&self, cx: &C) {
+        #[cfg(all(feature = "napi-1", debug_assertions))]
+        if !self.env.is_null() && self.env != cx.env().to_raw() {
+            panic!(
+                "handle was created in a different environment than the context it is used with"
+            );
+        }
+
+        #[cfg(not(all(feature = "napi-1", debug_assertions)))]
+        let _ = cx;
+    }
+}
+
+impl<'a, T: Managed + 'a> Debug for Handle<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(all(feature = "napi-1", debug_assertions))]
+        if let Some(description) = self.describe() {
+            return f
+                .debug_tuple("Handle")
+                .field(&format_args!("{}", description))
+                .finish();
+        }
+
+        f.debug_struct("Handle")
+            .field("value", &self.value.to_raw())
+            .finish()
+    }
+}
+
+#[cfg(all(feature = "napi-1", debug_assertions))]
+impl<'a, T: Managed + 'a> Handle<'a, T> {
+    // Describes the value in a new scope, if the environment of the handle is
+    // current on this thread and calling into JavaScript cannot clear a
+    // pending exception
+    fn describe(&self) -> Option<String> {
+        use crate::context::internal::{current_env, ContextInternal};
+        use crate::context::TaskContext;
+        use crate::types::JsValue;
+
+        if self.env.is_null() || self.env != current_env() {
+            return None;
+        }
+
+        if unsafe { neon_runtime::error::is_throwing(self.env) } {
+            return None;
         }
+
+        let value = self.value.to_raw();
+
+        TaskContext::with_context(Env::from(self.env), |mut cx| {
+            cx.try_catch_internal(|cx| JsValue::new_internal(value).to_debug_string(cx))
+                .ok()
+        })
     }
 }
 
 /// An error representing a failed downcast.
+///
+/// With the N-API backend, the message describes the value that was found,
+/// e.g., `failed to downcast object (Array) to function`.
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct DowncastError<F: Value, T: Value> {
     phantom_from: PhantomData<F>,
     phantom_to: PhantomData<T>,
+    found: Option<String>,
 }
 
 impl<F: Value, T: Value> Debug for DowncastError<F, T> {
@@ -122,17 +202,27 @@ impl<F: Value, T: Value> Debug for DowncastError<F, T> {
 }
 
 impl<F: Value, T: Value> DowncastError<F, T> {
-    fn new() -> Self {
+    fn new(found: Option<String>) -> Self {
         DowncastError {
             phantom_from: PhantomData,
             phantom_to: PhantomData,
+            found,
         }
     }
+
+    /// Describes the value that failed to downcast, e.g., `string` or
+    /// `object (Date)`. This is `None` with the legacy backend.
+    pub fn found(&self) -> Option<&str> {
+        self.found.as_deref()
+    }
 }
 
 impl<F: Value, T: Value> Display for DowncastError<F, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "failed to downcast {} to {}", F::name(), T::name())
+        match &self.found {
+            Some(found) => write!(f, "failed to downcast {} to {}", found, T::name()),
+            None => write!(f, "failed to downcast {} to {}", F::name(), T::name()),
+        }
     }
 }
 
@@ -155,7 +245,18 @@ impl<'a, T: Value> Handle<'a, T> {
     ///
     /// This method does not require an execution context because it only copies a handle.
     pub fn upcast<U: Value + SuperType<T>>(&self) -> Handle<'a, U> {
-        Handle::new_internal(SuperType::upcast_internal(self.value))
+        self.with_value(SuperType::upcast_internal(self.value))
+    }
+
+    // Creates a handle to another view of the same value, keeping the
+    // environment that created this handle
+    fn with_value<U: Value>(&self, value: U) -> Handle<'a, U> {
+        Handle {
+            value,
+            phantom: PhantomData,
+            #[cfg(all(feature = "napi-1", debug_assertions))]
+            env: self.env,
+        }
     }
 
     #[cfg(feature = "legacy-runtime")]
@@ -192,7 +293,9 @@ impl<'a, T: Value> Handle<'a, T> {
     /// # Ok(cx.undefined())
     /// # }
     /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn is_a<'b, U: Value, C: Context<'b>>(&self, cx: &mut C) -> bool {
+        self.check_env(cx);
         U::is_typeof(cx.env(), self.value)
     }
 
@@ -204,7 +307,7 @@ impl<'a, T: Value> Handle<'a, T> {
     pub fn downcast<U: Value>(&self) -> DowncastResult<'a, T, U> {
         match U::downcast(Env::current(), self.value) {
             Some(v) => Ok(Handle::new_internal(v)),
-            None => Err(DowncastError::new()),
+            None => Err(DowncastError::new(None)),
         }
     }
 
@@ -213,10 +316,13 @@ impl<'a, T: Value> Handle<'a, T> {
     /// to downcast **does not** throw a JavaScript exception, so it's OK to
     /// continue interacting with the JS engine if this method produces an `Err`
     /// result.
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn downcast<'b, U: Value, C: Context<'b>>(&self, cx: &mut C) -> DowncastResult<'a, T, U> {
+        self.check_env(cx);
+
         match U::downcast(cx.env(), self.value) {
-            Some(v) => Ok(Handle::new_internal(v)),
-            None => Err(DowncastError::new()),
+            Some(v) => Ok(self.with_value(v)),
+            None => Err(DowncastError::new(Some(describe(cx, self.value.to_raw())))),
         }
     }
 
@@ -232,6 +338,7 @@ impl<'a, T: Value> Handle<'a, T> {
     /// Attempts to downcast a handle to another type, raising a JavaScript `TypeError`
     /// exception on failure. This method is a convenient shorthand, equivalent to
     /// `self.downcast::<U>().or_throw::<C>(cx)`.
+    #[cfg_attr(debug_assertions, track_caller)]
     pub fn downcast_or_throw<'b, U: Value, C: Context<'b>>(&self, cx: &mut C) -> JsResult<'a, U> {
         self.downcast(cx).or_throw(cx)
     }
@@ -242,12 +349,80 @@ impl<'a, T: Value> Handle<'a, T> {
         cx: &mut C,
         other: Handle<'b, U>,
     ) -> bool {
+        self.check_env(cx);
+        other.check_env(cx);
+
         unsafe {
             neon_runtime::mem::strict_equals(cx.env().to_raw(), self.to_raw(), other.to_raw())
         }
     }
+
+    #[cfg(feature = "napi-1")]
+    /// Compares two values with the SameValue algorithm of `Object.is`, which
+    /// differs from [`strict_equals`](Handle::strict_equals) in that `NaN` is
+    /// equal to itself and `+0` is not equal to `-0`.
+    pub fn same_value<'b, U: Value, C: Context<'b>>(
+        &self,
+        cx: &mut C,
+        other: Handle<'b, U>,
+    ) -> bool {
+        use crate::types::JsNumber;
+
+        if self.is_a::<JsNumber, _>(cx) && other.is_a::<JsNumber, _>(cx) {
+            let a = JsNumber::from_raw(cx.env(), self.to_raw()).value(cx);
+            let b = JsNumber::from_raw(cx.env(), other.to_raw()).value(cx);
+
+            return (a.is_nan() && b.is_nan())
+                || (a == b && a.is_sign_negative() == b.is_sign_negative());
+        }
+
+        self.strict_equals(cx, other)
+    }
+
+    #[cfg(feature = "napi-6")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+    /// Compares two values with the `==` operator of JavaScript, converting
+    /// them as needed. Throws if a conversion throws, e.g., from `valueOf`.
+    pub fn loose_equals<'b, U: Value, C: Context<'b>>(
+        &self,
+        cx: &mut C,
+        other: Handle<'b, U>,
+    ) -> crate::result::NeonResult<bool> {
+        use crate::object::Object;
+        use crate::types::{JsBoolean, JsFunction, JsValue};
+
+        if self.strict_equals(cx, other) {
+            return Ok(true);
+        }
+
+        let equals = LOOSE_EQUALS.get(cx);
+        let equals = match equals {
+            Some(equals) => equals.to_inner(cx),
+            None => {
+                let script = cx.string("(a, b) => a == b");
+                let equals =
+                    crate::reflect::eval(cx, script)?.downcast_or_throw::<JsFunction, _>(cx)?;
+                let root = equals.root(cx);
+
+                LOOSE_EQUALS.get_or_init(cx, move || root);
+                equals
+            }
+        };
+
+        let undefined = cx.undefined();
+        let args = [self.upcast::<JsValue>(), other.upcast()];
+        let result = equals.call(cx, undefined, args)?;
+
+        Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
 }
 
+// Compiled once per instance of the module, since JavaScript has no builtin
+// function for `==`
+#[cfg(feature = "napi-6")]
+static LOOSE_EQUALS: crate::context::ContextLocal<Root<crate::types::JsFunction>> =
+    crate::context::ContextLocal::new();
+
 impl<'a, T: Managed> Deref for Handle<'a, T> {
     type Target = T;
     fn deref(&self) -> &T {
@@ -260,3 +435,48 @@ impl<'a, T: Managed> DerefMut for Handle<'a, T> {
         &mut self.value
     }
 }
+
+// Describes a value for a downcast error: its `typeof`, followed by the name
+// of its constructor if it is an object, e.g., `object (Date)`. Reading the
+// constructor may run JavaScript; if it throws, the exception is discarded.
+#[cfg(feature = "napi-1")]
+fn describe<'b, C: Context<'b>>(cx: &mut C, value: raw::Local) -> String {
+    use crate::object::Object;
+    use crate::types::{JsFunction, JsObject, JsString};
+
+    let env = cx.env();
+    let type_name = unsafe { neon_runtime::tag::type_name(env.to_raw(), value) };
+
+    match type_name {
+        "Array" => return "object (Array)".to_string(),
+        "object" => {}
+        _ => return type_name.to_string(),
+    }
+
+    let object = Handle::new_internal(JsObject::from_raw(env, value));
+    // Types are checked with `is_a` before downcasting, since a failed
+    // downcast would describe the value again
+    let constructor = cx.try_catch_internal(|cx| {
+        let constructor = object.get(cx, "constructor")?;
+
+        if !constructor.is_a::<JsFunction, _>(cx) {
+            return Ok(None);
+        }
+
+        let constructor = constructor.downcast::<JsFunction, _>(cx).unwrap();
+        let name = constructor.get(cx, "name")?;
+
+        if !name.is_a::<JsString, _>(cx) {
+            return Ok(None);
+        }
+
+        let name = name.downcast::<JsString, _>(cx).unwrap().value(cx);
+
+        Ok(Some(name).filter(|name| !name.is_empty()))
+    });
+
+    match constructor {
+        Ok(Some(name)) => format!("object ({})", name),
+        _ => "object".to_string(),
+    }
+}
@@ -8,6 +8,8 @@ use neon_runtime::reference;
 use neon_runtime::tsfn::ThreadsafeFunction;
 
 use crate::context::Context;
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::Tracked;
 use crate::handle::Handle;
 #[cfg(feature = "napi-6")]
 use crate::lifecycle::InstanceData;
@@ -30,13 +32,17 @@ unsafe impl Sync for NapiRef {}
 /// prevents it from being garbage collected.
 ///
 /// A `Root<T>` may be sent across threads, but the referenced object may
-/// only be accessed on the JavaScript thread that created it.
+/// only be accessed on the JavaScript thread that created it. To share one
+/// among threads, convert it into a [`SharedRoot`](crate::handle::SharedRoot)
+/// with `Root::into_shared`.
 pub struct Root<T> {
     // `Option` is used to skip `Drop` when `Root::drop` or `Root::into_inner` is used.
     // It will *always* be `Some` when a user is interacting with `Root`.
     internal: Option<NapiRef>,
     #[cfg(feature = "napi-6")]
     drop_queue: Arc<ThreadsafeFunction<NapiRef>>,
+    #[cfg(feature = "diagnostics")]
+    _tracked: Tracked,
     _phantom: PhantomData<T>,
 }
 
@@ -62,6 +68,7 @@ impl<T: Object> Root<T> {
     /// calling one of these methods:
     /// * N-API < 6, Neon will `panic` to notify of the leak
     /// * N-API >= 6, Neon will drop from a global queue at a runtime cost
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub fn new<'a, C: Context<'a>>(cx: &mut C, value: &T) -> Self {
         let env = cx.env().to_raw();
         let internal = unsafe { reference::new(env, value.to_raw()) };
@@ -70,6 +77,8 @@ impl<T: Object> Root<T> {
             internal: Some(NapiRef(internal as *mut _)),
             #[cfg(feature = "napi-6")]
             drop_queue: InstanceData::drop_queue(cx),
+            #[cfg(feature = "diagnostics")]
+            _tracked: Tracked::root(),
             _phantom: PhantomData,
         }
     }
@@ -86,6 +95,7 @@ impl<T: Object> Root<T> {
     /// # Ok(cx.undefined())
     /// # }
     /// ```
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub fn clone<'a, C: Context<'a>>(&self, cx: &mut C) -> Self {
         let env = cx.env();
         let internal = self.as_napi_ref().0 as *mut _;
@@ -98,6 +108,8 @@ impl<T: Object> Root<T> {
             internal: self.internal.clone(),
             #[cfg(feature = "napi-6")]
             drop_queue: Arc::clone(&self.drop_queue),
+            #[cfg(feature = "diagnostics")]
+            _tracked: Tracked::root(),
             _phantom: PhantomData,
         }
     }
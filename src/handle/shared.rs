@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+
+/// A reference to a JavaScript object that can be cloned and dropped on any
+/// thread.
+///
+/// A [`Root`] can only be cloned with a context, so sharing one among worker
+/// threads requires wrapping it, e.g., in `Arc<Mutex<Option<Root<T>>>>`. A
+/// `SharedRoot` is `Clone`, `Send` and `Sync`, and follows these rules:
+///
+/// * Clones share a single `Root`. Cloning and dropping only change an atomic
+///   count and never call into JavaScript, so both may happen on any thread.
+/// * The object is released when the last clone is dropped. If that happens
+///   on another thread, the reference is released on the JavaScript thread
+///   later, through the same queue as a `Root` dropped without a context.
+/// * The object can only be accessed with [`SharedRoot::to_inner`] on the
+///   JavaScript thread of the instance of the module that created it.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-6", feature = "channel-api"))] {
+/// # use neon::prelude::*;
+/// fn call_from_workers(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+///     let callback = callback.into_shared(&mut cx);
+///     let channel = cx.channel();
+///
+///     for i in 0..4 {
+///         let callback = callback.clone();
+///         let channel = channel.clone();
+///
+///         std::thread::spawn(move || {
+///             channel.send(move |mut cx| {
+///                 let callback = callback.to_inner(&mut cx);
+///                 let this = cx.undefined();
+///                 let i = cx.number(i);
+///
+///                 callback.call(&mut cx, this, [i.upcast::<JsValue>()])?;
+///
+///                 Ok(())
+///             });
+///         });
+///     }
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+pub struct SharedRoot<T> {
+    inner: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    root: Root<T>,
+    // The environment that created the root, as an address so that it is
+    // `Send`. It is only compared, never dereferenced.
+    env: usize,
+}
+
+impl<T: Object> SharedRoot<T> {
+    /// Creates a reference to a JavaScript object. The object will not be
+    /// garbage collected until every clone is dropped.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, value: &T) -> Self {
+        Root::new(cx, value).into_shared(cx)
+    }
+
+    /// Returns the referenced JavaScript object, keeping the reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cx` does not belong to the instance of the module that
+    /// created the `SharedRoot`, e.g., if it was sent to another worker.
+    pub fn to_inner<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, T> {
+        assert_eq!(
+            cx.env().to_raw() as usize,
+            self.inner.env,
+            "`SharedRoot` may only be accessed by the instance of the module that created it",
+        );
+
+        self.inner.root.to_inner(cx)
+    }
+
+    /// Returns the `Root` if this is the only clone, or `self` otherwise.
+    pub fn try_unwrap(self) -> Result<Root<T>, Self> {
+        Arc::try_unwrap(self.inner)
+            .map(|shared| shared.root)
+            .map_err(|inner| Self { inner })
+    }
+}
+
+impl<T: Object> Root<T> {
+    /// Converts the `Root` into a [`SharedRoot`], which can be cloned on any
+    /// thread.
+    pub fn into_shared<'a, C: Context<'a>>(self, cx: &mut C) -> SharedRoot<T> {
+        SharedRoot {
+            inner: Arc::new(Shared {
+                root: self,
+                env: cx.env().to_raw() as usize,
+            }),
+        }
+    }
+}
+
+impl<T> Clone for SharedRoot<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for SharedRoot<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SharedRoot<{}>", std::any::type_name::<T>())
+    }
+}
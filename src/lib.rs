@@ -82,21 +82,48 @@
 
 pub mod borrow;
 pub mod context;
-#[cfg(any(
-    feature = "event-handler-api",
-    all(feature = "napi-4", feature = "channel-api")
-))]
-#[cfg_attr(docsrs, doc(cfg(all(feature = "napi-4", feature = "channel-api"))))]
+#[cfg(all(feature = "napi-1", feature = "diagnostics"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "diagnostics")))]
+pub mod diagnostics;
+#[cfg(any(feature = "event-handler-api", feature = "napi-1"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
 pub mod event;
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub mod feature;
 pub mod handle;
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "log"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(feature = "napi-4", feature = "channel-api", feature = "log")))
+)]
+pub mod log;
 pub mod meta;
 pub mod object;
 pub mod prelude;
 #[cfg(feature = "napi-1")]
 pub mod reflect;
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub mod registry;
 pub mod result;
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub mod runtime;
+#[cfg(all(feature = "napi-1", feature = "standalone"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "standalone")))]
+pub mod standalone;
+#[cfg(all(feature = "napi-1", feature = "sys"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "sys")))]
+pub mod sys;
 #[cfg(feature = "legacy-runtime")]
 pub mod task;
+#[cfg(all(feature = "napi-1", feature = "mock-context"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock-context")))]
+pub mod test;
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub mod transfer;
 pub mod types;
 
 #[doc(hidden)]
@@ -106,8 +133,16 @@ pub mod macro_internal;
 #[cfg_attr(docsrs, doc(cfg(feature = "proc-macros")))]
 pub use neon_macros::*;
 
+#[cfg(feature = "napi-1")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+pub use registry::registered;
+
 #[cfg(feature = "napi-6")]
-mod lifecycle;
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub mod lifecycle;
+
+#[cfg(feature = "napi-1")]
+mod trace;
 
 #[cfg(all(feature = "legacy-runtime", feature = "napi-1"))]
 compile_error!("Cannot enable both `legacy-runtime` and `napi-*` features.\n\nTo use `napi-*`, disable `legacy-runtime` by setting `default-features` to `false` in Cargo.toml\nor with cargo's --no-default-features flag.");
@@ -133,17 +168,64 @@ macro_rules! register_module {
 
             fn __init_neon_module($module: $crate::context::ModuleContext) -> $crate::result::NeonResult<()> $init
 
-            $crate::macro_internal::initialize_module(
-                env,
-                std::mem::transmute(m),
-                __init_neon_module,
-            );
+            $crate::macro_internal::initialize_module(env, m, __init_neon_module);
 
             m
         }
+
+        // Entry point used by emnapi when the module is built for WebAssembly
+        #[cfg(target_arch = "wasm32")]
+        #[no_mangle]
+        pub unsafe extern "C" fn napi_register_wasm_v1(
+            env: $crate::macro_internal::runtime::raw::Env,
+            m: $crate::macro_internal::runtime::raw::Local
+        ) -> $crate::macro_internal::runtime::raw::Local
+        {
+            napi_register_module_v1(env, m)
+        }
     }
 }
 
+/// Creates a `Handle<JsString>` for a string literal, creating the string only
+/// once for each instance of the module.
+///
+/// Strings that are used on every call, such as event names or the tags of an
+/// enum, are otherwise copied into a new JavaScript string each time. The
+/// string created by `js_string!` is kept alive in the data of the instance
+/// and dropped when the instance is unloaded.
+///
+/// The first argument is a mutable reference to a context, like the argument
+/// of other Neon functions, so the macro can also be used in helpers that are
+/// passed `&mut C` or `&mut FunctionContext`.
+///
+/// ```
+/// # #[cfg(feature = "napi-6")] {
+/// # use neon::prelude::*;
+/// use neon::js_string;
+///
+/// fn status(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let status = cx.empty_object();
+///     let key = js_string!(&mut cx, "kind");
+///     let kind = js_string!(&mut cx, "ready");
+///
+///     status.set(&mut cx, key, kind)?;
+///
+///     Ok(status)
+/// }
+/// # }
+/// ```
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+#[macro_export]
+macro_rules! js_string {
+    ($cx:expr, $value:literal) => {{
+        static INTERNED: $crate::macro_internal::InternedString =
+            $crate::macro_internal::InternedString::new($value);
+
+        INTERNED.get($cx)
+    }};
+}
+
 #[cfg(feature = "legacy-runtime")]
 /// Register the current crate as a Node module, providing startup
 /// logic for initializing the module object at runtime.
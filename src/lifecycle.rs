@@ -4,11 +4,58 @@
 //! environment. If a Neon module is loaded multiple times (Web Workers, worker
 //! threads), these API will be handle data associated with a specific instance.
 //!
-//! See the [N-API Lifecycle][npai-docs] documentation for more details.
+//! An instance of a module is unloaded when its environment is torn down,
+//! e.g., when a worker thread exits. Test runners and dev servers that recycle
+//! workers, such as Jest and Vitest, load and unload a module many times in
+//! the same process. When an instance is unloaded, Neon:
+//!
+//! 1. Runs the functions passed to [`on_unload`], most recent first
+//! 2. Drops the values of every [`ContextLocal`](crate::context::ContextLocal),
+//!    including the [`Root`](crate::handle::Root)s and caches Neon keeps for
+//!    the instance
+//! 3. Closes the shared [`Channel`](crate::event::Channel) of the instance
+//!
+//! State that belongs to an instance should be kept in a `ContextLocal`, so
+//! that it is dropped with the instance instead of leaking. Anything that
+//! outlives the module's JavaScript, such as threads or an async runtime the
+//! module started, should be stopped with [`on_unload`]:
+//!
+//! ```
+//! # #[cfg(feature = "napi-6")] {
+//! # use neon::prelude::*;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//! use std::sync::Arc;
+//! use std::thread;
+//!
+//! fn start_worker(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let stop = Arc::new(AtomicBool::new(false));
+//!     let handle = thread::spawn({
+//!         let stop = stop.clone();
+//!
+//!         move || {
+//!             while !stop.load(Ordering::Relaxed) {
+//!                 thread::yield_now();
+//!             }
+//!         }
+//!     });
+//!
+//!     neon::lifecycle::on_unload(&mut cx, move || {
+//!         stop.store(true, Ordering::Relaxed);
+//!         let _ = handle.join();
+//!     });
+//!
+//!     Ok(cx.undefined())
+//! }
+//! # }
+//! ```
+//!
+//! See the [N-API Lifecycle][napi-docs] documentation for more details.
 //!
 //! [napi-docs]: https://nodejs.org/api/n-api.html#n_api_environment_life_cycle_apis
 
+use std::any::Any;
 use std::mem;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::Arc;
 
 use neon_runtime::raw::Env;
@@ -36,6 +83,27 @@ pub(crate) struct InstanceData {
     /// Shared `Channel` that is cloned to be returned by the `cx.channel()` method
     #[cfg(all(feature = "channel-api"))]
     shared_channel: Channel,
+
+    /// Values of each `ContextLocal`, indexed by the key's id
+    locals: Vec<Option<Box<dyn Any + Send>>>,
+
+    /// Functions passed to `on_unload`, in the order they were added
+    unload: Vec<Box<dyn FnOnce() + Send>>,
+}
+
+/// Runs `f` when the current instance of the module is unloaded, e.g., to stop
+/// threads the module started or to free resources it holds globally.
+///
+/// Functions run in the reverse of the order they were added, while the
+/// environment is being torn down, so they cannot call into JavaScript. They
+/// run before the values of `ContextLocal`s are dropped. A panic in one
+/// function does not prevent the others from running.
+pub fn on_unload<'a, C, F>(cx: &mut C, f: F)
+where
+    C: Context<'a>,
+    F: FnOnce() + Send + 'static,
+{
+    InstanceData::get(cx).unload.push(Box::new(f));
 }
 
 fn drop_napi_ref(env: Option<Env>, data: NapiRef) {
@@ -79,6 +147,8 @@ impl InstanceData {
             drop_queue: Arc::new(drop_queue),
             #[cfg(all(feature = "channel-api"))]
             shared_channel,
+            locals: Vec::new(),
+            unload: Vec::new(),
         };
 
         unsafe { &mut *neon_runtime::lifecycle::set_instance_data(env, data) }
@@ -97,4 +167,32 @@ impl InstanceData {
         channel.reference(cx);
         channel
     }
+
+    /// Helper to return the slot for the `ContextLocal` with the given id,
+    /// growing the table if necessary.
+    pub(crate) fn local<'a, C: Context<'a>>(
+        cx: &mut C,
+        id: usize,
+    ) -> &'a mut Option<Box<dyn Any + Send>> {
+        let locals = &mut InstanceData::get(cx).locals;
+
+        if locals.len() <= id {
+            locals.resize_with(id + 1, || None);
+        }
+
+        &mut locals[id]
+    }
+}
+
+impl Drop for InstanceData {
+    fn drop(&mut self) {
+        // A panic cannot unwind out of the finalizer that drops the instance
+        while let Some(f) = self.unload.pop() {
+            let _ = panic::catch_unwind(AssertUnwindSafe(f));
+        }
+
+        // Values of `ContextLocal`s are dropped before the rest of the
+        // instance data, in the order documented for the module
+        self.locals.clear();
+    }
 }
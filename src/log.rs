@@ -0,0 +1,214 @@
+//! Forwarding of [`log`](https://docs.rs/log) records to JavaScript.
+//!
+//! With the `log` feature enabled, [`init`] installs a global logger that
+//! forwards every record to the JavaScript thread that called it. By
+//! default, records are written to the console: errors with
+//! `console.error`, warnings with `console.warn` and everything else with
+//! `console.log`.
+//!
+//! `init` returns an object for configuring the logger from JavaScript:
+//!
+//! * `setLevel(level)` sets the most verbose level that is forwarded, one of
+//!   `"off"`, `"error"`, `"warn"`, `"info"`, `"debug"` or `"trace"`. The
+//!   default is `"info"`.
+//! * `setSink(fn)` forwards records to `fn` instead of the console. The
+//!   function is called with an object with the `level`, `target`,
+//!   `message`, `file` and `line` of the record. Passing `null` or
+//!   `undefined` restores the console.
+//!
+//! ```
+//! # #[cfg(all(feature = "napi-4", feature = "channel-api", feature = "log"))] {
+//! # use neon::prelude::*;
+//! fn init_logger(mut cx: FunctionContext) -> JsResult<JsObject> {
+//!     neon::log::init(&mut cx)
+//! }
+//! # }
+//! ```
+//!
+//! ```js
+//! const logger = addon.initLogger();
+//!
+//! logger.setLevel("debug");
+//! logger.setSink(({ level, target, message }) => {
+//!     myLogger[level](`${target}: ${message}`);
+//! });
+//! ```
+//!
+//! Records may be logged from any thread. They are delivered asynchronously
+//! through a [`Channel`], which does not keep the event loop running; records
+//! logged while the event loop is shutting down are discarded.
+
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+use ::log::{Level, LevelFilter, Log, Metadata, Record};
+
+use crate::context::{Context, FunctionContext, TaskContext};
+use crate::event::Channel;
+use crate::handle::Root;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::{JsFunction, JsNull, JsObject, JsString, JsUndefined, JsValue};
+
+thread_local! {
+    // The function that receives records instead of the console, if any.
+    // Records are only delivered on the thread that installed the logger.
+    static SINK: RefCell<Option<Root<JsFunction>>> = const { RefCell::new(None) };
+}
+
+/// Installs a logger that forwards records logged with the `log` crate to
+/// the calling JavaScript thread.
+///
+/// Returns an object with `setLevel` and `setSink` methods for configuring
+/// the logger. Throws an `Error` if a logger, from Neon or any other crate,
+/// is already installed.
+pub fn init<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+    let mut channel = cx.channel();
+
+    channel.unref(cx);
+
+    let logger = Bridge {
+        channel: Mutex::new(channel),
+    };
+
+    if ::log::set_boxed_logger(Box::new(logger)).is_err() {
+        return cx.throw_error("a logger is already installed");
+    }
+
+    ::log::set_max_level(LevelFilter::Info);
+
+    let controls = cx.empty_object();
+    let set_level = JsFunction::new(cx, set_level)?;
+    let set_sink = JsFunction::new(cx, set_sink)?;
+
+    controls.set(cx, "setLevel", set_level)?;
+    controls.set(cx, "setSink", set_sink)?;
+
+    Ok(controls)
+}
+
+struct Bridge {
+    channel: Mutex<Channel>,
+}
+
+impl Log for Bridge {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= ::log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let entry = Entry {
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            file: record.file().map(str::to_string),
+            line: record.line(),
+        };
+
+        let channel = self.channel.lock().unwrap_or_else(|err| err.into_inner());
+
+        // The event loop has stopped and there is nowhere to log the record
+        let _ = channel.try_send(move |mut cx| entry.write(&mut cx));
+    }
+
+    fn flush(&self) {}
+}
+
+struct Entry {
+    level: Level,
+    target: String,
+    message: String,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+impl Entry {
+    fn write(self, cx: &mut TaskContext) -> NeonResult<()> {
+        let sink = SINK.with(|sink| sink.borrow().as_ref().map(|sink| sink.to_inner(cx)));
+
+        match sink {
+            Some(sink) => {
+                let record = self.to_object(cx)?;
+                let this = cx.undefined();
+
+                sink.call(cx, this, vec![record])?;
+            }
+            None => {
+                let method = match self.level {
+                    Level::Error => "error",
+                    Level::Warn => "warn",
+                    _ => "log",
+                };
+
+                let message = cx.string(format!("[{}] {}", self.target, self.message));
+
+                cx.global()
+                    .get(cx, "console")?
+                    .downcast_or_throw::<JsObject, _>(cx)?
+                    .method(cx, method)?
+                    .arg(message)
+                    .call(cx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn to_object<'a>(&self, cx: &mut TaskContext<'a>) -> JsResult<'a, JsObject> {
+        let record = cx.empty_object();
+        let level = cx.string(self.level.as_str().to_lowercase());
+        let target = cx.string(&self.target);
+        let message = cx.string(&self.message);
+        let file = match &self.file {
+            Some(file) => cx.string(file).upcast::<JsValue>(),
+            None => cx.undefined().upcast(),
+        };
+        let line = match self.line {
+            Some(line) => cx.number(line).upcast::<JsValue>(),
+            None => cx.undefined().upcast(),
+        };
+
+        record.set(cx, "level", level)?;
+        record.set(cx, "target", target)?;
+        record.set(cx, "message", message)?;
+        record.set(cx, "file", file)?;
+        record.set(cx, "line", line)?;
+
+        Ok(record)
+    }
+}
+
+fn set_level(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let level = cx.argument::<JsString>(0)?.value(&mut cx);
+    let level = match level.parse::<LevelFilter>() {
+        Ok(level) => level,
+        Err(_) => return cx.throw_range_error(format!("unknown log level `{}`", level)),
+    };
+
+    ::log::set_max_level(level);
+
+    Ok(cx.undefined())
+}
+
+fn set_sink(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let sink = match cx.argument_opt(0) {
+        None => None,
+        Some(sink) if sink.is_a::<JsUndefined, _>(&mut cx) || sink.is_a::<JsNull, _>(&mut cx) => {
+            None
+        }
+        Some(sink) => Some(
+            sink.downcast_or_throw::<JsFunction, _>(&mut cx)?
+                .root(&mut cx),
+        ),
+    };
+
+    if let Some(previous) = SINK.with(|current| current.replace(sink)) {
+        previous.drop(&mut cx);
+    }
+
+    Ok(cx.undefined())
+}
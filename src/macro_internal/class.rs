@@ -0,0 +1,335 @@
+//! Support for the `#[neon::class]` macro.
+//!
+//! The state of an instance is a `Mutex` shared by an `Arc`, which is attached
+//! to the JavaScript object with `napi_wrap`. Methods lock the state for the
+//! duration of the call, and `async` methods clone the state and release the
+//! lock before their future is spawned on the JavaScript thread.
+
+use std::cell::RefCell;
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+use std::future::Future;
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+use std::task::Poll;
+
+use neon_runtime::class::Wrapped;
+use neon_runtime::object::Property;
+
+use crate::context::internal::ContextInternal;
+#[cfg(feature = "napi-6")]
+use crate::context::ContextLocal;
+use crate::context::{CallKind, Context, FunctionContext, ModuleContext};
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+use crate::event::with_local_context;
+#[cfg(feature = "napi-6")]
+use crate::handle::Root;
+use crate::handle::{Handle, Managed};
+use crate::macro_internal::export::ExportWrapper;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult, Throw};
+use crate::types::extract::TryIntoJs;
+use crate::types::internal::{Callback, FunctionCallback};
+#[cfg(feature = "napi-6")]
+use crate::types::{JsBoolean, JsObject};
+use crate::types::{JsFunction, JsValue};
+
+/// The shared state of an instance of a class.
+pub type ClassState<T> = Arc<Mutex<T>>;
+
+/// A member of the prototype or, if static, the constructor of a class.
+pub enum ClassMember {
+    Method(ExportWrapper),
+    StaticMethod(ExportWrapper),
+    /// A property whose getter, setter or both call the wrapper. The setter
+    /// is called with one argument and the getter with none.
+    Accessor {
+        wrapper: ExportWrapper,
+        get: bool,
+        set: bool,
+    },
+}
+
+/// Creates the constructor of a class with the given prototype members.
+pub fn define_class<'a>(
+    cx: &mut ModuleContext<'a>,
+    name: &str,
+    constructor: ExportWrapper,
+    members: &[(&str, ClassMember)],
+) -> JsResult<'a, JsFunction> {
+    let mut properties = Vec::with_capacity(members.len());
+    let mut statics = Vec::new();
+
+    for (name, member) in members {
+        let name = cx.string(name).to_raw();
+
+        match member {
+            ClassMember::Method(f) => properties.push((
+                name,
+                Property::Method(FunctionCallback(*f).into_c_callback()),
+            )),
+            ClassMember::StaticMethod(f) => statics.push((
+                name,
+                Property::Method(FunctionCallback(*f).into_c_callback()),
+            )),
+            ClassMember::Accessor { wrapper, get, set } => properties.push((
+                name,
+                Property::Accessor {
+                    callback: FunctionCallback(*wrapper).into_c_callback(),
+                    get: *get,
+                    set: *set,
+                },
+            )),
+        }
+    }
+
+    let env = cx.env().to_raw();
+    let constructor = FunctionCallback(constructor).into_c_callback();
+    let class =
+        unsafe { neon_runtime::class::define(env, name, constructor, &properties, &statics) };
+
+    match class {
+        Some(class) => Ok(Handle::new_internal(JsFunction::from_raw(cx.env(), class))),
+        None => Err(Throw),
+    }
+}
+
+/// Defines a static property of a class, such as an associated constant.
+pub fn define_constant<'a, V: TryIntoJs<'a>>(
+    cx: &mut ModuleContext<'a>,
+    class: Handle<'a, JsFunction>,
+    name: &str,
+    value: V,
+) -> NeonResult<()> {
+    let value = value.try_into_js(cx)?;
+
+    class.set(cx, name, value)?;
+
+    Ok(())
+}
+
+/// The constructor of a class in each instance of the module, which is needed
+/// to create instances from Rust.
+#[cfg(feature = "napi-6")]
+pub struct ClassRef(ContextLocal<Root<JsFunction>>);
+
+#[cfg(feature = "napi-6")]
+impl ClassRef {
+    pub const fn new() -> Self {
+        Self(ContextLocal::new())
+    }
+
+    /// Keeps the constructor of the class after it is defined.
+    pub fn set<'a>(&self, cx: &mut ModuleContext<'a>, class: Handle<'a, JsFunction>) {
+        let class = class.root(cx);
+
+        self.0.get_or_init(cx, move || class);
+    }
+
+    /// Creates an instance of the class with `value` as its state, without
+    /// calling `new`. If `this` is a JavaScript subclass, as in
+    /// `Subclass.create()`, the instance inherits from the subclass, but the
+    /// constructor of the subclass is not run.
+    pub fn construct<'a, T: Send + 'static>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        value: T,
+    ) -> JsResult<'a, JsValue> {
+        let class = match self.0.get(cx) {
+            Some(class) => class.to_inner(cx),
+            None => return cx.throw_error("class is not defined in this instance of the module"),
+        };
+
+        let target = new_target(cx, class)?;
+        let state: Wrapped = Box::new(ClassState::new(Mutex::new(value)));
+
+        PENDING.with(|pending| *pending.borrow_mut() = Some(state));
+
+        let result = if target.strict_equals(cx, class) {
+            class
+                .construct(cx, Vec::<Handle<JsValue>>::new())
+                .map(|this| this.upcast())
+        } else {
+            construct_with_target(cx, class, target)
+        };
+
+        // The constructor may have thrown before taking the state
+        PENDING.with(|pending| pending.borrow_mut().take());
+
+        result
+    }
+}
+
+// Returns `this` if it is the class or a subclass, or otherwise the class
+#[cfg(feature = "napi-6")]
+fn new_target<'a>(
+    cx: &mut FunctionContext<'a>,
+    class: Handle<'a, JsFunction>,
+) -> JsResult<'a, JsFunction> {
+    let this = match cx.this().downcast::<JsFunction, _>(cx) {
+        Ok(this) => this,
+        Err(_) => return Ok(class),
+    };
+
+    let inherits = class.method(cx, "isPrototypeOf")?.arg(this).call(cx)?;
+
+    match inherits.downcast::<JsBoolean, _>(cx) {
+        Ok(inherits) if inherits.value(cx) => Ok(this),
+        _ => Ok(class),
+    }
+}
+
+// Constructs `class` with `target` as `new.target`, like `super()` in the
+// constructor of a subclass
+#[cfg(feature = "napi-6")]
+fn construct_with_target<'a>(
+    cx: &mut FunctionContext<'a>,
+    class: Handle<'a, JsFunction>,
+    target: Handle<'a, JsFunction>,
+) -> JsResult<'a, JsValue> {
+    let reflect = cx
+        .global()
+        .get(cx, "Reflect")?
+        .downcast_or_throw::<JsObject, _>(cx)?;
+    let args = cx.empty_array();
+
+    reflect
+        .method(cx, "construct")?
+        .arg(class)
+        .arg(args)
+        .arg(target)
+        .call(cx)
+}
+
+#[cfg(feature = "napi-6")]
+impl Default for ClassRef {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+thread_local! {
+    // State of an instance being created by `ClassRef::construct`
+    static PENDING: RefCell<Option<Wrapped>> = RefCell::new(None);
+}
+
+/// Attaches the state passed to `ClassRef::construct`, if the constructor was
+/// called by it, and returns the object being constructed.
+pub fn wrap_pending<'a>(cx: &mut FunctionContext<'a>) -> NeonResult<Option<Handle<'a, JsValue>>> {
+    let state = match PENDING.with(|pending| pending.borrow_mut().take()) {
+        Some(state) => state,
+        None => return Ok(None),
+    };
+
+    let this = cx.this();
+
+    if unsafe { neon_runtime::class::wrap(cx.env().to_raw(), this.to_raw(), state) } {
+        Ok(Some(this.upcast()))
+    } else {
+        cx.throw_type_error("object was already constructed")
+    }
+}
+
+/// Throws a `TypeError` unless the constructor of a class was called with
+/// `new`.
+pub fn check_construct(cx: &mut FunctionContext, class: &str) -> NeonResult<()> {
+    match cx.kind() {
+        CallKind::Construct => Ok(()),
+        CallKind::Call => cx.throw_type_error(format!(
+            "Class constructor {} cannot be invoked without 'new'",
+            class
+        )),
+    }
+}
+
+/// Attaches `value` to the object being constructed and returns the object.
+pub fn wrap<'a, T: Send + 'static>(
+    cx: &mut FunctionContext<'a>,
+    value: T,
+) -> JsResult<'a, JsValue> {
+    let this = cx.this();
+    let state: Wrapped = Box::new(ClassState::new(Mutex::new(value)));
+
+    if unsafe { neon_runtime::class::wrap(cx.env().to_raw(), this.to_raw(), state) } {
+        Ok(this.upcast())
+    } else {
+        cx.throw_type_error("object was already constructed")
+    }
+}
+
+/// Returns the state of `this`, throwing a `TypeError` if it is not an
+/// instance of the class.
+pub fn class_state<T: Send + 'static>(
+    cx: &mut FunctionContext,
+    class: &str,
+) -> NeonResult<ClassState<T>> {
+    let this = cx.this();
+    let state = unsafe { neon_runtime::class::unwrap(cx.env().to_raw(), this.to_raw()) };
+
+    match state.and_then(|state| state.downcast_ref::<ClassState<T>>()) {
+        Some(state) => Ok(state.clone()),
+        None => cx.throw_type_error(format!("expected `this` to be an instance of {}", class)),
+    }
+}
+
+/// Locks the state of an instance for a method call, throwing an `Error` if
+/// it is in use by a pending `async` method or a method further up the stack.
+pub fn lock<'s, T>(
+    cx: &mut FunctionContext,
+    state: &'s ClassState<T>,
+    class: &str,
+) -> NeonResult<MutexGuard<'s, T>> {
+    match state.try_lock() {
+        Ok(guard) => Ok(guard),
+        // A panic in a method was already thrown as an exception
+        Err(TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+        Err(TryLockError::WouldBlock) => cx.throw_error(format!("{} is already in use", class)),
+    }
+}
+
+/// Clones the state of an instance for an `async` method, holding the lock
+/// only while cloning, so that the instance stays usable while the method is
+/// pending.
+pub fn clone_state<T: Clone>(
+    cx: &mut FunctionContext,
+    state: &ClassState<T>,
+    class: &str,
+) -> NeonResult<T> {
+    let guard = lock(cx, state, class)?;
+
+    Ok(T::clone(&guard))
+}
+
+/// Drives the future of an `async` method on the JavaScript thread and
+/// returns a promise for its converted output. A panic rejects the promise.
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+pub fn spawn_method<'a, O, F>(cx: &mut FunctionContext<'a>, future: F) -> JsResult<'a, JsValue>
+where
+    O: for<'b> TryIntoJs<'b> + 'static,
+    F: Future<Output = O> + 'static,
+{
+    let (deferred, promise) = cx.promise();
+    let mut future = Box::pin(future);
+
+    cx.spawn_local(async move {
+        let output = std::future::poll_fn(|task| {
+            match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(task))) {
+                Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+                Ok(Poll::Pending) => Poll::Pending,
+                Err(panic) => Poll::Ready(Err(panic)),
+            }
+        })
+        .await;
+
+        with_local_context(|cx| {
+            deferred.settle(cx.env(), move |mut cx| match output {
+                Ok(output) => Ok(output.try_into_js(&mut cx)?.upcast::<JsValue>()),
+                // Settling converts the panic to an exception
+                Err(panic) => panic::resume_unwind(panic),
+            })
+        });
+    });
+
+    Ok(promise.upcast())
+}
@@ -0,0 +1,824 @@
+//! Registry of items marked with `#[neon::export]`.
+//!
+//! Each exported item registers itself from a global constructor before the
+//! module is loaded. Registrations form an intrusive linked list so that no
+//! allocation or locking happens during static initialization.
+//!
+//! When the module is loaded, each registration adds its item to a list of
+//! pending exports on the `ModuleContext`. The items of each namespace are
+//! then defined together with a single call to `napi_define_properties`,
+//! which also creates exported functions, instead of one call per item.
+
+use std::borrow::Cow;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::sync::Arc;
+
+use neon_runtime::object::Property;
+
+use crate::context::internal::ContextInternal;
+use crate::context::{Context, FunctionContext, ModuleContext};
+use crate::handle::{Handle, Managed};
+use crate::object::Object;
+use crate::registry::Export;
+use crate::result::{JsResult, NeonResult, Throw};
+use crate::trace::Entered;
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::internal::{Callback, FunctionCallback};
+use crate::types::{JsBox, JsError, JsFunction, JsObject, JsString, JsUndefined, JsValue, Value};
+
+pub type ExportFn = fn(&mut ModuleContext) -> NeonResult<()>;
+
+/// The wrapper function generated for an exported Rust function.
+pub type ExportWrapper = fn(FunctionContext) -> JsResult<JsValue>;
+
+/// An item added by a registration, waiting to be defined by [`export_all`].
+pub(crate) struct PendingExport<'a> {
+    namespace: &'static str,
+    name: Handle<'a, JsString>,
+    property: Property,
+}
+
+pub struct Registration {
+    export: ExportFn,
+    metadata: Option<&'static Export>,
+    // The wrapper of an exported function, called by `neon::standalone`
+    #[allow(dead_code)]
+    function: Option<ExportWrapper>,
+    // The description read from the compiled library by `neon_build::Loader`,
+    // which is only kept by the linker if it is referenced
+    #[allow(dead_code)]
+    loader: Option<&'static str>,
+    next: AtomicPtr<Registration>,
+}
+
+impl Registration {
+    pub const fn new(export: ExportFn) -> Self {
+        Self {
+            export,
+            metadata: None,
+            function: None,
+            loader: None,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// A registration described by `metadata` in [`crate::registered`].
+    pub const fn with_metadata(export: ExportFn, metadata: &'static Export) -> Self {
+        Self {
+            export,
+            metadata: Some(metadata),
+            function: None,
+            loader: None,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// The wrapper of an exported function, which extracts its arguments and
+    /// converts its result.
+    pub const fn with_function(mut self, function: ExportWrapper) -> Self {
+        self.function = Some(function);
+        self
+    }
+
+    /// Embeds the description of an exported function in the library, for
+    /// the loader generated by `neon_build::Loader`.
+    pub const fn with_loader(mut self, loader: &'static str) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+}
+
+static REGISTRATIONS: AtomicPtr<Registration> = AtomicPtr::new(ptr::null_mut());
+
+// Set by `#[neon::main(camel_case)]` before the module is initialized, to a
+// marker found in the library by `neon_build::Loader`. Storing the marker
+// keeps it in the library.
+static CAMEL_CASE: AtomicPtr<u8> = AtomicPtr::new(ptr::null_mut());
+
+pub fn set_export_camel_case(marker: &'static str) {
+    CAMEL_CASE.store(marker.as_ptr() as *mut u8, Ordering::Relaxed);
+}
+
+/// The name a Rust item is exported under, converting `snake_case` to
+/// `camelCase` if the module opted in. Leading underscores are preserved.
+pub fn export_name(name: &'static str) -> Cow<'static, str> {
+    if CAMEL_CASE.load(Ordering::Relaxed).is_null() {
+        return Cow::Borrowed(name);
+    }
+
+    let trimmed = name.trim_start_matches('_');
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+
+    result.push_str(&name[..name.len() - trimmed.len()]);
+
+    for c in trimmed.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    Cow::Owned(result)
+}
+
+pub fn register(registration: &'static Registration) {
+    let ptr = registration as *const Registration as *mut Registration;
+    let mut head = REGISTRATIONS.load(Ordering::Acquire);
+
+    loop {
+        registration.next.store(head, Ordering::Relaxed);
+
+        match REGISTRATIONS.compare_exchange_weak(head, ptr, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return,
+            Err(current) => head = current,
+        }
+    }
+}
+
+// Every registration, in the order they were registered
+fn registrations() -> Vec<&'static Registration> {
+    let mut registrations = Vec::new();
+    let mut next = REGISTRATIONS.load(Ordering::Acquire);
+
+    // Safety: Registrations are `'static` and are never removed from the list
+    while let Some(registration) = unsafe { next.as_ref() } {
+        registrations.push(registration);
+        next = registration.next.load(Ordering::Acquire);
+    }
+
+    // The list is built in reverse
+    registrations.reverse();
+    registrations
+}
+
+/// The entry point of a module, marked with `#[neon::main]`.
+pub type MainFn = fn(ModuleContext) -> NeonResult<()>;
+
+/// A function marked with `#[neon::main(order = N)]`, which runs after the
+/// exports are defined, along with the module's entry point.
+pub struct MainRegistration {
+    main: MainFn,
+    order: i32,
+    name: &'static str,
+    next: AtomicPtr<MainRegistration>,
+}
+
+impl MainRegistration {
+    pub const fn new(main: MainFn, order: i32, name: &'static str) -> Self {
+        Self {
+            main,
+            order,
+            name,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+static MAINS: AtomicPtr<MainRegistration> = AtomicPtr::new(ptr::null_mut());
+
+pub fn register_main(registration: &'static MainRegistration) {
+    let ptr = registration as *const MainRegistration as *mut MainRegistration;
+    let mut head = MAINS.load(Ordering::Acquire);
+
+    loop {
+        registration.next.store(head, Ordering::Relaxed);
+
+        match MAINS.compare_exchange_weak(head, ptr, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return,
+            Err(current) => head = current,
+        }
+    }
+}
+
+/// The functions to run when the module is loaded, sorted by their `order`.
+/// The entry point `main` has order `0` and runs before other functions of
+/// the same order, which are sorted by their path to be deterministic.
+pub(crate) fn mains(main: MainFn) -> Vec<MainFn> {
+    let mut mains = vec![(0, "", main)];
+    let mut next = MAINS.load(Ordering::Acquire);
+
+    // Safety: Registrations are `'static` and are never removed from the list
+    while let Some(registration) = unsafe { next.as_ref() } {
+        mains.push((registration.order, registration.name, registration.main));
+        next = registration.next.load(Ordering::Acquire);
+    }
+
+    mains.sort_by_key(|&(order, name, _)| (order, name));
+    mains.into_iter().map(|(_, _, main)| main).collect()
+}
+
+/// The descriptions of the registered items, for [`crate::registered`].
+pub(crate) fn registered() -> Vec<&'static Export> {
+    registrations()
+        .into_iter()
+        .filter_map(|registration| registration.metadata)
+        .collect()
+}
+
+/// The exported functions and their wrappers, for `neon::standalone`.
+#[cfg(feature = "standalone")]
+pub(crate) fn functions() -> Vec<(&'static Export, ExportWrapper)> {
+    registrations()
+        .into_iter()
+        .filter_map(|registration| Some((registration.metadata?, registration.function?)))
+        .collect()
+}
+
+/// The function exported with `#[neon::export(init)]`, which must be called
+/// before the other exports of each instance of the module. Registered when
+/// the module is loaded.
+pub struct InitRegistration {
+    name: &'static str,
+}
+
+impl InitRegistration {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+static INIT: AtomicPtr<InitRegistration> = AtomicPtr::new(ptr::null_mut());
+
+// Set if more than one function is exported with `init`
+static MULTIPLE_INITS: AtomicBool = AtomicBool::new(false);
+
+// Marks the instances of the module that were initialized
+#[cfg(feature = "napi-6")]
+static INITIALIZED: crate::context::ContextLocal<()> = crate::context::ContextLocal::new();
+
+#[cfg(feature = "napi-6")]
+pub fn register_init(registration: &'static InitRegistration) {
+    let ptr = registration as *const InitRegistration as *mut InitRegistration;
+
+    // Registered each time an instance of the module is loaded
+    match INIT.compare_exchange(ptr::null_mut(), ptr, Ordering::AcqRel, Ordering::Acquire) {
+        Err(current) if current != ptr => MULTIPLE_INITS.store(true, Ordering::Relaxed),
+        _ => {}
+    }
+}
+
+/// Throws if the module has an `init` export that has not been called yet in
+/// this instance. Called by every other exported function, so the name is
+/// only converted with [`export_name`] if `convert` is set and it throws.
+pub fn check_initialized(
+    cx: &mut FunctionContext,
+    name: &'static str,
+    convert: bool,
+) -> NeonResult<()> {
+    // Safety: The registration is `'static`
+    let init = match unsafe { INIT.load(Ordering::Acquire).as_ref() } {
+        Some(init) => init,
+        None => return Ok(()),
+    };
+
+    #[cfg(feature = "napi-6")]
+    if INITIALIZED.get(cx).is_some() {
+        return Ok(());
+    }
+
+    let name = if convert {
+        export_name(name)
+    } else {
+        Cow::Borrowed(name)
+    };
+
+    cx.throw_error(format!(
+        "`{}` cannot be used before the module is initialized with `{}`",
+        name, init.name
+    ))
+}
+
+/// Throws if `init` was already called in this instance of the module.
+#[cfg(feature = "napi-6")]
+pub fn begin_init(cx: &mut FunctionContext) -> NeonResult<()> {
+    if INITIALIZED.get(cx).is_none() {
+        return Ok(());
+    }
+
+    let name = unsafe { INIT.load(Ordering::Acquire).as_ref() }.map_or("init", |init| init.name);
+
+    cx.throw_error(format!("`{}` was already called", name))
+}
+
+/// Marks this instance of the module as initialized after `init` succeeded.
+#[cfg(feature = "napi-6")]
+pub fn finish_init(cx: &mut FunctionContext) {
+    INITIALIZED.get_or_init(cx, || ());
+}
+
+/// Adds every registered item to `module.exports`.
+pub(crate) fn export_all(cx: &mut ModuleContext) -> NeonResult<()> {
+    for registration in registrations() {
+        (registration.export)(cx)?;
+    }
+
+    if MULTIPLE_INITS.load(Ordering::Relaxed) {
+        return cx.throw_error("only one function may be exported with `init`");
+    }
+
+    super::test::export_runner(cx)?;
+
+    define_pending(cx)
+}
+
+// Defines the pending exports of each namespace, in the order the namespaces
+// were first used, with one call for each namespace.
+fn define_pending(cx: &mut ModuleContext) -> NeonResult<()> {
+    let pending = std::mem::take(cx.pending_exports());
+    let mut namespaces: Vec<(&'static str, Vec<_>)> = Vec::new();
+
+    for export in pending {
+        let index = match namespaces
+            .iter()
+            .position(|(namespace, _)| *namespace == export.namespace)
+        {
+            Some(index) => index,
+            None => {
+                namespaces.push((export.namespace, Vec::new()));
+                namespaces.len() - 1
+            }
+        };
+
+        namespaces[index]
+            .1
+            .push((export.name.to_raw(), export.property));
+    }
+
+    for (namespace, properties) in namespaces {
+        let object = namespace_object(cx, namespace)?;
+
+        define_properties(cx, object, &properties)?;
+    }
+
+    Ok(())
+}
+
+/// Enters the `tracing` span of a call to the exported function `name`, for
+/// the duration of the wrapper. Does nothing without the `tracing` feature.
+pub fn enter_export(name: &'static str) -> Entered {
+    crate::trace::export(name)
+}
+
+/// Exports the wrapper of a Rust function as `name` in the dot-separated
+/// `namespace` of `module.exports`. An empty namespace refers to
+/// `module.exports` itself.
+pub fn export_function(
+    cx: &mut ModuleContext,
+    namespace: &'static str,
+    name: &str,
+    f: ExportWrapper,
+) -> NeonResult<()> {
+    let callback = FunctionCallback(f).into_c_callback();
+
+    push_export(cx, namespace, name, Property::Method(callback));
+
+    Ok(())
+}
+
+/// Exports `value` as `name`, like [`export_function`].
+pub fn export_value<'a, V: Value>(
+    cx: &mut ModuleContext<'a>,
+    namespace: &'static str,
+    name: &str,
+    value: Handle<'a, V>,
+) -> NeonResult<()> {
+    push_export(cx, namespace, name, Property::Value(value.to_raw()));
+
+    Ok(())
+}
+
+/// Installs `getter` as an accessor for `name`, like [`export_function`]. The
+/// getter is expected to call [`replace_lazy`] when it runs.
+pub fn export_lazy(
+    cx: &mut ModuleContext,
+    namespace: &'static str,
+    name: &str,
+    getter: ExportWrapper,
+) -> NeonResult<()> {
+    let callback = FunctionCallback(getter).into_c_callback();
+
+    push_export(cx, namespace, name, Property::Getter(callback));
+
+    Ok(())
+}
+
+/// Returns why an export with `requires` or `when` is not available on the
+/// host, or `None` if the host has at least `napi_version` and `when` returns
+/// `true`.
+pub fn export_unavailable(napi_version: u32, when: Option<fn() -> bool>) -> Option<String> {
+    let found = crate::feature::napi_version();
+
+    if found < napi_version {
+        return Some(format!(
+            "requires N-API version {}, found {}",
+            napi_version, found
+        ));
+    }
+
+    match when {
+        Some(when) if !when() => Some("not supported by this runtime".to_string()),
+        _ => None,
+    }
+}
+
+/// Throws the error of the stub installed in place of an unavailable export
+/// marked with `stub`.
+pub fn throw_unavailable<'a>(
+    cx: &mut FunctionContext<'a>,
+    name: &str,
+    reason: &str,
+) -> JsResult<'a, JsValue> {
+    cx.throw_error(format!("`{}` is unavailable: {}", name, reason))
+}
+
+fn push_export<'a>(
+    cx: &mut ModuleContext<'a>,
+    namespace: &'static str,
+    name: &str,
+    property: Property,
+) {
+    let name = cx.string(name);
+
+    cx.pending_exports().push(PendingExport {
+        namespace,
+        name,
+        property,
+    });
+}
+
+/// Replaces the lazy accessor for `name` on the receiver of the getter with a
+/// plain data property holding `value`, and returns `value`.
+pub fn replace_lazy<'a>(
+    cx: &mut FunctionContext<'a>,
+    name: &str,
+    value: Handle<'a, JsValue>,
+) -> JsResult<'a, JsValue> {
+    let this = cx.this();
+    let name = cx.string(name);
+
+    define_properties(
+        cx,
+        this,
+        &[(name.to_raw(), Property::Value(value.to_raw()))],
+    )?;
+
+    Ok(value)
+}
+
+// Finds or creates the object at `namespace`.
+fn namespace_object<'a>(cx: &mut ModuleContext<'a>, namespace: &str) -> JsResult<'a, JsObject> {
+    let mut object = cx.exports_object()?;
+
+    for segment in namespace.split('.').filter(|s| !s.is_empty()) {
+        let child = object.get(cx, segment)?;
+
+        object = if child.is_a::<JsUndefined, _>(cx) {
+            let child = cx.empty_object();
+
+            object.set(cx, segment, child)?;
+            child
+        } else {
+            match child.downcast::<JsObject, _>(cx) {
+                Ok(child) => child,
+                Err(_) => {
+                    return cx.throw_type_error(format!(
+                        "cannot export to `{}`: `{}` is not an object",
+                        namespace, segment
+                    ))
+                }
+            }
+        };
+    }
+
+    Ok(object)
+}
+
+fn define_properties<'a, C: Context<'a>>(
+    cx: &mut C,
+    target: Handle<JsObject>,
+    properties: &[(neon_runtime::raw::Local, Property)],
+) -> NeonResult<()> {
+    let env = cx.env().to_raw();
+
+    if unsafe { neon_runtime::object::define_properties(env, target.to_raw(), properties) } {
+        Ok(())
+    } else {
+        Err(Throw)
+    }
+}
+
+/// Extracts the `i`th argument, treating a missing argument as `undefined`.
+/// An error names the argument, e.g., `argument 2 ("timeout") expected
+/// number, found string`.
+pub fn extract_argument<'a, T: TryFromJs<'a>>(
+    cx: &mut FunctionContext<'a>,
+    i: i32,
+    name: &str,
+) -> NeonResult<T> {
+    let v = match cx.argument_opt(i) {
+        Some(v) => v,
+        None => cx.undefined().upcast(),
+    };
+
+    from_argument(cx, i, name, v)
+}
+
+/// Extracts the `i`th argument, calling `default` if it is missing or `undefined`.
+pub fn extract_argument_or<'a, T, F>(
+    cx: &mut FunctionContext<'a>,
+    i: i32,
+    name: &str,
+    default: F,
+) -> NeonResult<T>
+where
+    T: TryFromJs<'a>,
+    F: FnOnce() -> T,
+{
+    match cx.argument_opt(i) {
+        Some(v) if !v.is_a::<JsUndefined, _>(cx) => from_argument(cx, i, name, v),
+        _ => Ok(default()),
+    }
+}
+
+/// Extracts the `this` binding of the call.
+pub fn extract_this<'a, T: TryFromJs<'a>>(cx: &mut FunctionContext<'a>) -> NeonResult<T> {
+    let this = cx.this().upcast();
+
+    T::from_js(cx, this)
+}
+
+/// Extracts every argument from `start` onward.
+pub fn extract_rest<'a, T: TryFromJs<'a>>(
+    cx: &mut FunctionContext<'a>,
+    start: i32,
+    name: &str,
+) -> NeonResult<Vec<T>> {
+    let len = cx.len();
+    let mut rest = Vec::with_capacity((len - start).max(0) as usize);
+
+    for i in start..len {
+        let v = cx.argument::<JsValue>(i)?;
+
+        rest.push(from_argument(cx, i, name, v)?);
+    }
+
+    Ok(rest)
+}
+
+/// Extracts the `i`th argument as a `JsBox<Arc<T>>`, returning a clone of the
+/// `Arc`, which is not tied to the lifetime of the call.
+pub fn extract_arc<'a, T>(cx: &mut FunctionContext<'a>, i: i32, name: &str) -> NeonResult<Arc<T>>
+where
+    T: Send + Sync + 'static,
+{
+    let boxed = extract_argument::<Handle<JsBox<Arc<T>>>>(cx, i, name)?;
+
+    Ok(Arc::clone(&boxed))
+}
+
+// Converts the `i`th argument, naming the argument and the type of the value
+// in the message of a conversion error
+fn from_argument<'a, T: TryFromJs<'a>>(
+    cx: &mut FunctionContext<'a>,
+    i: i32,
+    name: &str,
+    v: Handle<'a, JsValue>,
+) -> NeonResult<T> {
+    let err = match T::try_from_js(cx, v)? {
+        Ok(v) => return Ok(v),
+        Err(err) => err,
+    };
+
+    let err = match err.error_parts() {
+        Some(parts) => {
+            let found = match parts.found {
+                Some(found) => found,
+                None => unsafe { neon_runtime::tag::type_name(cx.env().to_raw(), v.to_raw()) }
+                    .to_owned(),
+            };
+
+            ArgumentError {
+                index: i,
+                name,
+                kind: if parts.range {
+                    ErrorKind::Range
+                } else {
+                    ErrorKind::Type
+                },
+                message: format!("expected {}, found {}", parts.expected, found),
+            }
+        }
+        // Errors that are only available as JavaScript values keep their
+        // message, prefixed with the argument
+        None => {
+            let err = err.try_into_js(cx)?.upcast::<JsValue>();
+
+            match ArgumentError::from_js_error(cx, i, name, err)? {
+                Some(err) => err,
+                None => return cx.throw(err),
+            }
+        }
+    };
+
+    let err = err.try_into_js(cx)?;
+
+    cx.throw(err)
+}
+
+// E.g., `argument 2 ("timeout")`, counting from one
+fn describe_argument(i: i32, name: &str) -> String {
+    if name.is_empty() {
+        format!("argument {}", i + 1)
+    } else {
+        format!("argument {} (\"{}\")", i + 1, name)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ErrorKind {
+    Error,
+    Type,
+    Range,
+}
+
+// An argument that could not be converted, e.g., `argument 2 ("timeout")
+// expected number, found string`. The JavaScript error is created from the
+// full message, so that its stack trace matches.
+struct ArgumentError<'n> {
+    index: i32,
+    name: &'n str,
+    kind: ErrorKind,
+    message: String,
+}
+
+impl<'n> ArgumentError<'n> {
+    // Reads the class and message of a JavaScript error. Returns `None` for
+    // other values, which are thrown unchanged.
+    fn from_js_error<'a>(
+        cx: &mut FunctionContext<'a>,
+        index: i32,
+        name: &'n str,
+        err: Handle<'a, JsValue>,
+    ) -> NeonResult<Option<Self>> {
+        let err = match err.downcast::<JsError, _>(cx) {
+            Ok(err) => err,
+            Err(_) => return Ok(None),
+        };
+
+        let message = err.get(cx, "message")?;
+        let message = match message.downcast::<JsString, _>(cx) {
+            Ok(message) => message.value(cx),
+            Err(_) => return Ok(None),
+        };
+
+        let kind = err.get(cx, "name")?;
+        let kind = match kind.downcast::<JsString, _>(cx) {
+            Ok(kind) => match kind.value(cx).as_str() {
+                "TypeError" => ErrorKind::Type,
+                "RangeError" => ErrorKind::Range,
+                "Error" => ErrorKind::Error,
+                _ => return Ok(None),
+            },
+            Err(_) => return Ok(None),
+        };
+
+        Ok(Some(ArgumentError {
+            index,
+            name,
+            kind,
+            message,
+        }))
+    }
+}
+
+impl<'n> std::fmt::Display for ArgumentError<'n> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            describe_argument(self.index, self.name),
+            self.message
+        )
+    }
+}
+
+impl<'a, 'n> TryIntoJs<'a> for ArgumentError<'n> {
+    type Value = JsError;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsError> {
+        let message = self.to_string();
+
+        match self.kind {
+            ErrorKind::Error => JsError::error(cx, message),
+            ErrorKind::Type => JsError::type_error(cx, message),
+            ErrorKind::Range => JsError::range_error(cx, message),
+        }
+    }
+}
+
+/// Converts the return value of an exported function.
+pub fn into_js_value<'a, T: TryIntoJs<'a>>(
+    cx: &mut FunctionContext<'a>,
+    v: T,
+) -> JsResult<'a, JsValue> {
+    Ok(v.try_into_js(cx)?.upcast())
+}
+
+/// Extracts the `i`th argument of a `json` export by passing the output of
+/// `JSON.stringify` to `parse`. Values without a JSON representation, including
+/// a missing argument, are passed as `null`.
+pub fn extract_json<'a, T, F>(
+    cx: &mut FunctionContext<'a>,
+    i: i32,
+    name: &str,
+    parse: F,
+) -> NeonResult<T>
+where
+    F: FnOnce(&str) -> Result<T, String>,
+{
+    let v = match cx.argument_opt(i) {
+        Some(v) => v,
+        None => cx.undefined().upcast(),
+    };
+    let json = json_stringify(cx, v)?;
+
+    match parse(&json) {
+        Ok(v) => Ok(v),
+        Err(err) => cx.throw_type_error(format!("invalid {}: {}", describe_argument(i, name), err)),
+    }
+}
+
+/// Like [`extract_json`], but calls `default` if the argument is missing or
+/// `undefined`.
+pub fn extract_json_or<'a, T, F, D>(
+    cx: &mut FunctionContext<'a>,
+    i: i32,
+    name: &str,
+    default: D,
+    parse: F,
+) -> NeonResult<T>
+where
+    F: FnOnce(&str) -> Result<T, String>,
+    D: FnOnce() -> T,
+{
+    match cx.argument_opt(i) {
+        Some(v) if !v.is_a::<JsUndefined, _>(cx) => extract_json(cx, i, name, parse),
+        _ => Ok(default()),
+    }
+}
+
+/// Converts the serialized return value of a `json` export with `JSON.parse`.
+/// A serialization error is thrown as an `Error`.
+pub fn into_js_json<'a>(
+    cx: &mut FunctionContext<'a>,
+    json: Result<String, String>,
+) -> JsResult<'a, JsValue> {
+    let json = match json {
+        Ok(json) => cx.string(json),
+        Err(err) => return cx.throw_error(format!("invalid return value: {}", err)),
+    };
+    let parse = json_method(cx, "parse")?;
+    let null = cx.null();
+
+    parse.call(cx, null, [json])
+}
+
+fn json_stringify<'a>(cx: &mut FunctionContext<'a>, v: Handle<'a, JsValue>) -> NeonResult<String> {
+    let stringify = json_method(cx, "stringify")?;
+    let null = cx.null();
+    let json = stringify.call(cx, null, [v])?;
+
+    match json.downcast::<JsString, _>(cx) {
+        Ok(json) => Ok(json.value(cx)),
+        Err(_) => Ok("null".to_string()),
+    }
+}
+
+fn json_method<'a>(cx: &mut FunctionContext<'a>, name: &str) -> JsResult<'a, JsFunction> {
+    cx.global()
+        .get(cx, "JSON")?
+        .downcast_or_throw::<JsObject, _>(cx)?
+        .get(cx, name)?
+        .downcast_or_throw(cx)
+}
+
+/// Runs an exported function marked with `task` on the Node worker pool and
+/// returns a promise for its converted result.
+#[cfg(all(feature = "promise-api", feature = "task-api"))]
+pub fn spawn_task<'a, O, F>(cx: &mut FunctionContext<'a>, f: F) -> JsResult<'a, JsValue>
+where
+    O: for<'b> TryIntoJs<'b> + Send + 'static,
+    F: FnOnce() -> O + Send + 'static,
+{
+    let promise = cx
+        .task(f)
+        .promise(|mut cx, output| Ok(output.try_into_js(&mut cx)?.upcast::<JsValue>()));
+
+    Ok(promise.upcast())
+}
@@ -0,0 +1,57 @@
+//! Support for the `js_string!` macro.
+
+use std::mem::MaybeUninit;
+
+use crate::context::{Context, ContextLocal};
+use crate::handle::{Handle, Managed, Root};
+use crate::object::Object;
+use crate::types::{JsArray, JsString};
+
+/// A string literal that is created once for each instance of the module and
+/// kept alive until the instance is unloaded.
+pub struct InternedString {
+    value: &'static str,
+    // A `Root` can only reference objects, so the string is held by an array
+    local: ContextLocal<Root<JsArray>>,
+}
+
+impl InternedString {
+    pub const fn new(value: &'static str) -> Self {
+        Self {
+            value,
+            local: ContextLocal::new(),
+        }
+    }
+
+    pub fn get<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsString> {
+        if let Some(root) = self.local.get(cx) {
+            let array = root.to_inner(cx);
+            let env = cx.env();
+            let mut local = MaybeUninit::uninit();
+
+            // The array is never exposed to JavaScript, so the first element
+            // is always the string
+            unsafe {
+                if neon_runtime::object::get_index(
+                    &mut *local.as_mut_ptr(),
+                    env.to_raw(),
+                    array.to_raw(),
+                    0,
+                ) {
+                    return Handle::new_internal(JsString::from_raw(env, local.assume_init()));
+                }
+            }
+        }
+
+        let string = cx.string(self.value);
+        let array = JsArray::new(cx, 1);
+
+        if array.set(cx, 0, string).is_ok() {
+            let root = array.root(cx);
+
+            self.local.get_or_init(cx, move || root);
+        }
+
+        string
+    }
+}
@@ -8,6 +8,83 @@ pub use crate::object::class::internal::{
     AllocateCallback, ConstructCallback, ConstructorCallCallback, MethodCallback,
 };
 
+// Used by the export macro.
+#[cfg(feature = "napi-1")]
+pub use self::export::{
+    check_initialized, enter_export, export_function, export_lazy, export_name, export_unavailable,
+    export_value, extract_arc, extract_argument, extract_argument_or, extract_json,
+    extract_json_or, extract_rest, extract_this, into_js_json, into_js_value, register,
+    register_main, replace_lazy, set_export_camel_case, throw_unavailable, ExportFn, ExportWrapper,
+    InitRegistration, MainFn, MainRegistration, Registration,
+};
+
+#[cfg(all(feature = "napi-1", feature = "promise-api", feature = "task-api"))]
+pub use self::export::spawn_task;
+
+// Used by the export macro with `json`, so that the calling crate does not
+// need to depend on `serde_json`.
+#[cfg(feature = "serde")]
+pub use serde_json;
+
+#[cfg(feature = "napi-6")]
+pub use self::export::{begin_init, finish_init, register_init};
+
+// Used by the export macro with `init`, which cannot check the features of
+// `neon` itself. Expands to the export only with `napi-6`.
+#[doc(hidden)]
+#[cfg(feature = "napi-6")]
+#[macro_export]
+macro_rules! __neon_init_requires_napi_6 {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "napi-6"))]
+#[macro_export]
+macro_rules! __neon_init_requires_napi_6 {
+    ($($item:tt)*) => {
+        compile_error!("`init` requires napi-6");
+    };
+}
+
+#[cfg(feature = "napi-1")]
+pub use crate::__neon_init_requires_napi_6 as init_requires_napi_6;
+
+#[cfg(feature = "napi-1")]
+pub(crate) mod export;
+
+// Used by the test macro.
+#[cfg(feature = "napi-1")]
+pub use self::test::{export_test, TestFn, TestOutput};
+
+#[cfg(feature = "napi-1")]
+pub(crate) mod test;
+
+// Used by the class macro.
+#[cfg(feature = "napi-1")]
+pub use self::class::{
+    check_construct, class_state, clone_state, define_class, define_constant, lock, wrap,
+    wrap_pending, ClassMember, ClassState,
+};
+
+#[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api"))]
+pub use self::class::spawn_method;
+
+#[cfg(feature = "napi-6")]
+pub use self::class::ClassRef;
+
+#[cfg(feature = "napi-1")]
+mod class;
+
+// Used by the `js_string!` macro.
+#[cfg(feature = "napi-6")]
+pub use self::interned::InternedString;
+
+#[cfg(feature = "napi-6")]
+mod interned;
+
 // An alias for neon_runtime so macros can refer to it.
 pub mod runtime {
     pub use neon_runtime::*;
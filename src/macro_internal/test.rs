@@ -0,0 +1,126 @@
+//! Tests marked with `#[neon::test]`.
+//!
+//! Tests are registered like exports. When the module is loaded, the tests
+//! are collected on the `ModuleContext` and, if there are any, a single
+//! `__neon_run_tests` function that owns the list is exported. Tests are only
+//! compiled with `debug_assertions`, and neither is the runner.
+
+#[cfg(debug_assertions)]
+use std::panic::AssertUnwindSafe;
+
+use crate::context::{FunctionContext, ModuleContext};
+use crate::result::NeonResult;
+#[cfg(debug_assertions)]
+use crate::{
+    context::{internal::ContextInternal, Context},
+    object::Object,
+    result::JsResult,
+    types::{error::convert_panics, JsArray, JsFunction, JsString, JsValue},
+};
+
+/// The wrapper function generated for a test.
+pub type TestFn = fn(&mut FunctionContext) -> NeonResult<()>;
+
+/// A test added by a registration, waiting to be exported by
+/// [`export_runner`].
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+pub(crate) struct PendingTest {
+    name: &'static str,
+    run: TestFn,
+}
+
+/// The return types allowed for a test: `()` or `NeonResult<()>`.
+pub trait TestOutput {
+    fn into_result(self) -> NeonResult<()>;
+}
+
+impl TestOutput for () {
+    fn into_result(self) -> NeonResult<()> {
+        Ok(())
+    }
+}
+
+impl TestOutput for NeonResult<()> {
+    fn into_result(self) -> NeonResult<()> {
+        self
+    }
+}
+
+/// Adds the test `name` to the tests run by `__neon_run_tests`.
+pub fn export_test(cx: &mut ModuleContext, name: &'static str, run: TestFn) -> NeonResult<()> {
+    cx.pending_tests().push(PendingTest { name, run });
+
+    Ok(())
+}
+
+/// Exports `__neon_run_tests` if any tests were registered.
+#[cfg(debug_assertions)]
+pub(crate) fn export_runner(cx: &mut ModuleContext) -> NeonResult<()> {
+    let tests = std::mem::take(cx.pending_tests());
+
+    if tests.is_empty() {
+        return Ok(());
+    }
+
+    let runner = JsFunction::with_data(cx, tests, run_tests)?;
+
+    super::export_value(cx, "", "__neon_run_tests", runner)
+}
+
+/// Release builds have no runner. Tests are only registered by a crate built
+/// with `debug_assertions` that links a release build of Neon, and are dropped.
+#[cfg(not(debug_assertions))]
+pub(crate) fn export_runner(cx: &mut ModuleContext) -> NeonResult<()> {
+    cx.pending_tests().clear();
+
+    Ok(())
+}
+
+#[cfg(debug_assertions)]
+// Runs every test, or the tests whose name contains the optional string
+// argument, and returns an array of `{ name, passed, error }` objects.
+fn run_tests(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let filter = match cx.argument_opt(0) {
+        Some(filter) => match filter.downcast::<JsString, _>(&mut cx) {
+            Ok(filter) => Some(filter.value(&mut cx)),
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    let tests = match cx.data::<Vec<PendingTest>>() {
+        Some(tests) => tests,
+        None => return cx.throw_error("`__neon_run_tests` is missing its tests"),
+    };
+
+    let results = cx.empty_array();
+    let mut len = 0;
+
+    for test in tests {
+        if let Some(filter) = &filter {
+            if !test.name.contains(filter.as_str()) {
+                continue;
+            }
+        }
+
+        let env = cx.env();
+        let outcome =
+            cx.try_catch_internal(|cx| convert_panics(env, AssertUnwindSafe(|| (test.run)(cx))));
+
+        let result = cx.empty_object();
+        let name = cx.string(test.name);
+        let passed = cx.boolean(outcome.is_ok());
+        let error = match outcome {
+            Ok(()) => cx.undefined().upcast::<JsValue>(),
+            Err(error) => error,
+        };
+
+        result.set(&mut cx, "name", name)?;
+        result.set(&mut cx, "passed", passed)?;
+        result.set(&mut cx, "error", error)?;
+        results.set(&mut cx, len, result)?;
+        len += 1;
+    }
+
+    Ok(results)
+}
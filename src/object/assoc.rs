@@ -0,0 +1,140 @@
+use std::marker::PhantomData;
+
+use crate::context::{Context, ContextLocal};
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::{Finalize, JsBox, JsWeakMap};
+
+/// Associates Rust values with JavaScript objects, without keeping the
+/// objects alive.
+///
+/// Each value is held in a [`JsBox`] in a `WeakMap` keyed by its object, so
+/// it is dropped, and [finalized](Finalize), after the object is garbage
+/// collected. This attaches native state to objects that the module did not
+/// create, e.g., to memoize work on objects passed in from JavaScript.
+///
+/// Like a [`ContextLocal`], an `ObjectAssoc` is created in a `static` and
+/// holds a separate map for each instance of the module.
+///
+/// ```
+/// # #[cfg(feature = "napi-6")] {
+/// # use neon::prelude::*;
+/// use neon::object::ObjectAssoc;
+///
+/// struct Stats {
+///     keys: usize,
+/// }
+///
+/// impl Finalize for Stats {}
+///
+/// static STATS: ObjectAssoc<Stats> = ObjectAssoc::new();
+///
+/// fn count_keys(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let obj = cx.argument::<JsObject>(0)?;
+///     let stats = STATS.get_or_try_insert_with(&mut cx, obj, |cx| {
+///         let keys = obj.get_own_property_names(cx)?.len(cx);
+///
+///         Ok(Stats { keys: keys as usize })
+///     })?;
+///
+///     Ok(cx.number(stats.keys as f64))
+/// }
+/// # }
+/// ```
+pub struct ObjectAssoc<T> {
+    maps: ContextLocal<Root<JsWeakMap>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Finalize + Send + 'static> ObjectAssoc<T> {
+    /// Creates an association with no values.
+    pub const fn new() -> Self {
+        Self {
+            maps: ContextLocal::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the value associated with `object`, if there is one.
+    pub fn get<'a, C: Context<'a>, O: Object>(
+        &self,
+        cx: &mut C,
+        object: Handle<'a, O>,
+    ) -> NeonResult<Option<Handle<'a, JsBox<T>>>> {
+        let map = self.map(cx)?;
+        let value = map.get(cx, object)?;
+
+        Ok(value.downcast::<JsBox<T>, _>(cx).ok())
+    }
+
+    /// Associates `value` with `object`, replacing any previous value.
+    pub fn insert<'a, C: Context<'a>, O: Object>(
+        &self,
+        cx: &mut C,
+        object: Handle<'a, O>,
+        value: T,
+    ) -> JsResult<'a, JsBox<T>> {
+        let map = self.map(cx)?;
+        let value = cx.boxed(value);
+
+        map.set(cx, object, value)?;
+
+        Ok(value)
+    }
+
+    /// Returns the value associated with `object`, calling `f` to associate
+    /// one if there is none. Errors from `f` are returned without associating
+    /// a value.
+    pub fn get_or_try_insert_with<'a, C, O, F>(
+        &self,
+        cx: &mut C,
+        object: Handle<'a, O>,
+        f: F,
+    ) -> JsResult<'a, JsBox<T>>
+    where
+        C: Context<'a>,
+        O: Object,
+        F: FnOnce(&mut C) -> NeonResult<T>,
+    {
+        if let Some(value) = self.get(cx, object)? {
+            return Ok(value);
+        }
+
+        let value = f(cx)?;
+
+        self.insert(cx, object, value)
+    }
+
+    /// Removes the value associated with `object`, returning `true` if there
+    /// was one. The value is dropped once the `JsBox` holding it is garbage
+    /// collected.
+    pub fn remove<'a, C: Context<'a>, O: Object>(
+        &self,
+        cx: &mut C,
+        object: Handle<'a, O>,
+    ) -> NeonResult<bool> {
+        let map = self.map(cx)?;
+
+        map.delete(cx, object)
+    }
+
+    fn map<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsWeakMap> {
+        if let Some(map) = self.maps.get(cx) {
+            return Ok(map.to_inner(cx));
+        }
+
+        let map = JsWeakMap::new(cx)?;
+        let root = map.root(cx);
+
+        self.maps.get_or_init(cx, move || root);
+
+        Ok(map)
+    }
+}
+
+impl<T: Finalize + Send + 'static> Default for ObjectAssoc<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::context::{Context, ContextLocal};
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::NeonResult;
+use crate::types::{JsNumber, JsWeakMap};
+
+// Ids are shared by every instance of the module, so keys taken in different
+// instances never compare equal
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// A `WeakMap` from each object that has a key to its id
+static IDS: ContextLocal<Root<JsWeakMap>> = ContextLocal::new();
+
+/// A key for the identity of a JavaScript object, for use in Rust
+/// collections such as a `HashMap`.
+///
+/// Taking the key of the same object always returns an equal key, and the
+/// keys of different objects are never equal, like comparing the objects with
+/// `===`. The id of each object is held in a `WeakMap`, so a key does not keep
+/// its object alive and cannot be turned back into the object.
+///
+/// ```
+/// # #[cfg(feature = "napi-6")] {
+/// # use neon::prelude::*;
+/// use std::cell::RefCell;
+/// use std::collections::HashMap;
+///
+/// use neon::context::ContextLocal;
+/// use neon::object::ObjectKey;
+///
+/// static VISITS: ContextLocal<RefCell<HashMap<ObjectKey, u32>>> = ContextLocal::new();
+///
+/// fn visit(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let obj = cx.argument::<JsObject>(0)?;
+///     let key = ObjectKey::new(&mut cx, obj)?;
+///     let mut visits = VISITS.get_or_init(&mut cx, Default::default).borrow_mut();
+///     let count = visits.entry(key).or_insert(0);
+///
+///     *count += 1;
+///
+///     Ok(cx.number(*count))
+/// }
+/// # }
+/// ```
+///
+/// Entries of a collection are not removed when their object is garbage
+/// collected. Use an [`ObjectAssoc`](crate::object::ObjectAssoc) to hold values
+/// only as long as their object is alive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ObjectKey(u64);
+
+impl ObjectKey {
+    /// Returns the key of `object`, assigning one if it does not have one.
+    pub fn new<'a, C: Context<'a>, O: Object>(
+        cx: &mut C,
+        object: Handle<'a, O>,
+    ) -> NeonResult<Self> {
+        let ids = match IDS.get(cx) {
+            Some(ids) => ids.to_inner(cx),
+            None => {
+                let ids = JsWeakMap::new(cx)?;
+                let root = ids.root(cx);
+
+                IDS.get_or_init(cx, move || root);
+                ids
+            }
+        };
+
+        let id = ids.get(cx, object)?;
+
+        if let Ok(id) = id.downcast::<JsNumber, _>(cx) {
+            return Ok(Self(id.value(cx) as u64));
+        }
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let value = cx.number(id as f64);
+
+        ids.set(cx, object, value)?;
+
+        Ok(Self(id))
+    }
+
+    /// Returns the key as a number, e.g., to hash it with a different hasher.
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
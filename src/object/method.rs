@@ -0,0 +1,64 @@
+use smallvec::SmallVec;
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::result::JsResult;
+use crate::types::{JsFunction, JsObject, JsValue, Value};
+
+/// A call to a method of an object, created by [`Object::method`](super::Object::method).
+///
+/// Arguments are added with [`arg`](MethodCall::arg) and the method is called
+/// with the object as `this` by [`call`](MethodCall::call):
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// fn push_twice(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let array = cx.argument::<JsArray>(0)?;
+///     let value = cx.argument::<JsValue>(1)?;
+///
+///     array.method(&mut cx, "push")?.arg(value).arg(value).call(&mut cx)
+/// }
+/// # }
+/// ```
+pub struct MethodCall<'a> {
+    this: Handle<'a, JsObject>,
+    callee: Handle<'a, JsFunction>,
+    args: SmallVec<[Handle<'a, JsValue>; 8]>,
+}
+
+impl<'a> MethodCall<'a> {
+    pub(crate) fn new(this: Handle<'a, JsObject>, callee: Handle<'a, JsFunction>) -> Self {
+        Self {
+            this,
+            callee,
+            args: SmallVec::new(),
+        }
+    }
+
+    /// Adds an argument to the call.
+    pub fn arg<V: Value>(mut self, arg: Handle<'a, V>) -> Self {
+        self.args.push(arg.upcast());
+        self
+    }
+
+    /// Adds each of `args` to the call.
+    pub fn args<V, AS>(mut self, args: AS) -> Self
+    where
+        V: Value,
+        AS: IntoIterator<Item = Handle<'a, V>>,
+    {
+        self.args.extend(args.into_iter().map(|arg| arg.upcast()));
+        self
+    }
+
+    /// Returns the method that will be called.
+    pub fn callee(&self) -> Handle<'a, JsFunction> {
+        self.callee
+    }
+
+    /// Calls the method with the object as `this`.
+    pub fn call<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        self.callee.call(cx, self.this, self.args)
+    }
+}
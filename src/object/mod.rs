@@ -37,8 +37,23 @@
 #[cfg(feature = "legacy-runtime")]
 pub(crate) mod class;
 
+#[cfg(feature = "napi-6")]
+mod assoc;
+#[cfg(feature = "napi-6")]
+mod key;
+#[cfg(feature = "napi-1")]
+mod method;
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::assoc::ObjectAssoc;
 #[cfg(feature = "legacy-runtime")]
 pub use self::class::{Class, ClassDescriptor};
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+pub use self::key::ObjectKey;
+#[cfg(feature = "napi-1")]
+pub use self::method::MethodCall;
 pub use self::traits::*;
 
 #[cfg(feature = "legacy-runtime")]
@@ -132,13 +147,15 @@ mod traits {
     use crate::context::internal::Env;
     use crate::context::Context;
     use crate::handle::{Handle, Managed, Root};
-    use crate::result::{NeonResult, Throw};
+    use crate::object::MethodCall;
+    use crate::result::{JsResult, NeonResult, Throw};
     use crate::types::utf8::Utf8;
-    use crate::types::{build, JsValue, Value};
+    use crate::types::{
+        build, JsBoolean, JsFunction, JsNull, JsNumber, JsObject, JsString, JsUndefined, JsValue,
+        Value,
+    };
     use neon_runtime::raw;
 
-    #[cfg(feature = "napi-6")]
-    use crate::result::JsResult;
     #[cfg(feature = "napi-6")]
     use crate::types::JsArray;
 
@@ -158,6 +175,21 @@ mod traits {
             obj: raw::Local,
             val: raw::Local,
         ) -> bool;
+
+        /// Converts the key to a JavaScript value that names the property, for
+        /// `has`, `has_own` and `delete`.
+        ///
+        /// The default implementation throws a `TypeError`, so that keys
+        /// implemented before this method existed keep working with `get`
+        /// and `set`.
+        unsafe fn to_key<'c, C: Context<'c>>(self, cx: &mut C, _out: &mut raw::Local) -> bool
+        where
+            Self: Sized,
+        {
+            let _ = cx.throw_type_error::<_, ()>("property key cannot be converted to a value");
+
+            false
+        }
     }
 
     impl PropertyKey for u32 {
@@ -179,6 +211,11 @@ mod traits {
         ) -> bool {
             neon_runtime::object::set_index(out, cx.env().to_raw(), obj, self, val)
         }
+
+        unsafe fn to_key<'c, C: Context<'c>>(self, cx: &mut C, out: &mut raw::Local) -> bool {
+            // Index keys are converted to strings so that they also name own properties
+            self.to_string().as_str().to_key(cx, out)
+        }
     }
 
     impl<'a, K: Value> PropertyKey for Handle<'a, K> {
@@ -204,6 +241,11 @@ mod traits {
 
             neon_runtime::object::set(out, env, obj, self.to_raw(), val)
         }
+
+        unsafe fn to_key<'c, C: Context<'c>>(self, _: &mut C, out: &mut raw::Local) -> bool {
+            *out = self.to_raw();
+            true
+        }
     }
 
     impl<'a> PropertyKey for &'a str {
@@ -231,6 +273,12 @@ mod traits {
 
             neon_runtime::object::set_string(env, out, obj, ptr, len, val)
         }
+
+        unsafe fn to_key<'c, C: Context<'c>>(self, cx: &mut C, out: &mut raw::Local) -> bool {
+            let (ptr, len) = Utf8::from(self).into_small_unwrap().lower();
+
+            neon_runtime::string::new(out, cx.env().to_raw(), ptr, len)
+        }
     }
 
     /// The trait of all object types.
@@ -245,6 +293,195 @@ mod traits {
             })
         }
 
+        /// Returns `true` if the object or its prototype chain has the property,
+        /// as with the `in` operator.
+        fn has<'a, C: Context<'a>, K: PropertyKey>(self, cx: &mut C, key: K) -> NeonResult<bool> {
+            let mut result = false;
+            let mut key_val = unsafe { std::mem::zeroed() };
+
+            unsafe {
+                if key.to_key(cx, &mut key_val)
+                    && neon_runtime::object::has(
+                        &mut result,
+                        cx.env().to_raw(),
+                        self.to_raw(),
+                        key_val,
+                    )
+                {
+                    Ok(result)
+                } else {
+                    Err(Throw)
+                }
+            }
+        }
+
+        /// Returns `true` if the object itself has the property, ignoring its
+        /// prototype chain, as with `Object.hasOwn`.
+        fn has_own<'a, C: Context<'a>, K: PropertyKey>(
+            self,
+            cx: &mut C,
+            key: K,
+        ) -> NeonResult<bool> {
+            let mut result = false;
+            let mut key_val = unsafe { std::mem::zeroed() };
+
+            unsafe {
+                if key.to_key(cx, &mut key_val)
+                    && neon_runtime::object::has_own(
+                        &mut result,
+                        cx.env().to_raw(),
+                        self.to_raw(),
+                        key_val,
+                    )
+                {
+                    Ok(result)
+                } else {
+                    Err(Throw)
+                }
+            }
+        }
+
+        /// Deletes a property, as with the `delete` operator. Returns `false`
+        /// if the property exists but cannot be deleted.
+        fn delete<'a, C: Context<'a>, K: PropertyKey>(
+            self,
+            cx: &mut C,
+            key: K,
+        ) -> NeonResult<bool> {
+            let mut result = false;
+            let mut key_val = unsafe { std::mem::zeroed() };
+
+            unsafe {
+                if key.to_key(cx, &mut key_val)
+                    && neon_runtime::object::delete(
+                        &mut result,
+                        cx.env().to_raw(),
+                        self.to_raw(),
+                        key_val,
+                    )
+                {
+                    Ok(result)
+                } else {
+                    Err(Throw)
+                }
+            }
+        }
+
+        /// Gets the value of a property, returning `None` if the property is
+        /// missing or `undefined`. Throws if the value is not a `V`.
+        ///
+        /// ```
+        /// # #[cfg(feature = "napi-1")] {
+        /// # use neon::prelude::*;
+        /// fn greet(mut cx: FunctionContext) -> JsResult<JsString> {
+        ///     let options = cx.argument::<JsObject>(0)?;
+        ///     let name = match options.get_opt::<JsString>(&mut cx, "name")? {
+        ///         Some(name) => name.value(&mut cx),
+        ///         None => "stranger".to_string(),
+        ///     };
+        ///
+        ///     Ok(cx.string(format!("hello, {}", name)))
+        /// }
+        /// # }
+        /// ```
+        fn get_opt<'a, V: Value>(
+            self,
+            cx: &mut impl Context<'a>,
+            key: impl PropertyKey,
+        ) -> NeonResult<Option<Handle<'a, V>>> {
+            let value = self.get(cx, key)?;
+
+            if value.is_a::<JsUndefined, _>(cx) {
+                return Ok(None);
+            }
+
+            value.downcast_or_throw(cx).map(Some)
+        }
+
+        /// Gets the value of a property, calling `default` if the property is
+        /// missing or `undefined`. Throws if the value is not a `V`.
+        ///
+        /// ```
+        /// # #[cfg(feature = "napi-1")] {
+        /// # use neon::prelude::*;
+        /// fn port(mut cx: FunctionContext) -> JsResult<JsNumber> {
+        ///     let options = cx.argument::<JsObject>(0)?;
+        ///
+        ///     options.get_or(&mut cx, "port", |cx| cx.number(8080))
+        /// }
+        /// # }
+        /// ```
+        fn get_or<'a, C, K, V, F>(self, cx: &mut C, key: K, default: F) -> JsResult<'a, V>
+        where
+            C: Context<'a>,
+            K: PropertyKey,
+            V: Value,
+            F: FnOnce(&mut C) -> Handle<'a, V>,
+        {
+            match self.get_opt(cx, key)? {
+                Some(value) => Ok(value),
+                None => Ok(default(cx)),
+            }
+        }
+
+        /// Gets the value at a path of property names separated by `.`, e.g.,
+        /// `"a.b.c"` for `obj.a.b.c`, and downcasts it to `V`.
+        ///
+        /// Throws a `TypeError` naming the segment that could not be read if
+        /// an intermediate value is not an object, or if the value at the end
+        /// of the path is not a `V`.
+        ///
+        /// ```
+        /// # #[cfg(feature = "napi-1")] {
+        /// # use neon::prelude::*;
+        /// fn port(mut cx: FunctionContext) -> JsResult<JsNumber> {
+        ///     let config = cx.argument::<JsObject>(0)?;
+        ///
+        ///     // Throws "expected `server.listen` to be an object, found undefined"
+        ///     // if `config.server.listen` is missing
+        ///     config.prop_path(&mut cx, "server.listen.port")
+        /// }
+        /// # }
+        /// ```
+        fn prop_path<'a, C: Context<'a>, V: Value>(
+            self,
+            cx: &mut C,
+            path: &str,
+        ) -> JsResult<'a, V> {
+            let (parent, key) = walk_path(cx, self, path)?;
+            let value = parent.get(cx, key)?;
+
+            match value.downcast::<V, _>(cx) {
+                Ok(value) => Ok(value),
+                Err(_) => {
+                    let found = type_name(cx, value);
+
+                    cx.throw_type_error(format!(
+                        "expected `{}` to be {}, found {}",
+                        path,
+                        article(&V::name()),
+                        found,
+                    ))
+                }
+            }
+        }
+
+        /// Sets the value at a path of property names separated by `.`. Each
+        /// object before the last segment must already exist.
+        ///
+        /// Throws a `TypeError` naming the segment that could not be read if
+        /// an intermediate value is not an object.
+        fn set_prop_path<'a, C: Context<'a>, W: Value>(
+            self,
+            cx: &mut C,
+            path: &str,
+            val: Handle<W>,
+        ) -> NeonResult<bool> {
+            let (parent, key) = walk_path(cx, self, path)?;
+
+            parent.set(cx, key, val)
+        }
+
         #[cfg(feature = "napi-6")]
         #[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
         fn get_own_property_names<'a, C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsArray> {
@@ -261,6 +498,8 @@ mod traits {
             key: K,
             val: Handle<W>,
         ) -> NeonResult<bool> {
+            val.check_env(cx);
+
             let mut result = false;
             if unsafe { key.set_from(cx, &mut result, self.to_raw(), val.to_raw()) } {
                 Ok(result)
@@ -269,6 +508,35 @@ mod traits {
             }
         }
 
+        /// Looks up the method `name` and returns a [`MethodCall`] that calls
+        /// it with the object as `this`. Throws a `TypeError` if the property
+        /// is not a function.
+        fn method<'a, C: Context<'a>>(self, cx: &mut C, name: &str) -> NeonResult<MethodCall<'a>> {
+            let this = Handle::new_internal(self).upcast::<JsValue>();
+            let callee = self.get(cx, name)?;
+
+            match callee.downcast::<JsFunction, _>(cx) {
+                Ok(callee) => {
+                    let this = this.downcast::<JsObject, _>(cx).unwrap_or_else(|_| {
+                        // Functions and other object types are not `JsObject`s
+                        // to `downcast`, but are objects to JavaScript
+                        Handle::new_internal(JsObject::from_raw(cx.env(), this.to_raw()))
+                    });
+
+                    Ok(MethodCall::new(this, callee))
+                }
+                Err(_) => {
+                    let found = type_name(cx, callee);
+
+                    cx.throw_type_error(format!(
+                        "expected `{}` to be a function, found {}",
+                        name, found
+                    ))
+                }
+            }
+        }
+
+        #[cfg_attr(feature = "diagnostics", track_caller)]
         fn root<'a, C: Context<'a>>(&self, cx: &mut C) -> Root<Self> {
             Root::new(cx, self)
         }
@@ -279,4 +547,72 @@ mod traits {
         #[allow(clippy::wrong_self_convention)]
         fn as_this(env: Env, h: raw::Local) -> Self;
     }
+
+    // Follows every segment of `path` but the last, returning the object that
+    // holds the final property and the name of that property
+    fn walk_path<'a, 'p, C: Context<'a>, O: Object>(
+        cx: &mut C,
+        obj: O,
+        path: &'p str,
+    ) -> NeonResult<(Handle<'a, JsObject>, &'p str)> {
+        let (parents, key) = match path.rfind('.') {
+            Some(i) => (Some(&path[..i]), &path[i + 1..]),
+            None => (None, path),
+        };
+
+        let mut current = Handle::new_internal(obj).upcast::<JsValue>();
+
+        if let Some(parents) = parents {
+            let mut end = 0;
+
+            for segment in parents.split('.') {
+                end += segment.len();
+
+                let parent = current.downcast::<JsObject, _>(cx).unwrap();
+
+                current = parent.get(cx, segment)?;
+
+                if current.downcast::<JsObject, _>(cx).is_err() {
+                    let found = type_name(cx, current);
+
+                    return cx.throw_type_error(format!(
+                        "expected `{}` to be an object, found {}",
+                        &path[..end],
+                        found,
+                    ));
+                }
+
+                // Skip the separator
+                end += 1;
+            }
+        }
+
+        Ok((current.downcast::<JsObject, _>(cx).unwrap(), key))
+    }
+
+    // Describes a value for an error message, e.g., "undefined" or "a string"
+    fn type_name<'a, C: Context<'a>>(cx: &mut C, value: Handle<JsValue>) -> String {
+        if value.is_a::<JsUndefined, _>(cx) {
+            "undefined".to_string()
+        } else if value.is_a::<JsNull, _>(cx) {
+            "null".to_string()
+        } else if value.is_a::<JsString, _>(cx) {
+            "a string".to_string()
+        } else if value.is_a::<JsNumber, _>(cx) {
+            "a number".to_string()
+        } else if value.is_a::<JsBoolean, _>(cx) {
+            "a boolean".to_string()
+        } else if value.is_a::<JsFunction, _>(cx) {
+            "a function".to_string()
+        } else {
+            "an object".to_string()
+        }
+    }
+
+    fn article(name: &str) -> String {
+        match name.chars().next().map(|c| c.to_ascii_lowercase()) {
+            Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => format!("an {}", name),
+            _ => format!("a {}", name),
+        }
+    }
 }
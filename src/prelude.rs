@@ -2,6 +2,9 @@
 
 #[doc(no_inline)]
 pub use crate::borrow::{Borrow, BorrowMut};
+#[cfg(feature = "napi-1")]
+#[doc(no_inline)]
+pub use crate::context::Cx;
 #[doc(no_inline)]
 pub use crate::context::{
     CallContext, CallKind, ComputeContext, Context, ExecuteContext, FunctionContext, MethodContext,
@@ -38,6 +41,9 @@ pub use crate::types::{
     BinaryData, JsArray, JsArrayBuffer, JsBoolean, JsBuffer, JsError, JsFunction, JsNull, JsNumber,
     JsObject, JsString, JsUndefined, JsValue, Value,
 };
+#[cfg(all(feature = "napi-1", feature = "promise-api"))]
+#[doc(no_inline)]
+pub use crate::types::{Deferred, JsPromise};
 #[cfg(feature = "napi-1")]
 #[doc(no_inline)]
 pub use crate::{
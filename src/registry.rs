@@ -0,0 +1,280 @@
+//! Introspection of the items exported with `#[neon::export]` and
+//! `#[neon::class]`.
+//!
+//! Each exported item records a description of itself when it is compiled:
+//! where it is exported, what kind of item it is, and the Rust and TypeScript
+//! types of its parameters and return value. [`registered`] lists these
+//! descriptions, so that code layered over a module, such as routing or
+//! argument validation, can work with every export generically:
+//!
+//! ```
+//! # #[cfg(feature = "napi-1")] {
+//! # use neon::prelude::*;
+//! use neon::registry::ExportKind;
+//!
+//! fn function_names(mut cx: FunctionContext) -> JsResult<JsArray> {
+//!     let names = neon::registered()
+//!         .iter()
+//!         .filter(|export| export.kind() == ExportKind::Function)
+//!         .map(|export| export.path())
+//!         .collect::<Vec<_>>();
+//!
+//!     JsArray::from_iter(&mut cx, names)
+//! }
+//! # }
+//! ```
+//!
+//! Items exported by hand, e.g., with
+//! [`ModuleContext::export_function`](crate::context::ModuleContext::export_function),
+//! are not listed.
+
+use std::borrow::Cow;
+use std::slice;
+
+use crate::macro_internal::export;
+
+/// Returns a description of every item exported with `#[neon::export]` or
+/// `#[neon::class]`, in the order they are exported.
+pub fn registered() -> Registered {
+    Registered {
+        exports: export::registered(),
+    }
+}
+
+/// The exports listed by [`registered`].
+#[derive(Clone, Debug)]
+pub struct Registered {
+    exports: Vec<&'static Export>,
+}
+
+impl Registered {
+    /// Iterates over the exports.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self.exports.iter(),
+        }
+    }
+
+    /// Returns the export at `path`, the names of its namespace and itself
+    /// separated by `.`, e.g., `"fs.sync.readFile"`.
+    pub fn get(&self, path: &str) -> Option<&'static Export> {
+        self.iter().find(|export| export.path() == path)
+    }
+
+    /// Returns the number of exports.
+    pub fn len(&self) -> usize {
+        self.exports.len()
+    }
+
+    /// Returns `true` if nothing was exported with the macros.
+    pub fn is_empty(&self) -> bool {
+        self.exports.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a Registered {
+    type Item = &'static Export;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Iterator over the exports of a [`Registered`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, &'static Export>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'static Export;
+
+    fn next(&mut self) -> Option<&'static Export> {
+        self.inner.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+/// What an export is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExportKind {
+    /// A function, exported with `#[neon::export]`
+    Function,
+    /// A function that returns a promise and runs on the worker pool,
+    /// exported with `#[neon::export(task)]`
+    Task,
+    /// A value computed the first time it is accessed, exported with
+    /// `#[neon::export(lazy)]`
+    Lazy,
+    /// A class, exported with `#[neon::class]`
+    Class,
+}
+
+/// A description of an exported item.
+#[derive(Debug)]
+pub struct Export {
+    rust_name: &'static str,
+    name: Option<&'static str>,
+    namespace: &'static str,
+    kind: ExportKind,
+    params: &'static [Param],
+    output: Type,
+}
+
+impl Export {
+    #[doc(hidden)]
+    pub const fn new(
+        rust_name: &'static str,
+        name: Option<&'static str>,
+        namespace: &'static str,
+        kind: ExportKind,
+        params: &'static [Param],
+        output: Type,
+    ) -> Self {
+        Self {
+            rust_name,
+            name,
+            namespace,
+            kind,
+            params,
+            output,
+        }
+    }
+
+    /// The name the item is exported under, which is converted to
+    /// `camelCase` if the module uses `#[neon::main(camel_case)]` and the
+    /// export was not given a `name`.
+    pub fn name(&self) -> Cow<'static, str> {
+        match self.name {
+            Some(name) => Cow::Borrowed(name),
+            None => export::export_name(self.rust_name),
+        }
+    }
+
+    /// The name of the Rust function or type.
+    pub fn rust_name(&self) -> &'static str {
+        self.rust_name
+    }
+
+    /// The dot-separated path of the object the item is exported on, or an
+    /// empty string for `module.exports` itself.
+    pub fn namespace(&self) -> &'static str {
+        self.namespace
+    }
+
+    /// The namespace and name of the export separated by `.`, e.g.,
+    /// `"fs.sync.readFile"`, or only the name if it has no namespace.
+    pub fn path(&self) -> String {
+        if self.namespace.is_empty() {
+            self.name().into_owned()
+        } else {
+            format!("{}.{}", self.namespace, self.name())
+        }
+    }
+
+    /// The path of the item without `camelCase` conversion, which
+    /// `neon::standalone` calls it by.
+    #[cfg(feature = "standalone")]
+    pub(crate) fn unconverted_path(&self) -> String {
+        let name = self.name.unwrap_or(self.rust_name);
+
+        if self.namespace.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.namespace, name)
+        }
+    }
+
+    /// What the item is.
+    pub fn kind(&self) -> ExportKind {
+        self.kind
+    }
+
+    /// The parameters taken from the arguments of a call, excluding the
+    /// context and `this`. Empty for a class.
+    pub fn params(&self) -> &'static [Param] {
+        self.params
+    }
+
+    /// The type returned by the function, or the class itself.
+    pub fn output(&self) -> &Type {
+        &self.output
+    }
+}
+
+/// A parameter of an exported function.
+#[derive(Debug)]
+pub struct Param {
+    name: &'static str,
+    ty: Type,
+    optional: bool,
+    rest: bool,
+}
+
+impl Param {
+    #[doc(hidden)]
+    pub const fn new(name: &'static str, ty: Type, optional: bool, rest: bool) -> Self {
+        Self {
+            name,
+            ty,
+            optional,
+            rest,
+        }
+    }
+
+    /// The name of the parameter, or an empty string if it is a pattern.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The type of the parameter. For a rest parameter, this is the type of
+    /// the `Vec` that collects the arguments.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Returns `true` if the argument may be omitted, because the parameter
+    /// is an `Option` or has a default.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns `true` if the parameter collects every remaining argument,
+    /// with `#[neon(rest)]`.
+    pub fn is_rest(&self) -> bool {
+        self.rest
+    }
+}
+
+/// A type in the signature of an export.
+#[derive(Debug)]
+pub struct Type {
+    rust: &'static str,
+    typescript: &'static str,
+}
+
+impl Type {
+    #[doc(hidden)]
+    pub const fn new(rust: &'static str, typescript: &'static str) -> Self {
+        Self { rust, typescript }
+    }
+
+    /// The Rust type as written, e.g., `Option<Vec<f64>>`.
+    pub fn rust(&self) -> &'static str {
+        self.rust
+    }
+
+    /// The TypeScript type of the JavaScript value, e.g., `number[] |
+    /// undefined`, or `unknown` if it could not be determined from the name
+    /// of the Rust type.
+    pub fn typescript(&self) -> &'static str {
+        self.typescript
+    }
+}
@@ -0,0 +1,164 @@
+//! Detection of the JavaScript runtime hosting the module.
+//!
+//! Runtimes other than Node.js implement Node-API with some differences.
+//! Neon adapts to the ones it knows about, so the same binary loads in each
+//! of them:
+//!
+//! * Hosts that do not allow external buffers, such as Electron with the V8
+//!   memory cage, get a copy of the bytes from [`JsBuffer::external`] and
+//!   [`JsArrayBuffer::external`]. See
+//!   [`Context::supports_external_buffers`](crate::context::Context::supports_external_buffers).
+//! * Hosts without `async_hooks` may not create async contexts or callback
+//!   scopes. Events on a [`Channel`](crate::event::Channel) run without them.
+//! * Hosts that do not implement referencing thread-safe functions keep the
+//!   event loop running while a `Channel` is unreferenced, instead of
+//!   panicking.
+//!
+//! Other differences are left to the module, which can check [`host`]:
+//!
+//! ```
+//! # #[cfg(feature = "napi-1")] {
+//! # use neon::prelude::*;
+//! use neon::runtime::{self, Host};
+//!
+//! fn runtime_name(mut cx: FunctionContext) -> JsResult<JsString> {
+//!     let name = match runtime::host() {
+//!         Host::Bun | Host::Deno => "not node",
+//!         _ => "node",
+//!     };
+//!
+//!     Ok(cx.string(name))
+//! }
+//! # }
+//! ```
+//!
+//! [`JsBuffer::external`]: crate::types::JsBuffer::external
+//! [`JsArrayBuffer::external`]: crate::types::JsArrayBuffer::external
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::NeonResult;
+use crate::types::{JsObject, JsUndefined};
+
+// The host detected when the first module was initialized, which is the same
+// for every instance in the process
+static HOST: AtomicU8 = AtomicU8::new(Host::Other as u8);
+static DETECTED: AtomicU8 = AtomicU8::new(0);
+
+/// A JavaScript runtime that can load Node-API modules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Host {
+    /// Node.js
+    Node = 0,
+    /// Electron, in the main process or a renderer with Node.js integration
+    Electron = 1,
+    /// Bun
+    Bun = 2,
+    /// Deno, with its Node.js compatibility layer
+    Deno = 3,
+    /// A host that was not recognized, e.g., a browser using emnapi
+    Other = 4,
+}
+
+impl Host {
+    /// The name of the host, e.g., `"node"` or `"bun"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Host::Node => "node",
+            Host::Electron => "electron",
+            Host::Bun => "bun",
+            Host::Deno => "deno",
+            Host::Other => "other",
+        }
+    }
+
+    /// Returns `true` if the host is known to implement `async_hooks`, so
+    /// that async context is propagated to callbacks from Rust.
+    pub fn has_async_hooks(self) -> bool {
+        matches!(self, Host::Node | Host::Electron)
+    }
+
+    fn from_u8(host: u8) -> Self {
+        match host {
+            0 => Host::Node,
+            1 => Host::Electron,
+            2 => Host::Bun,
+            3 => Host::Deno,
+            _ => Host::Other,
+        }
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the runtime hosting the module.
+///
+/// The host is detected when the module is initialized, so this returns
+/// [`Host::Other`] if it is called before then, e.g., from a test that does
+/// not load the module.
+pub fn host() -> Host {
+    Host::from_u8(HOST.load(Ordering::Relaxed))
+}
+
+// Detects the host from the globals it defines. Called when each module is
+// initialized, but only the first detection reads any properties.
+pub(crate) fn detect<'a, C: Context<'a>>(cx: &mut C) {
+    if DETECTED.swap(1, Ordering::Relaxed) != 0 {
+        return;
+    }
+
+    // Detection is best effort; a host that throws from a getter is `Other`
+    let host = cx.try_catch_internal(detect_host).unwrap_or(Host::Other);
+
+    HOST.store(host as u8, Ordering::Relaxed);
+}
+
+fn detect_host<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<Host> {
+    let global = cx.global();
+
+    // Bun and Deno also define `process.versions.node` for compatibility
+    if is_defined(cx, global, "Bun")? {
+        return Ok(Host::Bun);
+    }
+
+    if is_defined(cx, global, "Deno")? {
+        return Ok(Host::Deno);
+    }
+
+    let versions = match global.get_opt::<JsObject>(cx, "process")? {
+        Some(process) => process.get_opt::<JsObject>(cx, "versions")?,
+        None => None,
+    };
+
+    let versions = match versions {
+        Some(versions) => versions,
+        None => return Ok(Host::Other),
+    };
+
+    if is_defined(cx, versions, "electron")? {
+        Ok(Host::Electron)
+    } else if is_defined(cx, versions, "node")? {
+        Ok(Host::Node)
+    } else {
+        Ok(Host::Other)
+    }
+}
+
+fn is_defined<'a, C: Context<'a>>(
+    cx: &mut C,
+    object: Handle<'a, JsObject>,
+    key: &str,
+) -> NeonResult<bool> {
+    let value = object.get(cx, key)?;
+
+    Ok(!value.is_a::<JsUndefined, _>(cx))
+}
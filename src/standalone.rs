@@ -0,0 +1,118 @@
+//! Calling exported functions without Node.js.
+//!
+//! With the `standalone` feature enabled, every function marked with
+//! [`#[neon::export]`](macro@crate::export) can also be called directly
+//! from Rust, with arguments given as [`MockValue`]s or as a JSON array.
+//! The function is called through the same wrapper as from JavaScript, in a
+//! [`MockContext`], so it extracts its arguments and converts its result
+//! exactly as it would under Node.js. Exported functions can be fuzzed and
+//! benchmarked from `cargo test`, `cargo bench` or `cargo fuzz`.
+//!
+//! ```
+//! # #[cfg(all(feature = "napi-1", feature = "standalone"))] {
+//! #[neon::export]
+//! fn add(a: f64, b: f64) -> f64 {
+//!     a + b
+//! }
+//!
+//! assert_eq!(neon::standalone::call_json("add", "[1, 2]").unwrap(), "3");
+//! # }
+//! ```
+//!
+//! Functions are called by the name they are exported under, prefixed with
+//! their namespace, e.g., `"fs.read"`. `camelCase` conversion by
+//! `#[neon::main(camel_case)]` is not applied, since no module is loaded.
+//!
+//! Each thread calls the functions in its own instance of the module, which
+//! keeps its state across calls, e.g., after calling an
+//! [`init`](macro@crate::export) export. Only code that the mock supports
+//! can run: a function exported with `task` fails with
+//! [`StandaloneError::Unsupported`], and a function that uses, e.g.,
+//! buffers or promises throws.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use crate::macro_internal::export;
+use crate::registry::ExportKind;
+use crate::test::{MockContext, MockValue};
+
+thread_local! {
+    // The instance of the module that exports are called in
+    static INSTANCE: RefCell<MockContext> = RefCell::new(MockContext::new());
+}
+
+/// Error returned when an exported function cannot be called or throws.
+#[derive(Clone, Debug, PartialEq)]
+pub enum StandaloneError {
+    /// No function is exported under the name.
+    NotFound(String),
+    /// The function cannot be called without Node.js, for the given reason.
+    Unsupported(String, &'static str),
+    /// The arguments were not a valid JSON array.
+    InvalidJson(String),
+    /// The function threw the value.
+    Threw(MockValue),
+}
+
+impl fmt::Display for StandaloneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StandaloneError::NotFound(name) => write!(f, "no function is exported as `{}`", name),
+            StandaloneError::Unsupported(name, reason) => {
+                write!(f, "`{}` cannot be called without Node: {}", name, reason)
+            }
+            StandaloneError::InvalidJson(err) => write!(f, "invalid JSON arguments: {}", err),
+            StandaloneError::Threw(MockValue::Error { name, message }) => {
+                write!(f, "{}: {}", name, message)
+            }
+            StandaloneError::Threw(value) => write!(f, "threw {}", value.to_json()),
+        }
+    }
+}
+
+impl std::error::Error for StandaloneError {}
+
+/// The names of the exported functions, in registration order.
+pub fn exports() -> Vec<String> {
+    export::functions()
+        .into_iter()
+        .map(|(export, _)| export.unconverted_path())
+        .collect()
+}
+
+/// Calls the function exported as `name` with `args`.
+pub fn call(name: &str, args: Vec<MockValue>) -> Result<MockValue, StandaloneError> {
+    let (export, function) = export::functions()
+        .into_iter()
+        .find(|(export, _)| export.unconverted_path() == name)
+        .ok_or_else(|| StandaloneError::NotFound(name.to_string()))?;
+
+    let unsupported = match export.kind() {
+        ExportKind::Task => Some("it runs on the worker pool"),
+        ExportKind::Lazy => Some("it is a lazily computed value"),
+        _ => None,
+    };
+
+    if let Some(reason) = unsupported {
+        return Err(StandaloneError::Unsupported(name.to_string(), reason));
+    }
+
+    INSTANCE
+        .with(|cx| cx.borrow_mut().call(function, args))
+        .map_err(StandaloneError::Threw)
+}
+
+/// Calls the function exported as `name` with the elements of the JSON array
+/// `args`, and returns the result as JSON. Values are converted like
+/// `JSON.parse` and `JSON.stringify` do, with [`MockValue::to_json`], so
+/// `undefined` is returned as `null`.
+pub fn call_json(name: &str, args: &str) -> Result<String, StandaloneError> {
+    let args = serde_json::from_str::<Vec<serde_json::Value>>(args)
+        .map_err(|err| StandaloneError::InvalidJson(err.to_string()))?
+        .into_iter()
+        .map(MockValue::from)
+        .collect();
+
+    call(name, args).map(|result| result.to_json().to_string())
+}
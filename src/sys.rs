@@ -0,0 +1,33 @@
+//! Raw N-API types, for interoperating with other N-API wrappers in the same
+//! process, such as napi-rs or hand-written C.
+//!
+//! [`Context::with_raw_env`](crate::context::Context::with_raw_env) lends the
+//! `napi_env` of a context to foreign code, and
+//! [`Cx::from_raw`](crate::context::Cx::from_raw) creates a Neon context from
+//! a `napi_env` received from foreign code:
+//!
+//! ```
+//! # #[cfg(all(feature = "napi-1", feature = "sys"))] {
+//! # use neon::prelude::*;
+//! use neon::sys;
+//!
+//! // Called by a library that only knows about N-API
+//! unsafe extern "C" fn on_event(env: sys::Env) {
+//!     Cx::from_raw(env, |mut cx| {
+//!         let global = cx.global();
+//!         let count = cx.number(1);
+//!
+//!         let _ = global.set(&mut cx, "eventCount", count);
+//!     });
+//! }
+//! # }
+//! ```
+//!
+//! Values are exchanged with [`Managed::to_raw`](crate::handle::Managed::to_raw)
+//! and the `napi_value` type, [`Value`].
+
+/// A `napi_env`, the environment of a JavaScript thread.
+pub type Env = neon_runtime::raw::Env;
+
+/// A `napi_value`, a handle to a JavaScript value in the current handle scope.
+pub type Value = neon_runtime::raw::Local;
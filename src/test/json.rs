@@ -0,0 +1,104 @@
+//! Conversions between [`MockValue`]s and JSON, which also implement the
+//! global `JSON` object of the mock.
+
+use serde_json::{Number, Value};
+
+use super::{MockFunction, MockValue};
+
+// The largest integer that a JavaScript number represents exactly
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Converts JSON like `JSON.parse`.
+impl From<Value> for MockValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => MockValue::Null,
+            Value::Bool(b) => MockValue::Boolean(b),
+            Value::Number(n) => MockValue::Number(n.as_f64().unwrap_or(f64::NAN)),
+            Value::String(s) => MockValue::String(s),
+            Value::Array(elements) => elements
+                .into_iter()
+                .map(MockValue::from)
+                .collect::<Vec<_>>()
+                .into(),
+            Value::Object(properties) => {
+                let object = MockValue::object();
+
+                for (key, value) in properties {
+                    object.set(&key, MockValue::from(value));
+                }
+
+                object
+            }
+        }
+    }
+}
+
+impl MockValue {
+    /// Converts the value to JSON like `JSON.stringify`, except that a value
+    /// that `JSON.stringify` does not serialize, like `undefined`, is `null`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> Value {
+        self.to_json_opt().unwrap_or(Value::Null)
+    }
+
+    // `None` for the values that `JSON.stringify` skips in objects
+    fn to_json_opt(&self) -> Option<Value> {
+        let value = match self {
+            MockValue::Undefined | MockValue::Function(_) => return None,
+            MockValue::Null => Value::Null,
+            MockValue::Boolean(b) => Value::Bool(*b),
+            // Integers are written without a fraction, like JavaScript does
+            MockValue::Number(n) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+                Value::from(*n as i64)
+            }
+            MockValue::Number(n) => Number::from_f64(*n).map_or(Value::Null, Value::Number),
+            MockValue::String(s) => Value::String(s.clone()),
+            // Skipped elements are `null`, to keep the indices
+            MockValue::Array(elements) => {
+                Value::Array(elements.borrow().iter().map(MockValue::to_json).collect())
+            }
+            MockValue::Object(properties) => Value::Object(
+                properties
+                    .borrow()
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.to_json_opt()?)))
+                    .collect(),
+            ),
+            // The name and message of an error are not enumerable
+            MockValue::Error { .. } => Value::Object(Default::default()),
+        };
+
+        Some(value)
+    }
+}
+
+/// The global `JSON` object.
+pub(super) fn global() -> MockValue {
+    let json = MockValue::object();
+
+    json.set("parse", MockFunction::new(parse));
+    json.set("stringify", MockFunction::new(stringify));
+    json
+}
+
+fn parse(_this: MockValue, args: Vec<MockValue>) -> Result<MockValue, MockValue> {
+    let text = args.first().unwrap_or(&MockValue::Undefined).to_js_string();
+
+    match serde_json::from_str::<Value>(&text) {
+        Ok(value) => Ok(value.into()),
+        Err(err) => Err(MockValue::error("SyntaxError", &err.to_string())),
+    }
+}
+
+// Replacers and indentation are not supported
+fn stringify(_this: MockValue, args: Vec<MockValue>) -> Result<MockValue, MockValue> {
+    let json = args
+        .first()
+        .and_then(MockValue::to_json_opt)
+        .map_or(MockValue::Undefined, |json| {
+            MockValue::String(json.to_string())
+        });
+
+    Ok(json)
+}
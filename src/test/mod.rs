@@ -0,0 +1,445 @@
+//! A context for testing Neon functions without Node.js.
+//!
+//! With the `mock-context` feature enabled, a [`MockContext`] calls a Neon
+//! function in ordinary `cargo test` unit tests. It implements the part of
+//! N-API that contexts, objects and conversions use, so the function runs with
+//! a real [`FunctionContext`] and converts its arguments and result with
+//! [`TryFromJs`](crate::types::extract::TryFromJs) and
+//! [`TryIntoJs`](crate::types::extract::TryIntoJs), producing the same errors
+//! as under Node.js. Values are passed in and out as [`MockValue`]s.
+//!
+//! ```
+//! # #[cfg(all(feature = "napi-1", feature = "mock-context"))] {
+//! use neon::prelude::*;
+//! use neon::test::{MockContext, MockValue};
+//!
+//! fn area(mut cx: FunctionContext) -> JsResult<JsNumber> {
+//!     let width = cx.argument::<JsNumber>(0)?.value(&mut cx);
+//!     let height = cx.argument::<JsNumber>(1)?.value(&mut cx);
+//!
+//!     Ok(cx.number(width * height))
+//! }
+//!
+//! let mut cx = MockContext::new();
+//!
+//! assert_eq!(cx.call(area, [2.0, 3.0]), Ok(MockValue::Number(6.0)));
+//! assert!(matches!(
+//!     cx.call(area, ["2"]),
+//!     Err(MockValue::Error { name, .. }) if name == "TypeError",
+//! ));
+//! # }
+//! ```
+//!
+//! The mock models the values that conversions inspect, not the JavaScript
+//! engine: there are no prototypes, promises, buffers or garbage collection,
+//! and the only functions are [`MockFunction`]s implemented in Rust. N-API
+//! functions for the rest fail. With the `serde` feature, the global `JSON`
+//! object provides `parse` and `stringify`, and [`MockValue`]s convert to and
+//! from [`serde_json::Value`]. Code that needs more should be tested under
+//! Node.js, e.g., with [`#[neon::test]`](macro@crate::test).
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use neon_runtime::napi::mock;
+
+use crate::context::internal::Env;
+use crate::context::{CallbackInfo, FunctionContext};
+use crate::result::JsResult;
+use crate::types::Value;
+
+use self::napi::{MockCall, MockEnv};
+
+#[cfg(feature = "serde")]
+mod json;
+mod napi;
+
+/// A JavaScript value, modeled as Rust data.
+///
+/// Like JavaScript objects, arrays and objects are shared by reference:
+/// cloning a `MockValue` clones the reference, and changes through either
+/// clone are visible in both.
+#[derive(Clone, Debug)]
+pub enum MockValue {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Rc<RefCell<Vec<MockValue>>>),
+    Object(Rc<RefCell<BTreeMap<String, MockValue>>>),
+    /// An instance of `Error` or one of its subclasses, e.g., `TypeError`.
+    Error {
+        name: String,
+        message: String,
+    },
+    /// A function implemented in Rust.
+    Function(MockFunction),
+}
+
+impl MockValue {
+    /// Creates an empty array.
+    pub fn array() -> Self {
+        MockValue::Array(Default::default())
+    }
+
+    /// Creates an empty object.
+    pub fn object() -> Self {
+        MockValue::Object(Default::default())
+    }
+
+    /// Creates an error of the class `name`, e.g., `"TypeError"`.
+    pub fn error(name: &str, message: &str) -> Self {
+        MockValue::Error {
+            name: name.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Reads the property `key` of an object, the element at the index `key`
+    /// of an array, or the `name` or `message` of an error. Missing
+    /// properties are `undefined`.
+    pub fn get(&self, key: &str) -> MockValue {
+        match self {
+            MockValue::Object(properties) => properties.borrow().get(key).cloned(),
+            MockValue::Array(elements) if key == "length" => {
+                Some(MockValue::Number(elements.borrow().len() as f64))
+            }
+            MockValue::Array(elements) => key
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| elements.borrow().get(i).cloned()),
+            MockValue::Error { name, .. } if key == "name" => Some(MockValue::String(name.clone())),
+            MockValue::Error { message, .. } if key == "message" => {
+                Some(MockValue::String(message.clone()))
+            }
+            _ => None,
+        }
+        .unwrap_or(MockValue::Undefined)
+    }
+
+    /// Sets the property `key` of an object, or the element at the index
+    /// `key` of an array, filling any gap with `undefined`. Returns `false`
+    /// if the value is neither.
+    pub fn set(&self, key: &str, value: impl Into<MockValue>) -> bool {
+        match self {
+            MockValue::Object(properties) => {
+                properties
+                    .borrow_mut()
+                    .insert(key.to_string(), value.into());
+            }
+            MockValue::Array(elements) => {
+                let i = match key.parse::<usize>() {
+                    Ok(i) => i,
+                    Err(_) => return false,
+                };
+
+                let mut elements = elements.borrow_mut();
+
+                if i >= elements.len() {
+                    elements.resize(i + 1, MockValue::Undefined);
+                }
+
+                elements[i] = value.into();
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// The length of an array, or `None` if the value is not an array.
+    pub fn array_len(&self) -> Option<usize> {
+        match self {
+            MockValue::Array(elements) => Some(elements.borrow().len()),
+            _ => None,
+        }
+    }
+
+    /// Appends an element to an array. Returns `false` if the value is not
+    /// an array.
+    pub fn push(&self, value: impl Into<MockValue>) -> bool {
+        match self {
+            MockValue::Array(elements) => {
+                elements.borrow_mut().push(value.into());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // The value converted to a string, like `String(value)`
+    fn to_js_string(&self) -> String {
+        match self {
+            MockValue::Undefined => "undefined".to_string(),
+            MockValue::Null => "null".to_string(),
+            MockValue::Boolean(b) => b.to_string(),
+            MockValue::Number(n) if n.is_nan() => "NaN".to_string(),
+            MockValue::Number(n) if n.is_infinite() && *n > 0.0 => "Infinity".to_string(),
+            MockValue::Number(n) if n.is_infinite() => "-Infinity".to_string(),
+            // Including `-0`
+            MockValue::Number(n) if *n == 0.0 => "0".to_string(),
+            MockValue::Number(n) => n.to_string(),
+            MockValue::String(s) => s.clone(),
+            // `null` and `undefined` elements are empty
+            MockValue::Array(elements) => elements
+                .borrow()
+                .iter()
+                .map(|element| match element {
+                    MockValue::Undefined | MockValue::Null => String::new(),
+                    element => element.to_js_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            MockValue::Object(_) => "[object Object]".to_string(),
+            MockValue::Error { name, message } if message.is_empty() => name.clone(),
+            MockValue::Error { name, message } => format!("{}: {}", name, message),
+            MockValue::Function(_) => "function () { [native code] }".to_string(),
+        }
+    }
+
+    // Compares values like `===`. Errors have no identity in the model, so
+    // they are compared by contents.
+    fn strict_equals(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MockValue::Number(a), MockValue::Number(b)) => a == b,
+            (MockValue::Array(a), MockValue::Array(b)) => Rc::ptr_eq(a, b),
+            (MockValue::Object(a), MockValue::Object(b)) => Rc::ptr_eq(a, b),
+            (a, b) => a == b,
+        }
+    }
+}
+
+/// Values are compared like `Object.is` for primitives and functions, and by
+/// contents for arrays, objects and errors, which is what tests usually
+/// expect.
+impl PartialEq for MockValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MockValue::Undefined, MockValue::Undefined) => true,
+            (MockValue::Null, MockValue::Null) => true,
+            (MockValue::Boolean(a), MockValue::Boolean(b)) => a == b,
+            (MockValue::Number(a), MockValue::Number(b)) => {
+                (a.is_nan() && b.is_nan())
+                    || (a == b && a.is_sign_negative() == b.is_sign_negative())
+            }
+            (MockValue::String(a), MockValue::String(b)) => a == b,
+            (MockValue::Array(a), MockValue::Array(b)) => *a.borrow() == *b.borrow(),
+            (MockValue::Object(a), MockValue::Object(b)) => *a.borrow() == *b.borrow(),
+            (
+                MockValue::Error { name, message },
+                MockValue::Error {
+                    name: other_name,
+                    message: other_message,
+                },
+            ) => name == other_name && message == other_message,
+            (MockValue::Function(a), MockValue::Function(b)) => Rc::ptr_eq(&a.0, &b.0),
+            _ => false,
+        }
+    }
+}
+
+/// A JavaScript function of the mock, implemented by a Rust closure that is
+/// called with `this` and the arguments, and returns a value or throws one.
+///
+/// The closure runs inside an N-API call, so it must not panic: the process
+/// aborts instead of unwinding.
+#[derive(Clone)]
+pub struct MockFunction(Rc<MockFunctionImpl>);
+
+type MockFunctionImpl = dyn Fn(MockValue, Vec<MockValue>) -> Result<MockValue, MockValue>;
+
+impl MockFunction {
+    /// Creates a function that calls `f`.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(MockValue, Vec<MockValue>) -> Result<MockValue, MockValue> + 'static,
+    {
+        MockFunction(Rc::new(f))
+    }
+
+    /// Calls the function with `this` and `args`.
+    pub fn call(&self, this: MockValue, args: Vec<MockValue>) -> Result<MockValue, MockValue> {
+        (self.0)(this, args)
+    }
+}
+
+impl fmt::Debug for MockFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockFunction").finish()
+    }
+}
+
+impl From<MockFunction> for MockValue {
+    fn from(f: MockFunction) -> Self {
+        MockValue::Function(f)
+    }
+}
+
+impl From<()> for MockValue {
+    fn from(_: ()) -> Self {
+        MockValue::Undefined
+    }
+}
+
+impl From<bool> for MockValue {
+    fn from(b: bool) -> Self {
+        MockValue::Boolean(b)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for MockValue {
+                fn from(n: $t) -> Self {
+                    MockValue::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(f64, f32, i8, i16, i32, u8, u16, u32);
+
+impl From<&str> for MockValue {
+    fn from(s: &str) -> Self {
+        MockValue::String(s.to_string())
+    }
+}
+
+impl From<String> for MockValue {
+    fn from(s: String) -> Self {
+        MockValue::String(s)
+    }
+}
+
+/// `None` is `undefined`.
+impl<T: Into<MockValue>> From<Option<T>> for MockValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(MockValue::Undefined, Into::into)
+    }
+}
+
+impl<T: Into<MockValue>> From<Vec<T>> for MockValue {
+    fn from(elements: Vec<T>) -> Self {
+        let elements = elements.into_iter().map(Into::into).collect();
+
+        MockValue::Array(Rc::new(RefCell::new(elements)))
+    }
+}
+
+/// An instance of a module without Node.js, which calls Neon functions with
+/// [`MockValue`] arguments.
+///
+/// Like an instance loaded by Node.js, it keeps its
+/// [instance data](crate::context::ContextLocal) across calls, and drops it
+/// when the `MockContext` is dropped. Closures sent to its
+/// [`Channel`](crate::event::Channel) run after each call, like a turn of the
+/// event loop.
+pub struct MockContext {
+    env: *mut MockEnv,
+}
+
+impl MockContext {
+    /// Creates an instance.
+    pub fn new() -> Self {
+        mock::install(napi::resolve);
+
+        Self {
+            env: Box::into_raw(Box::new(MockEnv::new())),
+        }
+    }
+
+    /// Calls `f` with `args`, as a function called with `this` as
+    /// `undefined`. Returns the value returned by `f`, or the exception it
+    /// threw.
+    pub fn call<F, V, I>(&mut self, f: F, args: I) -> Result<MockValue, MockValue>
+    where
+        F: for<'a> FnOnce(FunctionContext<'a>) -> JsResult<'a, V>,
+        V: Value,
+        I: IntoIterator,
+        I::Item: Into<MockValue>,
+    {
+        self.call_with_this(f, MockValue::Undefined, args)
+    }
+
+    /// Calls `f` with `args`, as a method of `this`. Returns the value
+    /// returned by `f`, or the exception it threw.
+    pub fn call_with_this<F, V, I>(
+        &mut self,
+        f: F,
+        this: impl Into<MockValue>,
+        args: I,
+    ) -> Result<MockValue, MockValue>
+    where
+        F: for<'a> FnOnce(FunctionContext<'a>) -> JsResult<'a, V>,
+        V: Value,
+        I: IntoIterator,
+        I::Item: Into<MockValue>,
+    {
+        // Safety: Only the mock environment is passed to N-API while entered
+        let _mock = unsafe { mock::enter() };
+        // Safety: The environment is owned by `self`, which is borrowed mutably
+        let env = unsafe { &mut *self.env };
+        let mark = env.mark();
+        let call = MockCall {
+            this: env.push(this.into()),
+            args: args.into_iter().map(|arg| env.push(arg.into())).collect(),
+        };
+
+        let info = CallbackInfo::from_raw(call.to_raw());
+        let result = FunctionContext::with(Env::from(self.env.cast()), &info, |cx| {
+            f(cx).map(|v| v.to_raw())
+        });
+
+        // Safety: The context has ended
+        let env = unsafe { &mut *self.env };
+        let result = match result {
+            Ok(value) => Ok(env.get(value).cloned().unwrap_or(MockValue::Undefined)),
+            Err(_) => Err(env.take_exception().unwrap_or(MockValue::Undefined)),
+        };
+
+        env.truncate(mark);
+
+        // Safety: The mock is entered
+        unsafe {
+            napi::run_pending(self.env);
+        }
+
+        // Callbacks run from the event loop report their exceptions as
+        // uncaught, which is not modeled
+        let env = unsafe { &mut *self.env };
+
+        env.take_exception();
+        env.truncate(mark);
+
+        result
+    }
+}
+
+impl Default for MockContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MockContext {
+    fn drop(&mut self) {
+        // Safety: The environment is not used after it is freed
+        unsafe {
+            let _mock = mock::enter();
+
+            napi::teardown(self.env);
+            drop(Box::from_raw(self.env));
+        }
+    }
+}
+
+impl fmt::Debug for MockContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockContext").finish()
+    }
+}
@@ -0,0 +1,1131 @@
+//! The N-API functions behind [`MockContext`](super::MockContext).
+//!
+//! A `napi_env` points to a [`MockEnv`] and a `napi_value` is an index into
+//! its table of handles, plus one so that it is never null. The table is
+//! truncated when a handle scope closes. Only the functions used by contexts,
+//! objects and conversions are implemented; the others fail after printing
+//! their name.
+//!
+//! Panics cannot unwind out of these functions, so they report errors with a
+//! status, or with a pending exception where JavaScript would throw.
+
+use std::collections::VecDeque;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+use std::slice;
+use std::sync::Mutex;
+
+use neon_runtime::napi::{Ref, Status};
+use neon_runtime::raw::{Env, FunctionCallbackInfo, Local};
+
+use super::MockValue;
+
+type Finalize = Option<unsafe extern "C" fn(env: Env, data: *mut c_void, hint: *mut c_void)>;
+
+type CallJs = Option<
+    unsafe extern "C" fn(env: Env, callback: Local, context: *mut c_void, data: *mut c_void),
+>;
+
+// `napi_valuetype`
+const TYPE_UNDEFINED: u32 = 0;
+const TYPE_NULL: u32 = 1;
+const TYPE_BOOLEAN: u32 = 2;
+const TYPE_NUMBER: u32 = 3;
+const TYPE_STRING: u32 = 4;
+const TYPE_OBJECT: u32 = 6;
+const TYPE_FUNCTION: u32 = 7;
+
+// `NAPI_AUTO_LENGTH`
+const AUTO_LENGTH: usize = usize::MAX;
+
+// `napi_tsfn_abort`
+const RELEASE_ABORT: u32 = 1;
+
+/// The state of a mock instance of a module.
+pub(super) struct MockEnv {
+    handles: Vec<MockValue>,
+    exception: Option<MockValue>,
+    global: MockValue,
+    references: Vec<Option<(MockValue, u32)>>,
+    instance_data: Option<(*mut c_void, Finalize, *mut c_void)>,
+    external_memory: i64,
+    tsfns: Vec<*mut Tsfn>,
+}
+
+/// The arguments and `this` of a call, which its `napi_callback_info` points
+/// to.
+pub(super) struct MockCall {
+    pub(super) args: Vec<Local>,
+    pub(super) this: Local,
+}
+
+impl MockCall {
+    pub(super) fn to_raw(&self) -> FunctionCallbackInfo {
+        self as *const MockCall as FunctionCallbackInfo
+    }
+}
+
+impl MockEnv {
+    pub(super) fn new() -> Self {
+        let global = MockValue::object();
+
+        #[cfg(feature = "serde")]
+        global.set("JSON", super::json::global());
+
+        Self {
+            handles: Vec::new(),
+            exception: None,
+            global,
+            references: Vec::new(),
+            instance_data: None,
+            external_memory: 0,
+            tsfns: Vec::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, value: MockValue) -> Local {
+        self.handles.push(value);
+        self.handles.len() as Local
+    }
+
+    pub(super) fn get(&self, value: Local) -> Option<&MockValue> {
+        (value as usize)
+            .checked_sub(1)
+            .and_then(|i| self.handles.get(i))
+    }
+
+    fn get_mut(&mut self, value: Local) -> Option<&mut MockValue> {
+        (value as usize)
+            .checked_sub(1)
+            .and_then(move |i| self.handles.get_mut(i))
+    }
+
+    /// The number of handles, to be passed to [`MockEnv::truncate`].
+    pub(super) fn mark(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Releases the handles created since `mark`.
+    pub(super) fn truncate(&mut self, mark: usize) {
+        self.handles.truncate(mark);
+    }
+
+    pub(super) fn take_exception(&mut self) -> Option<MockValue> {
+        self.exception.take()
+    }
+
+    fn tsfn(&self, i: usize) -> Option<*mut Tsfn> {
+        self.tsfns.get(i).copied()
+    }
+
+    fn add_tsfn(&mut self, tsfn: *mut Tsfn) {
+        self.tsfns.push(tsfn);
+    }
+
+    fn throw_type_error(&mut self, message: &str) -> Status {
+        self.exception = Some(MockValue::error("TypeError", message));
+
+        Status::PendingException
+    }
+}
+
+/// Calls the callbacks scheduled on threadsafe functions, like a turn of the
+/// event loop, until none are left. Then finalizes the threadsafe functions
+/// that were released.
+///
+/// # Safety
+/// `env` must be a live `MockEnv` that the mock has been entered for.
+pub(super) unsafe fn run_pending(env: *mut MockEnv) {
+    loop {
+        let mut called = false;
+        let mut i = 0;
+
+        // Callbacks may create threadsafe functions, so the list is indexed
+        // again on each iteration
+        while let Some(tsfn) = (*env).tsfn(i) {
+            let data = (*tsfn)
+                .state
+                .lock()
+                .ok()
+                .and_then(|mut state| state.queue.pop_front());
+
+            if let Some(data) = data {
+                if let Some(call_js) = (*tsfn).call_js {
+                    call_js(
+                        env.cast(),
+                        ptr::null_mut(),
+                        (*tsfn).context,
+                        data as *mut c_void,
+                    );
+                }
+
+                called = true;
+            }
+
+            i += 1;
+        }
+
+        if !called {
+            break;
+        }
+    }
+
+    let tsfns = std::mem::take(&mut (*env).tsfns);
+
+    for tsfn in tsfns {
+        let released = (*tsfn)
+            .state
+            .lock()
+            .map(|state| state.threads == 0)
+            .unwrap_or(true);
+
+        if released {
+            finalize_tsfn(env, tsfn);
+        } else {
+            (*env).add_tsfn(tsfn);
+        }
+    }
+}
+
+/// Unloads the instance: runs the callbacks that are still scheduled, drops
+/// the instance data and finalizes every threadsafe function.
+///
+/// # Safety
+/// `env` must be a live `MockEnv` that the mock has been entered for, and it
+/// must not be used afterwards.
+pub(super) unsafe fn teardown(env: *mut MockEnv) {
+    run_pending(env);
+
+    if let Some((data, Some(finalize), hint)) = (*env).instance_data.take() {
+        finalize(env.cast(), data, hint);
+    }
+
+    // Dropping the instance data may schedule the release of references
+    run_pending(env);
+
+    for tsfn in std::mem::take(&mut (*env).tsfns) {
+        finalize_tsfn(env, tsfn);
+    }
+}
+
+unsafe fn finalize_tsfn(env: *mut MockEnv, tsfn: *mut Tsfn) {
+    let tsfn = Box::from_raw(tsfn);
+
+    if let Some(finalize) = tsfn.finalize {
+        finalize(env.cast(), tsfn.finalize_data, tsfn.finalize_hint);
+    }
+}
+
+/// Returns the mock implementation of the N-API function `name`.
+pub(super) fn resolve(name: &str) -> Option<*const c_void> {
+    let f = match name {
+        "napi_get_undefined" => get_undefined as *const c_void,
+        "napi_get_null" => get_null as *const c_void,
+        "napi_get_global" => get_global as *const c_void,
+        "napi_get_boolean" => get_boolean as *const c_void,
+        "napi_create_double" => create_double as *const c_void,
+        "napi_create_object" => create_object as *const c_void,
+        "napi_create_array_with_length" => create_array_with_length as *const c_void,
+        "napi_create_string_utf8" | "node_api_create_property_key_utf8" => {
+            create_string_utf8 as *const c_void
+        }
+        "napi_create_error" => create_error as *const c_void,
+        "napi_create_type_error" => create_type_error as *const c_void,
+        "napi_create_range_error" => create_range_error as *const c_void,
+        "node_api_create_syntax_error" => create_syntax_error as *const c_void,
+        "napi_get_value_bool" => get_value_bool as *const c_void,
+        "napi_get_value_double" => get_value_double as *const c_void,
+        "napi_get_value_string_utf8" => get_value_string_utf8 as *const c_void,
+        "napi_get_array_length" => get_array_length as *const c_void,
+        "napi_coerce_to_string" => coerce_to_string as *const c_void,
+        "napi_typeof" => typeof_value as *const c_void,
+        "napi_is_array" => is_array as *const c_void,
+        "napi_is_error" => is_error as *const c_void,
+        "napi_is_arraybuffer"
+        | "napi_is_buffer"
+        | "napi_is_typedarray"
+        | "napi_is_date"
+        | "napi_is_promise" => is_never as *const c_void,
+        "napi_instanceof" => instanceof as *const c_void,
+        "napi_strict_equals" => strict_equals as *const c_void,
+        "napi_get_property" => get_property as *const c_void,
+        "napi_set_property" => set_property as *const c_void,
+        "napi_has_property" | "napi_has_own_property" => has_property as *const c_void,
+        "napi_delete_property" => delete_property as *const c_void,
+        "napi_get_element" => get_element as *const c_void,
+        "napi_set_element" => set_element as *const c_void,
+        "napi_get_all_property_names" => get_all_property_names as *const c_void,
+        "napi_object_freeze" | "napi_object_seal" => object_freeze as *const c_void,
+        "napi_throw" => throw as *const c_void,
+        "napi_is_exception_pending" => is_exception_pending as *const c_void,
+        "napi_get_and_clear_last_exception" => get_and_clear_last_exception as *const c_void,
+        "napi_fatal_error" => fatal_error as *const c_void,
+        "napi_call_function" => call_function as *const c_void,
+        "napi_get_cb_info" => get_cb_info as *const c_void,
+        "napi_get_new_target" => get_new_target as *const c_void,
+        "napi_open_handle_scope" => open_handle_scope as *const c_void,
+        "napi_close_handle_scope" | "napi_close_escapable_handle_scope" => {
+            close_handle_scope as *const c_void
+        }
+        "napi_open_escapable_handle_scope" => open_escapable_handle_scope as *const c_void,
+        "napi_escape_handle" => escape_handle as *const c_void,
+        "napi_create_reference" => create_reference as *const c_void,
+        "napi_reference_ref" => reference_ref as *const c_void,
+        "napi_reference_unref" => reference_unref as *const c_void,
+        "napi_delete_reference" => delete_reference as *const c_void,
+        "napi_get_reference_value" => get_reference_value as *const c_void,
+        "napi_set_instance_data" => set_instance_data as *const c_void,
+        "napi_get_instance_data" => get_instance_data as *const c_void,
+        "napi_adjust_external_memory" => adjust_external_memory as *const c_void,
+        "napi_create_threadsafe_function" => create_threadsafe_function as *const c_void,
+        "napi_call_threadsafe_function" => call_threadsafe_function as *const c_void,
+        "napi_acquire_threadsafe_function" => acquire_threadsafe_function as *const c_void,
+        "napi_release_threadsafe_function" => release_threadsafe_function as *const c_void,
+        "napi_ref_threadsafe_function" | "napi_unref_threadsafe_function" => {
+            ref_threadsafe_function as *const c_void
+        }
+        _ => return None,
+    };
+
+    Some(f)
+}
+
+unsafe fn cx<'a>(env: Env) -> &'a mut MockEnv {
+    &mut *(env as *mut MockEnv)
+}
+
+// Reads a string argument, which may be NUL terminated
+unsafe fn string_arg(s: *const c_char, len: usize) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+
+    let len = if len == AUTO_LENGTH {
+        std::ffi::CStr::from_ptr(s).to_bytes().len()
+    } else {
+        len
+    };
+
+    String::from_utf8_lossy(slice::from_raw_parts(s.cast::<u8>(), len)).into_owned()
+}
+
+unsafe extern "C" fn get_undefined(env: Env, result: *mut Local) -> Status {
+    *result = cx(env).push(MockValue::Undefined);
+    Status::Ok
+}
+
+unsafe extern "C" fn get_null(env: Env, result: *mut Local) -> Status {
+    *result = cx(env).push(MockValue::Null);
+    Status::Ok
+}
+
+unsafe extern "C" fn get_global(env: Env, result: *mut Local) -> Status {
+    let cx = cx(env);
+
+    *result = cx.push(cx.global.clone());
+    Status::Ok
+}
+
+unsafe extern "C" fn get_boolean(env: Env, value: bool, result: *mut Local) -> Status {
+    *result = cx(env).push(MockValue::Boolean(value));
+    Status::Ok
+}
+
+unsafe extern "C" fn create_double(env: Env, value: f64, result: *mut Local) -> Status {
+    *result = cx(env).push(MockValue::Number(value));
+    Status::Ok
+}
+
+unsafe extern "C" fn create_object(env: Env, result: *mut Local) -> Status {
+    *result = cx(env).push(MockValue::object());
+    Status::Ok
+}
+
+unsafe extern "C" fn create_array_with_length(
+    env: Env,
+    length: usize,
+    result: *mut Local,
+) -> Status {
+    let array = MockValue::Array(Default::default());
+
+    if let MockValue::Array(elements) = &array {
+        elements.borrow_mut().resize(length, MockValue::Undefined);
+    }
+
+    *result = cx(env).push(array);
+    Status::Ok
+}
+
+unsafe extern "C" fn create_string_utf8(
+    env: Env,
+    s: *const c_char,
+    length: usize,
+    result: *mut Local,
+) -> Status {
+    *result = cx(env).push(MockValue::String(string_arg(s, length)));
+    Status::Ok
+}
+
+unsafe fn new_error(env: Env, name: &str, msg: Local, result: *mut Local) -> Status {
+    let cx = cx(env);
+    let message = match cx.get(msg) {
+        Some(MockValue::String(message)) => message.clone(),
+        _ => return Status::StringExpected,
+    };
+
+    *result = cx.push(MockValue::error(name, &message));
+    Status::Ok
+}
+
+unsafe extern "C" fn create_error(
+    env: Env,
+    _code: Local,
+    msg: Local,
+    result: *mut Local,
+) -> Status {
+    new_error(env, "Error", msg, result)
+}
+
+unsafe extern "C" fn create_type_error(
+    env: Env,
+    _code: Local,
+    msg: Local,
+    result: *mut Local,
+) -> Status {
+    new_error(env, "TypeError", msg, result)
+}
+
+unsafe extern "C" fn create_range_error(
+    env: Env,
+    _code: Local,
+    msg: Local,
+    result: *mut Local,
+) -> Status {
+    new_error(env, "RangeError", msg, result)
+}
+
+unsafe extern "C" fn create_syntax_error(
+    env: Env,
+    _code: Local,
+    msg: Local,
+    result: *mut Local,
+) -> Status {
+    new_error(env, "SyntaxError", msg, result)
+}
+
+unsafe extern "C" fn get_value_bool(env: Env, value: Local, result: *mut bool) -> Status {
+    match cx(env).get(value) {
+        Some(MockValue::Boolean(b)) => {
+            *result = *b;
+            Status::Ok
+        }
+        _ => Status::BooleanExpected,
+    }
+}
+
+unsafe extern "C" fn get_value_double(env: Env, value: Local, result: *mut f64) -> Status {
+    match cx(env).get(value) {
+        Some(MockValue::Number(n)) => {
+            *result = *n;
+            Status::Ok
+        }
+        _ => Status::NumberExpected,
+    }
+}
+
+unsafe extern "C" fn get_value_string_utf8(
+    env: Env,
+    value: Local,
+    buf: *mut c_char,
+    bufsize: usize,
+    result: *mut usize,
+) -> Status {
+    let s = match cx(env).get(value) {
+        Some(MockValue::String(s)) => s.as_bytes(),
+        _ => return Status::StringExpected,
+    };
+
+    if buf.is_null() {
+        if !result.is_null() {
+            *result = s.len();
+        }
+
+        return Status::Ok;
+    }
+
+    if bufsize == 0 {
+        return Status::Ok;
+    }
+
+    // Room is left for the NUL terminator
+    let len = s.len().min(bufsize - 1);
+
+    ptr::copy_nonoverlapping(s.as_ptr(), buf.cast::<u8>(), len);
+    *buf.add(len) = 0;
+
+    if !result.is_null() {
+        *result = len;
+    }
+
+    Status::Ok
+}
+
+unsafe extern "C" fn get_array_length(env: Env, value: Local, result: *mut u32) -> Status {
+    match cx(env).get(value).and_then(MockValue::array_len) {
+        Some(len) => {
+            *result = len as u32;
+            Status::Ok
+        }
+        None => Status::ArrayExpected,
+    }
+}
+
+unsafe extern "C" fn coerce_to_string(env: Env, value: Local, result: *mut Local) -> Status {
+    let cx = cx(env);
+    let s = match cx.get(value) {
+        Some(value) => value.to_js_string(),
+        None => return Status::InvalidArg,
+    };
+
+    *result = cx.push(MockValue::String(s));
+    Status::Ok
+}
+
+unsafe extern "C" fn typeof_value(env: Env, value: Local, result: *mut u32) -> Status {
+    *result = match cx(env).get(value) {
+        Some(MockValue::Undefined) => TYPE_UNDEFINED,
+        Some(MockValue::Null) => TYPE_NULL,
+        Some(MockValue::Boolean(_)) => TYPE_BOOLEAN,
+        Some(MockValue::Number(_)) => TYPE_NUMBER,
+        Some(MockValue::String(_)) => TYPE_STRING,
+        Some(MockValue::Array(_) | MockValue::Object(_) | MockValue::Error { .. }) => TYPE_OBJECT,
+        Some(MockValue::Function(_)) => TYPE_FUNCTION,
+        None => return Status::InvalidArg,
+    };
+
+    Status::Ok
+}
+
+unsafe extern "C" fn is_array(env: Env, value: Local, result: *mut bool) -> Status {
+    *result = matches!(cx(env).get(value), Some(MockValue::Array(_)));
+    Status::Ok
+}
+
+unsafe extern "C" fn is_error(env: Env, value: Local, result: *mut bool) -> Status {
+    *result = matches!(cx(env).get(value), Some(MockValue::Error { .. }));
+    Status::Ok
+}
+
+// Buffers, typed arrays, dates and promises are not modeled
+unsafe extern "C" fn is_never(_env: Env, _value: Local, result: *mut bool) -> Status {
+    *result = false;
+    Status::Ok
+}
+
+// There are no constructors to be an instance of
+unsafe extern "C" fn instanceof(
+    _env: Env,
+    _object: Local,
+    _constructor: Local,
+    result: *mut bool,
+) -> Status {
+    *result = false;
+    Status::Ok
+}
+
+unsafe extern "C" fn strict_equals(env: Env, lhs: Local, rhs: Local, result: *mut bool) -> Status {
+    let cx = cx(env);
+
+    *result = match (cx.get(lhs), cx.get(rhs)) {
+        (Some(lhs), Some(rhs)) => lhs.strict_equals(rhs),
+        _ => return Status::InvalidArg,
+    };
+
+    Status::Ok
+}
+
+// The property key of a value, as JavaScript converts it
+unsafe fn key(cx: &MockEnv, key: Local) -> Option<String> {
+    cx.get(key).map(MockValue::to_js_string)
+}
+
+unsafe extern "C" fn get_property(
+    env: Env,
+    object: Local,
+    key: Local,
+    result: *mut Local,
+) -> Status {
+    let cx = cx(env);
+    let key = match self::key(cx, key) {
+        Some(key) => key,
+        None => return Status::InvalidArg,
+    };
+
+    let value = match cx.get(object) {
+        Some(MockValue::Undefined | MockValue::Null) | None => {
+            return cx.throw_type_error(&format!(
+                "Cannot read properties of a non-object ('{}')",
+                key
+            ));
+        }
+        Some(object) => object.get(&key),
+    };
+
+    *result = cx.push(value);
+    Status::Ok
+}
+
+unsafe extern "C" fn set_property(env: Env, object: Local, key: Local, value: Local) -> Status {
+    let cx = cx(env);
+    let (key, value) = match (self::key(cx, key), cx.get(value)) {
+        (Some(key), Some(value)) => (key, value.clone()),
+        _ => return Status::InvalidArg,
+    };
+
+    match cx.get_mut(object) {
+        Some(MockValue::Undefined | MockValue::Null) | None => cx.throw_type_error(&format!(
+            "Cannot set properties of a non-object ('{}')",
+            key
+        )),
+        // Errors are values, so their `name` and `message` are replaced in the
+        // handle itself
+        Some(MockValue::Error { name, message }) => {
+            match (key.as_str(), value) {
+                ("name", MockValue::String(value)) => *name = value,
+                ("message", MockValue::String(value)) => *message = value,
+                _ => {}
+            }
+
+            Status::Ok
+        }
+        Some(object) => {
+            object.set(&key, value);
+            Status::Ok
+        }
+    }
+}
+
+unsafe extern "C" fn has_property(
+    env: Env,
+    object: Local,
+    key: Local,
+    result: *mut bool,
+) -> Status {
+    let cx = cx(env);
+    let key = match self::key(cx, key) {
+        Some(key) => key,
+        None => return Status::InvalidArg,
+    };
+
+    *result = match cx.get(object) {
+        Some(MockValue::Object(properties)) => properties.borrow().contains_key(&key),
+        Some(MockValue::Array(elements)) => {
+            key == "length"
+                || key
+                    .parse::<usize>()
+                    .is_ok_and(|i| i < elements.borrow().len())
+        }
+        Some(MockValue::Error { .. }) => key == "name" || key == "message",
+        Some(MockValue::Function(_)) => false,
+        _ => return Status::ObjectExpected,
+    };
+
+    Status::Ok
+}
+
+unsafe extern "C" fn delete_property(
+    env: Env,
+    object: Local,
+    key: Local,
+    result: *mut bool,
+) -> Status {
+    let cx = cx(env);
+    let key = match self::key(cx, key) {
+        Some(key) => key,
+        None => return Status::InvalidArg,
+    };
+
+    if let Some(MockValue::Object(properties)) = cx.get(object) {
+        properties.borrow_mut().remove(&key);
+    }
+
+    if !result.is_null() {
+        *result = true;
+    }
+
+    Status::Ok
+}
+
+unsafe extern "C" fn get_element(
+    env: Env,
+    object: Local,
+    index: u32,
+    result: *mut Local,
+) -> Status {
+    let cx = cx(env);
+    let value = match cx.get(object) {
+        Some(MockValue::Undefined | MockValue::Null) | None => return Status::ObjectExpected,
+        Some(object) => object.get(&index.to_string()),
+    };
+
+    *result = cx.push(value);
+    Status::Ok
+}
+
+unsafe extern "C" fn set_element(env: Env, object: Local, index: u32, value: Local) -> Status {
+    let cx = cx(env);
+    let value = match cx.get(value) {
+        Some(value) => value.clone(),
+        None => return Status::InvalidArg,
+    };
+
+    match cx.get(object) {
+        Some(MockValue::Array(_) | MockValue::Object(_)) => {}
+        _ => return Status::ObjectExpected,
+    }
+
+    if let Some(object) = cx.get(object) {
+        object.set(&index.to_string(), value);
+    }
+
+    Status::Ok
+}
+
+// Every property is an own, enumerable string key, so the mode, filter and
+// conversion do not change the result
+unsafe extern "C" fn get_all_property_names(
+    env: Env,
+    object: Local,
+    _key_mode: u32,
+    _key_filter: u32,
+    _key_conversion: u32,
+    result: *mut Local,
+) -> Status {
+    let cx = cx(env);
+    let keys: Vec<MockValue> = match cx.get(object) {
+        Some(MockValue::Object(properties)) => properties
+            .borrow()
+            .keys()
+            .map(|key| MockValue::String(key.clone()))
+            .collect(),
+        Some(MockValue::Array(elements)) => (0..elements.borrow().len())
+            .map(|i| MockValue::String(i.to_string()))
+            .collect(),
+        Some(MockValue::Error { .. } | MockValue::Function(_)) => Vec::new(),
+        _ => return Status::ObjectExpected,
+    };
+
+    *result = cx.push(keys.into());
+    Status::Ok
+}
+
+// Objects are not frozen or sealed, which only matters to code that writes
+// to them afterwards
+unsafe extern "C" fn object_freeze(env: Env, object: Local) -> Status {
+    match cx(env).get(object) {
+        Some(
+            MockValue::Array(_)
+            | MockValue::Object(_)
+            | MockValue::Error { .. }
+            | MockValue::Function(_),
+        ) => Status::Ok,
+        _ => Status::ObjectExpected,
+    }
+}
+
+unsafe extern "C" fn call_function(
+    env: Env,
+    recv: Local,
+    func: Local,
+    argc: usize,
+    argv: *const Local,
+    result: *mut Local,
+) -> Status {
+    let cx = cx(env);
+    let function = match cx.get(func) {
+        Some(MockValue::Function(function)) => function.clone(),
+        Some(_) => return Status::FunctionExpected,
+        None => return Status::InvalidArg,
+    };
+
+    let this = match cx.get(recv) {
+        Some(this) => this.clone(),
+        None => return Status::InvalidArg,
+    };
+
+    let args = if argc == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(argv, argc)
+            .iter()
+            .map(|&arg| cx.get(arg).cloned().unwrap_or(MockValue::Undefined))
+            .collect()
+    };
+
+    match function.call(this, args) {
+        Ok(value) => {
+            *result = cx.push(value);
+            Status::Ok
+        }
+        Err(exception) => {
+            cx.exception = Some(exception);
+            Status::PendingException
+        }
+    }
+}
+
+unsafe extern "C" fn throw(env: Env, error: Local) -> Status {
+    let cx = cx(env);
+
+    match cx.get(error) {
+        Some(error) => {
+            cx.exception = Some(error.clone());
+            Status::Ok
+        }
+        None => Status::InvalidArg,
+    }
+}
+
+unsafe extern "C" fn is_exception_pending(env: Env, result: *mut bool) -> Status {
+    *result = cx(env).exception.is_some();
+    Status::Ok
+}
+
+unsafe extern "C" fn get_and_clear_last_exception(env: Env, result: *mut Local) -> Status {
+    let cx = cx(env);
+    let exception = cx.exception.take().unwrap_or(MockValue::Undefined);
+
+    *result = cx.push(exception);
+    Status::Ok
+}
+
+unsafe extern "C" fn fatal_error(
+    location: *const c_char,
+    location_len: usize,
+    message: *const c_char,
+    message_len: usize,
+) {
+    eprintln!(
+        "FATAL ERROR: {} {}",
+        string_arg(location, location_len),
+        string_arg(message, message_len),
+    );
+
+    std::process::abort()
+}
+
+unsafe extern "C" fn get_cb_info(
+    env: Env,
+    info: FunctionCallbackInfo,
+    argc: *mut usize,
+    argv: *mut Local,
+    this: *mut Local,
+    data: *mut *mut c_void,
+) -> Status {
+    let call = &*(info as *const MockCall);
+
+    if !argc.is_null() {
+        // Like N-API, `argv` is filled to its length with `undefined`
+        if !argv.is_null() {
+            for i in 0..*argc {
+                *argv.add(i) = match call.args.get(i) {
+                    Some(&arg) => arg,
+                    None => cx(env).push(MockValue::Undefined),
+                };
+            }
+        }
+
+        *argc = call.args.len();
+    }
+
+    if !this.is_null() {
+        *this = call.this;
+    }
+
+    if !data.is_null() {
+        *data = ptr::null_mut();
+    }
+
+    Status::Ok
+}
+
+// Mock calls are never constructor calls
+unsafe extern "C" fn get_new_target(
+    _env: Env,
+    _info: FunctionCallbackInfo,
+    result: *mut Local,
+) -> Status {
+    *result = ptr::null_mut();
+    Status::Ok
+}
+
+unsafe extern "C" fn open_handle_scope(env: Env, result: *mut *mut c_void) -> Status {
+    *result = (cx(env).mark() + 1) as *mut c_void;
+    Status::Ok
+}
+
+unsafe extern "C" fn close_handle_scope(env: Env, scope: *mut c_void) -> Status {
+    match (scope as usize).checked_sub(1) {
+        Some(mark) => {
+            cx(env).truncate(mark);
+            Status::Ok
+        }
+        None => Status::HandleScopeMismatch,
+    }
+}
+
+// The handle that `escape_handle` writes to is created before the scope
+unsafe extern "C" fn open_escapable_handle_scope(env: Env, result: *mut *mut c_void) -> Status {
+    let cx = cx(env);
+
+    cx.push(MockValue::Undefined);
+    *result = (cx.mark() + 1) as *mut c_void;
+    Status::Ok
+}
+
+unsafe extern "C" fn escape_handle(
+    env: Env,
+    scope: *mut c_void,
+    escapee: Local,
+    result: *mut Local,
+) -> Status {
+    let cx = cx(env);
+    let value = match cx.get(escapee) {
+        Some(value) => value.clone(),
+        None => return Status::InvalidArg,
+    };
+
+    // The escaped handle is the last one before the scope
+    let slot = (scope as usize - 1) as Local;
+
+    match cx.get_mut(slot) {
+        Some(escaped) => *escaped = value,
+        None => return Status::HandleScopeMismatch,
+    }
+
+    *result = slot;
+    Status::Ok
+}
+
+unsafe extern "C" fn create_reference(
+    env: Env,
+    value: Local,
+    initial_refcount: u32,
+    result: *mut Ref,
+) -> Status {
+    let cx = cx(env);
+    let value = match cx.get(value) {
+        Some(value) => value.clone(),
+        None => return Status::InvalidArg,
+    };
+
+    cx.references.push(Some((value, initial_refcount)));
+    *result = cx.references.len() as Ref;
+    Status::Ok
+}
+
+unsafe fn reference(cx: &mut MockEnv, reference: Ref) -> Option<&mut (MockValue, u32)> {
+    (reference as usize)
+        .checked_sub(1)
+        .and_then(move |i| cx.references.get_mut(i))
+        .and_then(Option::as_mut)
+}
+
+unsafe extern "C" fn reference_ref(env: Env, reference: Ref, result: *mut u32) -> Status {
+    match self::reference(cx(env), reference) {
+        Some((_, count)) => {
+            *count += 1;
+
+            if !result.is_null() {
+                *result = *count;
+            }
+
+            Status::Ok
+        }
+        None => Status::InvalidArg,
+    }
+}
+
+unsafe extern "C" fn reference_unref(env: Env, reference: Ref, result: *mut u32) -> Status {
+    match self::reference(cx(env), reference) {
+        Some((_, count)) if *count > 0 => {
+            *count -= 1;
+
+            if !result.is_null() {
+                *result = *count;
+            }
+
+            Status::Ok
+        }
+        _ => Status::GenericFailure,
+    }
+}
+
+unsafe extern "C" fn delete_reference(env: Env, reference: Ref) -> Status {
+    let cx = cx(env);
+
+    match (reference as usize)
+        .checked_sub(1)
+        .and_then(|i| cx.references.get_mut(i))
+    {
+        Some(reference @ Some(_)) => {
+            *reference = None;
+            Status::Ok
+        }
+        _ => Status::InvalidArg,
+    }
+}
+
+// Weak references are never collected
+unsafe extern "C" fn get_reference_value(env: Env, reference: Ref, result: *mut Local) -> Status {
+    let cx = cx(env);
+    let value = match self::reference(cx, reference) {
+        Some((value, _)) => value.clone(),
+        None => return Status::InvalidArg,
+    };
+
+    *result = cx.push(value);
+    Status::Ok
+}
+
+unsafe extern "C" fn set_instance_data(
+    env: Env,
+    data: *mut c_void,
+    finalize_cb: Finalize,
+    finalize_hint: *mut c_void,
+) -> Status {
+    cx(env).instance_data = Some((data, finalize_cb, finalize_hint));
+    Status::Ok
+}
+
+unsafe extern "C" fn get_instance_data(env: Env, data: *mut *mut c_void) -> Status {
+    *data = match cx(env).instance_data {
+        Some((data, _, _)) => data,
+        None => ptr::null_mut(),
+    };
+
+    Status::Ok
+}
+
+unsafe extern "C" fn adjust_external_memory(
+    env: Env,
+    change_in_bytes: i64,
+    adjusted_value: *mut i64,
+) -> Status {
+    let cx = cx(env);
+
+    cx.external_memory += change_in_bytes;
+
+    if !adjusted_value.is_null() {
+        *adjusted_value = cx.external_memory;
+    }
+
+    Status::Ok
+}
+
+/// A threadsafe function, whose calls are queued until [`run_pending`] and
+/// which may be called from any thread.
+struct Tsfn {
+    state: Mutex<TsfnState>,
+    context: *mut c_void,
+    call_js: CallJs,
+    finalize: Finalize,
+    finalize_data: *mut c_void,
+    finalize_hint: *mut c_void,
+}
+
+struct TsfnState {
+    // The data of each call, as addresses so that the state is `Send`
+    queue: VecDeque<usize>,
+    threads: usize,
+    closing: bool,
+}
+
+unsafe extern "C" fn create_threadsafe_function(
+    env: Env,
+    _func: Local,
+    _async_resource: Local,
+    _async_resource_name: Local,
+    _max_queue_size: usize,
+    initial_thread_count: usize,
+    thread_finalize_data: *mut c_void,
+    thread_finalize_cb: Finalize,
+    context: *mut c_void,
+    call_js_cb: CallJs,
+    result: *mut *mut c_void,
+) -> Status {
+    let tsfn = Box::into_raw(Box::new(Tsfn {
+        state: Mutex::new(TsfnState {
+            queue: VecDeque::new(),
+            threads: initial_thread_count,
+            closing: false,
+        }),
+        context,
+        call_js: call_js_cb,
+        finalize: thread_finalize_cb,
+        finalize_data: thread_finalize_data,
+        finalize_hint: ptr::null_mut(),
+    }));
+
+    cx(env).tsfns.push(tsfn);
+    *result = tsfn.cast();
+    Status::Ok
+}
+
+unsafe extern "C" fn call_threadsafe_function(
+    func: *mut c_void,
+    data: *mut c_void,
+    _is_blocking: u32,
+) -> Status {
+    let tsfn = &*(func as *const Tsfn);
+
+    match tsfn.state.lock() {
+        Ok(state) if state.closing => Status::Closing,
+        Ok(mut state) => {
+            state.queue.push_back(data as usize);
+            Status::Ok
+        }
+        Err(_) => Status::GenericFailure,
+    }
+}
+
+unsafe extern "C" fn acquire_threadsafe_function(func: *mut c_void) -> Status {
+    let tsfn = &*(func as *const Tsfn);
+
+    match tsfn.state.lock() {
+        Ok(state) if state.closing => Status::Closing,
+        Ok(mut state) => {
+            state.threads += 1;
+            Status::Ok
+        }
+        Err(_) => Status::GenericFailure,
+    }
+}
+
+// The function is finalized by `run_pending` once every thread released it
+unsafe extern "C" fn release_threadsafe_function(func: *mut c_void, mode: u32) -> Status {
+    let tsfn = &*(func as *const Tsfn);
+
+    match tsfn.state.lock() {
+        Ok(mut state) => {
+            state.threads = state.threads.saturating_sub(1);
+
+            if mode == RELEASE_ABORT {
+                state.closing = true;
+                state.threads = 0;
+            }
+
+            if state.threads == 0 {
+                state.closing = true;
+            }
+
+            Status::Ok
+        }
+        Err(_) => Status::GenericFailure,
+    }
+}
+
+// There is no event loop to keep running
+unsafe extern "C" fn ref_threadsafe_function(_env: Env, _func: *mut c_void) -> Status {
+    Status::Ok
+}
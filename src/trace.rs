@@ -0,0 +1,208 @@
+//! Spans around calls that cross the boundary between JavaScript and Rust.
+//!
+//! With the `tracing` feature enabled, calls into exported functions,
+//! closures sent through a `Channel` and tasks on the worker pool are each
+//! run in a `DEBUG` span with the target `neon`. Channel closures and tasks
+//! record how long they waited in the queue before running. Without the
+//! feature, every type here is empty and every function does nothing.
+
+#[cfg(all(
+    feature = "tracing",
+    any(all(feature = "napi-4", feature = "channel-api"), feature = "task-api")
+))]
+use std::time::Instant;
+
+/// A span that is exited when dropped.
+pub struct Entered {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+/// The time a closure or task was queued, for measuring the latency until it
+/// runs.
+#[cfg(any(all(feature = "napi-4", feature = "channel-api"), feature = "task-api"))]
+#[derive(Clone, Copy)]
+pub(crate) struct Queued {
+    #[cfg(feature = "tracing")]
+    at: Instant,
+}
+
+#[cfg(any(all(feature = "napi-4", feature = "channel-api"), feature = "task-api"))]
+impl Queued {
+    pub(crate) fn now() -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            at: Instant::now(),
+        }
+    }
+
+    /// Enters the span of a closure sent through a `Channel`.
+    #[cfg(all(feature = "napi-4", feature = "channel-api"))]
+    pub(crate) fn channel(self) -> Entered {
+        Entered {
+            #[cfg(feature = "tracing")]
+            _span: tracing::debug_span!(
+                target: "neon",
+                "channel",
+                queue_latency_us = self.latency_us(),
+            )
+            .entered(),
+        }
+    }
+
+    /// Enters the span of a task executing on the worker pool.
+    #[cfg(feature = "task-api")]
+    pub(crate) fn task(self) -> Entered {
+        Entered {
+            #[cfg(feature = "tracing")]
+            _span: tracing::debug_span!(
+                target: "neon",
+                "task",
+                queue_latency_us = self.latency_us(),
+            )
+            .entered(),
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn latency_us(&self) -> u64 {
+        self.at.elapsed().as_micros() as u64
+    }
+}
+
+/// Enters the span of a call to the exported function `name`.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn export(name: &'static str) -> Entered {
+    Entered {
+        #[cfg(feature = "tracing")]
+        _span: tracing::debug_span!(target: "neon", "export", function = name).entered(),
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::fmt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // A span that was opened, with the values of its fields
+    #[derive(Debug)]
+    struct Opened {
+        target: String,
+        name: &'static str,
+        fields: Vec<(&'static str, String)>,
+    }
+
+    impl Opened {
+        fn field(&self, name: &str) -> Option<&str> {
+            self.fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    impl Visit for Opened {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields.push((field.name(), format!("{:?}", value)));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.push((field.name(), value.to_string()));
+        }
+    }
+
+    // Records every span that is opened
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<Opened>>>,
+        next_id: Arc<AtomicU64>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut opened = Opened {
+                target: span.metadata().target().to_string(),
+                name: span.metadata().name(),
+                fields: Vec::new(),
+            };
+
+            span.record(&mut opened);
+            self.spans.lock().unwrap().push(opened);
+
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event) {}
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    // Runs `f` and returns the one span it opened
+    fn opened_by(f: impl FnOnce()) -> Opened {
+        let recorder = Recorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), f);
+
+        let mut spans = recorder.spans.lock().unwrap();
+
+        assert_eq!(spans.len(), 1, "expected exactly one span");
+        spans.remove(0)
+    }
+
+    #[cfg(any(all(feature = "napi-4", feature = "channel-api"), feature = "task-api"))]
+    // Waits in the queue for at least a millisecond
+    fn queued_for_a_millisecond() -> super::Queued {
+        let queued = super::Queued::now();
+
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        queued
+    }
+
+    #[test]
+    fn export_span_names_the_function() {
+        let span = opened_by(|| drop(super::export("add")));
+
+        assert_eq!(span.target, "neon");
+        assert_eq!(span.name, "export");
+        assert_eq!(span.field("function"), Some("add"));
+    }
+
+    #[cfg(all(feature = "napi-4", feature = "channel-api"))]
+    #[test]
+    fn channel_span_records_queue_latency() {
+        let queued = queued_for_a_millisecond();
+        let span = opened_by(|| drop(queued.channel()));
+        let latency = span.field("queue_latency_us").unwrap().parse::<u64>();
+
+        assert_eq!(span.target, "neon");
+        assert_eq!(span.name, "channel");
+        assert!(latency.unwrap() >= 1000);
+    }
+
+    #[cfg(feature = "task-api")]
+    #[test]
+    fn task_span_records_queue_latency() {
+        let queued = queued_for_a_millisecond();
+        let span = opened_by(|| drop(queued.task()));
+        let latency = span.field("queue_latency_us").unwrap().parse::<u64>();
+
+        assert_eq!(span.target, "neon");
+        assert_eq!(span.name, "task");
+        assert!(latency.unwrap() >= 1000);
+    }
+}
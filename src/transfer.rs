@@ -0,0 +1,259 @@
+//! Moving JavaScript values between environments.
+//!
+//! A [`Handle`] is only valid in the JavaScript environment that created it.
+//! To share a value with another environment, for example a
+//! [worker thread][workers] that loaded the same module, the value must be
+//! copied. [`Serialized`] copies a value with the [structured clone][clone]
+//! algorithm into bytes that can be sent to another thread and deserialized
+//! any number of times.
+//!
+//! ```
+//! # #[cfg(all(feature = "napi-4", feature = "channel-api"))] {
+//! # use neon::prelude::*;
+//! use neon::transfer::Serialized;
+//!
+//! fn clone_later(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+//!     let value = cx.argument::<JsValue>(0)?;
+//!     let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+//!     let value = Serialized::new(&mut cx, value)?;
+//!     let channel = cx.channel();
+//!
+//!     std::thread::spawn(move || {
+//!         channel.send(move |mut cx| {
+//!             let callback = callback.into_inner(&mut cx);
+//!             let this = cx.undefined();
+//!             let value = value.deserialize(&mut cx)?;
+//!
+//!             callback.call(&mut cx, this, vec![value])?;
+//!
+//!             Ok(())
+//!         });
+//!     });
+//!
+//!     Ok(cx.undefined())
+//! }
+//! # }
+//! ```
+//!
+//! Serialization uses the [`v8`][v8] module of Node.js and supports the same
+//! values as [`v8.serialize()`][serialize]. In ES modules and worker threads,
+//! the module is loaded with `process.getBuiltinModule`, which requires
+//! Node.js 20.16 or later.
+//!
+//! [workers]: https://nodejs.org/api/worker_threads.html
+//! [clone]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API/Structured_clone_algorithm
+//! [v8]: https://nodejs.org/api/v8.html#v8_serialization_api
+//! [serialize]: https://nodejs.org/api/v8.html#v8_v8_serialize_value
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{ByteBuf, TryFromJs};
+use crate::types::{JsArrayBuffer, JsBuffer, JsFunction, JsObject, JsValue, Value};
+
+/// A JavaScript value serialized with the structured clone algorithm.
+///
+/// A `Serialized` value is `Send` and may be deserialized in any environment,
+/// any number of times.
+#[derive(Clone, Debug)]
+pub struct Serialized {
+    data: Vec<u8>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl Serialized {
+    /// Serializes `value`, throwing a `DataCloneError` if it contains values
+    /// that cannot be cloned, such as functions.
+    pub fn new<'a, C, V>(cx: &mut C, value: Handle<V>) -> NeonResult<Self>
+    where
+        C: Context<'a>,
+        V: Value,
+    {
+        Self::serialize(cx, value, &[])
+    }
+
+    #[cfg(feature = "napi-7")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-7")))]
+    /// Serializes `value`, transferring each `ArrayBuffer` in `transfer`.
+    ///
+    /// Like `postMessage`, the contents of transferred buffers are moved out
+    /// of the current environment: they are stored once, no matter how many
+    /// times they are referenced by `value`, and each buffer is detached once
+    /// `value` has been serialized. Throws if a buffer cannot be detached,
+    /// e.g., because it is backed by `WebAssembly.Memory`.
+    pub fn with_transfer<'a, 'b, C, V>(
+        cx: &mut C,
+        value: Handle<'b, V>,
+        transfer: &[Handle<'a, JsArrayBuffer>],
+    ) -> NeonResult<Self>
+    where
+        C: Context<'a>,
+        V: Value,
+    {
+        let serialized = Self::serialize(cx, value, transfer)?;
+
+        for buffer in transfer {
+            buffer.detach(cx)?;
+        }
+
+        Ok(serialized)
+    }
+
+    // Serializes `value`, copying the contents of the buffers in `transfer`
+    // out of band
+    fn serialize<'a, 'b, C, V>(
+        cx: &mut C,
+        value: Handle<'b, V>,
+        transfer: &[Handle<'a, JsArrayBuffer>],
+    ) -> NeonResult<Self>
+    where
+        C: Context<'a>,
+        V: Value,
+    {
+        let serializer = construct(cx, "DefaultSerializer", vec![])?;
+
+        call_method(cx, serializer, "writeHeader", vec![])?;
+
+        let mut buffers = Vec::with_capacity(transfer.len());
+
+        for (id, &buffer) in transfer.iter().enumerate() {
+            let id = cx.number(id as f64).upcast();
+
+            call_method(
+                cx,
+                serializer,
+                "transferArrayBuffer",
+                vec![id, buffer.upcast()],
+            )?;
+            buffers.push(ByteBuf::from_js(cx, buffer.upcast())?.0);
+        }
+
+        call_method(cx, serializer, "writeValue", vec![value.upcast()])?;
+
+        let data = call_method(cx, serializer, "releaseBuffer", vec![])?;
+        let data = ByteBuf::from_js(cx, data)?.0;
+
+        Ok(Self { data, buffers })
+    }
+
+    /// Creates a copy of the serialized value in the current environment.
+    pub fn deserialize<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsValue> {
+        let data = JsBuffer::external(cx, self.data.clone()).upcast();
+        let deserializer = construct(cx, "DefaultDeserializer", vec![data])?;
+
+        call_method(cx, deserializer, "readHeader", vec![])?;
+
+        for (id, buffer) in self.buffers.iter().enumerate() {
+            let id = cx.number(id as f64).upcast();
+            let buffer = JsArrayBuffer::external(cx, buffer.clone()).upcast();
+
+            call_method(cx, deserializer, "transferArrayBuffer", vec![id, buffer])?;
+        }
+
+        call_method(cx, deserializer, "readValue", vec![])
+    }
+
+    /// The serialized data, excluding transferred buffers. It is compatible
+    /// with [`v8.deserialize()`][deserialize] if no buffers were transferred.
+    ///
+    /// [deserialize]: https://nodejs.org/api/v8.html#v8_v8_deserialize_buffer
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Clones `value` with the global `structuredClone`, moving each value in
+/// `transfer` to the clone. Falls back to serializing with the `v8` module on
+/// versions of Node.js without `structuredClone`, which cannot transfer values.
+pub(crate) fn structured_clone<'a, 'b, C, V>(
+    cx: &mut C,
+    value: Handle<'b, V>,
+    transfer: &[Handle<'b, JsValue>],
+) -> JsResult<'a, JsValue>
+where
+    C: Context<'a>,
+    V: Value,
+{
+    let global = cx.global();
+    let clone = global.get(cx, "structuredClone")?;
+
+    let clone = match clone.downcast::<JsFunction, _>(cx) {
+        Ok(clone) => clone,
+        Err(_) if transfer.is_empty() => return Serialized::new(cx, value)?.deserialize(cx),
+        Err(_) => return cx.throw_error("`structuredClone` is not available to transfer values"),
+    };
+
+    let options = cx.empty_object();
+    let list = cx.empty_array();
+
+    for (i, &item) in transfer.iter().enumerate() {
+        list.set(cx, i as u32, item)?;
+    }
+
+    options.set(cx, "transfer", list)?;
+    clone.call(
+        cx,
+        global,
+        vec![value.upcast::<JsValue>(), options.upcast()],
+    )
+}
+
+// Constructs a class exported by the `v8` module
+fn construct<'a, C: Context<'a>>(
+    cx: &mut C,
+    class: &str,
+    args: Vec<Handle<'a, JsValue>>,
+) -> JsResult<'a, JsObject> {
+    let v8 = v8(cx)?;
+    let class = v8.get(cx, class)?.downcast_or_throw::<JsFunction, _>(cx)?;
+
+    class.construct(cx, args)
+}
+
+fn call_method<'a, 'b, C: Context<'a>>(
+    cx: &mut C,
+    object: Handle<'b, JsObject>,
+    name: &str,
+    args: Vec<Handle<'b, JsValue>>,
+) -> JsResult<'a, JsValue> {
+    let method = object
+        .get(cx, name)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+
+    method.call(cx, object, args)
+}
+
+// Loads the `v8` module with `process.getBuiltinModule`, which works in ES
+// modules and worker threads. Versions of Node.js without it can only load
+// the module with the `require` of the main module, which only exists on the
+// main thread of a CommonJS program.
+fn v8<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsObject> {
+    let process = cx
+        .global()
+        .get(cx, "process")?
+        .downcast_or_throw::<JsObject, _>(cx)?;
+    let name = cx.string("v8").upcast::<JsValue>();
+
+    if let Ok(load) = process
+        .get(cx, "getBuiltinModule")?
+        .downcast::<JsFunction, _>(cx)
+    {
+        return load.call(cx, process, vec![name])?.downcast_or_throw(cx);
+    }
+
+    let main = process.get(cx, "mainModule")?;
+
+    if let Ok(main) = main.downcast::<JsObject, _>(cx) {
+        let require = main
+            .get(cx, "require")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        return require.call(cx, main, vec![name])?.downcast_or_throw(cx);
+    }
+
+    cx.throw_error(
+        "serializing values in ES modules and worker threads requires \
+         `process.getBuiltinModule` (Node.js 20.16 or later)",
+    )
+}
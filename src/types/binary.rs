@@ -7,15 +7,29 @@ use crate::context::{Context, Lock};
 #[cfg(feature = "napi-1")]
 use crate::handle::Handle;
 use crate::handle::Managed;
+#[cfg(feature = "napi-7")]
+use crate::handle::Root;
 use crate::result::JsResult;
+#[cfg(feature = "napi-1")]
+use crate::result::JsResultExt;
+#[cfg(feature = "napi-1")]
+use crate::result::NeonResult;
 use crate::types::internal::ValueInternal;
 use crate::types::{build, Object, Value};
+#[cfg(feature = "napi-1")]
+use crate::types::{JsBoolean, JsFunction, JsNumber, JsString, JsValue};
 use neon_runtime;
 use neon_runtime::raw;
+#[cfg(feature = "napi-1")]
+use std::borrow::Cow;
+#[cfg(feature = "napi-7")]
+use std::fmt;
 use std::marker::PhantomData;
 use std::mem::{self, MaybeUninit};
 use std::os::raw::c_void;
 use std::slice;
+#[cfg(feature = "napi-7")]
+use std::sync::{Arc, Mutex};
 
 /// The Node [`Buffer`](https://nodejs.org/api/buffer.html) type.
 #[repr(C)]
@@ -44,6 +58,9 @@ impl JsBuffer {
 
     #[cfg(feature = "napi-1")]
     /// Construct a new `Buffer` from bytes allocated by Rust
+    ///
+    /// Hosts that do not allow external buffers, such as Electron 21 and later
+    /// with the V8 memory cage, get a `Buffer` with a copy of the bytes instead.
     pub fn external<'a, C, T>(cx: &mut C, data: T) -> Handle<'a, JsBuffer>
     where
         C: Context<'a>,
@@ -56,6 +73,83 @@ impl JsBuffer {
     }
 }
 
+/// How [`Context::decode_utf8`](crate::context::Context::decode_utf8)
+/// handles bytes that are not valid UTF-8.
+#[cfg(feature = "napi-1")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidUtf8 {
+    /// Replace each invalid sequence with U+FFFD, like `TextDecoder`.
+    Replace,
+    /// Throw a `TypeError`, like `TextDecoder` with `fatal: true`.
+    Throw,
+}
+
+#[cfg(feature = "napi-1")]
+pub(crate) fn encode_utf8<'a, C: Context<'a>>(
+    cx: &mut C,
+    s: Handle<JsString>,
+) -> JsResult<'a, JsBuffer> {
+    let env = cx.env().to_raw();
+    let len = unsafe { neon_runtime::string::utf8_len(env, s.to_raw()) as usize };
+
+    if len == 0 {
+        return JsBuffer::new(cx, 0);
+    }
+
+    // Includes space for the null terminator written by N-API
+    let mut bytes = Vec::<u8>::with_capacity(len + 1);
+
+    unsafe {
+        let written = neon_runtime::string::data(
+            env,
+            bytes.as_mut_ptr(),
+            bytes.capacity() as isize,
+            s.to_raw(),
+        );
+
+        bytes.set_len(written as usize);
+    }
+
+    Ok(JsBuffer::external(cx, bytes))
+}
+
+#[cfg(feature = "napi-1")]
+pub(crate) fn decode_utf8<'a, C: Context<'a>>(
+    cx: &mut C,
+    buffer: Handle<JsBuffer>,
+    invalid: InvalidUtf8,
+) -> JsResult<'a, JsString> {
+    let env = cx.env().to_raw();
+
+    // The contents of a `Buffer` are not moved by the garbage collector, so
+    // they may be read while creating the string
+    let bytes = unsafe {
+        let mut base = std::ptr::null_mut();
+        let len = neon_runtime::buffer::data(env, &mut base, buffer.to_raw());
+
+        if len == 0 {
+            &[][..]
+        } else {
+            slice::from_raw_parts(base as *const u8, len)
+        }
+    };
+
+    let s = match invalid {
+        InvalidUtf8::Replace => String::from_utf8_lossy(bytes),
+        InvalidUtf8::Throw => match std::str::from_utf8(bytes) {
+            Ok(s) => Cow::Borrowed(s),
+            Err(err) => {
+                return cx.throw_type_error(format!(
+                    "invalid UTF-8 sequence at byte {}",
+                    err.valid_up_to()
+                ))
+            }
+        },
+    };
+
+    JsString::try_new(cx, s).or_throw(cx)
+}
+
 impl Managed for JsBuffer {
     fn to_raw(self) -> raw::Local {
         self.0
@@ -95,6 +189,10 @@ impl JsArrayBuffer {
 
     #[cfg(feature = "napi-1")]
     /// Construct a new `ArrayBuffer` from bytes allocated by Rust
+    ///
+    /// Hosts that do not allow external buffers, such as Electron 21 and later
+    /// with the V8 memory cage, get an `ArrayBuffer` with a copy of the bytes
+    /// instead.
     pub fn external<'a, C, T>(cx: &mut C, data: T) -> Handle<'a, JsArrayBuffer>
     where
         C: Context<'a>,
@@ -105,6 +203,242 @@ impl JsArrayBuffer {
 
         Handle::new_internal(JsArrayBuffer(value))
     }
+
+    #[cfg(feature = "napi-7")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-7")))]
+    /// Lends bytes owned by Rust to JavaScript as an `ArrayBuffer`, without
+    /// copying them. The returned [`LentBuffer`] takes the bytes back with
+    /// [`reclaim`](LentBuffer::reclaim), which detaches the `ArrayBuffer`.
+    ///
+    /// If the `LentBuffer` is dropped instead, the bytes are dropped after the
+    /// `ArrayBuffer` is garbage collected, like with
+    /// [`external`](JsArrayBuffer::external).
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-7")] {
+    /// # use neon::prelude::*;
+    /// fn checksum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let hash = cx.argument::<JsFunction>(0)?;
+    ///     let (buf, lent) = JsArrayBuffer::lend(&mut cx, vec![1u8, 2, 3]);
+    ///     let this = cx.undefined();
+    ///     let sum = hash
+    ///         .call(&mut cx, this, [buf])?
+    ///         .downcast_or_throw::<JsNumber, _>(&mut cx)?;
+    ///
+    ///     // JavaScript can no longer read the bytes
+    ///     let bytes = lent.reclaim(&mut cx)?;
+    ///     # drop(bytes);
+    ///
+    ///     Ok(sum)
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Mutable aliasing
+    ///
+    /// The bytes are lent mutably, not read-only: Node-API has no read-only
+    /// `ArrayBuffer`, and checking for writes would need the copy that lending
+    /// avoids. JavaScript may write to the `ArrayBuffer` until it is
+    /// reclaimed, and the writes are visible in the reclaimed bytes, which is
+    /// why `T` must implement `AsMut<[u8]>`. Rust cannot observe the bytes
+    /// while they are lent, since `LentBuffer` gives no access to them.
+    ///
+    /// The runtime checks are in [`reclaim`](LentBuffer::reclaim), which
+    /// detaches the `ArrayBuffer` before returning the bytes, so that
+    /// JavaScript cannot write to them afterwards, and refuses to return bytes
+    /// that JavaScript detached and may still be using.
+    ///
+    /// On hosts that do not allow external buffers, JavaScript gets a copy of
+    /// the bytes and writes are not visible.
+    pub fn lend<'a, C, T>(cx: &mut C, data: T) -> (Handle<'a, JsArrayBuffer>, LentBuffer<T>)
+    where
+        C: Context<'a>,
+        T: AsMut<[u8]> + Send + 'static,
+    {
+        // Boxed, so the bytes do not move when `T` is taken back out
+        let mut data = Box::new(data);
+        let bytes = (*data).as_mut();
+        let (ptr, len) = (bytes.as_mut_ptr(), bytes.len());
+        let slot = Arc::new(Mutex::new(Some(data)));
+        let lent = Lent {
+            _slot: slot.clone(),
+            ptr,
+            len,
+        };
+
+        let buffer = JsArrayBuffer::external(cx, lent);
+        let root = buffer.root(cx);
+
+        (buffer, LentBuffer { buffer: root, slot })
+    }
+
+    #[cfg(feature = "napi-7")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-7")))]
+    /// Detaches the `ArrayBuffer`, making its contents inaccessible to
+    /// JavaScript and its length zero. Throws if the `ArrayBuffer` cannot be
+    /// detached, e.g., because it is backed by `WebAssembly.Memory`.
+    pub fn detach<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<()> {
+        let env = cx.env().to_raw();
+
+        if unsafe { neon_runtime::arraybuffer::detach(env, self.to_raw()) } {
+            Ok(())
+        } else {
+            cx.throw_type_error("ArrayBuffer could not be detached")
+        }
+    }
+
+    #[cfg(feature = "napi-7")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-7")))]
+    /// Returns `true` if the `ArrayBuffer` has been detached.
+    pub fn is_detached<'a, C: Context<'a>>(self, cx: &mut C) -> bool {
+        unsafe { neon_runtime::arraybuffer::is_detached(cx.env().to_raw(), self.to_raw()) }
+    }
+}
+
+#[cfg(feature = "napi-7")]
+type LentSlot<T> = Arc<Mutex<Option<Box<T>>>>;
+
+#[cfg(feature = "napi-7")]
+// The bytes of a lent `ArrayBuffer`, kept alive until it is garbage collected
+// or the owner of the slot takes them back after detaching it
+struct Lent<T> {
+    _slot: LentSlot<T>,
+    ptr: *mut u8,
+    len: usize,
+}
+
+#[cfg(feature = "napi-7")]
+// Safety: The bytes are only accessed through `ptr` while JavaScript holds
+// the `ArrayBuffer`, and `T` is `Send`
+unsafe impl<T: Send> Send for Lent<T> {}
+
+#[cfg(feature = "napi-7")]
+impl<T> AsMut<[u8]> for Lent<T> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        if self.len == 0 {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+}
+
+#[cfg(feature = "napi-7")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-7")))]
+/// Bytes lent to JavaScript with [`JsArrayBuffer::lend`].
+pub struct LentBuffer<T> {
+    buffer: Root<JsArrayBuffer>,
+    slot: LentSlot<T>,
+}
+
+#[cfg(feature = "napi-7")]
+impl<T> LentBuffer<T> {
+    /// Returns the `ArrayBuffer` holding the lent bytes.
+    pub fn buffer<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsArrayBuffer> {
+        self.buffer.to_inner(cx)
+    }
+
+    /// Detaches the `ArrayBuffer` and takes back the lent bytes.
+    ///
+    /// Throws if the `ArrayBuffer` was already detached by JavaScript, e.g.,
+    /// by transferring it to a worker. The bytes may still be in use in that
+    /// case, so they are never dropped.
+    pub fn reclaim<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<T> {
+        let buffer = self.buffer.into_inner(cx);
+
+        if buffer.is_detached(cx) {
+            std::mem::forget(self.slot);
+
+            return cx.throw_error("ArrayBuffer was detached before it was reclaimed");
+        }
+
+        buffer.detach(cx)?;
+
+        let data = self
+            .slot
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+            .expect("lent bytes are only reclaimed once");
+
+        Ok(*data)
+    }
+}
+
+#[cfg(feature = "napi-7")]
+impl<T> fmt::Debug for LentBuffer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LentBuffer").finish()
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl JsArrayBuffer {
+    /// Constructs a resizable `ArrayBuffer` of `len` bytes, which can be
+    /// resized up to `max_len` bytes with [`resize`](JsArrayBuffer::resize).
+    ///
+    /// Throws a `TypeError` on hosts without resizable `ArrayBuffer`s, e.g.,
+    /// Node.js versions before 20.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn growing(mut cx: FunctionContext) -> JsResult<JsArrayBuffer> {
+    ///     let buf = JsArrayBuffer::resizable(&mut cx, 0, 1024)?;
+    ///
+    ///     buf.resize(&mut cx, 16)?;
+    ///
+    ///     Ok(buf)
+    /// }
+    /// # }
+    /// ```
+    pub fn resizable<'a, C: Context<'a>>(
+        cx: &mut C,
+        len: usize,
+        max_len: usize,
+    ) -> JsResult<'a, JsArrayBuffer> {
+        let buf = construct_resizable(cx, "ArrayBuffer", len, max_len)?;
+        let buf = buf.downcast_or_throw::<JsArrayBuffer, _>(cx)?;
+
+        if !buf.is_resizable(cx)? {
+            return cx.throw_type_error("resizable ArrayBuffers are not supported");
+        }
+
+        Ok(buf)
+    }
+
+    /// Returns the length of the `ArrayBuffer`, in bytes.
+    pub fn byte_length<'a, C: Context<'a>>(self, cx: &mut C) -> usize {
+        let mut base = std::ptr::null_mut();
+
+        unsafe { neon_runtime::arraybuffer::data(cx.env().to_raw(), &mut base, self.to_raw()) }
+    }
+
+    /// Returns `true` if the `ArrayBuffer` was constructed with a maximum
+    /// length and can be resized.
+    pub fn is_resizable<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<bool> {
+        flag(cx, self, "resizable")
+    }
+
+    /// Returns the maximum length the `ArrayBuffer` can be resized to, in
+    /// bytes. This is the length of an `ArrayBuffer` that is not resizable.
+    pub fn max_byte_length<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<usize> {
+        match length(cx, self, "maxByteLength")? {
+            Some(len) => Ok(len),
+            None => Ok(self.byte_length(cx)),
+        }
+    }
+
+    /// Resizes the `ArrayBuffer` to `len` bytes. Growing fills the new bytes
+    /// with zeros. Throws a `TypeError` if the `ArrayBuffer` is not resizable
+    /// or a `RangeError` if `len` is greater than its maximum length.
+    pub fn resize<'a, C: Context<'a>>(self, cx: &mut C, len: usize) -> NeonResult<()> {
+        let len = cx.number(len as f64);
+
+        self.method(cx, "resize")?.arg(len).call(cx)?;
+
+        Ok(())
+    }
 }
 
 impl Managed for JsArrayBuffer {
@@ -131,6 +465,154 @@ impl Value for JsArrayBuffer {}
 
 impl Object for JsArrayBuffer {}
 
+/// The standard JS [`SharedArrayBuffer`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/SharedArrayBuffer) type.
+///
+/// Node-API does not give access to the contents of a `SharedArrayBuffer`,
+/// so it can only be constructed, passed to JavaScript, and grown.
+#[cfg(feature = "napi-1")]
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct JsSharedArrayBuffer(raw::Local);
+
+#[cfg(feature = "napi-1")]
+impl JsSharedArrayBuffer {
+    /// Constructs a new `SharedArrayBuffer` of `len` bytes.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, len: usize) -> JsResult<'a, JsSharedArrayBuffer> {
+        let class = cx
+            .global()
+            .get(cx, "SharedArrayBuffer")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+        let len = cx.number(len as f64);
+
+        class.construct(cx, [len])?.downcast_or_throw(cx)
+    }
+
+    /// Constructs a growable `SharedArrayBuffer` of `len` bytes, which can
+    /// grow up to `max_len` bytes with [`grow`](JsSharedArrayBuffer::grow).
+    ///
+    /// Throws a `TypeError` on hosts without growable `SharedArrayBuffer`s,
+    /// e.g., Node.js versions before 20.
+    pub fn growable<'a, C: Context<'a>>(
+        cx: &mut C,
+        len: usize,
+        max_len: usize,
+    ) -> JsResult<'a, JsSharedArrayBuffer> {
+        let buf = construct_resizable(cx, "SharedArrayBuffer", len, max_len)?;
+        let buf = buf.downcast_or_throw::<JsSharedArrayBuffer, _>(cx)?;
+
+        if !buf.is_growable(cx)? {
+            return cx.throw_type_error("growable SharedArrayBuffers are not supported");
+        }
+
+        Ok(buf)
+    }
+
+    /// Returns the length of the `SharedArrayBuffer`, in bytes.
+    pub fn byte_length<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<usize> {
+        Ok(length(cx, self, "byteLength")?.unwrap_or(0))
+    }
+
+    /// Returns `true` if the `SharedArrayBuffer` was constructed with a
+    /// maximum length and can grow.
+    pub fn is_growable<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<bool> {
+        flag(cx, self, "growable")
+    }
+
+    /// Returns the maximum length the `SharedArrayBuffer` can grow to, in
+    /// bytes. This is the length of a `SharedArrayBuffer` that is not
+    /// growable.
+    pub fn max_byte_length<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<usize> {
+        match length(cx, self, "maxByteLength")? {
+            Some(len) => Ok(len),
+            None => self.byte_length(cx),
+        }
+    }
+
+    /// Grows the `SharedArrayBuffer` to `len` bytes. Throws a `TypeError` if
+    /// it is not growable or a `RangeError` if `len` is less than its length
+    /// or greater than its maximum length.
+    pub fn grow<'a, C: Context<'a>>(self, cx: &mut C, len: usize) -> NeonResult<()> {
+        let len = cx.number(len as f64);
+
+        self.method(cx, "grow")?.arg(len).call(cx)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl Managed for JsSharedArrayBuffer {
+    fn to_raw(self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_env: Env, h: raw::Local) -> Self {
+        JsSharedArrayBuffer(h)
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl ValueInternal for JsSharedArrayBuffer {
+    fn name() -> String {
+        "SharedArrayBuffer".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_shared_arraybuffer(env.to_raw(), other.to_raw()) }
+    }
+}
+
+#[cfg(feature = "napi-1")]
+impl Value for JsSharedArrayBuffer {}
+
+#[cfg(feature = "napi-1")]
+impl Object for JsSharedArrayBuffer {}
+
+// Constructs `new class(len, { maxByteLength: max_len })`
+#[cfg(feature = "napi-1")]
+fn construct_resizable<'a, C: Context<'a>>(
+    cx: &mut C,
+    class: &str,
+    len: usize,
+    max_len: usize,
+) -> JsResult<'a, JsValue> {
+    let class = cx
+        .global()
+        .get(cx, class)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+    let options = cx.empty_object();
+    let max_len = cx.number(max_len as f64);
+    let len = cx.number(len as f64);
+
+    options.set(cx, "maxByteLength", max_len)?;
+
+    let buf = class.construct(cx, [len.upcast::<JsValue>(), options.upcast()])?;
+
+    Ok(buf.upcast())
+}
+
+// Reads a boolean property that is missing on older hosts
+#[cfg(feature = "napi-1")]
+fn flag<'a, C: Context<'a>, O: Object>(cx: &mut C, o: O, key: &str) -> NeonResult<bool> {
+    let value = Handle::new_internal(o).get(cx, key)?;
+
+    Ok(value
+        .downcast::<JsBoolean, _>(cx)
+        .map(|b| b.value(cx))
+        .unwrap_or(false))
+}
+
+// Reads a length property that is missing on older hosts
+#[cfg(feature = "napi-1")]
+fn length<'a, C: Context<'a>, O: Object>(cx: &mut C, o: O, key: &str) -> NeonResult<Option<usize>> {
+    let value = Handle::new_internal(o).get(cx, key)?;
+
+    Ok(value
+        .downcast::<JsNumber, _>(cx)
+        .ok()
+        .map(|n| n.value(cx) as usize))
+}
+
 /// A reference to the internal backing buffer data of a `Buffer` or `ArrayBuffer` object, which can be accessed via the `Borrow` and `BorrowMut` traits.
 #[derive(Clone, Copy)]
 #[repr(C)]
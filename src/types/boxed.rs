@@ -233,6 +233,12 @@ impl<T: Finalize + Send + 'static> JsBox<T> {
         // contained value `T`.
         fn finalizer<U: Finalize + 'static>(env: raw::Env, data: BoxAny) {
             let data = *data.downcast::<U>().unwrap();
+
+            #[cfg(feature = "external-memory")]
+            unsafe {
+                neon_runtime::mem::adjust_external_memory(env, -(std::mem::size_of::<U>() as i64));
+            }
+
             let env = unsafe { std::mem::transmute(env) };
 
             FinalizeContext::with(env, move |mut cx| data.finalize(&mut cx));
@@ -243,6 +249,14 @@ impl<T: Finalize + Send + 'static> JsBox<T> {
         let raw_data = &*v as *const dyn Any as *const T;
         let local = unsafe { external::create(cx.env().to_raw(), v, finalizer::<T>) };
 
+        #[cfg(feature = "external-memory")]
+        unsafe {
+            neon_runtime::mem::adjust_external_memory(
+                cx.env().to_raw(),
+                std::mem::size_of::<T>() as i64,
+            );
+        }
+
         Handle::new_internal(Self { local, raw_data })
     }
 }
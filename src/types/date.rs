@@ -9,6 +9,7 @@ use neon_runtime::raw;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A JavaScript Date object
 #[repr(C)]
@@ -64,6 +65,20 @@ impl DateErrorKind {
     }
 }
 
+/// The error returned when converting an invalid Date, whose value is `NaN`,
+/// or a Date that cannot be represented by the platform
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-5")))]
+pub struct InvalidDate;
+
+impl fmt::Display for InvalidDate {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("Invalid Date")
+    }
+}
+
+impl Error for InvalidDate {}
+
 impl<'a, T: Value> JsResultExt<'a, T> for Result<Handle<'a, T>, DateError> {
     /// Creates an `Error` on error
     fn or_throw<'b, C: Context<'b>>(self, cx: &mut C) -> JsResult<'a, T> {
@@ -111,6 +126,68 @@ impl JsDate {
         unsafe { neon_runtime::date::value(env, self.to_raw()) }
     }
 
+    /// Creates a Date at the same point in time as `time`, truncated to whole
+    /// milliseconds. It errors when `time` is outside the range of valid
+    /// JavaScript Date values.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-5")] {
+    /// # use neon::prelude::*;
+    /// use neon::types::JsDate;
+    ///
+    /// fn now(mut cx: FunctionContext) -> JsResult<JsDate> {
+    ///     JsDate::from_system_time(&mut cx, std::time::SystemTime::now()).or_throw(&mut cx)
+    /// }
+    /// # }
+    /// ```
+    pub fn from_system_time<'a, C: Context<'a>>(
+        cx: &mut C,
+        time: SystemTime,
+    ) -> Result<Handle<'a, JsDate>, DateError> {
+        let millis = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_millis() as f64,
+            Err(err) => -(err.duration().as_millis() as f64),
+        };
+
+        JsDate::new(cx, millis)
+    }
+
+    /// Converts the Date to a `SystemTime`. Unlike [`value`](JsDate::value),
+    /// an invalid Date is an error instead of `NaN`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-5")] {
+    /// # use neon::prelude::*;
+    /// use neon::types::JsDate;
+    ///
+    /// fn is_past(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    ///     let date = cx.argument::<JsDate>(0)?;
+    ///     let time = match date.to_system_time(&mut cx) {
+    ///         Ok(time) => time,
+    ///         Err(err) => return cx.throw_range_error(err.to_string()),
+    ///     };
+    ///
+    ///     Ok(cx.boolean(time < std::time::SystemTime::now()))
+    /// }
+    /// # }
+    /// ```
+    pub fn to_system_time<'a, C: Context<'a>>(self, cx: &mut C) -> Result<SystemTime, InvalidDate> {
+        let value = self.value(cx);
+
+        if value.is_nan() {
+            return Err(InvalidDate);
+        }
+
+        let since = Duration::from_millis(value.abs() as u64);
+        let time = if value < 0.0 {
+            UNIX_EPOCH.checked_sub(since)
+        } else {
+            UNIX_EPOCH.checked_add(since)
+        };
+
+        time.ok_or(InvalidDate)
+    }
+
     /// Checks if the Date's value is valid. A Date is valid if its value is between
     /// `JsDate::MIN_VALUE` and `JsDate::MAX_VALUE` or if it is `NaN`
     pub fn is_valid<'a, C: Context<'a>>(self, cx: &mut C) -> bool {
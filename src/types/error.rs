@@ -77,6 +77,20 @@ impl JsError {
             true
         })
     }
+
+    /// Creates an instance of the [`SyntaxError`](https://developer.mozilla.org/docs/Web/JavaScript/Reference/Global_Objects/SyntaxError) class.
+    #[cfg(feature = "napi-9")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-9")))]
+    pub fn syntax_error<'a, C: Context<'a>, S: AsRef<str>>(
+        cx: &mut C,
+        msg: S,
+    ) -> NeonResult<Handle<'a, JsError>> {
+        let msg = cx.string(msg.as_ref());
+        build(cx.env(), |out| unsafe {
+            neon_runtime::error::new_syntax_error(cx.env().to_raw(), out, msg.to_raw());
+            true
+        })
+    }
 }
 
 pub(crate) fn convert_panics<T, F: UnwindSafe + FnOnce() -> NeonResult<T>>(
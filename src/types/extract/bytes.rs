@@ -0,0 +1,310 @@
+use std::ops::Deref;
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use std::slice;
+
+use crate::context::Context;
+use crate::handle::{Handle, Managed};
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::{JsBuffer, JsError, JsString, JsValue};
+
+/// Extracts a copy of the bytes of a `Buffer`, `ArrayBuffer`, any
+/// `TypedArray`, or the UTF-8 encoding of a string.
+///
+/// The bytes are always copied into a `Vec<u8>` owned by Rust, so a
+/// `ByteBuf` can outlive the call that extracted it and be sent to other
+/// threads. Code that only reads a large buffer during the call can avoid the
+/// copy by borrowing the contents of a [`JsBuffer`] or
+/// [`JsArrayBuffer`](crate::types::JsArrayBuffer) instead.
+///
+/// Typed arrays contribute only the bytes they view, not their whole backing
+/// `ArrayBuffer`. `ByteBuf` is converted back into a `Buffer`. Strings
+/// encoded as base64 or hex are extracted with [`Base64`] and [`Hex`].
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::extract::{ByteBuf, TryFromJs};
+///
+/// fn checksum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+///     let arg = cx.argument::<JsValue>(0)?;
+///     let bytes = ByteBuf::from_js(&mut cx, arg)?;
+///     let sum = bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+///
+///     Ok(cx.number(sum))
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByteBuf(pub Vec<u8>);
+
+/// Extracts the bytes of a base64 string, or a copy of the bytes of a
+/// `Buffer`, `ArrayBuffer` or any `TypedArray`, like [`ByteBuf`].
+///
+/// Both the standard and the URL-safe alphabets are accepted, with or without
+/// padding. `Base64` is converted back into a padded string with the standard
+/// alphabet.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Base64(pub Vec<u8>);
+
+/// Extracts the bytes of a hex string, or a copy of the bytes of a `Buffer`,
+/// `ArrayBuffer` or any `TypedArray`, like [`ByteBuf`].
+///
+/// Both upper and lower case digits are accepted. `Hex` is converted back
+/// into a lower case string.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hex(pub Vec<u8>);
+
+// Copies `len` bytes starting at `data`, which may be null for empty buffers.
+unsafe fn copy_bytes(data: *mut c_void, len: usize) -> Vec<u8> {
+    if len == 0 {
+        return Vec::new();
+    }
+
+    slice::from_raw_parts(data as *const u8, len).to_vec()
+}
+
+// Copies the bytes of a `Buffer`, `ArrayBuffer` or `TypedArray`, or returns
+// `None` if the value is none of them
+fn copy_binary<'a, C: Context<'a>>(cx: &mut C, v: Handle<'a, JsValue>) -> Option<Vec<u8>> {
+    let env = cx.env().to_raw();
+    let raw = v.to_raw();
+    let mut data = null_mut();
+
+    unsafe {
+        if neon_runtime::tag::is_buffer(env, raw) {
+            let len = neon_runtime::buffer::data(env, &mut data, raw);
+
+            return Some(copy_bytes(data, len));
+        }
+
+        if neon_runtime::tag::is_arraybuffer(env, raw) {
+            let len = neon_runtime::arraybuffer::data(env, &mut data, raw);
+
+            return Some(copy_bytes(data, len));
+        }
+
+        if neon_runtime::tag::is_typedarray(env, raw) {
+            let info = neon_runtime::typedarray::info(env, raw);
+            let len = info.length * neon_runtime::typedarray::element_size(info.typ);
+
+            return Some(copy_bytes(info.data, len));
+        }
+    }
+
+    None
+}
+
+// Extracts binary values as they are, and strings with `decode`
+fn try_from_encoded<'a, C, F>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+    encoding: &str,
+    decode: F,
+) -> NeonResult<Result<Vec<u8>, Handle<'a, JsValue>>>
+where
+    C: Context<'a>,
+    F: FnOnce(&str) -> Option<Vec<u8>>,
+{
+    if let Some(bytes) = copy_binary(cx, v) {
+        return Ok(Ok(bytes));
+    }
+
+    let msg = match v.downcast::<JsString, _>(cx) {
+        Ok(s) => match decode(&s.value(cx)) {
+            Some(bytes) => return Ok(Ok(bytes)),
+            None => format!("invalid {} string", encoding),
+        },
+        Err(_) => format!(
+            "expected {} string, Buffer, ArrayBuffer or TypedArray",
+            encoding
+        ),
+    };
+
+    Ok(Err(JsError::type_error(cx, msg)?.upcast()))
+}
+
+impl<'a> TryFromJs<'a> for ByteBuf {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if let Some(bytes) = copy_binary(cx, v) {
+            return Ok(Ok(ByteBuf(bytes)));
+        }
+
+        if let Ok(s) = v.downcast::<JsString, _>(cx) {
+            return Ok(Ok(ByteBuf(s.value(cx).into_bytes())));
+        }
+
+        let msg = "expected Buffer, ArrayBuffer, TypedArray or string";
+
+        Ok(Err(JsError::type_error(cx, msg)?.upcast()))
+    }
+}
+
+impl<'a> TryIntoJs<'a> for ByteBuf {
+    type Value = JsBuffer;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsBuffer> {
+        if self.0.len() > u32::MAX as usize {
+            return cx.throw_range_error("byte length exceeds the maximum Buffer size");
+        }
+
+        let buffer = JsBuffer::new(cx, self.0.len() as u32)?;
+
+        if !self.0.is_empty() {
+            let mut data = null_mut();
+
+            unsafe {
+                neon_runtime::buffer::data(cx.env().to_raw(), &mut data, buffer.to_raw());
+                slice::from_raw_parts_mut(data as *mut u8, self.0.len()).copy_from_slice(&self.0);
+            }
+        }
+
+        Ok(buffer)
+    }
+}
+
+impl Deref for ByteBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        ByteBuf(bytes)
+    }
+}
+
+impl From<ByteBuf> for Vec<u8> {
+    fn from(bytes: ByteBuf) -> Self {
+        bytes.0
+    }
+}
+
+impl<'a> TryFromJs<'a> for Base64 {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(try_from_encoded(cx, v, "base64", decode_base64)?.map(Base64))
+    }
+}
+
+impl<'a> TryIntoJs<'a> for Base64 {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsString> {
+        Ok(cx.string(encode_base64(&self.0)))
+    }
+}
+
+impl<'a> TryFromJs<'a> for Hex {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(try_from_encoded(cx, v, "hex", decode_hex)?.map(Hex))
+    }
+}
+
+impl<'a> TryIntoJs<'a> for Hex {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsString> {
+        Ok(cx.string(encode_hex(&self.0)))
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+
+    s
+}
+
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+    let mut n = 0u32;
+    let mut bits = 0;
+
+    for c in s.bytes() {
+        let digit = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+
+        n = n << 6 | digit as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+
+    // A single digit left over cannot encode a byte
+    if bits >= 6 {
+        return None;
+    }
+
+    Some(bytes)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
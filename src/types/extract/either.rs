@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{ErrorParts, TryFromJs, TryIntoJs};
+use crate::types::{JsError, JsObject, JsString, JsValue, Value};
+
+/// Extracts a value that may be one of two types, trying `L` before `R`.
+///
+/// Alternatives can be nested to accept more than two types, for example
+/// `Either<String, Either<f64, bool>>`. When no alternative matches, a
+/// `TypeError` listing every expected type is thrown.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::extract::{Either, TryFromJs};
+///
+/// fn describe(mut cx: FunctionContext) -> JsResult<JsString> {
+///     let arg = cx.argument::<JsValue>(0)?;
+///     let description = match Either::<String, f64>::from_js(&mut cx, arg)? {
+///         Either::Left(s) => format!("string of length {}", s.len()),
+///         Either::Right(n) => format!("number {}", n),
+///     };
+///
+///     Ok(cx.string(description))
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Error returned when neither alternative of an [`Either`](Either) matches.
+///
+/// Converts into a `TypeError` that lists the type expected by each
+/// alternative, e.g., `expected string or number`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EitherError {
+    // The types expected by the alternatives, e.g., `string` and `number`
+    expected: Vec<String>,
+    // The messages of alternatives whose errors do not name an expected type
+    others: Vec<String>,
+}
+
+impl EitherError {
+    // Adds the error of an alternative that did not match
+    fn push<'a, C: Context<'a>, E: TryIntoJs<'a>>(&mut self, cx: &mut C, err: E) -> NeonResult<()> {
+        match err.error_parts() {
+            Some(parts) => self.expected.push(parts.expected),
+            None => {
+                let err = err.try_into_js(cx)?.upcast();
+                let message = error_message(cx, err)?;
+
+                self.others.push(message);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, L: TryFromJs<'a>, R: TryFromJs<'a>> TryFromJs<'a> for Either<L, R> {
+    type Error = EitherError;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let mut err = EitherError {
+            expected: Vec::new(),
+            others: Vec::new(),
+        };
+
+        match L::try_from_js(cx, v)? {
+            Ok(l) => return Ok(Ok(Either::Left(l))),
+            Err(left) => err.push(cx, left)?,
+        }
+
+        match R::try_from_js(cx, v)? {
+            Ok(r) => return Ok(Ok(Either::Right(r))),
+            Err(right) => err.push(cx, right)?,
+        }
+
+        Ok(Err(err))
+    }
+}
+
+impl<'a, L: TryIntoJs<'a>, R: TryIntoJs<'a>> TryIntoJs<'a> for Either<L, R> {
+    type Value = JsValue;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        match self {
+            Either::Left(l) => Ok(l.try_into_js(cx)?.upcast()),
+            Either::Right(r) => Ok(r.try_into_js(cx)?.upcast()),
+        }
+    }
+}
+
+impl fmt::Display for EitherError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut messages = Vec::with_capacity(self.others.len() + 1);
+
+        if !self.expected.is_empty() {
+            messages.push(format!("expected {}", self.expected.join(" or ")));
+        }
+
+        messages.extend(self.others.iter().cloned());
+
+        write!(f, "{}", messages.join(", or "))
+    }
+}
+
+impl std::error::Error for EitherError {}
+
+impl<'a> TryIntoJs<'a> for EitherError {
+    type Value = JsError;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsError> {
+        JsError::type_error(cx, self.to_string())
+    }
+
+    // Nested alternatives are listed with the others
+    fn error_parts(&self) -> Option<ErrorParts> {
+        if !self.others.is_empty() {
+            return None;
+        }
+
+        Some(ErrorParts {
+            range: false,
+            expected: self.expected.join(" or "),
+            found: None,
+        })
+    }
+}
+
+// Reads the `message` of an error object, falling back to string coercion for
+// any other thrown value.
+fn error_message<'a, C: Context<'a>>(cx: &mut C, v: Handle<'a, JsValue>) -> NeonResult<String> {
+    if let Ok(err) = v.downcast::<JsObject, _>(cx) {
+        let message = err.get(cx, "message")?;
+
+        if let Ok(message) = message.downcast::<JsString, _>(cx) {
+            return Ok(message.value(cx));
+        }
+    }
+
+    Ok(v.to_string(cx)?.value(cx))
+}
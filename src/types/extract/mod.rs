@@ -0,0 +1,707 @@
+//! Traits for converting between Rust values and JavaScript values.
+//!
+//! The [`TryIntoJs`](TryIntoJs) trait describes Rust values that can be
+//! converted into a JavaScript value, for example when building up arrays
+//! or objects from Rust data:
+//!
+//! ```
+//! # #[cfg(feature = "napi-1")] {
+//! # use neon::prelude::*;
+//! fn primes(mut cx: FunctionContext) -> JsResult<JsArray> {
+//!     JsArray::from_iter(&mut cx, vec![2, 3, 5, 7, 11])
+//! }
+//! # }
+//! ```
+//!
+//! The [`TryFromJs`](TryFromJs) trait describes the reverse: Rust values that
+//! can be extracted from a JavaScript value. Container types such as `Vec<T>`
+//! and `HashMap<String, T>` convert whenever their elements do:
+//!
+//! ```
+//! # #[cfg(feature = "napi-1")] {
+//! # use neon::prelude::*;
+//! use neon::types::extract::TryFromJs;
+//!
+//! fn total(mut cx: FunctionContext) -> JsResult<JsNumber> {
+//!     let arg = cx.argument::<JsValue>(0)?;
+//!     let values = Vec::<f64>::from_js(&mut cx, arg)?;
+//!
+//!     Ok(cx.number(values.into_iter().sum::<f64>()))
+//! }
+//! # }
+//! ```
+//!
+//! With the `ndarray` or `nalgebra` feature, arrays and matrices convert to
+//! and from `{ data, shape }` objects, where `data` is a typed array of the
+//! elements in row-major order, e.g., a `Float64Array`, and `shape` is an
+//! array with the length of each axis. See [`TypedArrayElement`] for the
+//! supported element types.
+
+mod bytes;
+mod either;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod nullable;
+mod number;
+mod path;
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+mod progress;
+#[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+mod tensor;
+
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "napi-6")]
+use std::hash::BuildHasher;
+use std::marker::PhantomData;
+
+use crate::context::Context;
+use crate::handle::Handle;
+#[cfg(feature = "napi-6")]
+use crate::handle::Managed;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult, Throw};
+#[cfg(feature = "napi-6")]
+use crate::types::build;
+use crate::types::{
+    JsArray, JsBoolean, JsError, JsNull, JsNumber, JsObject, JsString, JsUndefined, JsValue, Value,
+};
+use neon_runtime::typedarray::TypedArrayType;
+
+pub use self::bytes::{Base64, ByteBuf, Hex};
+pub use self::either::{Either, EitherError};
+pub use self::nullable::{Defined, Nullable};
+pub use self::number::{IntegerError, Lossy};
+#[cfg(all(feature = "napi-4", feature = "channel-api"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "napi-4", feature = "channel-api"))))]
+pub use self::progress::Progress;
+#[cfg(any(feature = "ndarray", feature = "nalgebra"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "ndarray", feature = "nalgebra"))))]
+pub use self::tensor::TypedArrayElement;
+
+/// Convert a Rust value into a JavaScript value.
+///
+/// Conversions may throw, for example, if a string exceeds the maximum
+/// length supported by the JavaScript engine.
+pub trait TryIntoJs<'a> {
+    /// The type of JavaScript value produced by the conversion.
+    type Value: Value;
+
+    /// Convert `self` into a JavaScript value.
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, Self::Value>;
+
+    #[doc(hidden)]
+    // Describes a conversion error without converting it, so that context can
+    // be added to the message without parsing it. Errors that are already
+    // JavaScript values return `None`.
+    fn error_parts(&self) -> Option<ErrorParts> {
+        None
+    }
+
+    #[doc(hidden)]
+    // Converts the elements of a `Vec` into an array. Numbers override this
+    // to build the array in bulk.
+    fn vec_into_js<C: Context<'a>>(vec: Vec<Self>, cx: &mut C) -> JsResult<'a, JsArray>
+    where
+        Self: Sized,
+    {
+        JsArray::from_iter(cx, vec)
+    }
+}
+
+/// Extract a Rust value from a JavaScript value.
+pub trait TryFromJs<'a>: Sized {
+    /// The error produced when the JavaScript value has the wrong shape. It is
+    /// thrown by [`from_js`](TryFromJs::from_js).
+    type Error: TryIntoJs<'a>;
+
+    /// Extract a Rust value, returning `Err` if the JavaScript value cannot be
+    /// converted. The outer `NeonResult` is reserved for JavaScript exceptions
+    /// thrown while inspecting the value.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>>;
+
+    /// Extract a Rust value, throwing the conversion error as a JavaScript
+    /// exception if the JavaScript value cannot be converted.
+    fn from_js<C: Context<'a>>(cx: &mut C, v: Handle<'a, JsValue>) -> NeonResult<Self> {
+        match Self::try_from_js(cx, v)? {
+            Ok(v) => Ok(v),
+            Err(err) => {
+                let err = err.try_into_js(cx)?;
+
+                cx.throw(err)
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    // Extracts a `Vec` by copying the contents of a typed array, for numeric
+    // types that match the elements of the array. Returns `None` to extract
+    // each element of a JavaScript array instead.
+    fn vec_from_typed_array<C: Context<'a>>(
+        _cx: &mut C,
+        _v: Handle<'a, JsValue>,
+    ) -> Option<Vec<Self>> {
+        None
+    }
+}
+
+#[doc(hidden)]
+/// The parts of the message of a conversion error, e.g., `expected number`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorParts {
+    /// Whether the error converts into a `RangeError` instead of a `TypeError`.
+    pub range: bool,
+    /// The expected type or value, e.g., `number`.
+    pub expected: String,
+    /// The value that was found, e.g., `1.5`. When `None`, the message may
+    /// describe the type of the value instead.
+    pub found: Option<String>,
+}
+
+/// Error returned when a JavaScript value is not the expected type `T`.
+/// Converts into a `TypeError`.
+pub struct TypeExpected<T: Value>(PhantomData<T>);
+
+impl<T: Value> TypeExpected<T> {
+    fn new() -> Self {
+        TypeExpected(PhantomData)
+    }
+}
+
+impl<T: Value> std::fmt::Debug for TypeExpected<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TypeExpected<{}>", T::name())
+    }
+}
+
+impl<T: Value> std::fmt::Display for TypeExpected<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "expected {}", T::name())
+    }
+}
+
+impl<T: Value> std::error::Error for TypeExpected<T> {}
+
+impl<'a, T: Value> TryIntoJs<'a> for TypeExpected<T> {
+    type Value = JsError;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsError> {
+        JsError::type_error(cx, self.to_string())
+    }
+
+    fn error_parts(&self) -> Option<ErrorParts> {
+        Some(ErrorParts {
+            range: false,
+            expected: T::name(),
+            found: None,
+        })
+    }
+}
+
+impl<'a, V: Value> TryFromJs<'a> for Handle<'a, V> {
+    type Error = TypeExpected<V>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(v.downcast(cx).map_err(|_| TypeExpected::new()))
+    }
+}
+
+impl<'a, T: TryFromJs<'a>> TryFromJs<'a> for Option<T> {
+    type Error = T::Error;
+
+    /// `null` and `undefined` are extracted as `None`.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if v.is_a::<JsUndefined, _>(cx) || v.is_a::<JsNull, _>(cx) {
+            return Ok(Ok(None));
+        }
+
+        Ok(T::try_from_js(cx, v)?.map(Some))
+    }
+}
+
+impl<'a> TryFromJs<'a> for bool {
+    type Error = TypeExpected<JsBoolean>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(match v.downcast::<JsBoolean, _>(cx) {
+            Ok(v) => Ok(v.value(cx)),
+            Err(_) => Err(TypeExpected::new()),
+        })
+    }
+}
+
+impl<'a> TryFromJs<'a> for f64 {
+    type Error = TypeExpected<JsNumber>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(match v.downcast::<JsNumber, _>(cx) {
+            Ok(v) => Ok(v.value(cx)),
+            Err(_) => Err(TypeExpected::new()),
+        })
+    }
+
+    fn vec_from_typed_array<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> Option<Vec<Self>> {
+        number::copy_typed_array(cx, v, TypedArrayType::Float64Array)
+    }
+}
+
+impl<'a> TryFromJs<'a> for f32 {
+    type Error = TypeExpected<JsNumber>;
+
+    /// Numbers are rounded to the nearest `f32`, as with an `as` cast.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(f64::try_from_js(cx, v)?.map(|n| n as f32))
+    }
+
+    fn vec_from_typed_array<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> Option<Vec<Self>> {
+        number::copy_typed_array(cx, v, TypedArrayType::Float32Array)
+    }
+}
+
+impl<'a> TryFromJs<'a> for String {
+    type Error = TypeExpected<JsString>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(match v.downcast::<JsString, _>(cx) {
+            Ok(v) => Ok(v.value(cx)),
+            Err(_) => Err(TypeExpected::new()),
+        })
+    }
+}
+
+// Container conversions can fail either because the container itself has the
+// wrong type or because one of its elements does. Rather than nesting error
+// types, the error is converted to a JavaScript value eagerly.
+fn element_error<'a, C: Context<'a>, E: TryIntoJs<'a>>(
+    cx: &mut C,
+    err: E,
+) -> JsResult<'a, JsValue> {
+    Ok(err.try_into_js(cx)?.upcast())
+}
+
+impl<'a, T: TryFromJs<'a>> TryFromJs<'a> for Vec<T> {
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts each element of a JavaScript array.
+    ///
+    /// Vectors of `f64`, `f32` and the integer types up to 32 bits may also
+    /// be extracted from a typed array with the same element type, e.g., a
+    /// `Float64Array` for `Vec<f64>`, which copies the elements at once.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if let Some(result) = T::vec_from_typed_array(cx, v) {
+            return Ok(Ok(result));
+        }
+
+        let array = match v.downcast::<JsArray, _>(cx) {
+            Ok(array) => array,
+            Err(_) => return Ok(Err(element_error(cx, TypeExpected::<JsArray>::new())?)),
+        };
+
+        let mut result = Vec::new();
+        let mut items = array.iter(cx);
+
+        while let Some(item) = items.next() {
+            let item = item?;
+
+            match T::try_from_js(items.context(), item)? {
+                Ok(item) => result.push(item),
+                Err(err) => return Ok(Err(element_error(items.context(), err)?)),
+            }
+        }
+
+        Ok(Ok(result))
+    }
+}
+
+// Reads the entries of a `Map`, or the own enumerable properties of a plain
+// object, extracting each value. Arrays and functions are rejected, even
+// though they are objects, since their properties are not entries.
+#[cfg(feature = "napi-6")]
+fn try_from_object<'a, C, T, F>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+    mut insert: F,
+) -> NeonResult<Result<(), Handle<'a, JsValue>>>
+where
+    C: Context<'a>,
+    T: TryFromJs<'a>,
+    F: FnMut(String, T),
+{
+    let env = cx.env().to_raw();
+
+    if unsafe { neon_runtime::tag::is_map(env, v.to_raw()) } {
+        return try_from_map(cx, v, insert);
+    }
+
+    let object = match v.downcast::<JsObject, _>(cx) {
+        Ok(_) if v.is_a::<JsArray, _>(cx) => {
+            let err = JsError::type_error(cx, "expected object or `Map`, found array")?;
+
+            return Ok(Err(err.upcast()));
+        }
+        Ok(object) => object,
+        Err(_) => return Ok(Err(element_error(cx, TypeExpected::<JsObject>::new())?)),
+    };
+
+    let keys: Handle<JsArray> = build(cx.env(), |out| unsafe {
+        neon_runtime::object::get_own_enumerable_property_names(out, env, object.to_raw())
+    })?;
+
+    for key in keys.to_vec(cx)? {
+        let key = key.downcast_or_throw::<JsString, _>(cx)?;
+        let value = object.get(cx, key)?;
+
+        match T::try_from_js(cx, value)? {
+            Ok(value) => insert(key.value(cx), value),
+            Err(err) => return Ok(Err(element_error(cx, err)?)),
+        }
+    }
+
+    Ok(Ok(()))
+}
+
+// Iterates the `entries()` of a `Map`, whose keys must be strings
+#[cfg(feature = "napi-6")]
+fn try_from_map<'a, C, T, F>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+    mut insert: F,
+) -> NeonResult<Result<(), Handle<'a, JsValue>>>
+where
+    C: Context<'a>,
+    T: TryFromJs<'a>,
+    F: FnMut(String, T),
+{
+    let map = v.downcast_or_throw::<JsObject, _>(cx)?;
+    let entries = map
+        .method(cx, "entries")?
+        .call(cx)?
+        .downcast_or_throw::<JsObject, _>(cx)?;
+
+    loop {
+        let next = entries
+            .method(cx, "next")?
+            .call(cx)?
+            .downcast_or_throw::<JsObject, _>(cx)?;
+
+        let done = next.get(cx, "done")?;
+
+        if done
+            .downcast::<JsBoolean, _>(cx)
+            .is_ok_and(|done| done.value(cx))
+        {
+            return Ok(Ok(()));
+        }
+
+        let entry = next.get(cx, "value")?.downcast_or_throw::<JsArray, _>(cx)?;
+        let key = entry.get(cx, 0)?;
+        let key = match String::try_from_js(cx, key)? {
+            Ok(key) => key,
+            Err(err) => return Ok(Err(element_error(cx, err)?)),
+        };
+        let value = entry.get(cx, 1)?;
+
+        match T::try_from_js(cx, value)? {
+            Ok(value) => insert(key, value),
+            Err(err) => return Ok(Err(element_error(cx, err)?)),
+        }
+    }
+}
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+impl<'a, T, S> TryFromJs<'a> for HashMap<String, T, S>
+where
+    T: TryFromJs<'a>,
+    S: BuildHasher + Default,
+{
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts each entry of a JavaScript `Map` with string keys, or each
+    /// own enumerable string-keyed property of a plain object. Arrays and
+    /// functions are not extracted.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let mut result = HashMap::default();
+
+        Ok(try_from_object(cx, v, |k, v| {
+            result.insert(k, v);
+        })?
+        .map(|_| result))
+    }
+}
+
+#[cfg(feature = "napi-6")]
+#[cfg_attr(docsrs, doc(cfg(feature = "napi-6")))]
+impl<'a, T: TryFromJs<'a>> TryFromJs<'a> for BTreeMap<String, T> {
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts each entry of a JavaScript `Map` with string keys, or each
+    /// own enumerable string-keyed property of a plain object. Arrays and
+    /// functions are not extracted.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let mut result = BTreeMap::new();
+
+        Ok(try_from_object(cx, v, |k, v| {
+            result.insert(k, v);
+        })?
+        .map(|_| result))
+    }
+}
+
+impl<'a, V: Value> TryIntoJs<'a> for Handle<'a, V> {
+    type Value = V;
+
+    fn try_into_js<C: Context<'a>>(self, _cx: &mut C) -> JsResult<'a, V> {
+        Ok(self)
+    }
+}
+
+impl<'a, T: TryIntoJs<'a>> TryIntoJs<'a> for Option<T> {
+    type Value = JsValue;
+
+    /// `None` is converted to `undefined`.
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        match self {
+            Some(v) => Ok(v.try_into_js(cx)?.upcast()),
+            None => Ok(cx.undefined().upcast()),
+        }
+    }
+}
+
+impl<'a, T, E> TryIntoJs<'a> for Result<T, E>
+where
+    T: TryIntoJs<'a>,
+    E: TryIntoJs<'a>,
+{
+    type Value = T::Value;
+
+    /// `Err` is converted and thrown as a JavaScript exception.
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, T::Value> {
+        match self {
+            Ok(v) => v.try_into_js(cx),
+            Err(err) => {
+                let err = err.try_into_js(cx)?;
+
+                cx.throw(err)
+            }
+        }
+    }
+}
+
+impl<'a> TryIntoJs<'a> for Throw {
+    type Value = JsValue;
+
+    /// The exception is already pending; it propagates unchanged.
+    fn try_into_js<C: Context<'a>>(self, _cx: &mut C) -> JsResult<'a, JsValue> {
+        Err(self)
+    }
+}
+
+impl<'a> TryIntoJs<'a> for () {
+    type Value = JsUndefined;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsUndefined> {
+        Ok(cx.undefined())
+    }
+}
+
+impl<'a> TryIntoJs<'a> for bool {
+    type Value = JsBoolean;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsBoolean> {
+        Ok(cx.boolean(self))
+    }
+}
+
+impl<'a> TryIntoJs<'a> for String {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsString> {
+        self.as_str().try_into_js(cx)
+    }
+}
+
+impl<'a> TryIntoJs<'a> for &str {
+    type Value = JsString;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsString> {
+        match cx.try_string(self) {
+            Ok(s) => Ok(s),
+            Err(err) => cx.throw_range_error(err.to_string()),
+        }
+    }
+}
+
+// Numeric types that can be represented exactly by an `f64`.
+macro_rules! impl_number_into_js {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> TryIntoJs<'a> for $t {
+                type Value = JsNumber;
+
+                fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsNumber> {
+                    Ok(cx.number(self))
+                }
+
+                fn vec_into_js<C: Context<'a>>(vec: Vec<Self>, cx: &mut C) -> JsResult<'a, JsArray> {
+                    let vec = vec.into_iter().map(f64::from).collect::<Vec<_>>();
+
+                    JsArray::from_slice(cx, &vec)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_into_js!(f32, u8, u16, u32, i8, i16, i32);
+
+impl<'a> TryIntoJs<'a> for f64 {
+    type Value = JsNumber;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsNumber> {
+        Ok(cx.number(self))
+    }
+
+    fn vec_into_js<C: Context<'a>>(vec: Vec<Self>, cx: &mut C) -> JsResult<'a, JsArray> {
+        JsArray::from_slice(cx, &vec)
+    }
+}
+
+impl<'a, T: TryIntoJs<'a>> TryIntoJs<'a> for Vec<T> {
+    type Value = JsArray;
+
+    /// Vectors of numbers are converted in bulk with
+    /// [`JsArray::from_slice`](JsArray::from_slice).
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsArray> {
+        T::vec_into_js(self, cx)
+    }
+}
+
+// Builds a plain object from string keys and convertible values.
+fn object_from_iter<'a, C, K, T, I>(cx: &mut C, iter: I) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    K: AsRef<str>,
+    T: TryIntoJs<'a>,
+    I: IntoIterator<Item = (K, T)>,
+{
+    let object = JsObject::new(cx);
+
+    for (k, v) in iter {
+        let v = v.try_into_js(cx)?;
+
+        object.set(cx, k.as_ref(), v)?;
+    }
+
+    Ok(object)
+}
+
+impl<'a, K, T, S> TryIntoJs<'a> for HashMap<K, T, S>
+where
+    K: AsRef<str>,
+    T: TryIntoJs<'a>,
+{
+    type Value = JsObject;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsObject> {
+        object_from_iter(cx, self)
+    }
+}
+
+impl<'a, K, T> TryIntoJs<'a> for BTreeMap<K, T>
+where
+    K: AsRef<str>,
+    T: TryIntoJs<'a>,
+{
+    type Value = JsObject;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsObject> {
+        object_from_iter(cx, self)
+    }
+}
+
+macro_rules! impl_tuple_from_js {
+    ($len:expr; $($i:expr => $t:ident),+) => {
+        impl<'a, $($t: TryFromJs<'a>),+> TryFromJs<'a> for ($($t,)+) {
+            type Error = Handle<'a, JsValue>;
+
+            /// Extracts a JavaScript array with exactly as many elements as the tuple.
+            #[allow(non_snake_case)]
+            fn try_from_js<C: Context<'a>>(
+                cx: &mut C,
+                v: Handle<'a, JsValue>,
+            ) -> NeonResult<Result<Self, Self::Error>> {
+                let array = match v.downcast::<JsArray, _>(cx) {
+                    Ok(array) => array,
+                    Err(_) => return Ok(Err(element_error(cx, TypeExpected::<JsArray>::new())?)),
+                };
+
+                if array.len(cx) != $len {
+                    let msg = format!("expected an array of length {}", $len);
+
+                    return Ok(Err(JsError::type_error(cx, msg)?.upcast()));
+                }
+
+                $(
+                    let item = array.get(cx, $i)?;
+                    let $t = match $t::try_from_js(cx, item)? {
+                        Ok(item) => item,
+                        Err(err) => return Ok(Err(element_error(cx, err)?)),
+                    };
+                )+
+
+                Ok(Ok(($($t,)+)))
+            }
+        }
+    };
+}
+
+impl_tuple_from_js!(1; 0 => T1);
+impl_tuple_from_js!(2; 0 => T1, 1 => T2);
+impl_tuple_from_js!(3; 0 => T1, 1 => T2, 2 => T3);
+impl_tuple_from_js!(4; 0 => T1, 1 => T2, 2 => T3, 3 => T4);
+impl_tuple_from_js!(5; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5);
+impl_tuple_from_js!(6; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6);
+impl_tuple_from_js!(7; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7);
+impl_tuple_from_js!(8; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8);
+impl_tuple_from_js!(9; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9);
+impl_tuple_from_js!(10; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9, 9 => T10);
+impl_tuple_from_js!(11; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9, 9 => T10, 10 => T11);
+impl_tuple_from_js!(12; 0 => T1, 1 => T2, 2 => T3, 3 => T4, 4 => T5, 5 => T6, 6 => T7, 7 => T8, 8 => T9, 9 => T10, 10 => T11, 11 => T12);
@@ -0,0 +1,103 @@
+use ::nalgebra::storage::RawStorage;
+use ::nalgebra::{DMatrix, Dim, Matrix, SMatrix, Scalar};
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::tensor::{self, Tensor, TypedArrayElement};
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::{JsError, JsObject, JsValue};
+
+// Reads `{ data, shape }` with a shape of `[rows, columns]`
+fn matrix_from_js<'a, C, T>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+) -> NeonResult<Result<Tensor<T>, Handle<'a, JsValue>>>
+where
+    C: Context<'a>,
+    T: TypedArrayElement,
+{
+    let tensor = match tensor::from_js::<C, T>(cx, v)? {
+        Ok(tensor) => tensor,
+        Err(err) => return Ok(Err(err)),
+    };
+
+    match tensor.shape[..] {
+        [_, _] => Ok(Ok(tensor)),
+        _ => {
+            let msg = format!(
+                "expected a shape of [rows, columns], found {:?}",
+                tensor.shape
+            );
+
+            Ok(Err(JsError::range_error(cx, msg)?.upcast()))
+        }
+    }
+}
+
+impl<'a, T> TryFromJs<'a> for DMatrix<T>
+where
+    T: TypedArrayElement + Scalar,
+{
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts a matrix from `{ data, shape }`, where `data` holds the
+    /// elements in row-major order and `shape` is `[rows, columns]`.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        Ok(matrix_from_js::<C, T>(cx, v)?
+            .map(|m| DMatrix::from_row_slice(m.shape[0], m.shape[1], &m.data)))
+    }
+}
+
+impl<'a, T, const R: usize, const C: usize> TryFromJs<'a> for SMatrix<T, R, C>
+where
+    T: TypedArrayElement + Scalar,
+{
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts a matrix from `{ data, shape }`, where `data` holds the
+    /// elements in row-major order and `shape` must be `[R, C]`.
+    fn try_from_js<Cx: Context<'a>>(
+        cx: &mut Cx,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let matrix = match matrix_from_js::<Cx, T>(cx, v)? {
+            Ok(matrix) => matrix,
+            Err(err) => return Ok(Err(err)),
+        };
+        let (rows, columns) = (matrix.shape[0], matrix.shape[1]);
+
+        if (rows, columns) != (R, C) {
+            let msg = format!(
+                "expected a shape of [{}, {}], found [{}, {}]",
+                R, C, rows, columns
+            );
+
+            return Ok(Err(JsError::range_error(cx, msg)?.upcast()));
+        }
+
+        Ok(Ok(SMatrix::from_row_slice(&matrix.data)))
+    }
+}
+
+impl<'a, T, R, C, S> TryIntoJs<'a> for Matrix<T, R, C, S>
+where
+    T: TypedArrayElement + Scalar,
+    R: Dim,
+    C: Dim,
+    S: RawStorage<T, R, C>,
+{
+    type Value = JsObject;
+
+    /// Converts a matrix to `{ data, shape }`, copying the elements to a
+    /// typed array in row-major order.
+    fn try_into_js<Cx: Context<'a>>(self, cx: &mut Cx) -> JsResult<'a, JsObject> {
+        let (rows, columns) = self.shape();
+        let data = (0..rows * columns).map(|i| self[(i / columns, i % columns)]);
+
+        tensor::to_js(cx, data, &[rows, columns])
+    }
+}
@@ -0,0 +1,58 @@
+use ::ndarray::{Array, ArrayBase, Data, Dimension, IxDyn};
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::tensor::{self, TypedArrayElement};
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::{JsError, JsObject, JsValue};
+
+impl<'a, T, D> TryFromJs<'a> for Array<T, D>
+where
+    T: TypedArrayElement,
+    D: Dimension,
+{
+    type Error = Handle<'a, JsValue>;
+
+    /// Extracts an array from `{ data, shape }`, where `data` holds the
+    /// elements in row-major order as a typed array of `T` or an array of
+    /// numbers, and `shape` the length of each axis. The number of axes must
+    /// match `D`.
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let tensor::Tensor { data, shape } = match tensor::from_js::<C, T>(cx, v)? {
+            Ok(tensor) => tensor,
+            Err(err) => return Ok(Err(err)),
+        };
+
+        let array = Array::<T, IxDyn>::from_shape_vec(IxDyn(&shape), data)
+            .ok()
+            .and_then(|array| array.into_dimensionality::<D>().ok());
+
+        match array {
+            Some(array) => Ok(Ok(array)),
+            None => {
+                let msg = format!("shape {:?} has the wrong number of axes", shape);
+
+                Ok(Err(JsError::range_error(cx, msg)?.upcast()))
+            }
+        }
+    }
+}
+
+impl<'a, T, S, D> TryIntoJs<'a> for ArrayBase<S, D>
+where
+    T: TypedArrayElement,
+    S: Data<Elem = T>,
+    D: Dimension,
+{
+    type Value = JsObject;
+
+    /// Converts an array, or a view of one, to `{ data, shape }`, copying the
+    /// elements to a typed array in row-major order.
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsObject> {
+        tensor::to_js(cx, self.iter().copied(), self.shape())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use crate::types::{JsError, JsNull, JsUndefined, JsValue};
+
+/// Extracts `null` as `None`, passing every other value, including
+/// `undefined`, through to `T`.
+///
+/// Unlike `Option<T>`, which treats `null` and `undefined` the same, this
+/// distinguishes an explicit `null` from a value that was never provided.
+/// `None` is converted back into `null`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Nullable<T>(pub Option<T>);
+
+/// Rejects `undefined` with a `TypeError`, passing every other value,
+/// including `null`, through to `T`.
+///
+/// Combine with [`Nullable`](Nullable) or `Option` to describe exactly which
+/// missing values an API accepts:
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::extract::{Defined, Nullable, TryFromJs};
+///
+/// // Accepts a string or an explicit `null`, but not `undefined`.
+/// fn set_label(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let arg = cx.argument::<JsValue>(0)?;
+///     let Defined(Nullable(label)) = Defined::<Nullable<String>>::from_js(&mut cx, arg)?;
+///
+///     match label {
+///         Some(label) => println!("label: {}", label),
+///         None => println!("label cleared"),
+///     }
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// Missing arguments can be detected separately with
+/// [`argument_opt`](crate::context::CallContext::argument_opt).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Defined<T>(pub T);
+
+impl<'a, T: TryFromJs<'a>> TryFromJs<'a> for Nullable<T> {
+    type Error = T::Error;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if v.is_a::<JsNull, _>(cx) {
+            return Ok(Ok(Nullable(None)));
+        }
+
+        Ok(T::try_from_js(cx, v)?.map(|v| Nullable(Some(v))))
+    }
+}
+
+impl<'a, T: TryIntoJs<'a>> TryIntoJs<'a> for Nullable<T> {
+    type Value = JsValue;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsValue> {
+        match self.0 {
+            Some(v) => Ok(v.try_into_js(cx)?.upcast()),
+            None => Ok(cx.null().upcast()),
+        }
+    }
+}
+
+impl<'a, T: TryFromJs<'a>> TryFromJs<'a> for Defined<T> {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if v.is_a::<JsUndefined, _>(cx) {
+            let err = JsError::type_error(cx, "expected a value, found undefined")?;
+
+            return Ok(Err(err.upcast()));
+        }
+
+        match T::try_from_js(cx, v)? {
+            Ok(v) => Ok(Ok(Defined(v))),
+            Err(err) => Ok(Err(err.try_into_js(cx)?.upcast())),
+        }
+    }
+}
+
+impl<'a, T: TryIntoJs<'a>> TryIntoJs<'a> for Defined<T> {
+    type Value = T::Value;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, T::Value> {
+        self.0.try_into_js(cx)
+    }
+}
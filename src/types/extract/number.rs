@@ -0,0 +1,181 @@
+use std::fmt;
+use std::mem::size_of;
+use std::ptr;
+
+use neon_runtime::typedarray::TypedArrayType;
+
+use crate::context::Context;
+use crate::handle::{Handle, Managed};
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::{ErrorParts, TryFromJs, TryIntoJs};
+use crate::types::{JsError, JsNumber, JsValue};
+
+/// Error returned when a JavaScript value cannot be extracted as an integer.
+///
+/// Wrong types convert into a `TypeError`; numbers that are not integral or
+/// do not fit the target type convert into a `RangeError`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntegerError {
+    /// The value was not a number.
+    TypeExpected,
+    /// The number had a fractional part or was not finite.
+    NotAnInteger(f64),
+    /// The number was integral but could not be represented by the named type.
+    OutOfRange(f64, &'static str),
+}
+
+impl fmt::Display for IntegerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntegerError::TypeExpected => write!(f, "expected number"),
+            IntegerError::NotAnInteger(n) => write!(f, "expected an integer, found {}", n),
+            IntegerError::OutOfRange(n, ty) => write!(f, "{} is out of range for {}", n, ty),
+        }
+    }
+}
+
+impl std::error::Error for IntegerError {}
+
+impl<'a> TryIntoJs<'a> for IntegerError {
+    type Value = JsError;
+
+    fn try_into_js<C: Context<'a>>(self, cx: &mut C) -> JsResult<'a, JsError> {
+        match self {
+            IntegerError::TypeExpected => JsError::type_error(cx, self.to_string()),
+            _ => JsError::range_error(cx, self.to_string()),
+        }
+    }
+
+    fn error_parts(&self) -> Option<ErrorParts> {
+        let parts = match self {
+            IntegerError::TypeExpected => ErrorParts {
+                range: false,
+                expected: "number".to_owned(),
+                found: None,
+            },
+            IntegerError::NotAnInteger(n) => ErrorParts {
+                range: true,
+                expected: "an integer".to_owned(),
+                found: Some(n.to_string()),
+            },
+            // Not phrased as expected and found
+            IntegerError::OutOfRange(..) => return None,
+        };
+
+        Some(parts)
+    }
+}
+
+/// Extracts a number with a truncating `as` cast instead of the range checks
+/// performed by the integer extractors. Fractional parts are discarded and
+/// out-of-range values saturate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Lossy<T>(pub T);
+
+macro_rules! impl_integer_from_js {
+    ($($t:ident $(=> $typed_array:ident)?),*) => {
+        $(
+            impl<'a> TryFromJs<'a> for $t {
+                type Error = IntegerError;
+
+                fn try_from_js<C: Context<'a>>(
+                    cx: &mut C,
+                    v: Handle<'a, JsValue>,
+                ) -> NeonResult<Result<Self, Self::Error>> {
+                    let n = match v.downcast::<JsNumber, _>(cx) {
+                        Ok(n) => n.value(cx),
+                        Err(_) => return Ok(Err(IntegerError::TypeExpected)),
+                    };
+
+                    if !n.is_finite() || n.fract() != 0.0 {
+                        return Ok(Err(IntegerError::NotAnInteger(n)));
+                    }
+
+                    // Adding one to the maximum rounds to the next power of two
+                    // for 64-bit types, which is exactly the exclusive bound.
+                    if n < $t::MIN as f64 || n >= $t::MAX as f64 + 1.0 {
+                        return Ok(Err(IntegerError::OutOfRange(n, stringify!($t))));
+                    }
+
+                    Ok(Ok(n as $t))
+                }
+
+                $(
+                    fn vec_from_typed_array<C: Context<'a>>(
+                        cx: &mut C,
+                        v: Handle<'a, JsValue>,
+                    ) -> Option<Vec<Self>> {
+                        copy_typed_array(cx, v, TypedArrayType::$typed_array)
+                    }
+                )?
+            }
+
+            impl<'a> TryFromJs<'a> for Lossy<$t> {
+                type Error = IntegerError;
+
+                fn try_from_js<C: Context<'a>>(
+                    cx: &mut C,
+                    v: Handle<'a, JsValue>,
+                ) -> NeonResult<Result<Self, Self::Error>> {
+                    Ok(match v.downcast::<JsNumber, _>(cx) {
+                        Ok(n) => Ok(Lossy(n.value(cx) as $t)),
+                        Err(_) => Err(IntegerError::TypeExpected),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_from_js!(
+    u8 => Uint8Array,
+    u16 => Uint16Array,
+    u32 => Uint32Array,
+    u64,
+    usize,
+    i8 => Int8Array,
+    i16 => Int16Array,
+    i32 => Int32Array,
+    i64,
+    isize
+);
+
+// Copies the elements of a typed array of type `typ`, whose elements must have
+// the layout of `T`. Returns `None` if `v` is not a typed array of that type.
+pub(super) fn copy_typed_array<'a, C: Context<'a>, T: Copy>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+    typ: TypedArrayType,
+) -> Option<Vec<T>> {
+    let env = cx.env().to_raw();
+    let raw = v.to_raw();
+
+    unsafe {
+        if !neon_runtime::tag::is_typedarray(env, raw) {
+            return None;
+        }
+
+        let info = neon_runtime::typedarray::info(env, raw);
+
+        if info.typ != typ {
+            return None;
+        }
+
+        debug_assert_eq!(neon_runtime::typedarray::element_size(typ), size_of::<T>());
+
+        let mut result = Vec::<T>::with_capacity(info.length);
+
+        // The data of an empty array may be null, and the elements are copied
+        // as bytes since the data is not guaranteed to be aligned for `T`
+        if info.length > 0 {
+            ptr::copy_nonoverlapping(
+                info.data as *const u8,
+                result.as_mut_ptr() as *mut u8,
+                info.length * size_of::<T>(),
+            );
+            result.set_len(info.length);
+        }
+
+        Some(result)
+    }
+}
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::NeonResult;
+use crate::types::extract::{ByteBuf, TryFromJs};
+use crate::types::{JsBuffer, JsError, JsObject, JsString, JsValue};
+
+/// Accepts the same path arguments as the Node `fs` module: a string, a
+/// `Buffer` holding the raw path bytes, or a `URL` object using the `file:`
+/// protocol.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use std::path::PathBuf;
+/// use neon::types::extract::TryFromJs;
+///
+/// fn file_name(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let arg = cx.argument::<JsValue>(0)?;
+///     let path = PathBuf::from_js(&mut cx, arg)?;
+///
+///     match path.file_name() {
+///         Some(name) => Ok(cx.string(name.to_string_lossy()).upcast()),
+///         None => Ok(cx.null().upcast()),
+///     }
+/// }
+/// # }
+/// ```
+impl<'a> TryFromJs<'a> for PathBuf {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        if let Ok(s) = v.downcast::<JsString, _>(cx) {
+            return Ok(Ok(PathBuf::from(s.value(cx))));
+        }
+
+        if v.is_a::<JsBuffer, _>(cx) {
+            let bytes = match ByteBuf::try_from_js(cx, v)? {
+                Ok(ByteBuf(bytes)) => bytes,
+                Err(err) => return Ok(Err(err)),
+            };
+
+            return Ok(match path_from_bytes(bytes) {
+                Some(path) => Ok(path),
+                None => Err(JsError::type_error(cx, "path is not valid UTF-8")?.upcast()),
+            });
+        }
+
+        if let Ok(url) = v.downcast::<JsObject, _>(cx) {
+            if let Some(url) = file_url(cx, url)? {
+                return Ok(match url {
+                    Ok(path) => Ok(path),
+                    Err(msg) => Err(JsError::type_error(cx, msg)?.upcast()),
+                });
+            }
+        }
+
+        let msg = "expected string, Buffer or file URL";
+
+        Ok(Err(JsError::type_error(cx, msg)?.upcast()))
+    }
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    Some(PathBuf::from(OsString::from_vec(bytes)))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+// Duck-types a WHATWG `URL` the way Node does, by looking for `href` and
+// `protocol` properties. Returns `None` if the object is not URL-like.
+fn file_url<'a, C: Context<'a>>(
+    cx: &mut C,
+    url: Handle<'a, JsObject>,
+) -> NeonResult<Option<Result<PathBuf, &'static str>>> {
+    let href = url.get(cx, "href")?;
+    let protocol = url.get(cx, "protocol")?;
+
+    let protocol = match (
+        href.is_a::<JsString, _>(cx),
+        protocol.downcast::<JsString, _>(cx),
+    ) {
+        (true, Ok(protocol)) => protocol.value(cx),
+        _ => return Ok(None),
+    };
+
+    if protocol != "file:" {
+        return Ok(Some(Err("the URL must be of scheme file")));
+    }
+
+    let hostname = url.get(cx, "hostname")?;
+    let hostname = match hostname.downcast::<JsString, _>(cx) {
+        Ok(hostname) => hostname.value(cx),
+        Err(_) => String::new(),
+    };
+
+    let pathname = url.get(cx, "pathname")?;
+    let pathname = match pathname.downcast::<JsString, _>(cx) {
+        Ok(pathname) => pathname.value(cx),
+        Err(_) => return Ok(None),
+    };
+
+    Ok(Some(file_url_to_path(&hostname, &pathname)))
+}
+
+// Mirrors `url.fileURLToPath` from the Node standard library.
+#[cfg(not(windows))]
+fn file_url_to_path(hostname: &str, pathname: &str) -> Result<PathBuf, &'static str> {
+    if !hostname.is_empty() {
+        return Err("file URL host must be \"localhost\" or empty");
+    }
+
+    if pathname.contains("%2F") || pathname.contains("%2f") {
+        return Err("file URL path must not include encoded / characters");
+    }
+
+    let path = percent_decode(pathname)?;
+
+    path_from_bytes(path).ok_or("path is not valid UTF-8")
+}
+
+#[cfg(windows)]
+fn file_url_to_path(hostname: &str, pathname: &str) -> Result<PathBuf, &'static str> {
+    let lower = pathname.to_ascii_lowercase();
+
+    if lower.contains("%2f") || lower.contains("%5c") {
+        return Err("file URL path must not include encoded \\ or / characters");
+    }
+
+    let path = percent_decode(pathname)?;
+    let path = String::from_utf8(path).map_err(|_| "path is not valid UTF-8")?;
+    let path = path.replace('/', "\\");
+
+    if !hostname.is_empty() {
+        return Ok(PathBuf::from(format!("\\\\{}{}", hostname, path)));
+    }
+
+    // Strip the leading separator from `/C:/...`.
+    let path = path.strip_prefix('\\').unwrap_or(&path);
+    let bytes = path.as_bytes();
+
+    if bytes.len() < 2 || !bytes[0].is_ascii_alphabetic() || bytes[1] != b':' {
+        return Err("file URL path must be absolute");
+    }
+
+    Ok(PathBuf::from(path))
+}
+
+fn percent_decode(s: &str) -> Result<Vec<u8>, &'static str> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+
+    while let Some(b) = iter.next() {
+        if b != b'%' {
+            bytes.push(b);
+            continue;
+        }
+
+        let hex = [iter.next(), iter.next()];
+        let digits = match hex {
+            [Some(hi), Some(lo)] => (char::from(hi).to_digit(16), char::from(lo).to_digit(16)),
+            _ => (None, None),
+        };
+
+        match digits {
+            (Some(hi), Some(lo)) => bytes.push((hi * 16 + lo) as u8),
+            _ => return Err("file URL path contains an invalid percent-encoding"),
+        }
+    }
+
+    Ok(bytes)
+}
@@ -0,0 +1,122 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::context::Context;
+use crate::event::Channel;
+use crate::handle::{Handle, Root};
+use crate::object::Object;
+use crate::result::NeonResult;
+use crate::types::extract::{TryFromJs, TryIntoJs, TypeExpected};
+use crate::types::{JsFunction, JsValue};
+
+/// A JavaScript callback for reporting the progress of work on another
+/// thread, extracted from a function argument.
+///
+/// `Progress<T>` is `Send`, so it can be moved into the body of a
+/// [`#[neon::export(task)]`](macro@crate::export) function or an `async`
+/// method of a [`#[neon::class]`](macro@crate::class). Each call to
+/// [`report`](Progress::report) calls the callback on the JavaScript thread
+/// with the value converted by [`TryIntoJs`], while the `Promise` for the
+/// result is pending.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-4", feature = "channel-api", feature = "promise-api", feature = "task-api"))] {
+/// use neon::types::extract::Progress;
+///
+/// #[neon::export(task)]
+/// fn count_lines(files: Vec<String>, progress: Progress<f64>) -> f64 {
+///     let mut lines = 0;
+///
+///     for (i, file) in files.iter().enumerate() {
+///         lines += std::fs::read_to_string(file).map_or(0, |s| s.lines().count());
+///         progress.report((i + 1) as f64);
+///     }
+///
+///     lines as f64
+/// }
+/// # }
+/// ```
+///
+/// Reports are delivered in order through a [`Channel`], but they are not
+/// ordered with the settling of the `Promise`; the last reports may arrive
+/// after the `Promise` has resolved. An exception thrown by the callback is
+/// handled like an exception thrown from a closure sent through a `Channel`.
+pub struct Progress<T> {
+    callback: Arc<Callback>,
+    _marker: PhantomData<fn(T)>,
+}
+
+struct Callback {
+    channel: Channel,
+    // `None` once the root has been sent back to the JavaScript thread
+    function: Option<Root<JsFunction>>,
+}
+
+impl Drop for Callback {
+    fn drop(&mut self) {
+        if let Some(function) = self.function.take() {
+            let _ = self.channel.try_send(move |mut cx| {
+                function.drop(&mut cx);
+                Ok(())
+            });
+        }
+    }
+}
+
+impl<T> Progress<T>
+where
+    T: for<'b> TryIntoJs<'b> + Send + 'static,
+{
+    /// Calls the callback with `value` on the JavaScript thread. Reports are
+    /// dropped if the module is being unloaded.
+    pub fn report(&self, value: T) {
+        let callback = self.callback.clone();
+
+        let _ = self.callback.channel.try_send(move |mut cx| {
+            let function = match &callback.function {
+                Some(function) => function.to_inner(&mut cx),
+                None => return Ok(()),
+            };
+
+            let value = value.try_into_js(&mut cx)?.upcast::<JsValue>();
+            let this = cx.undefined();
+
+            function.call(&mut cx, this, [value])?;
+
+            Ok(())
+        });
+    }
+}
+
+impl<T> Clone for Progress<T> {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> TryFromJs<'a> for Progress<T> {
+    type Error = TypeExpected<JsFunction>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let function = match v.downcast::<JsFunction, _>(cx) {
+            Ok(function) => function,
+            Err(_) => return Ok(Err(TypeExpected::new())),
+        };
+
+        let callback = Callback {
+            channel: cx.channel(),
+            function: Some(function.root(cx)),
+        };
+
+        Ok(Ok(Progress {
+            callback: Arc::new(callback),
+            _marker: PhantomData,
+        }))
+    }
+}
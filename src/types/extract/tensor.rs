@@ -0,0 +1,132 @@
+// Conversions shared by the `ndarray` and `nalgebra` integrations, which
+// represent an array in JavaScript as a typed array of its elements in
+// row-major order with a shape, e.g., `{ data: Float64Array, shape: [2, 3] }`.
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::extract::TryFromJs;
+use crate::types::{
+    BinaryViewType, JsArray, JsArrayBuffer, JsError, JsFunction, JsObject, JsValue,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Numbers that can be the elements of a JavaScript typed array, for
+/// converting `ndarray` arrays and `nalgebra` matrices.
+///
+/// Implemented for `f64`, `f32`, `u8`, `u16`, `u32`, `i8`, `i16` and `i32`,
+/// which are stored in a `Float64Array`, `Float32Array`, `Uint8Array`, etc.
+pub trait TypedArrayElement:
+    BinaryViewType + Copy + Send + Sync + 'static + for<'a> TryFromJs<'a> + private::Sealed
+{
+    #[doc(hidden)]
+    const CLASS: &'static str;
+}
+
+macro_rules! impl_typed_array_element {
+    ($($t:ty => $class:ident),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl TypedArrayElement for $t {
+                const CLASS: &'static str = stringify!($class);
+            }
+        )*
+    };
+}
+
+impl_typed_array_element!(
+    f64 => Float64Array,
+    f32 => Float32Array,
+    u8 => Uint8Array,
+    u16 => Uint16Array,
+    u32 => Uint32Array,
+    i8 => Int8Array,
+    i16 => Int16Array,
+    i32 => Int32Array
+);
+
+// Creates `{ data, shape }` from elements in row-major order
+pub(super) fn to_js<'a, C, T, I>(cx: &mut C, data: I, shape: &[usize]) -> JsResult<'a, JsObject>
+where
+    C: Context<'a>,
+    T: TypedArrayElement,
+    I: ExactSizeIterator<Item = T>,
+{
+    let size = data.len() * std::mem::size_of::<T>();
+
+    if size > u32::MAX as usize {
+        return cx.throw_range_error("too many elements for a typed array");
+    }
+
+    let mut buffer = JsArrayBuffer::new(cx, size as u32)?;
+
+    cx.borrow_mut(&mut buffer, |buffer| {
+        for (element, value) in buffer.as_mut_slice::<T>().iter_mut().zip(data) {
+            *element = value;
+        }
+    });
+
+    let class = cx
+        .global()
+        .get(cx, T::CLASS)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+    let data = class.construct(cx, [buffer])?;
+    let shape = JsArray::from_iter(cx, shape.iter().map(|&n| n as f64))?;
+    let result = cx.empty_object();
+
+    result.set(cx, "data", data)?;
+    result.set(cx, "shape", shape)?;
+
+    Ok(result)
+}
+
+// Elements in row-major order with the length of each axis
+pub(super) struct Tensor<T> {
+    pub(super) data: Vec<T>,
+    pub(super) shape: Vec<usize>,
+}
+
+// Reads `{ data, shape }`, checking that the shape matches the number of
+// elements. `data` may be a typed array of `T` or an array of numbers.
+pub(super) fn from_js<'a, C, T>(
+    cx: &mut C,
+    v: Handle<'a, JsValue>,
+) -> NeonResult<Result<Tensor<T>, Handle<'a, JsValue>>>
+where
+    C: Context<'a>,
+    T: TypedArrayElement,
+{
+    let object = match v.downcast::<JsObject, _>(cx) {
+        Ok(object) => object,
+        Err(_) => {
+            let msg = format!("expected {{ data: {}, shape: number[] }}", T::CLASS);
+
+            return Ok(Err(JsError::type_error(cx, msg)?.upcast()));
+        }
+    };
+
+    let data = object.get(cx, "data")?;
+    let data = match Vec::<T>::try_from_js(cx, data)? {
+        Ok(data) => data,
+        Err(err) => return Ok(Err(err)),
+    };
+
+    let shape = object.get(cx, "shape")?;
+    let shape = match Vec::<usize>::try_from_js(cx, shape)? {
+        Ok(shape) => shape,
+        Err(err) => return Ok(Err(err)),
+    };
+
+    if shape.iter().try_fold(1usize, |n, &dim| n.checked_mul(dim)) != Some(data.len()) {
+        let msg = format!("shape {:?} does not match {} elements", shape, data.len());
+
+        return Ok(Err(JsError::range_error(cx, msg)?.upcast()));
+    }
+
+    Ok(Ok(Tensor { data, shape }))
+}
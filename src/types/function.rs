@@ -0,0 +1,223 @@
+use std::os::raw::c_void;
+
+use crate::context::Context;
+use crate::handle::{Handle, Managed, Root};
+use crate::object::Object;
+use crate::result::JsResult;
+use crate::types::boxed::Finalize;
+use crate::types::{build, JsFunction, JsObject, JsValue, Value, V8_ARGC_LIMIT};
+use neon_runtime::raw;
+
+/// A fixed number of arguments to a JavaScript function, passed to
+/// [`JsFunction::call_with_args`](crate::types::JsFunction::call_with_args)
+/// as a tuple of handles.
+///
+/// Unlike a `Vec` of handles, a tuple does not need to be allocated or
+/// upcast: the arguments are passed to the function from an array on the
+/// stack. Tuples of up to eight arguments are supported.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// fn call_with_pair(mut cx: FunctionContext) -> JsResult<JsValue> {
+///     let f = cx.argument::<JsFunction>(0)?;
+///     let this = cx.undefined();
+///     let a = cx.number(1);
+///     let b = cx.string("two");
+///
+///     f.call_with_args(&mut cx, this, (a, b))
+/// }
+/// # }
+/// ```
+pub trait Arguments<'a>: private::ArgumentsInternal<'a> {}
+
+pub(crate) mod private {
+    use neon_runtime::raw;
+
+    pub trait ArgumentsInternal<'a> {
+        fn with_raw<R>(self, f: impl FnOnce(&[raw::Local]) -> R) -> R;
+    }
+}
+
+macro_rules! impl_arguments {
+    ($(($($ty:ident $arg:ident),*);)*) => {
+        $(
+            impl<'a, $($ty: Value),*> private::ArgumentsInternal<'a> for ($(Handle<'a, $ty>,)*) {
+                fn with_raw<R>(self, f: impl FnOnce(&[raw::Local]) -> R) -> R {
+                    let ($($arg,)*) = self;
+
+                    f(&[$($arg.to_raw()),*])
+                }
+            }
+
+            impl<'a, $($ty: Value),*> Arguments<'a> for ($(Handle<'a, $ty>,)*) {}
+        )*
+    };
+}
+
+impl_arguments! {
+    ();
+    (A a);
+    (A a, B b);
+    (A a, B b, C c);
+    (A a, B b, C c, D d);
+    (A a, B b, C c, D d, E e);
+    (A a, B b, C c, D d, E e, F f);
+    (A a, B b, C c, D d, E e, F f, G g);
+    (A a, B b, C c, D d, E e, F f, G g, H h);
+}
+
+/// A JavaScript function prepared to be called many times, for example once
+/// for each row of a large result.
+///
+/// A `CallSite` keeps the function and its `this` rooted, and reuses one
+/// buffer for the arguments of every call, so a call neither creates a
+/// `Root` nor allocates once the buffer has grown to the number of arguments.
+///
+/// Like a [`Root`], a `CallSite` may be sent to another thread, but it can
+/// only be called on the JavaScript thread that created it, e.g., in a
+/// [`Channel`](crate::event::Channel) closure.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::CallSite;
+///
+/// fn for_each_row(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let callback = cx.argument::<JsFunction>(0)?;
+///     let mut site = CallSite::new(&mut cx, callback);
+///
+///     for i in 0..1000 {
+///         let index = cx.number(i);
+///         let label = cx.string(format!("row {}", i));
+///
+///         site.call_with(&mut cx, [index.upcast::<JsValue>(), label.upcast()])?;
+///     }
+///
+///     site.drop(&mut cx);
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+pub struct CallSite {
+    callee: Root<JsFunction>,
+    this: Option<Root<JsObject>>,
+    args: Vec<raw::Local>,
+}
+
+// Safety: `Root` is `Send` and the argument buffer only holds values for the
+// duration of a call; it is always empty when the `CallSite` can be moved.
+unsafe impl Send for CallSite {}
+
+impl CallSite {
+    /// Prepares `callee` to be called with `this` as `undefined`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, callee: Handle<JsFunction>) -> Self {
+        Self {
+            callee: Root::new(cx, &*callee),
+            this: None,
+            args: Vec::new(),
+        }
+    }
+
+    /// Prepares `callee` to be called with `this` as the given object.
+    pub fn with_this<'a, C, T>(cx: &mut C, callee: Handle<JsFunction>, this: Handle<T>) -> Self
+    where
+        C: Context<'a>,
+        T: Object,
+    {
+        Self {
+            callee: Root::new(cx, &*callee),
+            this: Some(Root::new(cx, &*this.upcast::<JsObject>())),
+            args: Vec::new(),
+        }
+    }
+
+    /// Returns the function that is called.
+    pub fn callee<'a, C: Context<'a>>(&self, cx: &mut C) -> Handle<'a, JsFunction> {
+        self.callee.to_inner(cx)
+    }
+
+    /// Calls the function with any number of arguments, reusing the argument
+    /// buffer of previous calls.
+    pub fn call_with<'a, 'b, C, A, AS>(&mut self, cx: &mut C, args: AS) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+        A: Value + 'b,
+        AS: IntoIterator<Item = Handle<'b, A>>,
+    {
+        self.args.clear();
+        self.args.extend(args.into_iter().map(|arg| arg.to_raw()));
+
+        if self.args.len() > V8_ARGC_LIMIT {
+            self.args.clear();
+            return cx.throw_range_error("too many arguments");
+        }
+
+        let (callee, this) = self.prepare(cx);
+        let env = cx.env();
+        let result = build(env, |out| unsafe {
+            neon_runtime::fun::call(
+                out,
+                env.to_raw(),
+                callee,
+                this,
+                self.args.len() as i32,
+                self.args.as_ptr() as *mut c_void,
+            )
+        });
+
+        self.args.clear();
+        result
+    }
+
+    /// Calls the function with a fixed number of arguments, given as a tuple
+    /// of handles. See [`Arguments`].
+    pub fn call_with_args<'a, 'b, C, AS>(&self, cx: &mut C, args: AS) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+        AS: Arguments<'b>,
+    {
+        let (callee, this) = self.prepare(cx);
+        let env = cx.env();
+
+        args.with_raw(|argv| {
+            build(env, |out| unsafe {
+                neon_runtime::fun::call(
+                    out,
+                    env.to_raw(),
+                    callee,
+                    this,
+                    argv.len() as i32,
+                    argv.as_ptr() as *mut c_void,
+                )
+            })
+        })
+    }
+
+    /// Releases the function and `this`. Like [`Root::drop`], this should be
+    /// called when the `CallSite` is no longer needed.
+    pub fn drop<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.callee.drop(cx);
+
+        if let Some(this) = self.this {
+            this.drop(cx);
+        }
+    }
+
+    fn prepare<'a, C: Context<'a>>(&self, cx: &mut C) -> (raw::Local, raw::Local) {
+        let callee = self.callee.to_inner(cx).to_raw();
+        let this = match &self.this {
+            Some(this) => this.to_inner(cx).to_raw(),
+            None => cx.undefined().to_raw(),
+        };
+
+        (callee, this)
+    }
+}
+
+impl Finalize for CallSite {
+    fn finalize<'a, C: Context<'a>>(self, cx: &mut C) {
+        self.drop(cx);
+    }
+}
@@ -7,6 +7,10 @@ use crate::types::{Handle, JsObject, Managed};
 use neon_runtime;
 use neon_runtime::call::CCallback;
 use neon_runtime::raw;
+#[cfg(feature = "napi-1")]
+use std::any::Any;
+#[cfg(feature = "napi-1")]
+use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
 
@@ -59,17 +63,55 @@ impl<T: Value> Callback<raw::Local> for FunctionCallback<T> {
                 let data = info.data(env);
                 let dynamic_callback: fn(FunctionContext) -> JsResult<T> =
                     mem::transmute(neon_runtime::fun::get_dynamic_callback(env.to_raw(), data));
-                if let Ok(value) = convert_panics(env, || dynamic_callback(cx)) {
-                    value.to_raw()
-                } else {
-                    // We do not have a Js Value to return, most likely due to an exception.
-                    // If we are in a throwing state, constructing a Js Value would be invalid.
-                    // While not explicitly written, the N-API documentation includes many examples
-                    // of returning `NULL` when a native function does not return a value.
-                    // Note, `raw::Local` in this context is a type alias for `*mut napi_value` and not a struct
-                    // https://nodejs.org/api/n-api.html#n_api_napi_create_function
-                    std::ptr::null_mut()
-                }
+
+                return_value(env, dynamic_callback, cx)
+            })
+        }
+    }
+
+    fn into_ptr(self) -> *mut c_void {
+        self.0 as *mut _
+    }
+}
+
+/// The callback and data of a function created with `JsFunction::with_data`.
+/// It is wrapped by the function, so it lives as long as the function.
+#[cfg(feature = "napi-1")]
+pub(crate) struct FunctionData {
+    callback: *mut c_void,
+    data: Box<dyn Any + Send>,
+}
+
+// Safety: `callback` is a function pointer
+#[cfg(feature = "napi-1")]
+unsafe impl Send for FunctionData {}
+
+#[cfg(feature = "napi-1")]
+impl FunctionData {
+    pub(crate) fn new<T: Value>(
+        callback: fn(FunctionContext) -> JsResult<T>,
+        data: Box<dyn Any + Send>,
+    ) -> Self {
+        Self {
+            callback: callback as *mut c_void,
+            data,
+        }
+    }
+}
+
+/// A callback that finds its function and data in a `FunctionData`.
+#[cfg(feature = "napi-1")]
+pub(crate) struct DataCallback<T: Value>(pub *const FunctionData, pub PhantomData<T>);
+
+#[cfg(feature = "napi-1")]
+impl<T: Value> Callback<raw::Local> for DataCallback<T> {
+    extern "C" fn invoke(env: Env, info: CallbackInfo<'_>) -> raw::Local {
+        unsafe {
+            let data = &*(info.data(env) as *const FunctionData);
+            let callback: fn(FunctionContext) -> JsResult<T> = mem::transmute(data.callback);
+
+            info.with_cx_data::<JsObject, _, _>(env, &*data.data, |cx| {
+                return_value(env, callback, cx)
             })
         }
     }
@@ -79,6 +121,25 @@ impl<T: Value> Callback<raw::Local> for FunctionCallback<T> {
     }
 }
 
+#[cfg(feature = "napi-1")]
+fn return_value<T: Value>(
+    env: Env,
+    callback: fn(FunctionContext) -> JsResult<T>,
+    cx: FunctionContext,
+) -> raw::Local {
+    if let Ok(value) = convert_panics(env, || callback(cx)) {
+        value.to_raw()
+    } else {
+        // We do not have a Js Value to return, most likely due to an exception.
+        // If we are in a throwing state, constructing a Js Value would be invalid.
+        // While not explicitly written, the N-API documentation includes many examples
+        // of returning `NULL` when a native function does not return a value.
+        // Note, `raw::Local` in this context is a type alias for `*mut napi_value` and not a struct
+        // https://nodejs.org/api/n-api.html#n_api_napi_create_function
+        std::ptr::null_mut()
+    }
+}
+
 /// A dynamically computed callback that can be passed through C to the engine.
 /// This type makes it possible to export a dynamically computed Rust function
 /// as a pair of 1) a raw pointer to the dynamically computed function, and 2)
@@ -63,7 +63,7 @@
 //!   types all implement the [`Object`](crate::object::Object) trait, which allows
 //!   getting and setting properties.
 //!   - **Standard object types:** [`JsFunction`](JsFunction), [`JsArray`](JsArray),
-//!     [`JsDate`](JsDate), and [`JsError`](JsError).
+//!     [`JsDate`](JsDate), [`JsRegExp`](JsRegExp), and [`JsError`](JsError).
 //!   - **Typed arrays:** [`JsBuffer`](JsBuffer) and [`JsArrayBuffer`](JsArrayBuffer).
 //!   - **Custom types:** [`JsBox`](JsBox), a special Neon type that allows the creation
 //!     of custom objects that own Rust data structures.
@@ -80,12 +80,34 @@ pub(crate) mod boxed;
 #[cfg(feature = "napi-5")]
 pub(crate) mod date;
 pub(crate) mod error;
+#[cfg(feature = "napi-1")]
+pub mod extract;
+#[cfg(feature = "napi-1")]
+pub(crate) mod function;
 
 pub(crate) mod internal;
+#[cfg(feature = "napi-1")]
+pub(crate) mod pool;
+#[cfg(all(feature = "napi-1", feature = "promise-api"))]
+pub(crate) mod promise;
+#[cfg(feature = "napi-1")]
+pub(crate) mod proxy;
+#[cfg(feature = "napi-1")]
+pub(crate) mod regexp;
+#[cfg(all(feature = "napi-1", feature = "temporal-api"))]
+pub(crate) mod temporal;
+#[cfg(feature = "napi-1")]
+pub(crate) mod url;
 pub(crate) mod utf8;
+#[cfg(feature = "napi-1")]
+pub(crate) mod weak;
 
+#[cfg(feature = "napi-1")]
+use self::internal::{DataCallback, FunctionData};
 use self::internal::{FunctionCallback, ValueInternal};
 use self::utf8::Utf8;
+#[cfg(feature = "napi-1")]
+use crate::context::internal::Arena;
 use crate::context::internal::Env;
 use crate::context::{Context, FunctionContext};
 use crate::handle::internal::SuperType;
@@ -96,17 +118,42 @@ use crate::types::internal::Callback;
 use neon_runtime;
 use neon_runtime::raw;
 use smallvec::SmallVec;
+#[cfg(feature = "napi-1")]
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::os::raw::c_void;
 
+#[cfg(feature = "napi-1")]
+pub use self::binary::InvalidUtf8;
+#[cfg(feature = "napi-1")]
+pub use self::binary::JsSharedArrayBuffer;
+#[cfg(feature = "napi-7")]
+pub use self::binary::LentBuffer;
 pub use self::binary::{BinaryData, BinaryViewType, JsArrayBuffer, JsBuffer};
 #[cfg(feature = "napi-1")]
 pub use self::boxed::{Finalize, JsBox};
 #[cfg(feature = "napi-5")]
-pub use self::date::{DateError, DateErrorKind, JsDate};
+pub use self::date::{DateError, DateErrorKind, InvalidDate, JsDate};
 pub use self::error::JsError;
+#[cfg(feature = "napi-1")]
+pub use self::function::{Arguments, CallSite};
+#[cfg(feature = "napi-1")]
+pub use self::pool::{BufferPool, PooledBytes};
+#[cfg(all(feature = "napi-1", feature = "promise-api"))]
+pub use self::promise::{Deferred, JsPromise};
+#[cfg(feature = "napi-1")]
+pub use self::proxy::{JsProxy, ProxyHandler};
+#[cfg(feature = "napi-1")]
+pub use self::regexp::JsRegExp;
+#[cfg(all(feature = "napi-1", feature = "temporal-api"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "temporal-api")))]
+pub use self::temporal::{JsTemporalDuration, JsTemporalInstant};
+#[cfg(feature = "napi-1")]
+pub use self::url::{JsUrl, JsUrlSearchParams};
+#[cfg(feature = "napi-1")]
+pub use self::weak::{JsWeakMap, JsWeakSet};
 
 pub(crate) fn build<'a, T: Managed, F: FnOnce(&mut raw::Local) -> bool>(
     env: Env,
@@ -122,6 +169,33 @@ pub(crate) fn build<'a, T: Managed, F: FnOnce(&mut raw::Local) -> bool>(
     }
 }
 
+// Checks the `Symbol.toStringTag` of an object, as used by
+// `Object.prototype.toString`, to identify built-in classes without
+// `instanceof`, throwing a `TypeError` if it does not match
+#[cfg(feature = "napi-1")]
+pub(crate) fn check_tag<'a, C: Context<'a>>(
+    cx: &mut C,
+    value: Handle<'a, JsValue>,
+    tag: &str,
+) -> JsResult<'a, JsObject> {
+    if let Ok(object) = value.downcast::<JsObject, _>(cx) {
+        let to_string_tag = cx
+            .global()
+            .get(cx, "Symbol")?
+            .downcast_or_throw::<JsFunction, _>(cx)?
+            .get(cx, "toStringTag")?;
+        let actual = object.get(cx, to_string_tag)?;
+
+        if let Ok(actual) = actual.downcast::<JsString, _>(cx) {
+            if actual.value(cx) == tag {
+                return Ok(object);
+            }
+        }
+    }
+
+    cx.throw_type_error(format!("expected a {}", tag))
+}
+
 impl<T: Value> SuperType<T> for JsValue {
     fn upcast_internal(v: T) -> JsValue {
         JsValue(v.to_raw())
@@ -146,6 +220,72 @@ pub trait Value: ValueInternal {
     fn as_value<'a, C: Context<'a>>(self, _: &mut C) -> Handle<'a, JsValue> {
         JsValue::new_internal(self.to_raw())
     }
+
+    /// Describes the value for debugging with Node's `util.inspect`, or with
+    /// `String()` where `util` is not available.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn log_options(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    ///     let options = cx.argument::<JsObject>(0)?;
+    ///
+    ///     // e.g., `{ verbose: true, depth: 2 }`
+    ///     eprintln!("options: {}", options.to_debug_string(&mut cx)?);
+    ///
+    ///     Ok(cx.undefined())
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    fn to_debug_string<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<String> {
+        let value = self.as_value(cx);
+        let global = cx.global();
+        let undefined = cx.undefined();
+        let describe = match inspect(cx, global)? {
+            Some(inspect) => inspect,
+            None => global
+                .get(cx, "String")?
+                .downcast_or_throw::<JsFunction, _>(cx)?,
+        };
+
+        let description = describe.call(cx, undefined, [value])?;
+
+        Ok(description.downcast_or_throw::<JsString, _>(cx)?.value(cx))
+    }
+}
+
+// Looks up `util.inspect` through `process.getBuiltinModule`, which is
+// available in Node 20.16 and later
+#[cfg(feature = "napi-1")]
+fn inspect<'a, C: Context<'a>>(
+    cx: &mut C,
+    global: Handle<'a, JsObject>,
+) -> NeonResult<Option<Handle<'a, JsFunction>>> {
+    let process = match global.get(cx, "process")?.downcast::<JsObject, _>(cx) {
+        Ok(process) => process,
+        Err(_) => return Ok(None),
+    };
+
+    let get_builtin_module = match process
+        .get(cx, "getBuiltinModule")?
+        .downcast::<JsFunction, _>(cx)
+    {
+        Ok(get_builtin_module) => get_builtin_module,
+        Err(_) => return Ok(None),
+    };
+
+    let name = cx.string("util");
+    let util = match get_builtin_module
+        .call(cx, process, [name])?
+        .downcast::<JsObject, _>(cx)
+    {
+        Ok(util) => util,
+        Err(_) => return Ok(None),
+    };
+
+    Ok(util.get(cx, "inspect")?.downcast::<JsFunction, _>(cx).ok())
 }
 
 /// A JavaScript value of any type.
@@ -448,6 +588,56 @@ impl JsString {
         }
     }
 
+    /// Returns the contents of the string as a `&str` borrowed from storage
+    /// owned by the context, avoiding an allocation for each string.
+    ///
+    /// The storage is freed when the context exits, so this is best suited
+    /// to reading many short strings, e.g., the keys of an object. Prefer
+    /// [`value`](JsString::value) to keep a string after the context exits.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn count_words(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let text = cx.argument::<JsString>(0)?.to_str(&mut cx);
+    ///
+    ///     Ok(cx.number(text.split_whitespace().count() as f64))
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn to_str<'a, C: Context<'a>>(self, cx: &mut C) -> &'a str {
+        let env = cx.env().to_raw();
+
+        unsafe {
+            // Includes space for the null terminator written by N-API
+            let capacity = neon_runtime::string::utf8_len(env, self.to_raw()) as usize + 1;
+
+            cx.arena().alloc_str(capacity, |p, capacity| {
+                neon_runtime::string::data(env, p, capacity as isize, self.to_raw()) as usize
+            })
+        }
+    }
+
+    /// Returns the contents of the string, borrowed from storage owned by the
+    /// context as with [`to_str`](JsString::to_str) if it is short, and
+    /// otherwise copied into a new `String`.
+    ///
+    /// Long strings are not held until the context exits, which bounds the
+    /// memory used when reading strings of unknown size in a loop.
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn value_cow<'a, C: Context<'a>>(self, cx: &mut C) -> Cow<'a, str> {
+        let len = self.size(cx) as usize;
+
+        if len < Arena::CHUNK_SIZE {
+            Cow::Borrowed(self.to_str(cx))
+        } else {
+            Cow::Owned(self.value(cx))
+        }
+    }
+
     pub fn new<'a, C: Context<'a>, S: AsRef<str>>(cx: &mut C, val: S) -> Handle<'a, JsString> {
         JsString::try_new(cx, val).unwrap()
     }
@@ -608,7 +798,289 @@ impl JsArray {
         }
     }
 
+    #[cfg(feature = "napi-1")]
+    /// Constructs a new array from the items of a Rust iterator, converting
+    /// each item with [`TryIntoJs`](extract::TryIntoJs).
+    ///
+    /// The array is preallocated using the iterator's
+    /// [`size_hint`](Iterator::size_hint). Throws a `RangeError` if the
+    /// iterator produces more elements than a JavaScript array can hold.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn squares(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    ///     JsArray::from_iter(&mut cx, (0..n).map(|i| i * i))
+    /// }
+    /// # }
+    /// ```
+    pub fn from_iter<'a, C, I>(cx: &mut C, iter: I) -> JsResult<'a, JsArray>
+    where
+        C: Context<'a>,
+        I: IntoIterator,
+        I::Item: extract::TryIntoJs<'a>,
+    {
+        use self::extract::TryIntoJs;
+
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let array = JsArray::new(cx, std::cmp::min(lower, u32::MAX as usize) as u32);
+
+        for (i, item) in iter.enumerate() {
+            if i >= u32::MAX as usize {
+                return cx.throw_range_error("too many elements for a JavaScript array");
+            }
+
+            let value = item.try_into_js(cx)?;
+            array.set(cx, i as u32, value)?;
+        }
+
+        Ok(array)
+    }
+
+    #[cfg(feature = "napi-1")]
+    /// Constructs a new array of numbers from a slice.
+    ///
+    /// The numbers are copied into a `Float64Array` and converted by
+    /// `Array.from`, which is faster than setting each element from Rust for
+    /// large slices.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn samples(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let samples = (0..1000).map(|i| (i as f64 / 100.0).sin()).collect::<Vec<_>>();
+    ///
+    ///     JsArray::from_slice(&mut cx, &samples)
+    /// }
+    /// # }
+    /// ```
+    pub fn from_slice<'a, C: Context<'a>>(cx: &mut C, values: &[f64]) -> JsResult<'a, JsArray> {
+        let size = std::mem::size_of_val(values);
+
+        if size > u32::MAX as usize {
+            return JsArray::from_iter(cx, values.iter().copied());
+        }
+
+        let global = cx.global();
+        let float64_array = global.get(cx, "Float64Array")?;
+
+        // Without typed arrays, e.g., in `neon::test::MockContext`, the array
+        // is built element by element
+        let float64_array = match float64_array.downcast::<JsFunction, _>(cx) {
+            Ok(float64_array) => float64_array,
+            Err(_) => return JsArray::from_iter(cx, values.iter().copied()),
+        };
+
+        let mut buffer = JsArrayBuffer::new(cx, size as u32)?;
+
+        cx.borrow_mut(&mut buffer, |data| {
+            data.as_mut_slice::<f64>().copy_from_slice(values)
+        });
+
+        let numbers = float64_array.construct(cx, [buffer])?;
+        let array = global
+            .get(cx, "Array")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+        let from = array
+            .get(cx, "from")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        from.call(cx, array, [numbers])?.downcast_or_throw(cx)
+    }
+
     pub fn to_vec<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<Vec<Handle<'a, JsValue>>> {
+        self.try_map(cx, |_, v| Ok(v))
+    }
+
+    #[cfg(feature = "napi-1")]
+    /// Reads the elements in `range`, stopping at the end of the array.
+    ///
+    /// The length is only checked once, so unlike [`to_vec`](JsArray::to_vec),
+    /// elements removed by a getter while reading are read as `undefined`.
+    /// Each element is still read with a separate call into the engine.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn head(mut cx: FunctionContext) -> JsResult<JsArray> {
+    ///     let array = cx.argument::<JsArray>(0)?;
+    ///     let head = array.get_range(&mut cx, 0..10)?;
+    ///     let result = JsArray::new(&mut cx, head.len() as u32);
+    ///
+    ///     result.set_range(&mut cx, 0, &head)?;
+    ///
+    ///     Ok(result)
+    /// }
+    /// # }
+    /// ```
+    pub fn get_range<'a, C: Context<'a>>(
+        self,
+        cx: &mut C,
+        range: std::ops::Range<u32>,
+    ) -> NeonResult<Vec<Handle<'a, JsValue>>> {
+        let end = std::cmp::min(range.end, self.len_inner(cx.env()));
+        let mut elements = Vec::new();
+
+        if !unsafe {
+            neon_runtime::array::get_range(
+                cx.env().to_raw(),
+                self.to_raw(),
+                range.start,
+                end,
+                &mut elements,
+            )
+        } {
+            return Err(Throw);
+        }
+
+        Ok(elements.into_iter().map(JsValue::new_internal).collect())
+    }
+
+    #[cfg(feature = "napi-1")]
+    /// Writes `values` to the elements of the array starting at `start`,
+    /// extending the array if needed. Throws a `RangeError` if an index would
+    /// not fit in a `u32`.
+    ///
+    /// The elements are written with one call to `Array.prototype.splice` for
+    /// up to 65,533 values, instead of one call per element.
+    pub fn set_range<'a, 'b, C: Context<'a>, V: Value>(
+        self,
+        cx: &mut C,
+        start: u32,
+        values: &[Handle<'b, V>],
+    ) -> NeonResult<()> {
+        if values.len() > (u32::MAX - start) as usize {
+            return cx.throw_range_error("too many elements for a JavaScript array");
+        }
+
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let array = cx.global().get(cx, "Array")?;
+
+        // Without `Array`, e.g., in `neon::test::MockContext`, the elements
+        // are written one by one
+        if let Ok(array) = array.downcast::<JsObject, _>(cx) {
+            let prototype = array
+                .get(cx, "prototype")?
+                .downcast_or_throw::<JsObject, _>(cx)?;
+            let splice = prototype
+                .get(cx, "splice")?
+                .downcast_or_throw::<JsFunction, _>(cx)?;
+
+            // `splice` does not write past the end, so a gap before `start`
+            // is left as holes by extending the array first
+            if start > self.len_inner(cx.env()) {
+                let length = cx.number(start);
+
+                self.set(cx, "length", length)?;
+            }
+
+            // The start and the number of elements to replace are also
+            // arguments
+            for (i, chunk) in values.chunks(V8_ARGC_LIMIT - 2).enumerate() {
+                let at = cx.number(start as f64 + (i * (V8_ARGC_LIMIT - 2)) as f64);
+                let count = cx.number(chunk.len() as f64);
+                let mut args = Vec::with_capacity(chunk.len() + 2);
+
+                args.push(at.upcast::<JsValue>());
+                args.push(count.upcast());
+                args.extend(chunk.iter().map(|value| value.upcast::<JsValue>()));
+
+                splice.call(cx, Handle::new_internal(self), args)?;
+            }
+
+            return Ok(());
+        }
+
+        let values = values
+            .iter()
+            .map(|value| {
+                value.check_env(cx);
+                value.to_raw()
+            })
+            .collect::<SmallVec<[_; 8]>>();
+
+        if unsafe {
+            neon_runtime::array::set_range(cx.env().to_raw(), self.to_raw(), start, &values)
+        } {
+            Ok(())
+        } else {
+            Err(Throw)
+        }
+    }
+
+    /// Produces an iterator over the elements of the array.
+    ///
+    /// Like [`to_vec`](JsArray::to_vec), the length of the array is re-checked
+    /// before reading each element, but elements are only read as the iterator
+    /// is advanced. Iteration stops after the first element that throws.
+    ///
+    /// The iterator borrows the context for as long as it is alive; use
+    /// [`ArrayIter::context`](ArrayIter::context) to access it while iterating.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn count_strings(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let array = cx.argument::<JsArray>(0)?;
+    ///     let mut count = 0;
+    ///
+    ///     let mut items = array.iter(&mut cx);
+    ///
+    ///     while let Some(item) = items.next() {
+    ///         if item?.is_a::<JsString, _>(items.context()) {
+    ///             count += 1;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(cx.number(count))
+    /// }
+    /// # }
+    /// ```
+    pub fn iter<'a, 'b, C: Context<'a>>(self, cx: &'b mut C) -> ArrayIter<'a, 'b, C> {
+        ArrayIter {
+            cx,
+            array: self,
+            index: 0,
+            done: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Reads each element of the array and converts it with `f`, collecting the
+    /// results into a `Vec`. Stops at the first error.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    ///     let numbers = cx.argument::<JsArray>(0)?.try_map(&mut cx, |cx, v| {
+    ///         Ok(v.downcast_or_throw::<JsNumber, _>(cx)?.value(cx))
+    ///     })?;
+    ///
+    ///     Ok(cx.number(numbers.into_iter().sum::<f64>()))
+    /// }
+    /// # }
+    /// ```
+    pub fn try_map<'a, C, T, F>(self, cx: &mut C, mut f: F) -> NeonResult<Vec<T>>
+    where
+        C: Context<'a>,
+        F: FnMut(&mut C, Handle<'a, JsValue>) -> NeonResult<T>,
+    {
         let mut result = Vec::with_capacity(self.len_inner(cx.env()) as usize);
         let mut i = 0;
         loop {
@@ -617,7 +1089,8 @@ impl JsArray {
             if i >= self.len_inner(cx.env()) {
                 return Ok(result);
             }
-            result.push(self.get(cx, i)?);
+            let v = self.get(cx, i)?;
+            result.push(f(cx, v)?);
             i += 1;
         }
     }
@@ -671,6 +1144,42 @@ impl ValueInternal for JsArray {
 
 impl Object for JsArray {}
 
+/// An iterator over the elements of a [`JsArray`](JsArray), created by
+/// [`JsArray::iter`](JsArray::iter).
+pub struct ArrayIter<'a, 'b, C: Context<'a>> {
+    cx: &'b mut C,
+    array: JsArray,
+    index: u32,
+    done: bool,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a, 'b, C: Context<'a>> ArrayIter<'a, 'b, C> {
+    /// Borrows the context the iterator was created with.
+    pub fn context(&mut self) -> &mut C {
+        self.cx
+    }
+}
+
+impl<'a, 'b, C: Context<'a>> Iterator for ArrayIter<'a, 'b, C> {
+    type Item = NeonResult<Handle<'a, JsValue>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Since getting a property can trigger arbitrary code,
+        // we have to re-check the length on every iteration.
+        if self.done || self.index >= self.array.len_inner(self.cx.env()) {
+            return None;
+        }
+
+        let result = self.array.get(self.cx, self.index);
+
+        self.index += 1;
+        self.done = result.is_err();
+
+        Some(result)
+    }
+}
+
 /// A JavaScript function object.
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -684,19 +1193,29 @@ impl<T: Object> Object for JsFunction<T> {}
 // Maximum number of function arguments in V8.
 const V8_ARGC_LIMIT: usize = 65535;
 
-unsafe fn prepare_call<'a, 'b, C: Context<'a>, A>(
+// Converts the arguments of a call to raw values, throwing a `RangeError` if
+// there are too many
+fn prepare_call<'a, 'b, C: Context<'a>, A, AS>(
     cx: &mut C,
-    args: &mut [Handle<'b, A>],
-) -> NeonResult<(i32, *mut c_void)>
+    args: AS,
+) -> NeonResult<SmallVec<[raw::Local; 8]>>
 where
     A: Value + 'b,
+    AS: IntoIterator<Item = Handle<'b, A>>,
 {
-    let argv = args.as_mut_ptr();
-    let argc = args.len();
-    if argc > V8_ARGC_LIMIT {
+    let args = args
+        .into_iter()
+        .map(|arg| {
+            arg.check_env(cx);
+            arg.to_raw()
+        })
+        .collect::<SmallVec<[_; 8]>>();
+
+    if args.len() > V8_ARGC_LIMIT {
         return cx.throw_range_error("too many arguments");
     }
-    Ok((argc as i32, argv as *mut c_void))
+
+    Ok(args)
 }
 
 impl JsFunction {
@@ -716,6 +1235,43 @@ impl JsFunction {
             }
         })
     }
+
+    /// Creates a function that calls `f` with `data` attached, which `f` can
+    /// borrow with [`CallContext::data`](crate::context::CallContext::data).
+    ///
+    /// This gives each function its own state without a closure. The data is
+    /// dropped when the function is garbage collected.
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn with_data<'a, C, T, U>(
+        cx: &mut C,
+        data: T,
+        f: fn(FunctionContext) -> JsResult<U>,
+    ) -> JsResult<'a, JsFunction>
+    where
+        C: Context<'a>,
+        T: Send + 'static,
+        U: Value,
+    {
+        let data = Box::new(FunctionData::new(f, Box::new(data)));
+        let ptr = &*data as *const FunctionData;
+        let env = cx.env().to_raw();
+        let function: Handle<JsFunction> = build(cx.env(), |out| unsafe {
+            let callback = DataCallback::<U>(ptr, PhantomData).into_c_callback();
+            neon_runtime::fun::new(out, env, callback)
+        })?;
+
+        // The function owns the data; the heap allocation does not move
+        unsafe {
+            assert!(neon_runtime::class::wrap(
+                env,
+                function.to_raw(),
+                data as Box<_>
+            ));
+        }
+
+        Ok(function)
+    }
 }
 
 impl<CL: Object> JsFunction<CL> {
@@ -730,8 +1286,10 @@ impl<CL: Object> JsFunction<CL> {
         A: Value + 'b,
         AS: IntoIterator<Item = Handle<'b, A>>,
     {
-        let mut args = args.into_iter().collect::<SmallVec<[_; 8]>>();
-        let (argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
+        this.check_env(cx);
+
+        let args = prepare_call(cx, args)?;
+        let (argc, argv) = (args.len() as i32, args.as_ptr() as *mut c_void);
         let env = cx.env().to_raw();
         build(cx.env(), |out| unsafe {
             neon_runtime::fun::call(out, env, self.to_raw(), this.to_raw(), argc, argv)
@@ -743,13 +1301,143 @@ impl<CL: Object> JsFunction<CL> {
         A: Value + 'b,
         AS: IntoIterator<Item = Handle<'b, A>>,
     {
-        let mut args = args.into_iter().collect::<SmallVec<[_; 8]>>();
-        let (argc, argv) = unsafe { prepare_call(cx, &mut args) }?;
+        let args = prepare_call(cx, args)?;
+        let (argc, argv) = (args.len() as i32, args.as_ptr() as *mut c_void);
         let env = cx.env().to_raw();
         build(cx.env(), |out| unsafe {
             neon_runtime::fun::construct(out, env, self.to_raw(), argc, argv)
         })
     }
+
+    /// Creates a bound function, like `Function.prototype.bind`, which calls
+    /// this function with `this` as the receiver and `args` followed by the
+    /// arguments it is called with.
+    ///
+    /// ```
+    /// # #[cfg(feature = "napi-1")] {
+    /// # use neon::prelude::*;
+    /// fn bind_greeting(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    ///     let log = cx.argument::<JsFunction>(0)?;
+    ///     let this = cx.undefined();
+    ///     let greeting = cx.string("Hello,");
+    ///
+    ///     log.bind(&mut cx, this, [greeting])
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn bind<'a, 'b, C, T, A, AS>(
+        self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        args: AS,
+    ) -> JsResult<'a, JsFunction<CL>>
+    where
+        C: Context<'a>,
+        T: Value,
+        A: Value + 'b,
+        AS: IntoIterator<Item = Handle<'b, A>>,
+    {
+        // `Function.prototype.bind` is used instead of `self.bind`, which may
+        // be shadowed by a property of the function. `Function.prototype` is
+        // itself a function.
+        let global = cx.global();
+        let function = global
+            .get(cx, "Function")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+        let bind = function
+            .get(cx, "prototype")?
+            .downcast_or_throw::<JsFunction, _>(cx)?
+            .get(cx, "bind")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        let mut bind_args = vec![this.upcast::<JsValue>()];
+
+        bind_args.extend(args.into_iter().map(|arg| arg.upcast::<JsValue>()));
+
+        bind.call(cx, Handle::new_internal(self), bind_args)?
+            .downcast_or_throw(cx)
+    }
+
+    /// Creates a bound function like [`bind`](JsFunction::bind) and roots
+    /// it, for storing a preconfigured callback.
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn bind_root<'a, 'b, C, T, A, AS>(
+        self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        args: AS,
+    ) -> NeonResult<crate::handle::Root<JsFunction<CL>>>
+    where
+        C: Context<'a>,
+        T: Value,
+        A: Value + 'b,
+        AS: IntoIterator<Item = Handle<'b, A>>,
+    {
+        Ok(self.bind(cx, this, args)?.root(cx))
+    }
+
+    /// Calls the function with a fixed number of arguments, given as a tuple
+    /// of handles, e.g., `(a, b)`.
+    ///
+    /// This is faster than [`call`](JsFunction::call) in hot loops, since
+    /// the arguments are passed from the stack without being collected or
+    /// upcast. See [`Arguments`].
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn call_with_args<'a, 'b, C, T, AS>(
+        self,
+        cx: &mut C,
+        this: Handle<'b, T>,
+        args: AS,
+    ) -> JsResult<'a, JsValue>
+    where
+        C: Context<'a>,
+        T: Value,
+        AS: Arguments<'b>,
+    {
+        let env = cx.env();
+
+        args.with_raw(|argv| {
+            build(env, |out| unsafe {
+                neon_runtime::fun::call(
+                    out,
+                    env.to_raw(),
+                    self.to_raw(),
+                    this.to_raw(),
+                    argv.len() as i32,
+                    argv.as_ptr() as *mut c_void,
+                )
+            })
+        })
+    }
+
+    /// Calls the function as a constructor with a fixed number of arguments,
+    /// given as a tuple of handles. See
+    /// [`call_with_args`](JsFunction::call_with_args).
+    #[cfg(feature = "napi-1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "napi-1")))]
+    pub fn construct_with_args<'a, 'b, C, AS>(self, cx: &mut C, args: AS) -> JsResult<'a, CL>
+    where
+        C: Context<'a>,
+        AS: Arguments<'b>,
+    {
+        let env = cx.env();
+
+        args.with_raw(|argv| {
+            build(env, |out| unsafe {
+                neon_runtime::fun::construct(
+                    out,
+                    env.to_raw(),
+                    self.to_raw(),
+                    argv.len() as i32,
+                    argv.as_ptr() as *mut c_void,
+                )
+            })
+        })
+    }
 }
 
 impl<T: Object> Value for JsFunction<T> {}
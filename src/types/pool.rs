@@ -0,0 +1,182 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::types::{JsArrayBuffer, JsBuffer};
+
+/// A pool of byte allocations for creating external buffers.
+///
+/// Each buffer created from the pool holds a [`PooledBytes`], which returns
+/// its allocation to the pool when the buffer is garbage collected, instead of
+/// freeing it. Addons that produce many short-lived buffers, e.g., one for
+/// each chunk of a stream, reuse the same allocations rather than allocating
+/// and freeing memory for every buffer.
+///
+/// A `BufferPool` is cheap to clone, and clones share the same allocations,
+/// so it can fill bytes on another thread before they are passed to
+/// JavaScript.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::BufferPool;
+///
+/// fn read_chunk(mut cx: FunctionContext, pool: &BufferPool) -> JsResult<JsBuffer> {
+///     let mut bytes = pool.take(4096);
+///
+///     bytes[..5].copy_from_slice(b"hello");
+///
+///     Ok(JsBuffer::external(&mut cx, bytes))
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BufferPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    free: Mutex<Vec<Vec<u8>>>,
+    capacity: usize,
+}
+
+impl BufferPool {
+    /// Creates an empty pool that holds on to at most `capacity` unused
+    /// allocations. Allocations returned to a full pool are freed.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                free: Mutex::new(Vec::with_capacity(capacity)),
+                capacity,
+            }),
+        }
+    }
+
+    /// Takes `len` zero-filled bytes, reusing an unused allocation if there
+    /// is one.
+    pub fn take(&self, len: usize) -> PooledBytes {
+        let mut bytes = self.inner.free().pop().unwrap_or_default();
+
+        bytes.clear();
+        bytes.resize(len, 0);
+
+        PooledBytes {
+            bytes,
+            pool: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Creates a `Buffer` of `len` bytes from the pool, filled by `f`.
+    pub fn buffer<'a, C, F>(&self, cx: &mut C, len: usize, f: F) -> Handle<'a, JsBuffer>
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut [u8]),
+    {
+        let mut bytes = self.take(len);
+
+        f(&mut bytes);
+        JsBuffer::external(cx, bytes)
+    }
+
+    /// Creates an `ArrayBuffer` of `len` bytes from the pool, filled by `f`.
+    pub fn array_buffer<'a, C, F>(&self, cx: &mut C, len: usize, f: F) -> Handle<'a, JsArrayBuffer>
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut [u8]),
+    {
+        let mut bytes = self.take(len);
+
+        f(&mut bytes);
+        JsArrayBuffer::external(cx, bytes)
+    }
+
+    /// Returns the number of unused allocations held by the pool.
+    pub fn available(&self) -> usize {
+        self.inner.free().len()
+    }
+
+    /// Frees every unused allocation held by the pool.
+    pub fn clear(&self) {
+        self.inner.free().clear();
+    }
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("available", &self.available())
+            .field("capacity", &self.inner.capacity)
+            .finish()
+    }
+}
+
+impl Inner {
+    fn free(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        // The list of allocations is always valid, even if a thread panicked
+        // while holding the lock
+        self.free.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Bytes taken from a [`BufferPool`], which return their allocation to the
+/// pool when dropped.
+///
+/// Passing `PooledBytes` to [`JsBuffer::external`] or
+/// [`JsArrayBuffer::external`] creates a buffer that returns the allocation
+/// when it is garbage collected. If the pool has been dropped, the allocation
+/// is freed instead.
+pub struct PooledBytes {
+    bytes: Vec<u8>,
+    pool: Weak<Inner>,
+}
+
+impl Deref for PooledBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl DerefMut for PooledBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl AsRef<[u8]> for PooledBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl AsMut<[u8]> for PooledBytes {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl fmt::Debug for PooledBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PooledBytes")
+            .field("len", &self.bytes.len())
+            .finish()
+    }
+}
+
+impl Drop for PooledBytes {
+    fn drop(&mut self) {
+        let pool = match self.pool.upgrade() {
+            Some(pool) => pool,
+            None => return,
+        };
+
+        let mut free = pool.free();
+
+        if free.len() < pool.capacity {
+            free.push(std::mem::take(&mut self.bytes));
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+
+use super::{JsValue, Value, ValueInternal};
+use crate::context::internal::Env;
+#[cfg(any(feature = "task-api", feature = "channel-api"))]
+use crate::context::{internal::ContextInternal, TaskContext};
+use crate::context::{Context, Cx, FunctionContext};
+#[cfg(feature = "napi-6")]
+use crate::event::rejection;
+use crate::event::schedule;
+#[cfg(feature = "channel-api")]
+use crate::event::Channel;
+use crate::handle::{Handle, Managed};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::boxed::{Finalize, JsBox};
+#[cfg(any(feature = "task-api", feature = "channel-api"))]
+use crate::types::error::convert_panics;
+use crate::types::extract::{TryFromJs, TryIntoJs};
+use neon_runtime;
+use neon_runtime::napi;
+use neon_runtime::raw;
+
+/// A JavaScript Promise object
+///
+/// A `JsPromise` is created together with a [`Deferred`], which is used to
+/// settle it. See [`Context::promise`](crate::context::Context::promise).
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "promise-api")))]
+pub struct JsPromise(raw::Local);
+
+impl JsPromise {
+    pub(crate) fn new<'a, C: Context<'a>>(cx: &mut C) -> (Deferred, Handle<'a, Self>) {
+        let (deferred, promise) = unsafe { neon_runtime::promise::create(cx.env().to_raw()) };
+        let deferred = Deferred {
+            internal: Some(NodeApiDeferred(deferred)),
+        };
+        let promise = Handle::new_internal(JsPromise(promise));
+
+        // Tracking only fails if `WeakSet.prototype.add` was replaced
+        #[cfg(feature = "napi-6")]
+        let _ = rejection::track(cx, promise);
+
+        (deferred, promise)
+    }
+
+    /// Calls `f` with the value the promise is fulfilled with, extracted as
+    /// `A`, and returns a new promise for the value returned by `f`.
+    ///
+    /// If the value cannot be extracted or `f` returns an error, the new
+    /// promise is rejected. A rejection of this promise is passed through
+    /// without calling `f`.
+    ///
+    /// Since `f` is called later, in another scope, `A` and the value returned
+    /// by `f` are Rust values, such as `f64` or `String`, rather than handles.
+    ///
+    /// ```
+    /// # #[cfg(feature = "promise-api")] {
+    /// # use neon::prelude::*;
+    /// fn double_later(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let promise = cx.argument::<JsPromise>(0)?;
+    ///
+    ///     promise.then(&mut cx, |_, n: f64| n * 2.0)
+    /// }
+    /// # }
+    /// ```
+    pub fn then<'a, C, A, R, F>(self, cx: &mut C, f: F) -> JsResult<'a, JsPromise>
+    where
+        C: Context<'a>,
+        A: for<'b> TryFromJs<'b>,
+        R: for<'b> TryIntoJs<'b>,
+        F: FnOnce(&mut Cx, A) -> R + Send + 'static,
+    {
+        let reaction = Reaction::new(move |cx| {
+            let value = cx.argument::<JsValue>(1)?;
+            let value = A::from_js(cx, value)?;
+
+            f(cx, value).try_into_js(cx).map(|v| v.upcast())
+        });
+
+        self.react(cx, "then", reaction)
+    }
+
+    /// Calls `f` with the reason the promise is rejected with, extracted as
+    /// `A`, and returns a new promise for the value returned by `f`.
+    ///
+    /// If the reason cannot be extracted or `f` returns an error, the new
+    /// promise is rejected. A fulfilled value is passed through without
+    /// calling `f`.
+    pub fn catch<'a, C, A, R, F>(self, cx: &mut C, f: F) -> JsResult<'a, JsPromise>
+    where
+        C: Context<'a>,
+        A: for<'b> TryFromJs<'b>,
+        R: for<'b> TryIntoJs<'b>,
+        F: FnOnce(&mut Cx, A) -> R + Send + 'static,
+    {
+        let reaction = Reaction::new(move |cx| {
+            let reason = cx.argument::<JsValue>(1)?;
+            let reason = A::from_js(cx, reason)?;
+
+            f(cx, reason).try_into_js(cx).map(|v| v.upcast())
+        });
+
+        self.react(cx, "catch", reaction)
+    }
+
+    /// Calls `f` when the promise is settled, either way, and returns a new
+    /// promise that is settled the same way as this one.
+    ///
+    /// If `f` returns an error, the new promise is rejected with it instead.
+    pub fn finally<'a, C, F>(self, cx: &mut C, f: F) -> JsResult<'a, JsPromise>
+    where
+        C: Context<'a>,
+        F: FnOnce(&mut Cx) -> NeonResult<()> + Send + 'static,
+    {
+        let reaction = Reaction::new(move |cx| {
+            f(cx)?;
+
+            Ok(cx.undefined().upcast())
+        });
+
+        self.react(cx, "finally", reaction)
+    }
+
+    fn react<'a, C: Context<'a>>(
+        self,
+        cx: &mut C,
+        method: &str,
+        reaction: Reaction,
+    ) -> JsResult<'a, JsPromise> {
+        let callback = schedule::bind(cx, react, reaction)?;
+        let promise = self
+            .method(cx, method)?
+            .arg(callback)
+            .call(cx)?
+            .downcast_or_throw(cx)?;
+
+        #[cfg(feature = "napi-6")]
+        rejection::track(cx, promise)?;
+
+        Ok(promise)
+    }
+}
+
+type ReactionFn = Box<dyn for<'b> FnOnce(&mut FunctionContext<'b>) -> JsResult<'b, JsValue> + Send>;
+
+// A Rust closure passed to `then`, `catch` or `finally`. Promise reactions are
+// called at most once, so the closure is taken on the first call.
+struct Reaction(RefCell<Option<ReactionFn>>);
+
+impl Reaction {
+    fn new<F>(f: F) -> Self
+    where
+        F: for<'b> FnOnce(&mut FunctionContext<'b>) -> JsResult<'b, JsValue> + Send + 'static,
+    {
+        Self(RefCell::new(Some(Box::new(f))))
+    }
+}
+
+impl Finalize for Reaction {}
+
+fn react(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let reaction = cx.argument::<JsBox<Reaction>>(0)?;
+    let f = reaction.0.borrow_mut().take();
+
+    match f {
+        Some(f) => f(&mut cx),
+        None => cx.throw_error("promise reaction was called more than once"),
+    }
+}
+
+impl Value for JsPromise {}
+
+impl Managed for JsPromise {
+    fn to_raw(self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsPromise(h)
+    }
+}
+
+impl ValueInternal for JsPromise {
+    fn name() -> String {
+        "Promise".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_promise(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Object for JsPromise {}
+
+/// A controller struct that can be used to resolve or reject a [`JsPromise`].
+///
+/// It is recommended to settle a [`Deferred`] with [`Deferred::resolve`] or
+/// [`Deferred::reject`] to ensure resources are released.
+///
+/// A `Deferred` is [`Send`] and may be moved to another thread, for example
+/// by a [`Channel`](crate::event::Channel) closure, but it can only be settled
+/// on the JavaScript main thread.
+#[cfg_attr(docsrs, doc(cfg(feature = "promise-api")))]
+pub struct Deferred {
+    internal: Option<NodeApiDeferred>,
+}
+
+impl Deferred {
+    /// Resolve a [`JsPromise`] with a JavaScript value
+    pub fn resolve<'a, V, C>(self, cx: &mut C, value: Handle<V>)
+    where
+        V: Value,
+        C: Context<'a>,
+    {
+        unsafe {
+            neon_runtime::promise::resolve(cx.env().to_raw(), self.into_inner(), value.to_raw());
+        }
+    }
+
+    /// Reject a [`JsPromise`] with a JavaScript value
+    pub fn reject<'a, V, C>(self, cx: &mut C, value: Handle<V>)
+    where
+        V: Value,
+        C: Context<'a>,
+    {
+        unsafe {
+            neon_runtime::promise::reject(cx.env().to_raw(), self.into_inner(), value.to_raw());
+        }
+    }
+
+    /// Settles the [`JsPromise`] with the result of a closure executed on the
+    /// JavaScript thread of `channel`.
+    ///
+    /// The promise is resolved with the value returned by `f`, or rejected
+    /// with the exception it throws. If `f` panics, the panic is converted to
+    /// an exception which rejects the promise.
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "promise-api", feature = "channel-api"))] {
+    /// # use neon::prelude::*;
+    /// # fn fibonacci(_: f64) -> f64 { todo!() }
+    /// fn async_fibonacci(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    ///     let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    ///     let channel = cx.channel();
+    ///     let (deferred, promise) = cx.promise();
+    ///
+    ///     std::thread::spawn(move || {
+    ///         let result = fibonacci(n);
+    ///
+    ///         deferred.settle_with(&channel, move |mut cx| Ok(cx.number(result)));
+    ///     });
+    ///
+    ///     Ok(promise)
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the closure cannot be scheduled, like [`Channel::send`].
+    #[cfg(feature = "channel-api")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "channel-api")))]
+    pub fn settle_with<V, F>(self, channel: &Channel, f: F)
+    where
+        V: Value,
+        F: for<'b> FnOnce(TaskContext<'b>) -> JsResult<'b, V> + Send + 'static,
+    {
+        channel.send(move |cx| {
+            self.settle(cx.env(), f);
+
+            Ok(())
+        });
+    }
+
+    // Settles the promise with the result of `f`, resolving with the returned
+    // value or rejecting with the exception that was thrown. Panics are
+    // converted to exceptions and reject the promise.
+    #[cfg(any(feature = "task-api", feature = "channel-api"))]
+    pub(crate) fn settle<V, F>(self, env: Env, f: F)
+    where
+        V: Value,
+        F: for<'b> FnOnce(TaskContext<'b>) -> JsResult<'b, V>,
+    {
+        use std::panic::AssertUnwindSafe;
+
+        // The value escapes the first scope as a raw local; it remains valid
+        // because N-API holds a handle scope open for the whole callback.
+        let result = TaskContext::with_context(env, |cx| {
+            convert_panics(env, AssertUnwindSafe(|| f(cx).map(|v| v.to_raw())))
+        });
+
+        TaskContext::with_context(env, move |mut cx| {
+            match cx.try_catch_internal(move |_| result) {
+                Ok(value) => self.resolve(&mut cx, JsValue::new_internal(value)),
+                Err(err) => self.reject(&mut cx, err),
+            }
+        });
+    }
+
+    fn into_inner(mut self) -> napi::Deferred {
+        self.internal.take().unwrap().0
+    }
+}
+
+#[repr(transparent)]
+struct NodeApiDeferred(napi::Deferred);
+
+unsafe impl Send for NodeApiDeferred {}
+
+impl Drop for Deferred {
+    fn drop(&mut self) {
+        // If `None`, the `Deferred` has already been settled
+        if self.internal.is_none() {
+            return;
+        }
+
+        // Destructors are called during stack unwinding, prevent a double
+        // panic and instead prefer to leak.
+        if std::thread::panicking() {
+            eprintln!("Warning: neon::types::Deferred leaked during a panic");
+            return;
+        }
+
+        // Only panic if the event loop is still running
+        if let Ok(true) = crate::context::internal::IS_RUNNING.try_with(|v| *v.borrow()) {
+            panic!("Must settle `Deferred` with `resolve` or `reject` before it is dropped");
+        }
+    }
+}
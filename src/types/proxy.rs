@@ -0,0 +1,384 @@
+use crate::context::{Context, FunctionContext};
+use crate::event::schedule;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::boxed::{Finalize, JsBox};
+use crate::types::{
+    JsArray, JsBoolean, JsFunction, JsObject, JsString, JsUndefined, JsValue, Value,
+};
+
+/// Creates JavaScript [`Proxy`][proxy] objects whose properties are provided
+/// by a Rust [`ProxyHandler`].
+///
+/// A proxy is an ordinary object to JavaScript and to Neon, so
+/// [`JsProxy::new`] returns a [`JsObject`].
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::{JsProxy, ProxyHandler};
+///
+/// // An object with a property for each environment variable
+/// struct Env;
+///
+/// impl ProxyHandler for Env {
+///     fn get<'a>(
+///         &self,
+///         cx: &mut FunctionContext<'a>,
+///         _target: Handle<'a, JsObject>,
+///         key: &str,
+///     ) -> JsResult<'a, JsValue> {
+///         match std::env::var(key) {
+///             Ok(value) => Ok(cx.string(value).upcast()),
+///             Err(_) => Ok(cx.undefined().upcast()),
+///         }
+///     }
+///
+///     fn has<'a>(
+///         &self,
+///         _cx: &mut FunctionContext<'a>,
+///         _target: Handle<'a, JsObject>,
+///         key: &str,
+///     ) -> NeonResult<bool> {
+///         Ok(std::env::var_os(key).is_some())
+///     }
+///
+///     fn own_keys<'a>(
+///         &self,
+///         _cx: &mut FunctionContext<'a>,
+///         _target: Handle<'a, JsObject>,
+///     ) -> NeonResult<Vec<String>> {
+///         Ok(std::env::vars().map(|(key, _)| key).collect())
+///     }
+/// }
+///
+/// fn env(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let target = cx.empty_object();
+///
+///     JsProxy::new(&mut cx, target, Env)
+/// }
+/// # }
+/// ```
+///
+/// [proxy]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Proxy
+pub struct JsProxy {
+    _private: (),
+}
+
+impl JsProxy {
+    /// Creates a proxy for `target` with the traps of `handler`.
+    ///
+    /// JavaScript requires a proxy to be consistent with its target, e.g., a
+    /// non-configurable property of the target must be reported by
+    /// [`own_keys`](ProxyHandler::own_keys). Using an empty, extensible object
+    /// as the target avoids these restrictions.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<'a, C, H>(
+        cx: &mut C,
+        target: Handle<'a, JsObject>,
+        handler: H,
+    ) -> JsResult<'a, JsObject>
+    where
+        C: Context<'a>,
+        H: ProxyHandler,
+    {
+        let data = cx.boxed(Handler(Box::new(handler))).upcast::<JsValue>();
+        let traps = cx.empty_object();
+
+        let f = schedule::bind_value(cx, trap_get, data)?;
+        traps.set(cx, "get", f)?;
+
+        let f = schedule::bind_value(cx, trap_set, data)?;
+        traps.set(cx, "set", f)?;
+
+        let f = schedule::bind_value(cx, trap_has, data)?;
+        traps.set(cx, "has", f)?;
+
+        let f = schedule::bind_value(cx, trap_delete, data)?;
+        traps.set(cx, "deleteProperty", f)?;
+
+        let f = schedule::bind_value(cx, trap_own_keys, data)?;
+        traps.set(cx, "ownKeys", f)?;
+
+        let f = schedule::bind_value(cx, trap_descriptor, data)?;
+        traps.set(cx, "getOwnPropertyDescriptor", f)?;
+
+        let proxy = cx
+            .global()
+            .get(cx, "Proxy")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+
+        proxy.construct(cx, vec![target.upcast::<JsValue>(), traps.upcast()])
+    }
+}
+
+/// The traps of a [`JsProxy`], called when JavaScript accesses a property
+/// of the proxy.
+///
+/// Each trap receives the key as a `&str`. Properties keyed by symbols are
+/// always forwarded to the target. The default implementations forward to
+/// the target with [`Reflect`][reflect], so a handler only needs to
+/// implement the traps it customizes.
+///
+/// Traps take `&self`; use interior mutability, e.g., a `RefCell`, for
+/// state that changes.
+///
+/// [reflect]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Reflect
+pub trait ProxyHandler: Send + 'static {
+    /// Gets the value of a property, e.g., `proxy.key`.
+    fn get<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> JsResult<'a, JsValue> {
+        let key = cx.string(key);
+
+        reflect(cx, "get", vec![target.upcast(), key.upcast()])
+    }
+
+    /// Sets the value of a property, e.g., `proxy.key = value`. Returns
+    /// `false` if the property could not be set, which throws in strict mode.
+    fn set<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+        key: &str,
+        value: Handle<'a, JsValue>,
+    ) -> NeonResult<bool> {
+        let key = cx.string(key);
+
+        reflect_bool(cx, "set", vec![target.upcast(), key.upcast(), value])
+    }
+
+    /// Returns `true` if the proxy has a property, e.g., `key in proxy`.
+    fn has<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> NeonResult<bool> {
+        let key = cx.string(key);
+
+        reflect_bool(cx, "has", vec![target.upcast(), key.upcast()])
+    }
+
+    /// Deletes a property, e.g., `delete proxy.key`. Returns `false` if the
+    /// property could not be deleted.
+    fn delete<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> NeonResult<bool> {
+        let key = cx.string(key);
+
+        reflect_bool(cx, "deleteProperty", vec![target.upcast(), key.upcast()])
+    }
+
+    /// Lists the names of the own properties of the proxy, e.g., for
+    /// `Object.keys(proxy)`. Listed properties that are not properties of the
+    /// target are reported as enumerable data properties with the value
+    /// returned by [`get`](ProxyHandler::get).
+    fn own_keys<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+    ) -> NeonResult<Vec<String>> {
+        let object = global::<_, JsFunction>(cx, "Object")?;
+        let keys = object
+            .method(cx, "getOwnPropertyNames")?
+            .arg(target)
+            .call(cx)?
+            .downcast_or_throw::<JsArray, _>(cx)?;
+
+        keys.to_vec(cx)?
+            .into_iter()
+            .map(|key| Ok(key.downcast_or_throw::<JsString, _>(cx)?.value(cx)))
+            .collect()
+    }
+}
+
+struct Handler(Box<dyn ProxyHandler>);
+
+impl Finalize for Handler {}
+
+// The arguments of a trap, after the bound `Handler`
+struct Trap<'a> {
+    handler: Handle<'a, JsBox<Handler>>,
+    target: Handle<'a, JsObject>,
+    key: Handle<'a, JsValue>,
+}
+
+impl<'a> Trap<'a> {
+    fn new(cx: &mut FunctionContext<'a>) -> NeonResult<Self> {
+        Ok(Self {
+            handler: cx.argument(0)?,
+            target: cx.argument(1)?,
+            key: cx
+                .argument_opt(2)
+                .unwrap_or_else(|| cx.undefined().upcast()),
+        })
+    }
+
+    // The key as a string, or `None` for a symbol
+    fn key(&self, cx: &mut FunctionContext<'a>) -> Option<String> {
+        self.key
+            .downcast::<JsString, _>(cx)
+            .ok()
+            .map(|key| key.value(cx))
+    }
+
+    // All arguments of the trap, for forwarding to `Reflect`
+    fn args(&self, cx: &mut FunctionContext<'a>) -> NeonResult<Vec<Handle<'a, JsValue>>> {
+        (1..cx.len()).map(|i| cx.argument(i)).collect()
+    }
+}
+
+fn trap_get(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let trap = Trap::new(&mut cx)?;
+
+    match trap.key(&mut cx) {
+        Some(key) => trap.handler.0.get(&mut cx, trap.target, &key),
+        None => {
+            let args = trap.args(&mut cx)?;
+
+            reflect(&mut cx, "get", args)
+        }
+    }
+}
+
+fn trap_set(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let trap = Trap::new(&mut cx)?;
+    let result = match trap.key(&mut cx) {
+        Some(key) => {
+            let value = cx.argument::<JsValue>(3)?;
+
+            trap.handler.0.set(&mut cx, trap.target, &key, value)?
+        }
+        None => {
+            let args = trap.args(&mut cx)?;
+
+            reflect_bool(&mut cx, "set", args)?
+        }
+    };
+
+    Ok(cx.boolean(result))
+}
+
+fn trap_has(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let trap = Trap::new(&mut cx)?;
+    let result = match trap.key(&mut cx) {
+        Some(key) => trap.handler.0.has(&mut cx, trap.target, &key)?,
+        None => {
+            let args = trap.args(&mut cx)?;
+
+            reflect_bool(&mut cx, "has", args)?
+        }
+    };
+
+    Ok(cx.boolean(result))
+}
+
+fn trap_delete(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let trap = Trap::new(&mut cx)?;
+    let result = match trap.key(&mut cx) {
+        Some(key) => trap.handler.0.delete(&mut cx, trap.target, &key)?,
+        None => {
+            let args = trap.args(&mut cx)?;
+
+            reflect_bool(&mut cx, "deleteProperty", args)?
+        }
+    };
+
+    Ok(cx.boolean(result))
+}
+
+fn trap_own_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let trap = Trap::new(&mut cx)?;
+    let names = trap.handler.0.own_keys(&mut cx, trap.target)?;
+    // Symbols are not visible to the handler and are listed from the target
+    let object = global::<_, JsFunction>(&mut cx, "Object")?;
+    let symbols = object
+        .method(&mut cx, "getOwnPropertySymbols")?
+        .arg(trap.target)
+        .call(&mut cx)?
+        .downcast_or_throw::<JsArray, _>(&mut cx)?
+        .to_vec(&mut cx)?;
+
+    let keys = JsArray::new(&mut cx, (names.len() + symbols.len()) as u32);
+
+    for (i, name) in names.iter().enumerate() {
+        let name = cx.string(name);
+
+        keys.set(&mut cx, i as u32, name)?;
+    }
+
+    for (i, symbol) in symbols.into_iter().enumerate() {
+        keys.set(&mut cx, (names.len() + i) as u32, symbol)?;
+    }
+
+    Ok(keys)
+}
+
+fn trap_descriptor(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let trap = Trap::new(&mut cx)?;
+    let args = trap.args(&mut cx)?;
+    let descriptor = reflect(&mut cx, "getOwnPropertyDescriptor", args)?;
+
+    if !descriptor.is_a::<JsUndefined, _>(&mut cx) {
+        return Ok(descriptor);
+    }
+
+    let key = match trap.key(&mut cx) {
+        Some(key) => key,
+        None => return Ok(descriptor),
+    };
+
+    if !trap
+        .handler
+        .0
+        .own_keys(&mut cx, trap.target)?
+        .contains(&key)
+    {
+        return Ok(descriptor);
+    }
+
+    // Describe a virtual property as if it were a writable data property
+    let value = trap.handler.0.get(&mut cx, trap.target, &key)?;
+    let descriptor = cx.empty_object();
+    let t = cx.boolean(true);
+
+    descriptor.set(&mut cx, "value", value)?;
+    descriptor.set(&mut cx, "writable", t)?;
+    descriptor.set(&mut cx, "enumerable", t)?;
+    descriptor.set(&mut cx, "configurable", t)?;
+
+    Ok(descriptor.upcast())
+}
+
+fn global<'a, C: Context<'a>, V: Value>(cx: &mut C, name: &str) -> JsResult<'a, V> {
+    cx.global().get(cx, name)?.downcast_or_throw(cx)
+}
+
+fn reflect<'a, C: Context<'a>>(
+    cx: &mut C,
+    name: &str,
+    args: Vec<Handle<'a, JsValue>>,
+) -> JsResult<'a, JsValue> {
+    global::<_, JsObject>(cx, "Reflect")?
+        .method(cx, name)?
+        .args(args)
+        .call(cx)
+}
+
+fn reflect_bool<'a, C: Context<'a>>(
+    cx: &mut C,
+    name: &str,
+    args: Vec<Handle<'a, JsValue>>,
+) -> NeonResult<bool> {
+    let result = reflect(cx, name, args)?;
+
+    Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+}
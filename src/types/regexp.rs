@@ -0,0 +1,127 @@
+use super::{JsArray, JsFunction, JsNull, JsString, Value, ValueInternal};
+use crate::context::internal::Env;
+use crate::context::Context;
+use crate::handle::{Handle, Managed};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use neon_runtime;
+use neon_runtime::raw;
+
+/// A JavaScript RegExp object
+///
+/// A `JsRegExp` may be created from Rust or accepted as an argument, which
+/// allows applying a user-supplied pattern to strings.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::JsRegExp;
+///
+/// fn all_match(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+///     let pattern = cx.argument::<JsRegExp>(0)?;
+///     let inputs = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+///
+///     for input in inputs {
+///         let input = input.downcast_or_throw::<JsString, _>(&mut cx)?.value(&mut cx);
+///
+///         if !pattern.test(&mut cx, &input)? {
+///             return Ok(cx.boolean(false));
+///         }
+///     }
+///
+///     Ok(cx.boolean(true))
+/// }
+/// # }
+/// ```
+///
+/// Since Node-API cannot identify regular expressions directly, a value is a
+/// `JsRegExp` if it is an instance of the global `RegExp` class of the
+/// current environment.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct JsRegExp(raw::Local);
+
+impl JsRegExp {
+    /// Creates a regular expression from a pattern and flags, for example
+    /// `"g"` or `"iu"`, throwing a `SyntaxError` if either is invalid.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C, pattern: &str, flags: &str) -> JsResult<'a, Self> {
+        let class = cx
+            .global()
+            .get(cx, "RegExp")?
+            .downcast_or_throw::<JsFunction, _>(cx)?;
+        let args = vec![cx.string(pattern), cx.string(flags)];
+        let regexp = class.construct(cx, args)?;
+
+        Ok(Handle::new_internal(JsRegExp(regexp.to_raw())))
+    }
+
+    /// The text of the pattern, without the enclosing slashes or flags.
+    pub fn source<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<String> {
+        self.string(cx, "source")
+    }
+
+    /// The flags of the regular expression, in alphabetical order.
+    pub fn flags<'a, C: Context<'a>>(self, cx: &mut C) -> NeonResult<String> {
+        self.string(cx, "flags")
+    }
+
+    /// Searches `input` for a match, returning `None` if there is none.
+    ///
+    /// The match is the array returned by [`RegExp.prototype.exec()`][exec]:
+    /// the matched text followed by each capture group, with `index` and
+    /// `groups` properties. Like `exec`, this advances `lastIndex` if the
+    /// regular expression is global or sticky.
+    ///
+    /// [exec]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/exec
+    pub fn exec<'a, C: Context<'a>>(
+        self,
+        cx: &mut C,
+        input: &str,
+    ) -> NeonResult<Option<Handle<'a, JsArray>>> {
+        let input = cx.string(input);
+        let result = self.method(cx, "exec")?.arg(input).call(cx)?;
+
+        result.downcast::<JsArray, _>(cx).map(Some).or_else(|_| {
+            if result.is_a::<JsNull, _>(cx) {
+                Ok(None)
+            } else {
+                cx.throw_type_error("expected `exec` to return an array or null")
+            }
+        })
+    }
+
+    /// Returns `true` if the regular expression matches `input`.
+    pub fn test<'a, C: Context<'a>>(self, cx: &mut C, input: &str) -> NeonResult<bool> {
+        Ok(self.exec(cx, input)?.is_some())
+    }
+
+    fn string<'a, C: Context<'a>>(self, cx: &mut C, name: &str) -> NeonResult<String> {
+        let value = self.get(cx, name)?;
+
+        Ok(value.downcast_or_throw::<JsString, _>(cx)?.value(cx))
+    }
+}
+
+impl Value for JsRegExp {}
+
+impl Managed for JsRegExp {
+    fn to_raw(self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsRegExp(h)
+    }
+}
+
+impl ValueInternal for JsRegExp {
+    fn name() -> String {
+        "RegExp".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_regexp(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Object for JsRegExp {}
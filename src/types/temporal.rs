@@ -0,0 +1,220 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::{check_tag, JsFunction, JsNumber, JsObject, JsValue, Value};
+
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+/// A [`Temporal.Instant`][instant], an exact point in time with nanosecond
+/// precision.
+///
+/// Instants are created with the global `Temporal` object, which is not yet
+/// available in every version of Node.js; creating or converting an instant
+/// throws a `TypeError` if it is missing.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-1", feature = "temporal-api"))] {
+/// # use neon::prelude::*;
+/// use neon::types::JsTemporalInstant;
+///
+/// fn now(mut cx: FunctionContext) -> JsResult<JsObject> {
+///     let now = JsTemporalInstant::new(&mut cx, std::time::SystemTime::now())?;
+///
+///     Ok(now.as_object())
+/// }
+/// # }
+/// ```
+///
+/// [instant]: https://tc39.es/proposal-temporal/docs/instant.html
+#[derive(Clone, Copy)]
+pub struct JsTemporalInstant<'a> {
+    instant: Handle<'a, JsObject>,
+}
+
+impl<'a> JsTemporalInstant<'a> {
+    /// Creates an instant at the same point in time as `time`.
+    pub fn new<C: Context<'a>>(cx: &mut C, time: SystemTime) -> NeonResult<Self> {
+        let nanos = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_nanos() as i128,
+            Err(err) => -(err.duration().as_nanos() as i128),
+        };
+
+        let nanos = big_int(cx, nanos)?;
+        let instant = class(cx, "Instant")?
+            .method(cx, "fromEpochNanoseconds")?
+            .arg(nanos)
+            .call(cx)?
+            .downcast_or_throw(cx)?;
+
+        Ok(Self { instant })
+    }
+
+    /// Wraps `value`, throwing a `TypeError` if it is not a `Temporal.Instant`.
+    pub fn from_value<C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> NeonResult<Self> {
+        Ok(Self {
+            instant: check_tag(cx, value, "Temporal.Instant")?,
+        })
+    }
+
+    /// Returns the wrapped `Temporal.Instant` object.
+    pub fn as_object(&self) -> Handle<'a, JsObject> {
+        self.instant
+    }
+
+    /// Converts the instant to a `SystemTime`.
+    pub fn to_system_time<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<SystemTime> {
+        let nanos = self.instant.get(cx, "epochNanoseconds")?;
+        let nanos = nanos
+            .to_string(cx)?
+            .value(cx)
+            .parse::<i128>()
+            .or_else(|_| cx.throw_type_error("expected `epochNanoseconds` to be a BigInt"))?;
+
+        let since = duration_from_nanos(nanos.unsigned_abs());
+
+        Ok(if nanos < 0 {
+            UNIX_EPOCH - since
+        } else {
+            UNIX_EPOCH + since
+        })
+    }
+}
+
+/// A [`Temporal.Duration`][duration], a length of time.
+///
+/// ```
+/// # #[cfg(all(feature = "napi-1", feature = "temporal-api"))] {
+/// # use neon::prelude::*;
+/// use neon::types::JsTemporalDuration;
+///
+/// fn sleep(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let duration = cx.argument::<JsValue>(0)?;
+///     let duration = JsTemporalDuration::from_value(&mut cx, duration)?;
+///
+///     std::thread::sleep(duration.to_duration(&mut cx)?);
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// [duration]: https://tc39.es/proposal-temporal/docs/duration.html
+#[derive(Clone, Copy)]
+pub struct JsTemporalDuration<'a> {
+    duration: Handle<'a, JsObject>,
+}
+
+impl<'a> JsTemporalDuration<'a> {
+    /// Creates a duration of the same length as `duration`, in seconds and
+    /// fractions of a second.
+    pub fn new<C: Context<'a>>(cx: &mut C, duration: Duration) -> NeonResult<Self> {
+        let nanos = duration.subsec_nanos();
+        let fields = [
+            duration.as_secs() as f64,
+            (nanos / 1_000_000) as f64,
+            (nanos / 1_000 % 1_000) as f64,
+            (nanos % 1_000) as f64,
+        ];
+
+        // Years, months, weeks, days, hours and minutes are zero
+        let mut args = (0..6)
+            .map(|_| cx.number(0).upcast())
+            .collect::<Vec<Handle<JsValue>>>();
+
+        args.extend(
+            fields
+                .iter()
+                .map(|&field| cx.number(field).upcast::<JsValue>()),
+        );
+
+        let duration = class(cx, "Duration")?.construct(cx, args)?;
+
+        Ok(Self { duration })
+    }
+
+    /// Wraps `value`, throwing a `TypeError` if it is not a `Temporal.Duration`.
+    pub fn from_value<C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> NeonResult<Self> {
+        Ok(Self {
+            duration: check_tag(cx, value, "Temporal.Duration")?,
+        })
+    }
+
+    /// Returns the wrapped `Temporal.Duration` object.
+    pub fn as_object(&self) -> Handle<'a, JsObject> {
+        self.duration
+    }
+
+    /// Converts the duration to a `Duration`, treating a day as 24 hours.
+    ///
+    /// Throws a `RangeError` if the duration is negative or has years, months
+    /// or weeks, which vary in length.
+    pub fn to_duration<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Duration> {
+        for unit in ["years", "months", "weeks"].iter() {
+            if self.field(cx, unit)? != 0.0 {
+                return cx.throw_range_error(format!(
+                    "cannot convert a duration with {} to a fixed length of time",
+                    unit
+                ));
+            }
+        }
+
+        let units = [
+            ("days", 86_400 * NANOS_PER_SEC),
+            ("hours", 3_600 * NANOS_PER_SEC),
+            ("minutes", 60 * NANOS_PER_SEC),
+            ("seconds", NANOS_PER_SEC),
+            ("milliseconds", 1_000_000),
+            ("microseconds", 1_000),
+            ("nanoseconds", 1),
+        ];
+
+        let mut nanos = 0i128;
+
+        for &(unit, scale) in units.iter() {
+            nanos += self.field(cx, unit)? as i128 * scale;
+        }
+
+        if nanos < 0 {
+            return cx.throw_range_error("cannot convert a negative duration");
+        }
+
+        Ok(duration_from_nanos(nanos as u128))
+    }
+
+    fn field<C: Context<'a>>(&self, cx: &mut C, name: &str) -> NeonResult<f64> {
+        let value = self.duration.get(cx, name)?;
+
+        Ok(value.downcast_or_throw::<JsNumber, _>(cx)?.value(cx))
+    }
+}
+
+fn duration_from_nanos(nanos: u128) -> Duration {
+    let secs = nanos / NANOS_PER_SEC as u128;
+    let nanos = nanos % NANOS_PER_SEC as u128;
+
+    Duration::new(secs as u64, nanos as u32)
+}
+
+// Looks up a class of the global `Temporal` namespace
+fn class<'a, C: Context<'a>>(cx: &mut C, name: &str) -> JsResult<'a, JsFunction> {
+    let temporal = cx.global().get(cx, "Temporal")?;
+    let temporal = match temporal.downcast::<JsObject, _>(cx) {
+        Ok(temporal) => temporal,
+        Err(_) => return cx.throw_type_error("the `Temporal` API is not available"),
+    };
+
+    temporal.get(cx, name)?.downcast_or_throw(cx)
+}
+
+fn big_int<'a, C: Context<'a>>(cx: &mut C, n: i128) -> JsResult<'a, JsValue> {
+    let global = cx.global();
+    let big_int = global
+        .get(cx, "BigInt")?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+    let n = cx.string(n.to_string());
+
+    big_int.call(cx, global, vec![n])
+}
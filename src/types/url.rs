@@ -0,0 +1,272 @@
+use crate::context::Context;
+use crate::handle::Handle;
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use crate::types::{check_tag, JsArray, JsBoolean, JsFunction, JsObject, JsString, JsValue};
+
+/// A [WHATWG `URL`][url], parsed with the global `URL` class.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::JsUrl;
+///
+/// fn host(mut cx: FunctionContext) -> JsResult<JsString> {
+///     let url = cx.argument::<JsString>(0)?.value(&mut cx);
+///     let url = JsUrl::new(&mut cx, &url)?;
+///     let host = url.host(&mut cx)?;
+///
+///     Ok(cx.string(host))
+/// }
+/// # }
+/// ```
+///
+/// [url]: https://nodejs.org/api/url.html#url_the_whatwg_url_api
+#[derive(Clone, Copy)]
+pub struct JsUrl<'a> {
+    url: Handle<'a, JsObject>,
+}
+
+impl<'a> JsUrl<'a> {
+    /// Parses an absolute URL, throwing a `TypeError` if it is invalid.
+    pub fn new<C: Context<'a>>(cx: &mut C, input: &str) -> NeonResult<Self> {
+        let input = cx.string(input).upcast();
+        let url = construct(cx, "URL", vec![input])?;
+
+        Ok(Self { url })
+    }
+
+    /// Parses `input` relative to `base`, throwing a `TypeError` if either is
+    /// invalid.
+    pub fn with_base<C: Context<'a>>(cx: &mut C, input: &str, base: &str) -> NeonResult<Self> {
+        let args = vec![cx.string(input).upcast(), cx.string(base).upcast()];
+        let url = construct(cx, "URL", args)?;
+
+        Ok(Self { url })
+    }
+
+    /// Wraps `value`, throwing a `TypeError` if it is not a `URL`.
+    pub fn from_value<C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> NeonResult<Self> {
+        Ok(Self {
+            url: check_tag(cx, value, "URL")?,
+        })
+    }
+
+    /// Returns the wrapped `URL` object.
+    pub fn as_object(&self) -> Handle<'a, JsObject> {
+        self.url
+    }
+
+    /// The serialized URL, e.g., `"https://user@example.com:8080/a?b#c"`.
+    pub fn href<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "href")
+    }
+
+    /// The scheme followed by `:`, e.g., `"https:"`.
+    pub fn protocol<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "protocol")
+    }
+
+    /// The username, or an empty string.
+    pub fn username<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "username")
+    }
+
+    /// The password, or an empty string.
+    pub fn password<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "password")
+    }
+
+    /// The host name and, if it is not the default, the port, e.g.,
+    /// `"example.com:8080"`.
+    pub fn host<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "host")
+    }
+
+    /// The host name without the port, e.g., `"example.com"`.
+    pub fn hostname<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "hostname")
+    }
+
+    /// The port, or `None` if it is the default port of the scheme.
+    pub fn port<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Option<u16>> {
+        let port = string(cx, self.url, "port")?;
+
+        if port.is_empty() {
+            return Ok(None);
+        }
+
+        match port.parse() {
+            Ok(port) => Ok(Some(port)),
+            Err(_) => cx.throw_type_error(format!("invalid port `{}`", port)),
+        }
+    }
+
+    /// The path, e.g., `"/a"`.
+    pub fn pathname<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "pathname")
+    }
+
+    /// The query including the leading `?`, or an empty string.
+    pub fn search<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "search")
+    }
+
+    /// The fragment including the leading `#`, or an empty string.
+    pub fn hash<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "hash")
+    }
+
+    /// The origin, e.g., `"https://example.com:8080"`.
+    pub fn origin<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        string(cx, self.url, "origin")
+    }
+
+    /// The parameters of the query. Changes to the parameters update the URL.
+    pub fn search_params<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<JsUrlSearchParams<'a>> {
+        let params = self.url.get(cx, "searchParams")?;
+
+        JsUrlSearchParams::from_value(cx, params)
+    }
+
+    /// Serializes the URL. Equivalent to [`href`](JsUrl::href).
+    pub fn to_string<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        self.href(cx)
+    }
+}
+
+/// The parameters of the query of a URL, a [`URLSearchParams`][params]
+/// object.
+///
+/// [params]: https://nodejs.org/api/url.html#url_class_urlsearchparams
+#[derive(Clone, Copy)]
+pub struct JsUrlSearchParams<'a> {
+    params: Handle<'a, JsObject>,
+}
+
+impl<'a> JsUrlSearchParams<'a> {
+    /// Parses a query string, with or without the leading `?`.
+    pub fn new<C: Context<'a>>(cx: &mut C, query: &str) -> NeonResult<Self> {
+        let query = cx.string(query).upcast();
+        let params = construct(cx, "URLSearchParams", vec![query])?;
+
+        Ok(Self { params })
+    }
+
+    /// Wraps `value`, throwing a `TypeError` if it is not a `URLSearchParams`.
+    pub fn from_value<C: Context<'a>>(cx: &mut C, value: Handle<'a, JsValue>) -> NeonResult<Self> {
+        Ok(Self {
+            params: check_tag(cx, value, "URLSearchParams")?,
+        })
+    }
+
+    /// Returns the wrapped `URLSearchParams` object.
+    pub fn as_object(&self) -> Handle<'a, JsObject> {
+        self.params
+    }
+
+    /// The first value of the parameter `name`, if any.
+    pub fn get<C: Context<'a>>(&self, cx: &mut C, name: &str) -> NeonResult<Option<String>> {
+        let name = cx.string(name);
+        let value = self.params.method(cx, "get")?.arg(name).call(cx)?;
+
+        match value.downcast::<JsString, _>(cx) {
+            Ok(value) => Ok(Some(value.value(cx))),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Every value of the parameter `name`, in order.
+    pub fn get_all<C: Context<'a>>(&self, cx: &mut C, name: &str) -> NeonResult<Vec<String>> {
+        let name = cx.string(name);
+        let values = self
+            .params
+            .method(cx, "getAll")?
+            .arg(name)
+            .call(cx)?
+            .downcast_or_throw::<JsArray, _>(cx)?
+            .to_vec(cx)?;
+
+        values
+            .into_iter()
+            .map(|value| Ok(value.downcast_or_throw::<JsString, _>(cx)?.value(cx)))
+            .collect()
+    }
+
+    /// Returns `true` if the parameter `name` has at least one value.
+    pub fn has<C: Context<'a>>(&self, cx: &mut C, name: &str) -> NeonResult<bool> {
+        let name = cx.string(name);
+        let has = self.params.method(cx, "has")?.arg(name).call(cx)?;
+
+        Ok(has.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+
+    /// Adds a value to the parameter `name`.
+    pub fn append<C: Context<'a>>(&self, cx: &mut C, name: &str, value: &str) -> NeonResult<()> {
+        self.call_with_value(cx, "append", name, value)
+    }
+
+    /// Replaces every value of the parameter `name` with `value`.
+    pub fn set<C: Context<'a>>(&self, cx: &mut C, name: &str, value: &str) -> NeonResult<()> {
+        self.call_with_value(cx, "set", name, value)
+    }
+
+    /// Removes every value of the parameter `name`.
+    pub fn delete<C: Context<'a>>(&self, cx: &mut C, name: &str) -> NeonResult<()> {
+        let name = cx.string(name);
+
+        self.params.method(cx, "delete")?.arg(name).call(cx)?;
+
+        Ok(())
+    }
+
+    /// Serializes the parameters as a query string, without the leading `?`.
+    pub fn to_string<C: Context<'a>>(&self, cx: &mut C) -> NeonResult<String> {
+        let query = self.params.method(cx, "toString")?.call(cx)?;
+
+        Ok(query.downcast_or_throw::<JsString, _>(cx)?.value(cx))
+    }
+
+    fn call_with_value<C: Context<'a>>(
+        &self,
+        cx: &mut C,
+        method: &str,
+        name: &str,
+        value: &str,
+    ) -> NeonResult<()> {
+        let name = cx.string(name);
+        let value = cx.string(value);
+
+        self.params
+            .method(cx, method)?
+            .arg(name)
+            .arg(value)
+            .call(cx)?;
+
+        Ok(())
+    }
+}
+
+// Constructs a global class, e.g., `URL`
+fn construct<'a, C: Context<'a>>(
+    cx: &mut C,
+    class: &str,
+    args: Vec<Handle<'a, JsValue>>,
+) -> JsResult<'a, JsObject> {
+    let class = cx
+        .global()
+        .get(cx, class)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+
+    class.construct(cx, args)
+}
+
+fn string<'a, C: Context<'a>>(
+    cx: &mut C,
+    object: Handle<'a, JsObject>,
+    name: &str,
+) -> NeonResult<String> {
+    let value = object.get(cx, name)?;
+
+    Ok(value.downcast_or_throw::<JsString, _>(cx)?.value(cx))
+}
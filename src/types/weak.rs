@@ -0,0 +1,215 @@
+use super::{JsBoolean, JsFunction, JsValue, Value, ValueInternal};
+use crate::context::internal::Env;
+use crate::context::Context;
+use crate::handle::{Handle, Managed};
+use crate::object::Object;
+use crate::result::{JsResult, NeonResult};
+use neon_runtime;
+use neon_runtime::raw;
+
+/// A JavaScript `WeakMap`, which holds a value for each object key without
+/// keeping the keys alive.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::JsWeakMap;
+///
+/// fn label(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+///     let labels = cx.argument::<JsWeakMap>(0)?;
+///     let obj = cx.argument::<JsObject>(1)?;
+///
+///     if !labels.has(&mut cx, obj)? {
+///         let label = cx.string("seen");
+///
+///         labels.set(&mut cx, obj, label)?;
+///     }
+///
+///     Ok(cx.undefined())
+/// }
+/// # }
+/// ```
+///
+/// The `get` and `set` methods of a `JsWeakMap` access its entries. The
+/// properties of the map itself are available through [`Object`], e.g.,
+/// `Object::get(map, &mut cx, "name")`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct JsWeakMap(raw::Local);
+
+impl JsWeakMap {
+    /// Creates an empty `WeakMap`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, Self> {
+        let map = construct(cx, "WeakMap")?;
+
+        Ok(Handle::new_internal(JsWeakMap(map)))
+    }
+
+    /// Returns the value for `key`, or `undefined` if it has none.
+    pub fn get<'a, C: Context<'a>, K: Object>(
+        self,
+        cx: &mut C,
+        key: Handle<'a, K>,
+    ) -> JsResult<'a, JsValue> {
+        self.method(cx, "get")?.arg(key).call(cx)
+    }
+
+    /// Sets the value for `key`.
+    pub fn set<'a, C: Context<'a>, K: Object, V: Value>(
+        self,
+        cx: &mut C,
+        key: Handle<'a, K>,
+        value: Handle<'a, V>,
+    ) -> NeonResult<()> {
+        self.method(cx, "set")?.arg(key).arg(value).call(cx)?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the map has a value for `key`.
+    pub fn has<'a, C: Context<'a>, K: Object>(
+        self,
+        cx: &mut C,
+        key: Handle<'a, K>,
+    ) -> NeonResult<bool> {
+        let result = self.method(cx, "has")?.arg(key).call(cx)?;
+
+        Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+
+    /// Removes the value for `key`, returning `true` if there was one.
+    pub fn delete<'a, C: Context<'a>, K: Object>(
+        self,
+        cx: &mut C,
+        key: Handle<'a, K>,
+    ) -> NeonResult<bool> {
+        let result = self.method(cx, "delete")?.arg(key).call(cx)?;
+
+        Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+}
+
+impl Value for JsWeakMap {}
+
+impl Managed for JsWeakMap {
+    fn to_raw(self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsWeakMap(h)
+    }
+}
+
+impl ValueInternal for JsWeakMap {
+    fn name() -> String {
+        "WeakMap".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_weak_map(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Object for JsWeakMap {}
+
+/// A JavaScript `WeakSet`, which holds objects without keeping them alive.
+///
+/// ```
+/// # #[cfg(feature = "napi-1")] {
+/// # use neon::prelude::*;
+/// use neon::types::JsWeakSet;
+///
+/// fn visit_once(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+///     let visited = cx.argument::<JsWeakSet>(0)?;
+///     let obj = cx.argument::<JsObject>(1)?;
+///
+///     if visited.has(&mut cx, obj)? {
+///         return Ok(cx.boolean(false));
+///     }
+///
+///     visited.add(&mut cx, obj)?;
+///
+///     Ok(cx.boolean(true))
+/// }
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct JsWeakSet(raw::Local);
+
+impl JsWeakSet {
+    /// Creates an empty `WeakSet`.
+    pub fn new<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, Self> {
+        let set = construct(cx, "WeakSet")?;
+
+        Ok(Handle::new_internal(JsWeakSet(set)))
+    }
+
+    /// Adds `value` to the set.
+    pub fn add<'a, C: Context<'a>, V: Object>(
+        self,
+        cx: &mut C,
+        value: Handle<'a, V>,
+    ) -> NeonResult<()> {
+        self.method(cx, "add")?.arg(value).call(cx)?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the set contains `value`.
+    pub fn has<'a, C: Context<'a>, V: Object>(
+        self,
+        cx: &mut C,
+        value: Handle<'a, V>,
+    ) -> NeonResult<bool> {
+        let result = self.method(cx, "has")?.arg(value).call(cx)?;
+
+        Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+
+    /// Removes `value` from the set, returning `true` if it was present.
+    pub fn delete<'a, C: Context<'a>, V: Object>(
+        self,
+        cx: &mut C,
+        value: Handle<'a, V>,
+    ) -> NeonResult<bool> {
+        let result = self.method(cx, "delete")?.arg(value).call(cx)?;
+
+        Ok(result.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+    }
+}
+
+impl Value for JsWeakSet {}
+
+impl Managed for JsWeakSet {
+    fn to_raw(self) -> raw::Local {
+        self.0
+    }
+
+    fn from_raw(_: Env, h: raw::Local) -> Self {
+        JsWeakSet(h)
+    }
+}
+
+impl ValueInternal for JsWeakSet {
+    fn name() -> String {
+        "WeakSet".to_string()
+    }
+
+    fn is_typeof<Other: Value>(env: Env, other: Other) -> bool {
+        unsafe { neon_runtime::tag::is_weak_set(env.to_raw(), other.to_raw()) }
+    }
+}
+
+impl Object for JsWeakSet {}
+
+fn construct<'a, C: Context<'a>>(cx: &mut C, class: &str) -> NeonResult<raw::Local> {
+    let class = cx
+        .global()
+        .get(cx, class)?
+        .downcast_or_throw::<JsFunction, _>(cx)?;
+    let instance = class.construct(cx, Vec::<Handle<JsValue>>::new())?;
+
+    Ok(instance.to_raw())
+}
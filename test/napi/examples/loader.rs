@@ -0,0 +1,7 @@
+// Generates `generated/index.js` from the `index.node` copied by
+// `cargo-cp-artifact`, after the library is built by `npm install`
+fn main() -> std::io::Result<()> {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    neon_build::Loader::new(dir.join("index.node")).generate(dir.join("generated/index.js"))
+}
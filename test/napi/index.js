@@ -0,0 +1,7 @@
+// Loads the addon and initializes it, since its other exports cannot be used
+// until `init` is called
+const addon = require('./index.node');
+
+addon.init({ greeting: 'Hello' });
+
+module.exports = addon;
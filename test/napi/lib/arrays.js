@@ -21,4 +21,81 @@ describe('JsArray', function() {
   it('returns undefined when accessing outside JsArray bounds', function () {
     assert.strictEqual(addon.read_js_array([]), undefined);
   });
+
+  it('collects a Rust iterator into a JsArray', function () {
+    assert.deepEqual([0, 1, 4, 9], addon.return_js_array_from_iter(4));
+    assert.deepEqual([], addon.return_js_array_from_iter(0));
+  });
+
+  it('collects Rust strings into a JsArray', function () {
+    assert.deepEqual(["hello", "node"], addon.return_js_array_from_strings());
+  });
+
+  it('iterates over the elements of a JsArray', function () {
+    assert.strictEqual(addon.count_js_array_strings(["a", 1, "b", null]), 2);
+    assert.strictEqual(addon.count_js_array_strings([]), 0);
+  });
+
+  it('stops iterating when the array shrinks', function () {
+    var array = ["a", "b", "c"];
+    Object.defineProperty(array, 0, {
+      get: function () {
+        array.length = 1;
+        return "a";
+      }
+    });
+    assert.strictEqual(addon.count_js_array_strings(array), 1);
+  });
+
+  it('maps the elements of a JsArray', function () {
+    assert.strictEqual(addon.sum_js_array_with_try_map([1, 2, 3.5]), 6.5);
+    assert.throws(() => addon.sum_js_array_with_try_map([1, "2"]), TypeError);
+  });
+
+  it('constructs a JsArray from a slice of numbers', function () {
+    assert.deepEqual(addon.return_js_array_from_slice(4), [0, 0.5, 1, 1.5]);
+    assert.deepEqual(addon.return_js_array_from_slice(0), []);
+    assert(Array.isArray(addon.return_js_array_from_slice(1)));
+  });
+
+  it('copies a range of elements between arrays', function () {
+    var source = ["a", "b", "c", "d"];
+
+    assert.deepEqual(addon.copy_js_array_range(source, 1, 3, [], 0), ["b", "c"]);
+    assert.deepEqual(addon.copy_js_array_range(source, 2, 10, ["x"], 1), ["x", "c", "d"]);
+    assert.deepEqual(addon.copy_js_array_range(source, 3, 1, ["x"], 0), ["x"]);
+  });
+
+  it('writes a range without shifting the following elements', function () {
+    var target = ["w", "x", "y", "z"];
+
+    assert.deepEqual(addon.copy_js_array_range([1, 2], 0, 2, target, 1), ["w", 1, 2, "z"]);
+  });
+
+  it('leaves holes before a range written past the end', function () {
+    var target = addon.copy_js_array_range([1, 2], 0, 2, ["x"], 3);
+
+    assert.equal(target.length, 5);
+    assert.isFalse(1 in target);
+    assert.isFalse(2 in target);
+    assert.deepEqual(target.slice(3), [1, 2]);
+  });
+
+  it('writes a range longer than the argument limit', function () {
+    var source = Array.from({ length: 70000 }, (_, i) => i);
+    var target = addon.copy_js_array_range(source, 0, source.length, [], 0);
+
+    assert.deepEqual(target, source);
+  });
+
+  it('throws from a getter while reading a range', function () {
+    var source = [1, 2];
+    Object.defineProperty(source, 1, {
+      get: function () {
+        throw new Error("getter");
+      }
+    });
+
+    assert.throws(() => addon.copy_js_array_range(source, 0, 2, [], 0), /getter/);
+  });
 });
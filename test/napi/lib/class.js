@@ -0,0 +1,169 @@
+var addon = require('..');
+var assert = require('chai').assert;
+
+describe('#[neon::class]', function() {
+  it('constructs instances with new', function () {
+    var counter = new addon.ClassCounter(2);
+    assert.instanceOf(counter, addon.ClassCounter);
+    assert.strictEqual(counter.get(), 2);
+    assert.strictEqual(new addon.ClassCounter().get(), 0);
+  });
+
+  it('throws the error of a failed constructor', function () {
+    assert.throws(() => new addon.ClassCounter(-1), /must not be negative/);
+  });
+
+  it('throws when called without new', function () {
+    assert.throws(() => addon.ClassCounter(1), TypeError, /without 'new'/);
+  });
+
+  it('calls methods on the state of the instance', function () {
+    var a = new addon.ClassCounter();
+    var b = new addon.ClassCounter(10);
+    assert.strictEqual(a.increment(), 1);
+    assert.strictEqual(a.increment(5), 6);
+    assert.strictEqual(b.increment(), 11);
+    assert.strictEqual(a.get(), 6);
+  });
+
+  it('defines methods on the prototype', function () {
+    var proto = addon.ClassCounter.prototype;
+    var descriptor = Object.getOwnPropertyDescriptor(proto, 'increment');
+    assert.isFalse(descriptor.enumerable);
+    assert.deepEqual(Object.keys(new addon.ClassCounter()), []);
+  });
+
+  it('defines properties with getters and setters', function () {
+    var counter = new addon.ClassCounter(1);
+    assert.strictEqual(counter.count, 1);
+    assert.isFalse(counter.isZero);
+    counter.count = 0;
+    assert.strictEqual(counter.get(), 0);
+    assert.isTrue(counter.isZero);
+    assert.throws(() => { counter.count = -1; }, /must not be negative/);
+    assert.strictEqual(counter.count, 0);
+  });
+
+  it('defines accessors on the prototype', function () {
+    var proto = addon.ClassCounter.prototype;
+    var count = Object.getOwnPropertyDescriptor(proto, 'count');
+    var isZero = Object.getOwnPropertyDescriptor(proto, 'isZero');
+    assert.isFunction(count.get);
+    assert.isFunction(count.set);
+    assert.isFunction(isZero.get);
+    assert.isUndefined(isZero.set);
+    assert.isFalse(count.enumerable);
+    assert.throws(() => { 'use strict'; new addon.ClassCounter().isZero = false; }, TypeError);
+  });
+
+  it('throws when this is not an instance', function () {
+    var increment = addon.ClassCounter.prototype.increment;
+    assert.throws(() => increment.call({}), TypeError);
+  });
+
+  it('returns promises from async methods', async function () {
+    var counter = new addon.ClassCounter(1);
+    var added = counter.addLater(2);
+    assert.instanceOf(added, Promise);
+    assert.strictEqual(await added, 3);
+  });
+
+  it('does not lock the instance while an async method is pending', async function () {
+    var counter = new addon.ClassCounter(1);
+    var added = [counter.addLater(2), counter.addLater(3)];
+    assert.strictEqual(counter.increment(), 2);
+    assert.strictEqual(counter.count, 2);
+    assert.deepEqual(await Promise.all(added), [3, 4]);
+    assert.strictEqual(counter.get(), 2);
+  });
+
+  it('rejects when an async method fails', async function () {
+    var counter = new addon.ClassCounter(4);
+    try {
+      await counter.fail_later();
+      assert.fail("expected the method to reject");
+    } catch (err) {
+      assert.strictEqual(err, "failed at 4");
+    }
+  });
+
+  it('throws when a class without new is constructed', function () {
+    assert.throws(() => new addon.classes.ClassUnconstructable(), TypeError, /does not have a constructor/);
+  });
+
+  it('defines constants on the class', function () {
+    assert.strictEqual(addon.ClassCounter.MAX, 100);
+  });
+
+  it('defines static methods on the class', function () {
+    assert.strictEqual(addon.ClassCounter.describe(), "a counter");
+    assert.isUndefined(addon.ClassCounter.prototype.describe);
+    assert.isFalse(Object.getOwnPropertyDescriptor(addon.ClassCounter, 'describe').enumerable);
+  });
+
+  it('creates instances from static methods returning the class', function () {
+    var counter = addon.ClassCounter.from_string("7");
+    assert.instanceOf(counter, addon.ClassCounter);
+    assert.strictEqual(counter.increment(), 8);
+    assert.throws(() => addon.ClassCounter.from_string("seven"), /invalid count `seven`/);
+
+    var from = addon.ClassCounter.from_string;
+    assert.strictEqual(from("1").get(), 1);
+  });
+
+  it('creates instances of classes without a constructor', function () {
+    var Unconstructable = addon.classes.ClassUnconstructable;
+    var instance = Unconstructable.create();
+    assert.instanceOf(instance, Unconstructable);
+    assert.strictEqual(instance.ping(), "pong");
+  });
+
+  describe('extended in JavaScript', function () {
+    class Doubled extends addon.ClassCounter {
+      constructor(n) {
+        super(n * 2);
+        this.doubled = true;
+      }
+
+      increment(n) {
+        return super.increment(n * 2);
+      }
+    }
+
+    it('constructs subclasses with the state of the class', function () {
+      var counter = new Doubled(2);
+      assert.instanceOf(counter, Doubled);
+      assert.instanceOf(counter, addon.ClassCounter);
+      assert.isTrue(counter.doubled);
+      assert.strictEqual(counter.get(), 4);
+      assert.strictEqual(counter.count, 4);
+    });
+
+    it('overrides methods and calls them with super', function () {
+      var counter = new Doubled(0);
+      assert.strictEqual(counter.increment(1), 2);
+      assert.strictEqual(addon.ClassCounter.prototype.increment.call(counter, 1), 3);
+    });
+
+    it('inherits static methods and constants', function () {
+      assert.strictEqual(Doubled.MAX, 100);
+      assert.strictEqual(Doubled.describe(), "a counter");
+    });
+
+    it('creates instances of the subclass from static methods', function () {
+      var counter = Doubled.from_string("5");
+      assert.instanceOf(counter, Doubled);
+      assert.strictEqual(counter.get(), 5);
+      assert.isUndefined(counter.doubled);
+      assert.instanceOf(addon.ClassCounter.from_string.call(undefined, "1"), addon.ClassCounter);
+    });
+
+    it('creates subclasses of classes without a constructor', function () {
+      class Pinger extends addon.classes.ClassUnconstructable {}
+      assert.throws(() => new Pinger(), TypeError, /does not have a constructor/);
+      var instance = Pinger.create();
+      assert.instanceOf(instance, Pinger);
+      assert.strictEqual(instance.ping(), "pong");
+    });
+  });
+});
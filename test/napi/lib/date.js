@@ -39,4 +39,112 @@ describe('JsDate', function() {
     const dateValue = addon.get_date_value();
     assert.equal(dateValue, 31415);
   });
+
+  it('should create a date from a SystemTime', function () {
+    assert.strictEqual(addon.date_from_system_time(31415).getTime(), 31415);
+    assert.strictEqual(addon.date_from_system_time(-31415).getTime(), -31415);
+    assert.throws(() => addon.date_from_system_time(8.64e15 + 1), RangeError, /overflow/);
+  });
+
+  it('should convert a date to a SystemTime', function () {
+    assert.strictEqual(addon.date_to_system_time(new Date(31415)), 31415);
+    assert.strictEqual(addon.date_to_system_time(new Date(-31415)), -31415);
+    assert.throws(() => addon.date_to_system_time(new Date(NaN)), RangeError, /Invalid Date/);
+  });
+});
+
+// A minimal stand-in for the parts of `Temporal` used by Neon, for versions
+// of Node.js that do not provide it
+class Instant {
+  constructor(ns) {
+    this.epochNanoseconds = ns;
+  }
+
+  static fromEpochNanoseconds(ns) {
+    return new Instant(ns);
+  }
+
+  get [Symbol.toStringTag]() {
+    return 'Temporal.Instant';
+  }
+}
+
+const UNITS = ['years', 'months', 'weeks', 'days', 'hours', 'minutes',
+  'seconds', 'milliseconds', 'microseconds', 'nanoseconds'];
+
+class Duration {
+  constructor(...fields) {
+    UNITS.forEach((unit, i) => {
+      this[unit] = fields[i] || 0;
+    });
+  }
+
+  get [Symbol.toStringTag]() {
+    return 'Temporal.Duration';
+  }
+}
+
+describe('Temporal', function() {
+  const native = globalThis.Temporal;
+  const Temporal = native || { Instant, Duration };
+
+  before(function () {
+    globalThis.Temporal = Temporal;
+  });
+
+  after(function () {
+    globalThis.Temporal = native;
+  });
+
+  it('should create an instant from a SystemTime', function () {
+    const instant = addon.temporal_instant_from_millis(1600000000123);
+    assert.equal(Object.prototype.toString.call(instant), '[object Temporal.Instant]');
+    assert.equal(instant.epochNanoseconds, 1600000000123000000n);
+  });
+
+  it('should create an instant before the epoch', function () {
+    const instant = addon.temporal_instant_from_millis(-1500);
+    assert.equal(instant.epochNanoseconds, -1500000000n);
+  });
+
+  it('should convert an instant to a SystemTime', function () {
+    const instant = Temporal.Instant.fromEpochNanoseconds(1600000000123456789n);
+    assert.equal(addon.temporal_instant_to_millis(instant), 1600000000123);
+    assert.equal(addon.temporal_instant_to_millis(addon.temporal_instant_from_millis(-1500)), -1500);
+  });
+
+  it('should create a duration from a Duration', function () {
+    const duration = addon.temporal_duration_from_nanos(3001002003);
+    assert.equal(Object.prototype.toString.call(duration), '[object Temporal.Duration]');
+    assert.equal(duration.seconds, 3);
+    assert.equal(duration.milliseconds, 1);
+    assert.equal(duration.microseconds, 2);
+    assert.equal(duration.nanoseconds, 3);
+  });
+
+  it('should convert a duration to a Duration', function () {
+    const duration = new Temporal.Duration(0, 0, 0, 1, 2, 3, 4, 5, 6, 7);
+    const expected = (((1 * 24 + 2) * 60 + 3) * 60 + 4) * 1e9 + 5006007;
+    assert.equal(addon.temporal_duration_to_nanos(duration), expected);
+  });
+
+  it('should reject durations without a fixed length', function () {
+    assert.throws(() => addon.temporal_duration_to_nanos(new Temporal.Duration(0, 1)), RangeError, /months/);
+    assert.throws(() => addon.temporal_duration_to_nanos(new Temporal.Duration(0, 0, 0, 0, 0, 0, -1)), RangeError, /negative/);
+  });
+
+  it('should reject values that are not Temporal objects', function () {
+    assert.throws(() => addon.temporal_instant_to_millis(new Date()), TypeError, /expected a Temporal.Instant/);
+    assert.throws(() => addon.temporal_duration_to_nanos({ seconds: 1 }), TypeError, /expected a Temporal.Duration/);
+  });
+
+  it('should throw if Temporal is not available', function () {
+    delete globalThis.Temporal;
+
+    try {
+      assert.throws(() => addon.temporal_instant_from_millis(0), TypeError, /`Temporal` API is not available/);
+    } finally {
+      globalThis.Temporal = Temporal;
+    }
+  });
 });
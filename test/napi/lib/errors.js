@@ -1,5 +1,6 @@
 const addon = require('..');
 const assert = require('chai').assert;
+const { spawnSync } = require('child_process');
 
 describe('errors', function() {
   it('should be able to create an error', function () {
@@ -28,6 +29,21 @@ describe('errors', function() {
     assert.strictEqual(err.message, msg);
   });
 
+  it('should be able to create a syntax error', function () {
+    const msg = "Unexpected token";
+    const err = addon.new_syntax_error(msg);
+
+    assert.instanceOf(err, SyntaxError);
+    assert.instanceOf(err, Error);
+    assert.strictEqual(err.message, msg);
+  });
+
+  it('should be able to throw a syntax error', function () {
+    const msg = "Unexpected token";
+
+    assert.throws(() => addon.throw_syntax_error(msg), SyntaxError, msg);
+  });
+
   it('should be able to throw an error', function () {
     const msg = "Out of Bounds";
     
@@ -39,4 +55,45 @@ describe('errors', function() {
     assert.strictEqual(msg, "failed to downcast string to number");
   });
 
+  it('should describe the value that failed to downcast', function () {
+    class Point {}
+    const throwing = { get constructor() { throw new Error("nope"); } };
+
+    assert.strictEqual(addon.downcast_error_found([]), "failed to downcast object (Array) to function");
+    assert.strictEqual(addon.downcast_error_found(new Date()), "failed to downcast object (Date) to function");
+    assert.strictEqual(addon.downcast_error_found(new Point()), "failed to downcast object (Point) to function");
+    assert.strictEqual(addon.downcast_error_found(Object.create(null)), "failed to downcast object to function");
+    assert.strictEqual(addon.downcast_error_found(throwing), "failed to downcast object to function");
+    assert.strictEqual(addon.downcast_error_found(null), "failed to downcast null to function");
+    assert.strictEqual(addon.downcast_error_found(1), "failed to downcast number to function");
+  });
+
+  it('should be able to inspect a pending exception without catching it', function () {
+    const err = new Error("pending");
+    const [throwing, pending, caught] = addon.pending_exception(() => { throw err; });
+
+    assert.strictEqual(throwing, true);
+    assert.strictEqual(pending, err);
+    assert.strictEqual(caught, err);
+  });
+
+  it('should not report a pending exception when nothing was thrown', function () {
+    const [throwing, pending, caught] = addon.pending_exception(() => 42);
+
+    assert.strictEqual(throwing, false);
+    assert.strictEqual(pending, undefined);
+    assert.strictEqual(caught, undefined);
+  });
+
+  it('should abort the process with a fatal error', function () {
+    const script = `require(${JSON.stringify(require.resolve('..'))}).fatal("invariant violated")`;
+    const { status, signal, stderr } = spawnSync(process.execPath, ['-e', script]);
+
+    assert.notStrictEqual(status, 0);
+    if (status === null) {
+      assert.strictEqual(signal, 'SIGABRT');
+    }
+    assert.include(stderr.toString(), "invariant violated");
+    assert.include(stderr.toString(), "errors.rs");
+  });
 });
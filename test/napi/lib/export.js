@@ -0,0 +1,294 @@
+var addon = require('..');
+var assert = require('chai').assert;
+const { Worker } = require('worker_threads');
+
+describe('#[neon::export]', function() {
+  it('exports functions with extracted arguments', function () {
+    assert.strictEqual(addon.export_add(1, 2), 3);
+    assert.throws(() => addon.export_add(1), TypeError, /expected number/);
+  });
+
+  it('names the argument that failed to extract', function () {
+    assert.throws(
+      () => addon.export_add(1, "2"),
+      TypeError,
+      /^argument 2 \("b"\) expected number, found string$/
+    );
+    assert.throws(
+      () => addon.export_add([]),
+      TypeError,
+      /^argument 1 \("a"\) expected number, found Array$/
+    );
+    assert.throws(
+      () => addon.export_join("-", "a", null),
+      TypeError,
+      /^argument 3 \("parts"\) expected string, found null$/
+    );
+    assert.throws(
+      () => addon.export_repeat("a", 1.5),
+      RangeError,
+      /^argument 2 \("n"\) expected an integer, found 1.5$/
+    );
+    assert.throws(
+      () => addon.export_either(null),
+      TypeError,
+      /^argument 1 \("v"\) expected string or number, found null$/
+    );
+    assert.throws(
+      () => addon.export_repeat("a", -1),
+      RangeError,
+      /^argument 2 \("n"\) -1 is out of range for u32$/
+    );
+
+    try {
+      addon.export_add(1, "2");
+    } catch (err) {
+      assert.match(err.stack, /^TypeError: argument 2 \("b"\) expected number, found string\n/);
+    }
+  });
+
+  it('treats missing arguments as undefined', function () {
+    assert.strictEqual(addon.export_greet(), "Hello, World!");
+    assert.strictEqual(addon.export_greet("Neon"), "Hello, Neon!");
+  });
+
+  it('passes the context to functions that ask for it', function () {
+    assert.deepEqual(addon.export_with_context(5), [5]);
+  });
+
+  it('collects rest parameters', function () {
+    assert.strictEqual(addon.export_join(", ", "a", "b", "c"), "a, b, c");
+    assert.strictEqual(addon.export_join("-"), "");
+    assert.throws(() => addon.export_join("-", "a", 1), TypeError, /expected string/);
+    assert.strictEqual(addon.export_count_rest(1, null, {}), 3);
+    assert.strictEqual(addon.export_count_rest(), 0);
+  });
+
+  it('throws the error of a failed Result', function () {
+    assert.strictEqual(addon.export_fallible(false), 1);
+    assert.throws(() => addon.export_fallible(true), /failed/);
+  });
+
+  it('converts arguments and return values of json exports with serde', function () {
+    assert.deepEqual(addon.export_json_scale([1, 2]), [2, 4]);
+    assert.deepEqual(addon.export_json_scale([1, 2], 3), [3, 6]);
+    assert.deepEqual(addon.export_json_scale([1, 2], undefined), [2, 4]);
+    assert.strictEqual(addon.export_json_lookup({ a: 1, b: 2 }, 'b'), 2);
+    assert.isNull(addon.export_json_lookup({ a: 1 }, 'c'));
+  });
+
+  it('throws when json exports cannot convert a value', function () {
+    assert.throws(
+      () => addon.export_json_scale('1'),
+      TypeError,
+      /^invalid argument 1 \("values"\): /
+    );
+    assert.throws(
+      () => addon.export_json_scale([1], 'x'),
+      TypeError,
+      /^invalid argument 2 \("factor"\): /
+    );
+    assert.throws(() => addon.export_json_lookup({}, ''), /key must not be empty/);
+  });
+
+  it('passes the this binding', function () {
+    var obj = { name: "neon", getName: addon.export_get_name };
+    assert.strictEqual(obj.getName(), "neon");
+  });
+
+  it('extracts a boxed this binding', function () {
+    var counter = addon.export_create_counter();
+    assert.strictEqual(addon.export_boxed_count.call(counter, 2), 2);
+    assert.strictEqual(addon.export_boxed_count.call(counter, 3), 5);
+    assert.throws(() => addon.export_boxed_count.call({}, 1), TypeError);
+  });
+
+  it('uses defaults for missing trailing arguments', function () {
+    assert.strictEqual(addon.export_repeat("a"), "aa");
+    assert.strictEqual(addon.export_repeat("a", undefined), "aa");
+    assert.strictEqual(addon.export_repeat("a", 3), "aaa");
+    assert.strictEqual(addon.export_repeat("a", 3, "-"), "a-a-a");
+    assert.throws(() => addon.export_repeat("a", null), TypeError, /expected number/);
+  });
+
+  it('passes a Cx to exported functions', function () {
+    assert.strictEqual(addon.export_with_cx("neon"), "NEON");
+  });
+
+  it('exports under an explicit name', function () {
+    assert.strictEqual(addon.exportRenamed(), "renamed");
+    assert.isUndefined(addon.export_renamed);
+  });
+
+  it('defines exports as plain data properties', function () {
+    var descriptor = Object.getOwnPropertyDescriptor(addon, 'exportRenamed');
+    assert.isTrue(descriptor.writable);
+    assert.isTrue(descriptor.enumerable);
+    assert.isTrue(descriptor.configurable);
+    assert.isFunction(descriptor.value);
+
+    descriptor = Object.getOwnPropertyDescriptor(addon.math.sync, 'triple');
+    assert.isTrue(descriptor.enumerable);
+    assert.strictEqual(descriptor.value(2), 6);
+  });
+
+  it('exports into nested namespaces', function () {
+    assert.strictEqual(addon.math.sync.export_double(2), 4);
+    assert.strictEqual(addon.math.sync.triple(2), 6);
+    assert.strictEqual(addon.math.export_negate(2), -2);
+    assert.isUndefined(addon.export_double);
+  });
+
+  it('initializes lazy exports on first access', function () {
+    assert.strictEqual(addon.export_lazy_init_count(), 0);
+
+    var descriptor = Object.getOwnPropertyDescriptor(addon, 'export_lazy_table');
+    assert.isFunction(descriptor.get);
+
+    assert.deepEqual(addon.export_lazy_table, [0, 1, 4, 9, 16]);
+    assert.strictEqual(addon.export_lazy_table, addon.export_lazy_table);
+    assert.strictEqual(addon.export_lazy_init_count(), 1);
+
+    descriptor = Object.getOwnPropertyDescriptor(addon, 'export_lazy_table');
+    assert.isUndefined(descriptor.get);
+    assert.isArray(descriptor.value);
+  });
+
+  it('runs task exports on the worker pool', async function () {
+    var promise = addon.export_task_sum([1, 2, 3]);
+
+    assert.instanceOf(promise, Promise);
+    assert.strictEqual(await promise, 6);
+  });
+
+  it('rejects task exports that return an error', async function () {
+    assert.strictEqual(await addon.export_task_fallible(false), "task succeeded");
+
+    try {
+      await addon.export_task_fallible(true);
+      assert.fail("expected the task to reject");
+    } catch (err) {
+      assert.strictEqual(err, "task failed");
+    }
+  });
+
+  it('clones an Arc out of a boxed argument', async function () {
+    var counter = addon.export_arc_counter();
+
+    assert.strictEqual(addon.export_arc_increment(counter), 1);
+    assert.strictEqual(await addon.export_arc_increment_task(counter, 2), 3);
+    assert.strictEqual(addon.export_arc_increment(counter), 4);
+    assert.throws(() => addon.export_arc_increment({}), TypeError);
+  });
+
+  it('rejects task exports with invalid arguments synchronously', function () {
+    assert.throws(() => addon.export_task_sum("not an array"), TypeError);
+    assert.throws(() => addon.export_task_progress(1, "not a function"), TypeError, /expected function/);
+  });
+
+  it('reports progress from task exports', async function () {
+    var reports = [];
+
+    assert.strictEqual(await addon.export_task_progress(3, n => reports.push(n)), 3);
+
+    // Reports may be delivered after the promise resolves
+    while (reports.length < 3) {
+      await new Promise(resolve => setImmediate(resolve));
+    }
+
+    assert.deepEqual(reports, [1, 2, 3]);
+  });
+
+  it('describes exports at runtime', function () {
+    assert.strictEqual(
+      addon.export_describe('export_repeat'),
+      'Function export_repeat(s: String, n?: u32, sep?: Option<String>) -> String [string]'
+    );
+    assert.strictEqual(
+      addon.export_describe('export_join'),
+      'Function export_join(separator: String, ...parts: Vec<String>) -> String [string]'
+    );
+    assert.strictEqual(
+      addon.export_describe('math.sync.triple'),
+      'Function export_triple(n: f64) -> f64 [number]'
+    );
+    assert.strictEqual(
+      addon.export_describe('export_lazy_table'),
+      'Lazy export_lazy_table() -> Vec<u32> [number[]]'
+    );
+    assert.strictEqual(
+      addon.export_describe('export_task_sum'),
+      'Task export_task_sum(values: Vec<f64>) -> f64 [number]'
+    );
+    assert.strictEqual(
+      addon.export_describe('classes.ClassUnconstructable'),
+      'Class Unconstructable() -> Unconstructable [ClassUnconstructable]'
+    );
+    assert.isUndefined(addon.export_describe('export_missing'));
+  });
+
+  it('exports conditionally', function () {
+    assert.isTrue(addon.export_requires_supported());
+    assert.notProperty(addon, 'export_requires_future');
+    assert.notProperty(addon, 'export_when_never');
+    assert.throws(
+      () => addon.export_requires_future_stub(),
+      /`export_requires_future_stub` is unavailable: requires N-API version 99/
+    );
+    assert.throws(
+      () => addon.export_when_never_lazy,
+      /`export_when_never_lazy` is unavailable: not supported by this runtime/
+    );
+  });
+
+  it('runs every main function in order', function () {
+    assert.deepEqual(addon.mainOrder, ['early:false', 'after:true', 'later:true']);
+  });
+
+  it('configures the module with init', function () {
+    assert.strictEqual(addon.init_greeting(), 'Hello');
+    assert.throws(() => addon.init({ greeting: 'Again' }), /`init` was already called/);
+  });
+
+  it('requires init before other exports', function (cb) {
+    const worker = new Worker(`
+      const { parentPort } = require('worker_threads');
+      const addon = require(${JSON.stringify(require.resolve('../index.node'))});
+      const errors = [];
+      const capture = (f) => {
+        try {
+          f();
+        } catch (err) {
+          errors.push(err instanceof Error ? err.message : err);
+        }
+      };
+
+      capture(() => addon.export_add(1, 2));
+      capture(() => addon.init({}));
+      capture(() => addon.init({ greeting: '' }));
+
+      const version = addon.init_version();
+
+      addon.init({ greeting: 'Hi' });
+      parentPort.postMessage({ errors, version, greeting: addon.init_greeting() });
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('message', ({ errors, version, greeting }) => {
+      try {
+        assert.strictEqual(errors.length, 3);
+        assert.strictEqual(
+          errors[0],
+          '`export_add` cannot be used before the module is initialized with `init`'
+        );
+        assert.match(errors[1], /argument 1 \("config"\) expected string/);
+        assert.strictEqual(errors[2], 'greeting must not be empty');
+        assert.strictEqual(version, '1.0.0');
+        assert.strictEqual(greeting, 'Hi');
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+});
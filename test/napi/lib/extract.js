@@ -0,0 +1,172 @@
+var addon = require('..');
+var assert = require('chai').assert;
+
+describe('extract', function() {
+  it('extracts a Vec from an array', function () {
+    assert.strictEqual(addon.extract_vec_sum([1, 2, 3]), 6);
+    assert.strictEqual(addon.extract_vec_sum([]), 0);
+  });
+
+  it('throws when a Vec element has the wrong type', function () {
+    assert.throws(() => addon.extract_vec_sum([1, "2"]), TypeError, /expected number/);
+    assert.throws(() => addon.extract_vec_sum({}), TypeError, /expected Array/);
+  });
+
+  it('extracts numeric Vecs from typed arrays', function () {
+    assert.strictEqual(addon.extract_vec_sum(new Float64Array([1, 2, 3.5])), 6.5);
+    assert.strictEqual(addon.extract_vec_sum(new Float64Array(new ArrayBuffer(32), 8, 2)), 0);
+    assert.deepEqual(addon.extract_f32_vec(new Float32Array([0.5, -1])), [0.5, -1]);
+    assert.deepEqual(addon.extract_f32_vec([0.5, 2]), [0.5, 2]);
+    assert.deepEqual(addon.extract_i32_vec_doubled(new Int32Array([1, -2])), [2, -4]);
+    assert.deepEqual(addon.extract_i32_vec_doubled([]), []);
+    assert.strictEqual(addon.extract_u8_vec_len(Buffer.from('hello')), 5);
+    assert.strictEqual(addon.extract_u8_vec_len(new Uint8Array(0)), 0);
+  });
+
+  it('does not extract typed arrays of a different type', function () {
+    assert.throws(() => addon.extract_vec_sum(new Float32Array([1])), TypeError, /expected Array/);
+    assert.throws(() => addon.extract_i32_vec_doubled(new Uint32Array([1])), TypeError, /expected Array/);
+  });
+
+  it('converts nested Vecs', function () {
+    assert.deepEqual(addon.extract_nested_vec([["a"], ["b", "c"]]), [["b", "c"], ["a"]]);
+  });
+
+  it('converts a HashMap to and from an object', function () {
+    assert.deepEqual(addon.extract_hash_map({ a: 1, b: 2.5 }), { a: 2, b: 5 });
+    assert.throws(() => addon.extract_hash_map({ a: "1" }), TypeError, /expected number/);
+    assert.throws(() => addon.extract_hash_map(1), TypeError, /expected object/);
+  });
+
+  it('extracts a HashMap from a Map', function () {
+    var map = new Map([["a", 1], ["b", 2.5]]);
+
+    assert.deepEqual(addon.extract_hash_map(map), { a: 2, b: 5 });
+    assert.deepEqual(addon.extract_btree_map_keys(new Map([["b", true], ["a", false]])), ["a", "b"]);
+    assert.throws(() => addon.extract_hash_map(new Map([[1, 1]])), TypeError, /expected string/);
+    assert.throws(() => addon.extract_hash_map(new Map([["a", "1"]])), TypeError, /expected number/);
+  });
+
+  it('only extracts enumerable properties of plain objects', function () {
+    var object = { a: 1 };
+
+    Object.defineProperty(object, "hidden", { value: "not a number", enumerable: false });
+
+    assert.deepEqual(addon.extract_hash_map(object), { a: 2 });
+    assert.throws(() => addon.extract_hash_map([1, 2]), TypeError, /found array/);
+    assert.throws(() => addon.extract_hash_map(function () {}), TypeError, /expected object/);
+  });
+
+  it('extracts a BTreeMap with sorted keys', function () {
+    assert.deepEqual(addon.extract_btree_map_keys({ c: true, a: null, b: undefined }), ["a", "b", "c"]);
+  });
+
+  it('extracts tuples from fixed-length arrays', function () {
+    assert.strictEqual(addon.extract_point([3, 4]), 5);
+    assert.strictEqual(addon.extract_mixed_tuple(["a", 2, true]), "a:2:true");
+  });
+
+  it('throws when a tuple has the wrong shape', function () {
+    assert.throws(() => addon.extract_point([3]), TypeError, /length 2/);
+    assert.throws(() => addon.extract_point([3, 4, 5]), TypeError, /length 2/);
+    assert.throws(() => addon.extract_mixed_tuple(["a", "b", true]), TypeError, /expected number/);
+  });
+
+  it('extracts the first matching alternative of an Either', function () {
+    assert.strictEqual(addon.extract_either("a"), "string:a");
+    assert.strictEqual(addon.extract_either(1.5), "number:1.5");
+    assert.strictEqual(addon.extract_either(false), "boolean:false");
+  });
+
+  it('throws a combined error when no alternative matches', function () {
+    assert.throws(() => addon.extract_either(null), TypeError, /^expected string or number or boolean$/);
+  });
+
+  it('keeps the messages of alternatives that do not name a type', function () {
+    assert.strictEqual(addon.extract_either_list([1, 2]), 3);
+    assert.throws(() => addon.extract_either_list(true), TypeError, /^expected number, or expected Array$/);
+  });
+
+  it('distinguishes null from undefined with Nullable', function () {
+    assert.strictEqual(addon.extract_nullable(1), 2);
+    assert.strictEqual(addon.extract_nullable(null), null);
+    assert.throws(() => addon.extract_nullable(undefined), TypeError, /expected number/);
+  });
+
+  it('rejects undefined with Defined', function () {
+    assert.strictEqual(addon.extract_defined("a"), "a");
+    assert.strictEqual(addon.extract_defined(null), "<null>");
+    assert.throws(() => addon.extract_defined(undefined), TypeError, /found undefined/);
+  });
+
+  it('extracts integers with range checking', function () {
+    assert.strictEqual(addon.extract_u8(0), 0);
+    assert.strictEqual(addon.extract_u8(255), 255);
+    assert.strictEqual(addon.extract_i64_string(-9007199254740991), "-9007199254740991");
+  });
+
+  it('rejects non-integral and out-of-range numbers', function () {
+    assert.throws(() => addon.extract_u8(1.5), RangeError, /expected an integer, found 1.5/);
+    assert.throws(() => addon.extract_u8(NaN), RangeError, /expected an integer/);
+    assert.throws(() => addon.extract_u8(256), RangeError, /256 is out of range for u8/);
+    assert.throws(() => addon.extract_u8(-1), RangeError, /out of range for u8/);
+    assert.throws(() => addon.extract_i64_string(2 ** 63), RangeError, /out of range for i64/);
+    assert.throws(() => addon.extract_u8("1"), TypeError, /expected number/);
+  });
+
+  it('truncates numbers with Lossy', function () {
+    assert.strictEqual(addon.extract_lossy_u8(1.5), 1);
+    assert.strictEqual(addon.extract_lossy_u8(300), 255);
+  });
+
+  it('extracts bytes from binary values and strings', function () {
+    assert.strictEqual(addon.extract_bytes_len(Buffer.from([1, 2, 3])), 3);
+    assert.strictEqual(addon.extract_bytes_len(new ArrayBuffer(4)), 4);
+    assert.strictEqual(addon.extract_bytes_len(new Uint16Array(5)), 10);
+    assert.strictEqual(addon.extract_bytes_len(new Uint8Array(new ArrayBuffer(8), 2, 3)), 3);
+    assert.strictEqual(addon.extract_bytes_len("h\u00e9"), 3);
+    assert.throws(() => addon.extract_bytes_len(1), TypeError, /expected Buffer/);
+  });
+
+  it('converts bytes into a Buffer', function () {
+    assert.deepEqual(addon.extract_bytes_reversed(Buffer.from([1, 2, 3])), Buffer.from([3, 2, 1]));
+    assert.deepEqual(addon.extract_bytes_reversed(""), Buffer.alloc(0));
+  });
+
+  it('extracts and converts base64 and hex strings', function () {
+    for (var length = 0; length < 8; length++) {
+      var bytes = Buffer.from([0, 1, 62, 63, 127, 128, 254, 255].slice(0, length));
+
+      assert.strictEqual(addon.extract_base64_to_hex(bytes.toString('base64')), bytes.toString('hex'));
+      assert.strictEqual(addon.extract_hex_to_base64(bytes.toString('hex')), bytes.toString('base64'));
+    }
+
+    assert.strictEqual(addon.extract_base64_to_hex("-_8"), "fbff");
+    assert.strictEqual(addon.extract_base64_to_hex(Buffer.from([1, 2])), "0102");
+    assert.strictEqual(addon.extract_hex_to_base64("FFfe"), "//4=");
+    assert.throws(() => addon.extract_base64_to_hex("a"), TypeError, /invalid base64/);
+    assert.throws(() => addon.extract_base64_to_hex("a=b"), TypeError, /invalid base64/);
+    assert.throws(() => addon.extract_hex_to_base64("abc"), TypeError, /invalid hex/);
+    assert.throws(() => addon.extract_hex_to_base64("+f"), TypeError, /invalid hex/);
+    assert.throws(() => addon.extract_hex_to_base64(1), TypeError, /expected hex string/);
+  });
+
+  it('extracts paths like the fs module', function () {
+    var path = require('path');
+    var url = require('url');
+    var file = path.resolve('some dir', 'file.txt');
+
+    assert.strictEqual(addon.extract_path(file), file);
+    assert.strictEqual(addon.extract_path(Buffer.from(file)), file);
+    assert.strictEqual(addon.extract_path(url.pathToFileURL(file)), file);
+    assert.throws(() => addon.extract_path(new URL('http://example.com')), TypeError, /scheme file/);
+    assert.throws(() => addon.extract_path(1), TypeError, /expected string/);
+
+    // Only Unix paths can hold bytes that are not UTF-8
+    if (process.platform === 'win32') {
+      assert.throws(() => addon.extract_path(new URL('file:///C:/%FF')), TypeError, /not valid UTF-8/);
+    } else {
+      assert.strictEqual(addon.extract_path(new URL('file:///%FF')), '/\uFFFD');
+    }
+  });
+});
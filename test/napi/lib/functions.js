@@ -14,6 +14,42 @@ describe('JsFunction', function() {
     assert.equal(addon.call_js_function(function(x) { return x + 1 }), 17);
   });
 
+  it('call a JsFunction with a tuple of arguments', function () {
+    const self = {};
+    const result = addon.call_js_function_with_args(function () {
+      return [this, Array.from(arguments)];
+    }, self);
+
+    assert.strictEqual(result[0], self);
+    assert.deepEqual(result[1], [1, 'two', true]);
+    assert.equal(addon.call_js_function_without_args(function () { return arguments.length }), 0);
+  });
+
+  it('call a JsFunction repeatedly with a CallSite', function () {
+    const self = { scale: 2 };
+    const sum = addon.call_site_sum(function (a, b) {
+      assert.strictEqual(this, self);
+      return (a + b) * this.scale;
+    }, self, 100);
+
+    assert.equal(sum, 2 * (4950 + 100));
+  });
+
+  it('call a CallSite from another thread', function (cb) {
+    addon.call_site_later(function (msg) {
+      assert.equal(msg, 'from a thread');
+      cb();
+    });
+  });
+
+  it('new a JsFunction with a tuple of arguments', function () {
+    const date = addon.construct_js_function_with_args(Date);
+
+    assert.instanceOf(date, Date);
+    assert.equal(date.getFullYear(), 2000);
+    assert.equal(date.getMonth(), 1);
+  });
+
   it('new a JsFunction', function () {
     assert.equal(addon.construct_js_function(Date), 1970);
   });
@@ -114,4 +150,58 @@ describe('JsFunction', function() {
     assert.equal(addon.is_construct.call({}).wasConstructed, false);
     assert.equal((new addon.is_construct()).wasConstructed, true);
   });
+
+  it('gets every argument at once', function() {
+    assert.strictEqual(addon.count_args(), 0);
+    assert.strictEqual(addon.count_args(1, "a", null, {}, [], 6), 6);
+    assert.strictEqual(addon.sum_args(), 0);
+    assert.strictEqual(addon.sum_args(1, 2, 3, 4, 5, 6, 7, 8), 36);
+    assert.throws(() => addon.sum_args(1, "2"), TypeError);
+  });
+
+  it('creates a context from a raw env', function() {
+    try {
+      assert.strictEqual(addon.set_global_with_raw_env("from a raw env"), true);
+      assert.strictEqual(global.rawEnvValue, "from a raw env");
+    } finally {
+      delete global.rawEnvValue;
+    }
+  });
+
+  it('attaches data to a function', function() {
+    const hello = addon.greeter("Hello");
+    const hi = addon.greeter("Hi");
+
+    assert.strictEqual(hello("Neon"), "Hello, Neon!");
+    assert.strictEqual(hi("Neon"), "Hi, Neon!");
+    assert.throws(() => addon.greeter_with_wrong_data()("Neon"), TypeError, /missing greeting/);
+    assert.throws(() => addon.greeter_without_data()("Neon"), TypeError, /missing greeting/);
+  });
+
+  it('exposes new.target', function() {
+    assert.isUndefined(addon.new_target_name());
+    assert.strictEqual(new addon.new_target_name().target, addon.new_target_name.name);
+    assert.strictEqual(Reflect.construct(addon.new_target_name, [], Date).target, "Date");
+
+    class Subclass extends addon.new_target_name {}
+    assert.strictEqual(new Subclass().target, "Subclass");
+  });
+
+  it('passes a concrete Cx to helpers', function() {
+    assert.deepEqual(addon.build_object_with_cx(1, 2), { x: 1, y: 2 });
+  });
+
+  it('binds a function', function() {
+    const bound = addon.bind_function(function (a, b, c) {
+      return [this.name, a, b, c];
+    }, { name: 'this' }, [1, 2]);
+
+    assert.deepEqual(bound(3), ['this', 1, 2, 3]);
+
+    const shadowed = function (a) { return a; };
+    shadowed.bind = () => { throw new Error('shadowed'); };
+
+    assert.strictEqual(addon.bind_function(shadowed, null, ['bound'])(), 'bound');
+    assert.deepEqual(addon.bind_root_and_call((a, b) => [a, b], 1, 2), [1, 2]);
+  });
 });
@@ -0,0 +1,37 @@
+var addon = require('..');
+var assert = require('chai').assert;
+
+describe('#[neon::test]', function() {
+  it('runs the tests compiled into the addon', function () {
+    var results = addon.__neon_run_tests('harness_');
+
+    assert.sameMembers(results.map(result => result.name), [
+      'harness_objects',
+      'harness_channel',
+      'harness_without_context',
+      'harness_throws',
+      'harness_panics'
+    ]);
+
+    var byName = Object.fromEntries(results.map(result => [result.name, result]));
+
+    assert.isTrue(byName.harness_objects.passed);
+    assert.isTrue(byName.harness_channel.passed);
+    assert.isTrue(byName.harness_without_context.passed);
+    assert.isUndefined(byName.harness_objects.error);
+
+    assert.isFalse(byName.harness_throws.passed);
+    assert.instanceOf(byName.harness_throws.error, RangeError);
+    assert.match(byName.harness_throws.error.message, /expected to throw/);
+
+    assert.isFalse(byName.harness_panics.passed);
+    assert.instanceOf(byName.harness_panics.error, Error);
+    assert.match(byName.harness_panics.error.message, /expected to panic/);
+  });
+
+  it('filters tests by name', function () {
+    var results = addon.__neon_run_tests('harness_throws');
+
+    assert.deepEqual(results.map(result => result.name), ['harness_throws']);
+  });
+});
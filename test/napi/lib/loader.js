@@ -0,0 +1,44 @@
+var fs = require('fs');
+var path = require('path');
+var assert = require('chai').assert;
+var loader = require('../generated');
+
+describe('generated loader', function() {
+  // The loader shares the module instance with `index.js`, which calls `init`
+  // once, even if this file runs on its own
+  before(function () {
+    require('..');
+  });
+
+  it('wraps exported functions', function () {
+    assert.strictEqual(loader.export_add(1, 2), 3);
+    assert.strictEqual(loader.export_add.name, 'export_add');
+    assert.strictEqual(loader.export_greet(), 'Hello, World!');
+    assert.strictEqual(loader.export_join(', ', 'a', 'b'), 'a, b');
+    assert.strictEqual(loader.math.sync.triple(2), 6);
+  });
+
+  it('validates the number of arguments', function () {
+    assert.throws(() => loader.export_add(1), TypeError, /export_add expected 2 argument\(s\), found 1/);
+    assert.throws(() => loader.export_add(1, 2, 3), TypeError, /found 3/);
+    assert.throws(() => loader.export_greet('a', 'b'), TypeError, /expected 0 to 1 argument\(s\)/);
+    assert.throws(() => loader.export_join(), TypeError, /expected at least 1 argument\(s\)/);
+    assert.throws(() => loader.math.sync.triple(), TypeError, /math\.sync\.triple expected 1/);
+  });
+
+  it('keeps other exports', function () {
+    assert.strictEqual(loader.greeting, 'Hello, World!');
+    assert.property(loader, 'export_lazy_table');
+  });
+
+  it('writes TypeScript declarations', function () {
+    var dts = fs.readFileSync(path.join(__dirname, '../generated/index.d.ts'), 'utf8');
+
+    assert.include(dts, 'export function export_add(a: number, b: number): number;');
+    assert.include(dts, 'export function export_greet(name?: string): string;');
+    assert.include(dts, 'export function export_join(separator: string, ...parts: string[]): string;');
+    assert.include(dts, 'export function export_task_sum(values: number[]): Promise<number>;');
+    assert.include(dts, 'export declare namespace math {');
+    assert.include(dts, '    export function triple(n: number): number;');
+  });
+});
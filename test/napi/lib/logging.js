@@ -0,0 +1,55 @@
+const addon = require('..');
+const assert = require('chai').assert;
+
+// The logger is global, so it is installed once for every test
+const logger = addon.log_init();
+
+// Records are delivered asynchronously through a channel
+function flush() {
+  return new Promise((resolve) => setImmediate(resolve));
+}
+
+describe('neon::log', function () {
+  afterEach(function () {
+    logger.setSink(null);
+    logger.setLevel('info');
+  });
+
+  it('forwards records to a sink', async function () {
+    const records = [];
+
+    logger.setSink((record) => records.push(record));
+    addon.log_from_thread('warn', 'disk almost full');
+    await flush();
+
+    assert.lengthOf(records, 1);
+    assert.strictEqual(records[0].level, 'warn');
+    assert.strictEqual(records[0].target, 'napi_tests');
+    assert.strictEqual(records[0].message, 'disk almost full');
+    assert.isString(records[0].file);
+    assert.isNumber(records[0].line);
+  });
+
+  it('filters records by the level set from JavaScript', async function () {
+    const records = [];
+
+    logger.setSink((record) => records.push(record.message));
+    addon.log_from_thread('debug', 'hidden');
+    logger.setLevel('debug');
+    addon.log_from_thread('debug', 'shown');
+    logger.setLevel('error');
+    addon.log_from_thread('warn', 'hidden');
+    addon.log_from_thread('error', 'failed');
+    await flush();
+
+    assert.deepEqual(records, ['shown', 'failed']);
+  });
+
+  it('rejects unknown levels', function () {
+    assert.throws(() => logger.setLevel('loud'), RangeError, /unknown log level `loud`/);
+  });
+
+  it('can only be installed once', function () {
+    assert.throws(() => addon.log_init(), Error, /a logger is already installed/);
+  });
+});
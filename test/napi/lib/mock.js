@@ -0,0 +1,14 @@
+var addon = require('..');
+var assert = require('chai').assert;
+
+describe('MockContext', function() {
+  it('runs Neon functions without Node.js', function () {
+    var results = addon.__neon_run_tests('mock_');
+
+    assert.lengthOf(results, 6);
+
+    for (var result of results) {
+      assert.isTrue(result.passed, result.name + ': ' + result.error);
+    }
+  });
+});
@@ -105,6 +105,68 @@ describe('JsObject', function() {
     assert.strictEqual(Buffer.from(buf).toString(), expected);
   });
 
+  it('detects support for external buffers', function() {
+    // Node.js allows external buffers; Electron with the memory cage does not
+    assert.isBoolean(addon.supports_external_buffers());
+    if (!process.versions.electron) {
+      assert.isTrue(addon.supports_external_buffers());
+    }
+  });
+
+  it('gets Buffers from a BufferPool', function() {
+    var expected = "String to copy";
+    var [buf, arrayBuf] = addon.return_pooled_buffers(expected);
+    assert.instanceOf(buf, Buffer);
+    assert.strictEqual(buf.toString(), expected);
+    assert.instanceOf(arrayBuf, ArrayBuffer);
+    assert.strictEqual(Buffer.from(arrayBuf).toString(), expected);
+  });
+
+  it('lends bytes to an ArrayBuffer and reclaims them', function() {
+    var lent;
+    var result = addon.lend_array_buffer(function (buf) {
+      lent = buf;
+      var view = new Uint8Array(buf);
+      assert.deepEqual(Array.from(view), [1, 2, 3]);
+      view[0] = 4;
+    });
+    assert.strictEqual(lent.byteLength, 0);
+    assert.deepEqual(result, [4, 2, 3]);
+  });
+
+  it('detaches an ArrayBuffer', function() {
+    var buf = new ArrayBuffer(8);
+    assert.isTrue(addon.detach_array_buffer(buf));
+    assert.strictEqual(buf.byteLength, 0);
+  });
+
+  it('resizes a resizable ArrayBuffer', function() {
+    assert.deepEqual(addon.resize_array_buffer(), [8, 16, true, false, 4]);
+  });
+
+  it('throws when resizing a fixed-length ArrayBuffer', function() {
+    assert.throws(() => addon.resize_fixed_array_buffer(new ArrayBuffer(4)), TypeError);
+  });
+
+  it('grows a growable SharedArrayBuffer', function() {
+    var buf = addon.grow_shared_array_buffer();
+    assert.instanceOf(buf, SharedArrayBuffer);
+    assert.strictEqual(buf.byteLength, 8);
+    assert.isTrue(buf.growable);
+  });
+
+  it('reports external memory to the garbage collector', function() {
+    var before = addon.adjust_external_memory(1024);
+    assert.strictEqual(addon.adjust_external_memory(-1024), before - 1024);
+  });
+
+  it('reports the memory of external buffers', function() {
+    var before = addon.adjust_external_memory(0);
+    var buf = addon.return_external_buffer("x".repeat(4096));
+    assert.isAtLeast(addon.adjust_external_memory(0), before + 4096);
+    assert.strictEqual(buf.length, 4096);
+  });
+
   it('correctly reads a Buffer using the lock API', function() {
     var b = Buffer.allocUnsafe(16);
     b.writeUInt32LE(147,    0);
@@ -180,4 +242,191 @@ describe('JsObject', function() {
     );
     assert.equal(addon.get_own_property_names(object).length, 1);
   });
+
+  it('gets a value at a property path', function() {
+    var obj = { a: { b: { c: 42 } }, top: 1 };
+
+    assert.strictEqual(addon.get_number_path(obj, 'a.b.c'), 42);
+    assert.strictEqual(addon.get_number_path(obj, 'top'), 1);
+  });
+
+  it('names the missing segment of a property path', function() {
+    var obj = { a: { b: 'text' } };
+
+    assert.throws(
+      () => addon.get_number_path(obj, 'a.x.c'),
+      TypeError,
+      'expected `a.x` to be an object, found undefined'
+    );
+    assert.throws(
+      () => addon.get_number_path(obj, 'a.b.c'),
+      TypeError,
+      'expected `a.b` to be an object, found a string'
+    );
+    assert.throws(
+      () => addon.get_number_path(obj, 'a.b'),
+      TypeError,
+      'expected `a.b` to be a number, found a string'
+    );
+  });
+
+  it('sets a value at a property path', function() {
+    var obj = { a: { b: {} } };
+
+    assert.strictEqual(addon.set_path(obj, 'a.b.c', 'hello'), true);
+    assert.strictEqual(obj.a.b.c, 'hello');
+    assert.throws(() => addon.set_path(obj, 'a.x.c', 1), TypeError, '`a.x`');
+  });
+
+  it('gets an optional property', function() {
+    assert.strictEqual(addon.get_opt_string({ name: 'neon' }), 'neon');
+    assert.strictEqual(addon.get_opt_string({}), null);
+    assert.strictEqual(addon.get_opt_string({ name: undefined }), null);
+    assert.throws(() => addon.get_opt_string({ name: 1 }), TypeError);
+  });
+
+  it('gets a property or a default', function() {
+    assert.strictEqual(addon.get_or_number({ port: 3000 }), 3000);
+    assert.strictEqual(addon.get_or_number({}), 8080);
+    assert.throws(() => addon.get_or_number({ port: 'http' }), TypeError);
+  });
+
+  it('checks for own and inherited properties', function() {
+    var child = Object.create({ inherited: 1 });
+    var sym = Symbol('own');
+
+    child.own = 2;
+    child[sym] = 3;
+    child[7] = 4;
+
+    assert.deepEqual(addon.has_property(child, 'own'), [true, true]);
+    assert.deepEqual(addon.has_property(child, 'inherited'), [true, false]);
+    assert.deepEqual(addon.has_property(child, 'missing'), [false, false]);
+    assert.deepEqual(addon.has_property(child, sym), [true, true]);
+    assert.deepEqual(addon.has_property(child, 7), [true, true]);
+    assert.strictEqual(addon.has_own_index(child, 7), true);
+    assert.strictEqual(addon.has_own_index(child, 8), false);
+  });
+
+  it('deletes properties', function() {
+    var obj = { a: 1 };
+
+    Object.defineProperty(obj, 'fixed', { value: 2, configurable: false });
+
+    assert.strictEqual(addon.delete_property(obj, 'a'), true);
+    assert.strictEqual('a' in obj, false);
+    assert.strictEqual(addon.delete_property(obj, 'missing'), true);
+    assert.strictEqual(addon.delete_property(obj, 'fixed'), false);
+    assert.strictEqual(obj.fixed, 2);
+  });
+
+  it('calls a method with the object as this', function() {
+    var obj = {
+      base: 10,
+      add(a, b) {
+        return this.base + a + b;
+      }
+    };
+
+    assert.strictEqual(addon.call_method_with_args(obj, 'add', 1, 2), 13);
+    assert.strictEqual(addon.call_method_with_args(new Map([[1, 'one']]), 'get', 1), 'one');
+  });
+
+  it('chains method call arguments', function() {
+    var array = [1];
+
+    assert.strictEqual(addon.push_twice(array, 'x'), 3);
+    assert.deepEqual(array, [1, 'x', 'x']);
+  });
+
+  it('throws if a method is not a function', function() {
+    assert.throws(
+      () => addon.call_method_with_args({ add: 1 }, 'add'),
+      TypeError,
+      'expected `add` to be a function, found a number'
+    );
+    assert.throws(
+      () => addon.call_method_with_args({}, 'missing'),
+      TypeError,
+      'expected `missing` to be a function, found undefined'
+    );
+  });
+
+  it('creates a proxy backed by Rust data', function() {
+    var record = addon.new_record_proxy();
+
+    assert.strictEqual(record.x, 1);
+    assert.strictEqual(record.y, 2);
+    assert.strictEqual(record.z, undefined);
+    assert.strictEqual('x' in record, true);
+    assert.strictEqual('z' in record, false);
+    assert.deepEqual(Object.keys(record), ['x', 'y']);
+
+    record.z = 3;
+    assert.strictEqual(record.z, 3);
+    assert.deepEqual({ ...record }, { x: 1, y: 2, z: 3 });
+
+    delete record.x;
+    assert.deepEqual(Object.keys(record), ['y', 'z']);
+    assert.strictEqual(Object.prototype.hasOwnProperty.call(record, 'y'), true);
+    assert.strictEqual(Object.prototype.hasOwnProperty.call(record, 'x'), false);
+
+    assert.throws(() => { record.w = 'text'; }, TypeError);
+  });
+
+  it('forwards proxy traps to the target by default', function() {
+    var sym = Symbol('sym');
+    var target = { a: 1, [sym]: 2 };
+    var proxy = addon.new_transparent_proxy(target);
+
+    assert.strictEqual(proxy.a, 1);
+    assert.strictEqual(proxy[sym], 2);
+    assert.strictEqual(typeof proxy.toString, 'function');
+    assert.deepEqual(Reflect.ownKeys(proxy), ['a', sym]);
+
+    proxy.b = 3;
+    assert.strictEqual(target.b, 3);
+
+    delete proxy.a;
+    assert.strictEqual('a' in target, false);
+  });
+
+  it('returns the same key for the same object', function() {
+    var a = {};
+    var b = {};
+
+    assert.strictEqual(addon.object_key(a), addon.object_key(a));
+    assert.notStrictEqual(addon.object_key(a), addon.object_key(b));
+    assert.strictEqual(addon.count_distinct_objects([a, b, a, b, a]), 2);
+    assert.deepEqual(Object.keys(a), []);
+  });
+
+  it('reads and writes a WeakMap', function() {
+    var key = {};
+    var value = { v: 1 };
+
+    assert.deepEqual(addon.weak_map_roundtrip(key, value), [false, value, true, undefined]);
+  });
+
+  it('adds objects to a WeakSet', function() {
+    var set = new WeakSet();
+    var obj = {};
+
+    assert.strictEqual(addon.weak_set_add(set, obj), true);
+    assert.strictEqual(addon.weak_set_add(set, obj), false);
+    assert.strictEqual(set.has(obj), true);
+    assert.throws(() => addon.weak_set_add(new Set(), obj), TypeError);
+  });
+
+  it('associates Rust values with objects', function() {
+    var a = {};
+    var b = {};
+
+    assert.strictEqual(addon.object_assoc_count(a), 1);
+    assert.strictEqual(addon.object_assoc_count(a), 2);
+    assert.strictEqual(addon.object_assoc_count(b), 1);
+    assert.strictEqual(addon.object_assoc_remove(a), true);
+    assert.strictEqual(addon.object_assoc_remove(a), false);
+    assert.strictEqual(addon.object_assoc_count(a), 1);
+  });
 });
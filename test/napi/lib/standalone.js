@@ -0,0 +1,14 @@
+var addon = require('..');
+var assert = require('chai').assert;
+
+describe('standalone', function() {
+  it('calls exported functions without a JavaScript context', function () {
+    var results = addon.__neon_run_tests('standalone_');
+
+    assert.lengthOf(results, 4);
+
+    for (var result of results) {
+      assert.isTrue(result.passed, result.name + ': ' + result.error);
+    }
+  });
+});
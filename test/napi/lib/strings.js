@@ -5,6 +5,45 @@ describe('JsString', function() {
   it('should return a JsString built in Rust', function () {
     assert.equal(addon.return_js_string(), "hello node");
   });
+  it('should borrow many strings', function () {
+    const strings = Array.from({ length: 1000 }, (_, i) => `string ${i} ✓`);
+
+    assert.strictEqual(addon.join_strings_borrowed(strings), strings.join(','));
+  });
+  it('should borrow short strings and copy long strings', function () {
+    assert.deepEqual(addon.string_value_cow('short'), ['short', true]);
+
+    const long = 'x'.repeat(10000);
+
+    assert.deepEqual(addon.string_value_cow(long), [long, false]);
+  });
+  it('should encode a string as UTF-8', function () {
+    const s = 'héllo, 世界 🦀';
+    const encoded = addon.encode_utf8(s);
+
+    assert.instanceOf(encoded, Buffer);
+    assert.deepEqual(encoded, Buffer.from(s));
+    assert.deepEqual(addon.encode_utf8(''), Buffer.alloc(0));
+    assert.deepEqual(addon.encode_utf8('\ud800'), Buffer.from([0xef, 0xbf, 0xbd]));
+  });
+  it('should decode UTF-8 with replacement', function () {
+    const s = 'héllo, 世界 🦀';
+
+    assert.strictEqual(addon.decode_utf8(Buffer.from(s), false), s);
+    assert.strictEqual(addon.decode_utf8(Buffer.alloc(0), false), '');
+
+    const invalid = Buffer.from([0x61, 0xff, 0x62, 0xe4, 0xb8]);
+
+    assert.strictEqual(addon.decode_utf8(invalid, false), new TextDecoder().decode(invalid));
+  });
+  it('should throw decoding invalid UTF-8 if requested', function () {
+    assert.strictEqual(addon.decode_utf8(Buffer.from('ok'), true), 'ok');
+    expect(() => addon.decode_utf8(Buffer.from([0x61, 0xff]), true)).to.throw(TypeError, /byte 1/);
+  });
+  it('should reuse interned strings', function () {
+    assert.deepEqual(addon.interned_strings(), { type: 'progress', source: 'helper' });
+    assert.deepEqual(addon.interned_strings(), { type: 'progress', source: 'helper' });
+  });
   describe('run_as_script', function () {
     it('should return the evaluated value', function () {
       assert.equal(addon.run_string_as_script('6 * 7'), 42);
@@ -0,0 +1,117 @@
+const addon = require('..');
+const assert = require('chai').assert;
+
+function tensor({ data, shape }) {
+  return { type: data.constructor.name, data: Array.from(data), shape };
+}
+
+describe('ndarray', function () {
+  it('round trips arrays with any number of axes', function () {
+    const data = Uint8Array.from({ length: 24 }, (_, i) => i);
+    const result = addon.ndarray_identity({ data, shape: [2, 3, 4] });
+
+    assert.deepEqual(tensor(result), {
+      type: 'Uint8Array',
+      data: Array.from(data),
+      shape: [2, 3, 4],
+    });
+  });
+
+  it('extracts arrays of numbers as well as typed arrays', function () {
+    const result = addon.ndarray_double({ data: [1, 2, 3, 4, 5, 6], shape: [2, 3] });
+
+    assert.deepEqual(tensor(result), {
+      type: 'Float64Array',
+      data: [2, 4, 6, 8, 10, 12],
+      shape: [2, 3],
+    });
+  });
+
+  it('converts arrays that are not in row-major order', function () {
+    const data = Float32Array.of(1, 2, 3, 4, 5, 6);
+    const result = addon.ndarray_transpose({ data, shape: [2, 3] });
+
+    assert.deepEqual(tensor(result), {
+      type: 'Float32Array',
+      data: [1, 4, 2, 5, 3, 6],
+      shape: [3, 2],
+    });
+  });
+
+  it('converts non-contiguous views', function () {
+    const data = Int32Array.of(1, 2, 3, 4, 5, 6);
+    const result = addon.ndarray_column({ data, shape: [2, 3] }, 1);
+
+    assert.deepEqual(tensor(result), { type: 'Int32Array', data: [2, 5], shape: [2] });
+  });
+
+  it('rejects shapes that do not match the data', function () {
+    assert.throws(
+      () => addon.ndarray_double({ data: [1, 2, 3], shape: [2, 2] }),
+      RangeError,
+      /shape \[2, 2\] does not match 3 elements/
+    );
+  });
+
+  it('rejects shapes with the wrong number of axes', function () {
+    assert.throws(
+      () => addon.ndarray_double({ data: [1, 2, 3, 4], shape: [4] }),
+      RangeError,
+      /shape \[4\] has the wrong number of axes/
+    );
+  });
+
+  it('rejects values without data and a shape', function () {
+    assert.throws(() => addon.ndarray_double(1), TypeError, /expected \{ data: Float64Array, shape: number\[\] \}/);
+    assert.throws(() => addon.ndarray_double({ data: [1, 2], shape: 2 }), TypeError);
+  });
+});
+
+describe('nalgebra', function () {
+  it('round trips dynamic matrices in row-major order', function () {
+    const result = addon.nalgebra_transpose({ data: [1, 2, 3, 4, 5, 6], shape: [2, 3] });
+
+    assert.deepEqual(tensor(result), {
+      type: 'Float64Array',
+      data: [1, 4, 2, 5, 3, 6],
+      shape: [3, 2],
+    });
+  });
+
+  it('round trips static matrices', function () {
+    const data = Int16Array.of(1, 2, 3, 4, 5, 6);
+    const result = addon.nalgebra_static({ data, shape: [2, 3] });
+
+    assert.deepEqual(tensor(result), { type: 'Int16Array', data: [1, 2, 3, 4, 5, 6], shape: [2, 3] });
+  });
+
+  it('converts non-contiguous views', function () {
+    const result = addon.nalgebra_row({ data: [1, 2, 3, 4, 5, 6], shape: [2, 3] }, 1);
+
+    assert.deepEqual(tensor(result), { type: 'Float64Array', data: [4, 5, 6], shape: [1, 3] });
+  });
+
+  it('rejects shapes that are not rows and columns', function () {
+    assert.throws(
+      () => addon.nalgebra_transpose({ data: [1, 2, 3, 4, 5, 6, 7, 8], shape: [2, 2, 2] }),
+      RangeError,
+      /expected a shape of \[rows, columns\], found \[2, 2, 2\]/
+    );
+  });
+
+  it('rejects shapes that do not match a static matrix', function () {
+    assert.throws(
+      () => addon.nalgebra_static({ data: Int16Array.of(1, 2, 3, 4, 5, 6), shape: [3, 2] }),
+      RangeError,
+      /expected a shape of \[2, 3\], found \[3, 2\]/
+    );
+  });
+
+  it('rejects shapes that do not match the data', function () {
+    assert.throws(
+      () => addon.nalgebra_transpose({ data: [1, 2, 3], shape: [2, 2] }),
+      RangeError,
+      /shape \[2, 2\] does not match 3 elements/
+    );
+  });
+});
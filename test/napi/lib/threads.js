@@ -1,5 +1,9 @@
 const addon = require('..');
 const assert = require('chai').assert;
+const { AsyncLocalStorage, createHook } = require('async_hooks');
+const EventEmitter = require('events');
+const { getEventListeners } = EventEmitter;
+const { Worker } = require('worker_threads');
 
 (function () {
   // These tests require GC exposed to shutdown properly; skip if it is not
@@ -71,4 +75,585 @@ const assert = require('chai').assert;
     // Asynchronously GC to give the task queue a chance to execute
     setTimeout(() => global.gc(), 10);
   });
+
+  it('should run callbacks in the async context of the channel', function (cb) {
+    const types = [];
+    const hook = createHook({
+      init(id, type) {
+        types.push(type);
+      }
+    }).enable();
+    const storage = new AsyncLocalStorage();
+    const store = {};
+
+    storage.run(store, () => {
+      addon.channel_with_resource_name('neon-test', function () {
+        hook.disable();
+
+        try {
+          assert.include(types, 'neon-test');
+          assert.strictEqual(storage.getStore(), store);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      });
+    });
+  });
+
+  it('should send on a weak channel', function (cb) {
+    addon.weak_channel_send(function (value) {
+      try {
+        assert.strictEqual(value, true);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should reference each clone of a channel separately', function () {
+    assert.deepEqual(addon.weak_channel_refs(), [true, false, false, true]);
+  });
+
+  it('should run closures on clones of an ordered channel in order', function (cb) {
+    addon.channel_ordered(function (order) {
+      try {
+        assert.deepEqual(order, Array.from({ length: 100 }, (_, i) => i));
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should run closures on a high priority channel first', function (cb) {
+    addon.channel_high_priority(function (order) {
+      try {
+        assert.strictEqual(order, 'urgent,first,second');
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should run a batch of closures in order', function (cb) {
+    addon.channel_send_batch(50, -1, function (order) {
+      try {
+        assert.deepEqual(order, Array.from({ length: 50 }, (_, i) => i));
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should report an exception thrown in a batch and run the rest', function (cb) {
+    const listeners = process.listeners('uncaughtException');
+    const errors = [];
+
+    process.removeAllListeners('uncaughtException');
+    process.on('uncaughtException', (err) => errors.push(err.message));
+
+    addon.channel_send_batch(3, 1, function (order) {
+      process.removeAllListeners('uncaughtException');
+      listeners.forEach((listener) => process.on('uncaughtException', listener));
+
+      try {
+        assert.deepEqual(order, [0, 1, 2]);
+        assert.deepEqual(errors, ['batch 1']);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should report the depth of a channel queue', function (cb) {
+    addon.channel_stats(function (stats) {
+      try {
+        assert.deepEqual(stats, [3, 4, 0, 5, 0, 1]);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should drive a local future to completion', function (cb) {
+    addon.spawn_local_double(21, function (result) {
+      try {
+        assert.strictEqual(result, 42);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should only provide a local context to local futures', function () {
+    assert.throws(() => addon.local_context_outside());
+  });
+
+  it('should restore a captured AsyncLocalStorage store', function () {
+    const storage = new AsyncLocalStorage();
+    const snapshot = storage.run('captured', () => addon.async_local_capture(storage));
+
+    storage.run('current', () => {
+      const store = addon.async_local_run(snapshot, () => storage.getStore());
+
+      assert.strictEqual(store, 'captured');
+      assert.strictEqual(storage.getStore(), 'current');
+    });
+  });
+
+  it('should restore the previous store if the callback throws', function () {
+    const storage = new AsyncLocalStorage();
+    const snapshot = storage.run('captured', () => addon.async_local_capture(storage));
+
+    storage.run('current', () => {
+      assert.throws(() => addon.async_local_run(snapshot, () => {
+        throw new Error('oops');
+      }), /oops/);
+
+      assert.strictEqual(storage.getStore(), 'current');
+    });
+  });
+
+  it('should schedule microtasks, immediates and timeouts', function (cb) {
+    const events = [];
+
+    addon.schedule_all((event) => {
+      events.push(event);
+
+      if (events.length === 4) {
+        try {
+          assert.deepStrictEqual(events, ['sync', 'microtask', 'immediate', 'timeout']);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    });
+
+    events.push('sync');
+  });
+
+  it('should call a Rust listener until the subscription is removed', function () {
+    const emitter = new EventEmitter();
+    const messages = [];
+
+    addon.emitter_subscribe(emitter, (message) => messages.push(message));
+
+    assert.strictEqual(emitter.listenerCount('data'), 1);
+
+    emitter.emit('data', 'a', 1);
+    emitter.emit('data', 'b', 2, 'ignored');
+    emitter.emit('close');
+    emitter.emit('data', 'c', 3);
+
+    assert.deepEqual(messages, ['a:2', 'b:4']);
+    assert.strictEqual(emitter.listenerCount('data'), 0);
+  });
+
+  it('should throw if event arguments cannot be extracted', function () {
+    const emitter = new EventEmitter();
+
+    addon.emitter_subscribe(emitter, () => {});
+
+    assert.throws(() => emitter.emit('data', 'a', 'not a number'), TypeError);
+  });
+
+  it('should emit events from Rust', function () {
+    const emitter = new EventEmitter();
+    let received;
+
+    assert.strictEqual(addon.emitter_emit(emitter, 'ping', 42), false);
+
+    emitter.on('ping', (value) => { received = value; });
+
+    assert.strictEqual(addon.emitter_emit(emitter, 'ping', 42), true);
+    assert.strictEqual(received, 42);
+  });
+
+  it('should keep a separate ContextLocal value for each worker', function (cb) {
+    const first = addon.context_local_increment();
+
+    assert.strictEqual(addon.context_local_increment(), first + 1);
+
+    const worker = new Worker(`
+      const { parentPort } = require('worker_threads');
+      const addon = require(${JSON.stringify(require.resolve('..'))});
+
+      addon.context_local_increment();
+      parentPort.postMessage(addon.context_local_increment());
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('message', (count) => {
+      try {
+        assert.strictEqual(count, 2);
+        assert.strictEqual(addon.context_local_increment(), first + 2);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should share a root among threads', function (cb) {
+    const calls = [];
+
+    addon.shared_root_threads(function (i) {
+      calls.push(i);
+
+      if (calls.length < 4) {
+        return;
+      }
+
+      try {
+        assert.deepEqual(calls.sort(), [0, 1, 2, 3]);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should unwrap the last clone of a shared root', function () {
+    const object = {};
+
+    assert.strictEqual(addon.shared_root_unwrap(object), object);
+  });
+
+  it('should cache values separately for each worker', function (cb) {
+    const regexp = addon.env_cache_regexp('a+');
+
+    assert.instanceOf(regexp, RegExp);
+    assert.strictEqual(addon.env_cache_regexp('a+'), regexp);
+    assert.notStrictEqual(addon.env_cache_regexp('b+'), regexp);
+    assert.isAtLeast(addon.env_cache_len(), 2);
+
+    const worker = new Worker(`
+      const { parentPort } = require('worker_threads');
+      const addon = require(${JSON.stringify(require.resolve('..'))});
+
+      addon.env_cache_regexp('a+');
+      parentPort.postMessage(addon.env_cache_len());
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('message', (len) => {
+      try {
+        assert.strictEqual(len, 1);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should run unload callbacks when a worker exits', function (cb) {
+    const before = addon.lifecycle_unload_count();
+    const worker = new Worker(`
+      const addon = require(${JSON.stringify(require.resolve('..'))});
+
+      addon.lifecycle_on_unload();
+      addon.lifecycle_on_unload();
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('exit', () => {
+      try {
+        assert.strictEqual(addon.lifecycle_unload_count(), before + 2);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should call a threadsafe function from another thread', function (cb) {
+    const received = [];
+
+    addon.tsfn_count(5, (i) => {
+      received.push(i);
+
+      if (received.length === 5) {
+        try {
+          assert.deepEqual(received, [1, 2, 3, 4, 5]);
+          cb();
+        } catch (err) {
+          cb(err);
+        }
+      }
+    });
+  });
+
+  it('should fail a nonblocking call when the queue is full', function (cb) {
+    const result = addon.tsfn_queue_full((value) => {
+      try {
+        assert.strictEqual(value, 'first');
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+
+    assert.strictEqual(result, 'QueueFull second');
+  });
+
+  it('should fail calls after a threadsafe function is aborted', function () {
+    assert.strictEqual(addon.tsfn_abort(() => {}), 'Closing');
+  });
+
+  it('should return the event loop of each environment', function (cb) {
+    const address = addon.uv_loop_address();
+
+    assert.strictEqual(addon.uv_loop_address(), address);
+
+    const worker = new Worker(`
+      const { parentPort } = require('worker_threads');
+      const addon = require(${JSON.stringify(require.resolve('..'))});
+
+      parentPort.postMessage(addon.uv_loop_address());
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('message', (workerAddress) => {
+      try {
+        assert.notStrictEqual(workerAddress, address);
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should structured clone values', function () {
+    const date = new Date(0);
+    const value = { a: [1, 'two', null], date, map: new Map([['k', new Uint8Array([1, 2])]]) };
+    const copy = addon.transfer_roundtrip(value);
+
+    assert.notStrictEqual(copy, value);
+    assert.deepEqual(copy, value);
+    assert.instanceOf(copy.date, Date);
+    assert.instanceOf(copy.map.get('k'), Uint8Array);
+  });
+
+  it('should transfer array buffers out of band', function () {
+    const buffer = new Uint8Array([1, 2, 3]).buffer;
+    const copy = addon.transfer_roundtrip({ first: buffer, second: buffer }, [buffer]);
+
+    assert.notStrictEqual(copy.first, buffer);
+    assert.strictEqual(copy.first, copy.second);
+    assert.deepEqual(Array.from(new Uint8Array(copy.first)), [1, 2, 3]);
+    assert.strictEqual(buffer.byteLength, 0);
+  });
+
+  it('should not detach buffers when serialization fails', function () {
+    const buffer = new Uint8Array([1, 2, 3]).buffer;
+
+    assert.throws(() => addon.transfer_roundtrip({ buffer, f() {} }, [buffer]), /could not be cloned/);
+    assert.strictEqual(buffer.byteLength, 3);
+  });
+
+  it('should serialize values in a worker', function (cb) {
+    const worker = new Worker(`
+      const { parentPort } = require('worker_threads');
+      const addon = require(${JSON.stringify(require.resolve('..'))});
+
+      parentPort.postMessage(addon.transfer_roundtrip({ list: [1, 2] }));
+    `, { eval: true });
+
+    worker.once('error', cb);
+    worker.once('message', (copy) => {
+      try {
+        assert.deepEqual(copy, { list: [1, 2] });
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should throw when cloning a function', function () {
+    assert.throws(() => addon.transfer_roundtrip({ f() {} }), /could not be cloned/);
+  });
+
+  it('should snapshot values with structuredClone', function () {
+    const value = { nested: { list: [1, 2] }, date: new Date(0) };
+    const copy = addon.structured_clone(value);
+
+    value.nested.list.push(3);
+
+    assert.deepEqual(copy, { nested: { list: [1, 2] }, date: new Date(0) });
+    assert.instanceOf(copy.date, Date);
+  });
+
+  it('should detach transferred values with structuredClone', function () {
+    const buffer = new Uint8Array([1, 2, 3]).buffer;
+    const copy = addon.structured_clone({ buffer }, [buffer]);
+
+    assert.strictEqual(buffer.byteLength, 0);
+    assert.deepEqual(Array.from(new Uint8Array(copy.buffer)), [1, 2, 3]);
+  });
+
+  it('should deserialize values sent from another thread', function (cb) {
+    addon.transfer_on_thread({ hello: 'world' }, (value) => {
+      try {
+        assert.deepEqual(value, { hello: 'world' });
+        cb();
+      } catch (err) {
+        cb(err);
+      }
+    });
+  });
+
+  it('should settle a promise from another thread', async function () {
+    assert.strictEqual(await addon.settle_with_on_thread(21), 42);
+
+    try {
+      await addon.settle_with_on_thread(-1);
+      assert.fail('expected the promise to reject');
+    } catch (err) {
+      assert.instanceOf(err, RangeError);
+      assert.match(err.message, /positive/);
+    }
+
+    try {
+      await addon.settle_with_on_thread(NaN);
+      assert.fail('expected the promise to reject');
+    } catch (err) {
+      assert.match(err.message, /not a number/);
+    }
+  });
+
+  it('should chain Rust closures on a promise', async function () {
+    assert.strictEqual(await addon.promise_then_double(Promise.resolve(21)), 42);
+
+    try {
+      await addon.promise_then_double(Promise.resolve('not a number'));
+      assert.fail('expected the promise to reject');
+    } catch (err) {
+      assert.instanceOf(err, TypeError);
+    }
+
+    const message = await addon.promise_catch_message(Promise.reject('boom'));
+    assert.strictEqual(message, 'caught: boom');
+    assert.strictEqual(await addon.promise_catch_message(Promise.resolve(1)), 1);
+
+    const counter = { count: 0 };
+
+    assert.strictEqual(await addon.promise_finally_count(Promise.resolve(1), counter), 1);
+
+    try {
+      await addon.promise_finally_count(Promise.reject(new Error('boom')), counter);
+      assert.fail('expected the promise to reject');
+    } catch (err) {
+      assert.strictEqual(err.message, 'boom');
+    }
+
+    assert.strictEqual(counter.count, 2);
+  });
+
+  it('should report unhandled rejections of promises created by the addon', async function () {
+    const reasons = [];
+
+    addon.track_rejections(reasons);
+
+    addon.reject_with('unhandled');
+    await addon.reject_with('handled').catch(() => {});
+    await new Promise((resolve) => setTimeout(resolve, 10));
+
+    assert.deepEqual(reasons, ['unhandled']);
+  });
+
+  it('should report live roots by call site', function () {
+    assert.deepEqual(addon.diagnostics_count_roots(3), [3, 0]);
+  });
+
+  it('should reject cancelled tasks with an AbortError', async function () {
+    // Keep every worker busy so that the task stays queued
+    const blockers = Array.from({ length: 16 }, () => addon.task_block_worker());
+    const controller = new AbortController();
+    const promise = addon.task_cancellable(controller.signal);
+
+    controller.abort();
+    addon.task_unblock_workers();
+    await Promise.all(blockers);
+
+    try {
+      await promise;
+      assert.fail('expected the task to be cancelled');
+    } catch (err) {
+      assert.instanceOf(err, Error);
+      assert.strictEqual(err.name, 'AbortError');
+      assert.strictEqual(err.code, 'ABORT_ERR');
+    }
+  });
+
+  it('should cancel tasks with an aborted signal immediately', async function () {
+    const blockers = Array.from({ length: 16 }, () => addon.task_block_worker());
+    const controller = new AbortController();
+
+    controller.abort();
+
+    const promise = addon.task_cancellable(controller.signal);
+
+    addon.task_unblock_workers();
+    await Promise.all(blockers);
+
+    try {
+      await promise;
+      assert.fail('expected the task to be cancelled');
+    } catch (err) {
+      assert.strictEqual(err.name, 'AbortError');
+    }
+  });
+
+  it('should ignore aborts after a task completes', async function () {
+    const controller = new AbortController();
+
+    assert.strictEqual(await addon.task_cancellable(controller.signal), 42);
+    controller.abort();
+  });
+
+  it('should remove the abort listener when a task completes', async function () {
+    const controller = new AbortController();
+    const promise = addon.task_cancellable(controller.signal);
+
+    assert.lengthOf(getEventListeners(controller.signal, 'abort'), 1);
+    await promise;
+    assert.lengthOf(getEventListeners(controller.signal, 'abort'), 0);
+  });
+
+  it('should remove abort listeners when a task is cancelled', async function () {
+    const blockers = Array.from({ length: 16 }, () => addon.task_block_worker());
+    const controller = new AbortController();
+    const other = new AbortController();
+    const promise = addon.task_cancellable(controller.signal, other.signal);
+
+    controller.abort();
+    addon.task_unblock_workers();
+    await Promise.all(blockers);
+
+    try {
+      await promise;
+      assert.fail('expected the task to be cancelled');
+    } catch (err) {
+      assert.strictEqual(err.name, 'AbortError');
+    }
+
+    assert.lengthOf(getEventListeners(other.signal, 'abort'), 0);
+  });
+
+  it('should call rooted functions from another thread', async function () {
+    const call = (f, n) => new Promise((resolve) => addon.call_on_thread(f, n, resolve));
+
+    assert.strictEqual(await call((n) => n * 2, 21), 42);
+    assert.strictEqual(await call(() => { throw new Error('boom'); }, 0), 'the call threw: Error: boom');
+    assert.match(await call(() => 'not a number', 0), /^the call threw: TypeError/);
+  });
 });
@@ -76,6 +76,76 @@ describe('type checks', function() {
     assert(!addon.is_undefined('anything other than undefined'));
   });
 
+  it('is_regexp', function () {
+    assert(addon.is_regexp(/a/));
+    assert(addon.is_regexp(new RegExp('a', 'g')));
+    assert(!addon.is_regexp('/a/'));
+    assert(!addon.is_regexp({ source: 'a', flags: '' }));
+  });
+
+  it('create_regexp', function () {
+    const re = addon.create_regexp('^a+$', 'iu');
+    assert.instanceOf(re, RegExp);
+    assert.strictEqual(re.source, '^a+$');
+    assert.strictEqual(re.flags, 'iu');
+    assert.throws(() => addon.create_regexp('(', ''), SyntaxError);
+    assert.throws(() => addon.create_regexp('a', 'x'), SyntaxError);
+  });
+
+  it('regexp source and flags', function () {
+    assert.deepEqual(addon.regexp_source_and_flags(/a\/b/gi), ['a\\/b', 'gi']);
+    assert.throws(() => addon.regexp_source_and_flags('a'), TypeError);
+  });
+
+  it('regexp exec and test', function () {
+    const result = addon.regexp_exec(/(?<key>\w+)=(\d+)/, 'x; size=42');
+    assert.deepEqual(Array.from(result), ['size=42', 'size', '42']);
+    assert.strictEqual(result.index, 3);
+    assert.strictEqual(result.groups.key, 'size');
+    assert.isNull(addon.regexp_exec(/\d/, 'abc'));
+    assert(addon.regexp_test(/^[a-z]+$/, 'abc'));
+    assert(!addon.regexp_test(/^[a-z]+$/, 'ABC'));
+  });
+
+  it('url components', function () {
+    assert.deepEqual(addon.url_components('https://user:pw@example.com:8080/a/b?c=d#e'), {
+      href: 'https://user:pw@example.com:8080/a/b?c=d#e',
+      protocol: 'https:',
+      username: 'user',
+      password: 'pw',
+      host: 'example.com:8080',
+      hostname: 'example.com',
+      port: 8080,
+      pathname: '/a/b',
+      search: '?c=d',
+      hash: '#e',
+      origin: 'https://example.com:8080'
+    });
+
+    const relative = addon.url_components('../c?x', 'http://example.com/a/b/');
+    assert.strictEqual(relative.href, 'http://example.com/a/c?x');
+    assert.isNull(relative.port);
+    assert.throws(() => addon.url_components('not a url'), TypeError);
+  });
+
+  it('url to string and search params', function () {
+    const url = new URL('https://example.com/?q=1');
+    assert.strictEqual(addon.url_to_string(url), 'https://example.com/?q=1');
+    assert.strictEqual(addon.url_set_param(url, 'q', 'a b'), url);
+    assert.strictEqual(url.href, 'https://example.com/?q=a+b');
+    assert.throws(() => addon.url_to_string('https://example.com/'), TypeError, /expected a URL/);
+  });
+
+  it('url search params', function () {
+    assert.deepEqual(addon.url_search_params('?a=1&removed=x&a=2'), {
+      first: '1',
+      all: ['1', '2'],
+      missing: true,
+      hasAdded: true,
+      query: 'a=1&a=2&added=1'
+    });
+  });
+
   it('strict_equals', function () {
     assert(addon.strict_equals(17, 17));
     assert(!addon.strict_equals(17, 18));
@@ -85,4 +155,47 @@ describe('type checks', function() {
     assert(!addon.strict_equals(o1, o2));
     assert(!addon.strict_equals(o1, 17));
   });
+
+  it('loose_equals', function () {
+    assert(addon.loose_equals(17, '17'));
+    assert(addon.loose_equals(null, undefined));
+    assert(addon.loose_equals({ valueOf: () => 1 }, 1));
+    assert(!addon.loose_equals(NaN, NaN));
+    assert(!addon.loose_equals({}, {}));
+    assert.throws(() => addon.loose_equals({ valueOf() { throw new Error('boom'); } }, 1), /boom/);
+  });
+
+  it('same_value', function () {
+    assert(addon.same_value(NaN, NaN));
+    assert(!addon.same_value(0, -0));
+    assert(addon.same_value(-0, -0));
+    assert(addon.same_value('a', 'a'));
+    assert(!addon.same_value(17, '17'));
+    const o = {};
+    assert(addon.same_value(o, o));
+    assert(!addon.same_value(o, {}));
+  });
+
+  it('to_debug_string', function () {
+    assert.strictEqual(addon.to_debug_string({ a: 1, b: [2, 'c'] }), "{ a: 1, b: [ 2, 'c' ] }");
+    assert.strictEqual(addon.to_debug_string('hello'), "'hello'");
+    assert.strictEqual(addon.to_debug_string(Symbol('s')), 'Symbol(s)');
+  });
+
+  it('debug_handle', function () {
+    assert.strictEqual(addon.debug_handle({ a: 1 }), 'Handle({ a: 1 })');
+  });
+
+  it('napi_version', function () {
+    assert.strictEqual(addon.napi_version(), Number(process.versions.napi));
+  });
+
+  it('supports', function () {
+    assert.strictEqual(addon.supports_date_and_bigint(), Number(process.versions.napi) >= 6);
+  });
+
+  it('runtime_host', function () {
+    var expected = process.versions.electron ? 'electron' : 'node';
+    assert.strictEqual(addon.runtime_host(), expected);
+  });
 });
@@ -24,3 +24,58 @@ pub fn read_js_array(mut cx: FunctionContext) -> JsResult<JsValue> {
 
     Ok(first_element)
 }
+
+pub fn return_js_array_from_iter(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+
+    JsArray::from_iter(&mut cx, (0..n).map(|i| i * i))
+}
+
+pub fn return_js_array_from_strings(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let words = vec!["hello".to_string(), "node".to_string()];
+
+    JsArray::from_iter(&mut cx, words)
+}
+
+pub fn count_js_array_strings(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let array = cx.argument::<JsArray>(0)?;
+    let mut count = 0;
+
+    let mut items = array.iter(&mut cx);
+
+    while let Some(item) = items.next() {
+        if item?.is_a::<JsString, _>(items.context()) {
+            count += 1;
+        }
+    }
+
+    Ok(cx.number(count))
+}
+
+pub fn sum_js_array_with_try_map(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let numbers = cx.argument::<JsArray>(0)?.try_map(&mut cx, |cx, v| {
+        Ok(v.downcast_or_throw::<JsNumber, _>(cx)?.value(cx))
+    })?;
+
+    Ok(cx.number(numbers.into_iter().sum::<f64>()))
+}
+
+pub fn return_js_array_from_slice(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let values = (0..n).map(|i| i as f64 / 2.0).collect::<Vec<_>>();
+
+    JsArray::from_slice(&mut cx, &values)
+}
+
+pub fn copy_js_array_range(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let array = cx.argument::<JsArray>(0)?;
+    let start = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let end = cx.argument::<JsNumber>(2)?.value(&mut cx) as u32;
+    let target = cx.argument::<JsArray>(3)?;
+    let at = cx.argument::<JsNumber>(4)?.value(&mut cx) as u32;
+    let elements = array.get_range(&mut cx, start..end)?;
+
+    target.set_range(&mut cx, at, &elements)?;
+
+    Ok(target)
+}
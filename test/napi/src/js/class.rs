@@ -0,0 +1,135 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct Counter {
+    count: f64,
+}
+
+#[neon::class(name = "ClassCounter")]
+impl Counter {
+    pub const MAX: f64 = 100.0;
+
+    pub fn new(count: Option<f64>) -> Result<Self, String> {
+        match count {
+            Some(count) if count < 0.0 => Err("count must not be negative".to_string()),
+            count => Ok(Self {
+                count: count.unwrap_or(0.0),
+            }),
+        }
+    }
+
+    pub fn from_string(count: String) -> Result<Counter, String> {
+        let count = count
+            .parse()
+            .map_err(|_| format!("invalid count `{}`", count))?;
+
+        Ok(Self { count })
+    }
+
+    pub fn describe() -> String {
+        "a counter".to_string()
+    }
+
+    pub fn increment(&mut self, #[neon(default = 1.0)] n: f64) -> f64 {
+        self.count += n;
+        self.count
+    }
+
+    pub fn get(&self) -> f64 {
+        self.count
+    }
+
+    #[neon(getter)]
+    pub fn count(&self) -> f64 {
+        self.count
+    }
+
+    #[neon(setter)]
+    pub fn set_count(&mut self, count: f64) -> Result<(), String> {
+        if count < 0.0 {
+            return Err("count must not be negative".to_string());
+        }
+
+        self.count = count;
+
+        Ok(())
+    }
+
+    #[neon(getter, name = "isZero")]
+    pub fn is_zero(&self) -> bool {
+        self.count == 0.0
+    }
+
+    #[neon(name = "addLater")]
+    pub async fn add_later(&self, n: f64) -> f64 {
+        Delay::new(Duration::from_millis(10)).await;
+        self.count + n
+    }
+
+    pub async fn fail_later(&self) -> Result<f64, String> {
+        Err(format!("failed at {}", self.count))
+    }
+}
+
+// Completes after `duration`, woken from another thread like the futures of
+// network clients
+struct Delay {
+    duration: Duration,
+    state: Arc<Mutex<(bool, bool)>>,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            state: Arc::new(Mutex::new((false, false))),
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let mut state = self.state.lock().unwrap();
+        let (started, done) = &mut *state;
+
+        if *done {
+            return Poll::Ready(());
+        }
+
+        if !*started {
+            let duration = self.duration;
+            let state = self.state.clone();
+            let waker = cx.waker().clone();
+
+            *started = true;
+
+            thread::spawn(move || {
+                thread::sleep(duration);
+                state.lock().unwrap().1 = true;
+                waker.wake();
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+pub struct Unconstructable;
+
+#[neon::class(name = "ClassUnconstructable", namespace = "classes")]
+impl Unconstructable {
+    pub fn create() -> Self {
+        Self
+    }
+
+    pub fn ping(&self) -> String {
+        "pong".to_string()
+    }
+}
@@ -1,7 +1,9 @@
 use std::f64::NAN;
 
 use neon::prelude::*;
-use neon::types::JsDate;
+use std::time::{Duration, UNIX_EPOCH};
+
+use neon::types::{JsDate, JsTemporalDuration, JsTemporalInstant};
 
 pub fn create_date(mut cx: FunctionContext) -> JsResult<JsDate> {
     let date = JsDate::new_lossy(&mut cx, 31415);
@@ -94,3 +96,65 @@ pub fn get_date_value(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let value = date.value(&mut cx);
     Ok(cx.number(value))
 }
+
+pub fn temporal_instant_from_millis(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let ms = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let time = if ms < 0.0 {
+        UNIX_EPOCH - Duration::from_millis(-ms as u64)
+    } else {
+        UNIX_EPOCH + Duration::from_millis(ms as u64)
+    };
+
+    Ok(JsTemporalInstant::new(&mut cx, time)?.as_object())
+}
+
+pub fn temporal_instant_to_millis(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let instant = cx.argument::<JsValue>(0)?;
+    let time = JsTemporalInstant::from_value(&mut cx, instant)?.to_system_time(&mut cx)?;
+    let ms = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_millis() as f64,
+        Err(err) => -(err.duration().as_millis() as f64),
+    };
+
+    Ok(cx.number(ms))
+}
+
+pub fn temporal_duration_from_nanos(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let nanos = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let duration = Duration::from_nanos(nanos as u64);
+
+    Ok(JsTemporalDuration::new(&mut cx, duration)?.as_object())
+}
+
+pub fn temporal_duration_to_nanos(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let duration = cx.argument::<JsValue>(0)?;
+    let duration = JsTemporalDuration::from_value(&mut cx, duration)?.to_duration(&mut cx)?;
+
+    Ok(cx.number(duration.as_nanos() as f64))
+}
+
+pub fn date_from_system_time(mut cx: FunctionContext) -> JsResult<JsDate> {
+    let millis = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let since = Duration::from_millis(millis.abs() as u64);
+    let time = if millis < 0.0 {
+        UNIX_EPOCH - since
+    } else {
+        UNIX_EPOCH + since
+    };
+
+    JsDate::from_system_time(&mut cx, time).or_throw(&mut cx)
+}
+
+pub fn date_to_system_time(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let date = cx.argument::<JsDate>(0)?;
+    let time = match date.to_system_time(&mut cx) {
+        Ok(time) => time,
+        Err(err) => return cx.throw_range_error(err.to_string()),
+    };
+    let millis = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_millis() as f64,
+        Err(err) => -(err.duration().as_millis() as f64),
+    };
+
+    Ok(cx.number(millis))
+}
@@ -18,6 +18,18 @@ pub fn new_range_error(mut cx: FunctionContext) -> JsResult<JsError> {
     cx.range_error(msg)
 }
 
+pub fn new_syntax_error(mut cx: FunctionContext) -> JsResult<JsError> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.syntax_error(msg)
+}
+
+pub fn throw_syntax_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.throw_syntax_error(msg)
+}
+
 pub fn throw_error(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     let msg = cx.argument::<JsString>(0)?.value(&mut cx);
 
@@ -32,3 +44,47 @@ pub fn downcast_error(mut cx: FunctionContext) -> JsResult<JsString> {
         panic!()
     }
 }
+
+pub fn pending_exception(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let mut pending = None;
+
+    let caught = cx.try_catch(|cx| {
+        let this = cx.undefined();
+        let args: Vec<Handle<JsValue>> = vec![];
+        let result = f.call(cx, this, args);
+
+        pending = Some((cx.is_throwing(), cx.pending_exception()));
+        result
+    });
+
+    let (throwing, exception) = pending.unwrap();
+    let result = cx.empty_array();
+    let throwing = cx.boolean(throwing);
+    let exception = exception.unwrap_or_else(|| cx.undefined().upcast());
+    let caught = match caught {
+        Ok(_) => cx.undefined().upcast(),
+        Err(err) => err,
+    };
+
+    result.set(&mut cx, 0, throwing)?;
+    result.set(&mut cx, 1, exception)?;
+    result.set(&mut cx, 2, caught)?;
+
+    Ok(result)
+}
+
+pub fn fatal(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let msg = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    cx.fatal(msg)
+}
+
+pub fn downcast_error_found(mut cx: FunctionContext) -> JsResult<JsString> {
+    let v = cx.argument::<JsValue>(0)?;
+
+    match v.downcast::<JsFunction, _>(&mut cx) {
+        Ok(_) => cx.throw_error("expected downcast to fail"),
+        Err(err) => Ok(cx.string(err.to_string())),
+    }
+}
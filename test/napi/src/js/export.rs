@@ -0,0 +1,319 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use neon::context::ContextLocal;
+use neon::prelude::*;
+use neon::types::extract::{Either, Progress, TryFromJs, TryIntoJs};
+
+#[neon::export]
+fn export_add(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[neon::export]
+fn export_greet(name: Option<String>) -> String {
+    format!("Hello, {}!", name.as_deref().unwrap_or("World"))
+}
+
+#[neon::export]
+fn export_with_context<'cx>(cx: &mut FunctionContext<'cx>, n: f64) -> JsResult<'cx, JsArray> {
+    let array = JsArray::new(cx, 1);
+    let n = cx.number(n);
+
+    array.set(cx, 0, n)?;
+
+    Ok(array)
+}
+
+#[neon::export]
+fn export_join(separator: String, #[neon(rest)] parts: Vec<String>) -> String {
+    parts.join(&separator)
+}
+
+#[neon::export]
+fn export_count_rest(#[neon(rest)] values: Vec<Handle<JsValue>>) -> f64 {
+    values.len() as f64
+}
+
+#[neon::export]
+fn export_either(v: Either<String, f64>) -> String {
+    match v {
+        Either::Left(s) => s,
+        Either::Right(n) => n.to_string(),
+    }
+}
+
+#[neon::export]
+fn export_fallible(fail: bool) -> Result<f64, String> {
+    if fail {
+        Err("failed".to_string())
+    } else {
+        Ok(1.0)
+    }
+}
+
+#[neon::export]
+fn export_get_name<'cx>(
+    cx: &mut FunctionContext<'cx>,
+    this: Handle<'cx, JsObject>,
+) -> JsResult<'cx, JsValue> {
+    this.get(cx, "name")
+}
+
+#[neon::export]
+fn export_boxed_count(this: Handle<JsBox<RefCell<f64>>>, n: f64) -> f64 {
+    let mut count = this.borrow_mut();
+
+    *count += n;
+    *count
+}
+
+#[neon::export]
+fn export_create_counter<'cx>(cx: &mut FunctionContext<'cx>) -> Handle<'cx, JsBox<RefCell<f64>>> {
+    cx.boxed(RefCell::new(0.0))
+}
+
+#[neon::export]
+fn export_repeat(s: String, #[neon(default = 2)] n: u32, sep: Option<String>) -> String {
+    vec![s; n as usize].join(sep.as_deref().unwrap_or(""))
+}
+
+#[neon::export(json)]
+fn export_json_scale(values: Vec<f64>, #[neon(default = 2.0)] factor: f64) -> Vec<f64> {
+    values.into_iter().map(|v| v * factor).collect()
+}
+
+#[neon::export(json)]
+fn export_json_lookup(table: BTreeMap<String, f64>, key: String) -> Result<Option<f64>, String> {
+    if key.is_empty() {
+        return Err("key must not be empty".to_string());
+    }
+
+    Ok(table.get(&key).copied())
+}
+
+#[neon::export(name = "exportRenamed")]
+fn export_renamed() -> &'static str {
+    "renamed"
+}
+
+#[neon::export(namespace = "math.sync")]
+fn export_double(n: f64) -> f64 {
+    n * 2.0
+}
+
+#[neon::export(namespace = "math.sync", name = "triple")]
+fn export_triple(n: f64) -> f64 {
+    n * 3.0
+}
+
+#[neon::export(namespace = "math")]
+fn export_negate(n: f64) -> f64 {
+    -n
+}
+
+static LAZY_INIT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[neon::export(lazy)]
+fn export_lazy_table() -> Vec<u32> {
+    LAZY_INIT_COUNT.fetch_add(1, Ordering::SeqCst);
+
+    (0..5).map(|i| i * i).collect()
+}
+
+#[neon::export]
+fn export_lazy_init_count() -> f64 {
+    LAZY_INIT_COUNT.load(Ordering::SeqCst) as f64
+}
+
+#[neon::export(task)]
+fn export_task_sum(values: Vec<f64>) -> f64 {
+    values.into_iter().sum()
+}
+
+#[neon::export(task)]
+fn export_task_fallible(fail: bool) -> Result<String, String> {
+    if fail {
+        Err("task failed".to_string())
+    } else {
+        Ok("task succeeded".to_string())
+    }
+}
+
+struct ArcCounter(AtomicUsize);
+
+impl Finalize for ArcCounter {}
+
+#[neon::export]
+fn export_arc_counter<'cx>(cx: &mut FunctionContext<'cx>) -> JsResult<'cx, JsBox<Arc<ArcCounter>>> {
+    Ok(cx.boxed(Arc::new(ArcCounter(AtomicUsize::new(0)))))
+}
+
+#[neon::export]
+fn export_arc_increment(#[neon(arc)] counter: Arc<ArcCounter>) -> f64 {
+    (counter.0.fetch_add(1, Ordering::SeqCst) + 1) as f64
+}
+
+#[neon::export(task)]
+fn export_arc_increment_task(#[neon(arc)] counter: Arc<ArcCounter>, by: f64) -> f64 {
+    (counter.0.fetch_add(by as usize, Ordering::SeqCst) + by as usize) as f64
+}
+
+#[neon::export(task)]
+fn export_task_progress(n: u32, progress: Progress<f64>) -> f64 {
+    for i in 1..=n {
+        progress.report(i as f64);
+    }
+
+    n as f64
+}
+
+#[neon::export]
+fn export_with_cx<'cx>(cx: &mut Cx<'cx>, s: String) -> JsResult<'cx, JsString> {
+    Ok(cx.string(s.to_uppercase()))
+}
+
+// Describes an export from its metadata, e.g., `Function add(a: f64) -> f64`
+#[neon::export]
+fn export_describe(path: String) -> Option<String> {
+    let export = neon::registered().get(&path)?;
+    let params = export
+        .params()
+        .iter()
+        .map(|param| {
+            format!(
+                "{}{}{}: {}",
+                if param.is_rest() { "..." } else { "" },
+                param.name(),
+                if param.is_optional() { "?" } else { "" },
+                param.ty().rust(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Some(format!(
+        "{:?} {}({}) -> {} [{}]",
+        export.kind(),
+        export.rust_name(),
+        params.join(", "),
+        export.output().rust(),
+        export.output().typescript(),
+    ))
+}
+
+fn export_never() -> bool {
+    false
+}
+
+#[neon::export(requires = "napi-1")]
+fn export_requires_supported() -> bool {
+    true
+}
+
+#[neon::export(requires = "napi-99")]
+fn export_requires_future() -> bool {
+    true
+}
+
+#[neon::export(requires = "napi-99", stub)]
+fn export_requires_future_stub() -> bool {
+    true
+}
+
+#[neon::export(when = export_never, stub, lazy)]
+fn export_when_never_lazy() -> bool {
+    true
+}
+
+#[neon::export(when = export_never)]
+fn export_when_never() -> bool {
+    true
+}
+
+// Records the order that initialization functions run in, and whether the
+// entry point has run, in `mainOrder`
+fn record_main(cx: &mut ModuleContext, name: &str) -> NeonResult<()> {
+    let exports = cx.exports_object()?;
+    let order = match exports.get_opt::<JsArray>(cx, "mainOrder")? {
+        Some(order) => order,
+        None => {
+            let order = cx.empty_array();
+
+            exports.set(cx, "mainOrder", order)?;
+            order
+        }
+    };
+    let main_ran = exports.get_opt::<JsString>(cx, "greeting")?.is_some();
+    let entry = cx.string(format!("{}:{}", name, main_ran));
+    let len = order.len(cx);
+
+    order.set(cx, len, entry)?;
+
+    Ok(())
+}
+
+#[neon::main(order = 2)]
+fn main_later(mut cx: ModuleContext) -> NeonResult<()> {
+    record_main(&mut cx, "later")
+}
+
+#[neon::main(order = -1)]
+fn main_early(mut cx: ModuleContext) -> NeonResult<()> {
+    record_main(&mut cx, "early")
+}
+
+#[neon::main(order = 1)]
+fn main_after(mut cx: ModuleContext) -> NeonResult<()> {
+    record_main(&mut cx, "after")
+}
+
+// Configuration passed to `init` by `index.js`, e.g., `{ greeting: "Hello" }`
+struct InitConfig {
+    greeting: String,
+}
+
+impl<'a> TryFromJs<'a> for InitConfig {
+    type Error = Handle<'a, JsValue>;
+
+    fn try_from_js<C: Context<'a>>(
+        cx: &mut C,
+        v: Handle<'a, JsValue>,
+    ) -> NeonResult<Result<Self, Self::Error>> {
+        let config = match v.downcast::<JsObject, _>(cx) {
+            Ok(config) => config,
+            Err(_) => return Ok(Err(JsError::type_error(cx, "expected a config")?.upcast())),
+        };
+
+        let greeting = config.get(cx, "greeting")?;
+
+        match String::try_from_js(cx, greeting)? {
+            Ok(greeting) => Ok(Ok(InitConfig { greeting })),
+            Err(err) => Ok(Err(err.try_into_js(cx)?.upcast())),
+        }
+    }
+}
+
+static INIT_GREETING: ContextLocal<String> = ContextLocal::new();
+
+#[neon::export(init)]
+fn init(cx: &mut FunctionContext, config: InitConfig) -> Result<(), String> {
+    if config.greeting.is_empty() {
+        return Err("greeting must not be empty".to_string());
+    }
+
+    INIT_GREETING.get_or_init(cx, || config.greeting);
+
+    Ok(())
+}
+
+#[neon::export]
+fn init_greeting(cx: &mut FunctionContext) -> Option<String> {
+    INIT_GREETING.get(cx).cloned()
+}
+
+#[neon::export(before_init)]
+fn init_version() -> String {
+    "1.0.0".to_string()
+}
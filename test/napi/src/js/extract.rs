@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use neon::prelude::*;
+use neon::types::extract::{
+    Base64, ByteBuf, Defined, Either, Hex, Lossy, Nullable, TryFromJs, TryIntoJs,
+};
+
+pub fn extract_vec_sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = Vec::<f64>::from_js(&mut cx, arg)?;
+
+    Ok(cx.number(values.into_iter().sum::<f64>()))
+}
+
+pub fn extract_f32_vec(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = Vec::<f32>::from_js(&mut cx, arg)?;
+
+    values.try_into_js(&mut cx)
+}
+
+pub fn extract_i32_vec_doubled(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = Vec::<i32>::from_js(&mut cx, arg)?;
+
+    values
+        .into_iter()
+        .map(|n| n * 2)
+        .collect::<Vec<_>>()
+        .try_into_js(&mut cx)
+}
+
+pub fn extract_u8_vec_len(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = Vec::<u8>::from_js(&mut cx, arg)?;
+
+    Ok(cx.number(values.len() as f64))
+}
+
+pub fn extract_nested_vec(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let mut values = Vec::<Vec<String>>::from_js(&mut cx, arg)?;
+
+    values.reverse();
+    values.try_into_js(&mut cx)
+}
+
+pub fn extract_hash_map(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = HashMap::<String, f64>::from_js(&mut cx, arg)?;
+    let doubled = values
+        .into_iter()
+        .map(|(k, v)| (k, v * 2.0))
+        .collect::<HashMap<_, _>>();
+
+    doubled.try_into_js(&mut cx)
+}
+
+pub fn extract_btree_map_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let values = BTreeMap::<String, Option<bool>>::from_js(&mut cx, arg)?;
+
+    values.into_keys().collect::<Vec<_>>().try_into_js(&mut cx)
+}
+
+pub fn extract_point(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let (x, y) = <(f64, f64)>::from_js(&mut cx, arg)?;
+
+    Ok(cx.number((x * x + y * y).sqrt()))
+}
+
+pub fn extract_mixed_tuple(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let (name, count, enabled) = <(String, f64, bool)>::from_js(&mut cx, arg)?;
+
+    Ok(cx.string(format!("{}:{}:{}", name, count, enabled)))
+}
+
+pub fn extract_either(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let description = match Either::<String, Either<f64, bool>>::from_js(&mut cx, arg)? {
+        Either::Left(s) => format!("string:{}", s),
+        Either::Right(Either::Left(n)) => format!("number:{}", n),
+        Either::Right(Either::Right(b)) => format!("boolean:{}", b),
+    };
+
+    Ok(cx.string(description))
+}
+
+pub fn extract_either_list(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let n = match Either::<f64, Vec<f64>>::from_js(&mut cx, arg)? {
+        Either::Left(n) => n,
+        Either::Right(list) => list.into_iter().sum(),
+    };
+
+    Ok(cx.number(n))
+}
+
+pub fn extract_nullable(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Nullable(n) = Nullable::<f64>::from_js(&mut cx, arg)?;
+
+    Nullable(n.map(|n| n + 1.0)).try_into_js(&mut cx)
+}
+
+pub fn extract_defined(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Defined(label) = Defined::<Option<String>>::from_js(&mut cx, arg)?;
+
+    Ok(cx.string(label.unwrap_or_else(|| "<null>".to_string())))
+}
+
+pub fn extract_u8(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let n = u8::from_js(&mut cx, arg)?;
+
+    Ok(cx.number(n))
+}
+
+pub fn extract_i64_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let n = i64::from_js(&mut cx, arg)?;
+
+    Ok(cx.string(n.to_string()))
+}
+
+pub fn extract_lossy_u8(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Lossy(n) = Lossy::<u8>::from_js(&mut cx, arg)?;
+
+    Ok(cx.number(n))
+}
+
+pub fn extract_bytes_len(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let bytes = ByteBuf::from_js(&mut cx, arg)?;
+
+    Ok(cx.number(bytes.len() as f64))
+}
+
+pub fn extract_bytes_reversed(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let ByteBuf(mut bytes) = ByteBuf::from_js(&mut cx, arg)?;
+
+    bytes.reverse();
+    ByteBuf(bytes).try_into_js(&mut cx)
+}
+
+// Decodes base64 and encodes the bytes as hex
+pub fn extract_base64_to_hex(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Base64(bytes) = Base64::from_js(&mut cx, arg)?;
+
+    Hex(bytes).try_into_js(&mut cx)
+}
+
+// Decodes hex and encodes the bytes as base64
+pub fn extract_hex_to_base64(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let Hex(bytes) = Hex::from_js(&mut cx, arg)?;
+
+    Base64(bytes).try_into_js(&mut cx)
+}
+
+pub fn extract_path(mut cx: FunctionContext) -> JsResult<JsString> {
+    let arg = cx.argument::<JsValue>(0)?;
+    let path = PathBuf::from_js(&mut cx, arg)?;
+
+    Ok(cx.string(path.to_string_lossy()))
+}
@@ -1,5 +1,6 @@
 use neon::object::This;
 use neon::prelude::*;
+use neon::types::CallSite;
 
 fn add1(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let x = cx.argument::<JsNumber>(0)?.value(&mut cx);
@@ -19,6 +20,76 @@ pub fn call_js_function(mut cx: FunctionContext) -> JsResult<JsNumber> {
         .or_throw(&mut cx)
 }
 
+pub fn call_js_function_with_args(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsValue>(1)?;
+    let a = cx.number(1);
+    let b = cx.string("two");
+    let c = cx.boolean(true);
+
+    f.call_with_args(&mut cx, this, (a, b, c))
+}
+
+pub fn call_js_function_without_args(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.undefined();
+
+    f.call_with_args(&mut cx, this, ())
+}
+
+pub fn construct_js_function_with_args(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let year = cx.number(2000);
+    let month = cx.number(1);
+
+    f.construct_with_args(&mut cx, (year, month))
+}
+
+pub fn call_site_sum(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsObject>(1)?;
+    let n = cx.argument::<JsNumber>(2)?.value(&mut cx) as u32;
+    let mut site = CallSite::with_this(&mut cx, f, this);
+    let mut sum = 0.0;
+
+    for i in 0..n {
+        let a = cx.number(i);
+        let b = cx.number(1);
+        let result = if i % 2 == 0 {
+            site.call_with(&mut cx, vec![a, b])?
+        } else {
+            site.call_with_args(&mut cx, (a, b))?
+        };
+
+        sum += result
+            .downcast_or_throw::<JsNumber, _>(&mut cx)?
+            .value(&mut cx);
+    }
+
+    site.drop(&mut cx);
+
+    Ok(cx.number(sum))
+}
+
+pub fn call_site_later(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let mut site = CallSite::new(&mut cx, f);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let arg = cx.string("from a thread");
+
+            site.call_with(&mut cx, vec![arg])?;
+            site.drop(&mut cx);
+
+            Ok(())
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
 pub fn construct_js_function(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let f = cx.argument::<JsFunction>(0)?;
     let zero = cx.number(0.0);
@@ -147,3 +218,132 @@ pub fn is_construct(mut cx: FunctionContext) -> JsResult<JsObject> {
     this.set(&mut cx, "wasConstructed", construct)?;
     Ok(this)
 }
+
+pub fn new_target_name(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let target = match cx.new_target() {
+        Some(target) => target,
+        None => return Ok(cx.undefined().upcast()),
+    };
+
+    let this = cx.this();
+    let name = target.get(&mut cx, "name")?;
+
+    this.set(&mut cx, "target", name)?;
+
+    Ok(this.upcast())
+}
+
+pub fn sum_args(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let args = cx.args().to_vec();
+    let mut sum = 0.0;
+
+    for arg in args {
+        sum += arg
+            .downcast_or_throw::<JsNumber, _>(&mut cx)?
+            .value(&mut cx);
+    }
+
+    Ok(cx.number(sum))
+}
+
+pub fn count_args(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let len = cx.args().len();
+
+    Ok(cx.number(len as f64))
+}
+
+// A builder that stores a concrete context instead of being generic over it.
+struct ObjectBuilder<'a, 'cx> {
+    cx: &'a mut Cx<'cx>,
+    object: Handle<'cx, JsObject>,
+}
+
+impl<'a, 'cx> ObjectBuilder<'a, 'cx> {
+    fn new(cx: &'a mut Cx<'cx>) -> Self {
+        let object = cx.empty_object();
+
+        Self { cx, object }
+    }
+
+    fn number(self, key: &str, n: f64) -> NeonResult<Self> {
+        let n = self.cx.number(n);
+
+        self.object.set(self.cx, key, n)?;
+
+        Ok(self)
+    }
+
+    fn build(self) -> Handle<'cx, JsObject> {
+        self.object
+    }
+}
+
+pub fn build_object_with_cx(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let x = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let y = cx.argument::<JsNumber>(1)?.value(&mut cx);
+
+    Ok(ObjectBuilder::new(&mut cx)
+        .number("x", x)?
+        .number("y", y)?
+        .build())
+}
+
+pub fn set_global_with_raw_env(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let value = cx.argument::<JsString>(0)?.value(&mut cx);
+    let ok = cx.with_raw_env(|env| unsafe {
+        Cx::from_raw(env, |mut cx| {
+            let global = cx.global();
+            let value = cx.string(value);
+
+            global.set(&mut cx, "rawEnvValue", value).is_ok()
+        })
+    });
+
+    Ok(cx.boolean(ok))
+}
+
+struct Greeting(String);
+
+fn greet(mut cx: FunctionContext) -> JsResult<JsString> {
+    let greeting = match cx.data::<Greeting>() {
+        Some(greeting) => greeting.0.clone(),
+        None => return cx.throw_type_error("missing greeting"),
+    };
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    Ok(cx.string(format!("{}, {}!", greeting, name)))
+}
+
+pub fn greeter(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let greeting = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    JsFunction::with_data(&mut cx, Greeting(greeting), greet)
+}
+
+pub fn greeter_with_wrong_data(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    JsFunction::with_data(&mut cx, 42u32, greet)
+}
+
+pub fn greeter_without_data(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    JsFunction::new(&mut cx, greet)
+}
+
+pub fn bind_function(mut cx: FunctionContext) -> JsResult<JsFunction> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let this = cx.argument::<JsValue>(1)?;
+    let args = cx.argument::<JsArray>(2)?.to_vec(&mut cx)?;
+
+    f.bind(&mut cx, this, args)
+}
+
+// Binds a function, stores the root and calls it with `arg` after retrieving it
+pub fn bind_root_and_call(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let bound_arg = cx.argument::<JsValue>(1)?;
+    let arg = cx.argument::<JsValue>(2)?;
+    let this = cx.undefined();
+    let root = f.bind_root(&mut cx, this, [bound_arg])?;
+    let bound = root.into_inner(&mut cx);
+
+    bound.call(&mut cx, this, [arg])
+}
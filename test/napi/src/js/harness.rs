@@ -0,0 +1,50 @@
+use std::cell::Cell;
+
+use neon::prelude::*;
+
+#[neon::test]
+fn harness_objects(cx: &mut FunctionContext) -> NeonResult<()> {
+    let obj = cx.empty_object();
+    let n = cx.number(1);
+
+    obj.set(cx, "n", n)?;
+
+    let n = obj.get(cx, "n")?.downcast_or_throw::<JsNumber, _>(cx)?;
+
+    assert_eq!(n.value(cx), 1.0);
+
+    Ok(())
+}
+
+#[neon::test]
+fn harness_channel(cx: &mut FunctionContext) {
+    let mut channel = cx.channel();
+
+    channel.unref(cx);
+    assert!(!channel.has_ref());
+}
+
+thread_local! {
+    static MODULE_THREAD: Cell<bool> = const { Cell::new(false) };
+}
+
+// Called when the module is loaded
+pub fn mark_module_thread() {
+    MODULE_THREAD.with(|loaded| loaded.set(true));
+}
+
+// A test without a context still runs on the JavaScript thread
+#[neon::test]
+fn harness_without_context() {
+    assert!(MODULE_THREAD.with(Cell::get));
+}
+
+#[neon::test]
+fn harness_throws(cx: &mut FunctionContext) -> NeonResult<()> {
+    cx.throw_range_error("expected to throw")
+}
+
+#[neon::test]
+fn harness_panics() {
+    panic!("expected to panic");
+}
@@ -0,0 +1,22 @@
+use neon::prelude::*;
+
+#[neon::export]
+fn log_init<'cx>(cx: &mut FunctionContext<'cx>) -> JsResult<'cx, JsObject> {
+    neon::log::init(cx)
+}
+
+// Logs `message` at `level` from another thread, like a background worker
+#[neon::export]
+fn log_from_thread(level: String, message: String) {
+    let level = match level.as_str() {
+        "error" => log::Level::Error,
+        "warn" => log::Level::Warn,
+        "info" => log::Level::Info,
+        "debug" => log::Level::Debug,
+        _ => log::Level::Trace,
+    };
+
+    std::thread::spawn(move || log::log!(target: "napi_tests", level, "{}", message))
+        .join()
+        .unwrap();
+}
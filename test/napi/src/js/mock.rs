@@ -0,0 +1,183 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use neon::context::ContextLocal;
+use neon::prelude::*;
+use neon::test::{MockContext, MockFunction, MockValue};
+use neon::types::extract::{TryFromJs, TryIntoJs};
+
+fn mock_area(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let width = cx.argument::<JsValue>(0)?;
+    let width = f64::from_js(&mut cx, width)?;
+    let height = cx.argument::<JsValue>(1)?;
+    let height = f64::from_js(&mut cx, height)?;
+
+    Ok(cx.number(width * height))
+}
+
+fn mock_index(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let i = cx.argument::<JsValue>(0)?;
+    let i = u32::from_js(&mut cx, i)?;
+
+    Ok(cx.number(i))
+}
+
+#[neon::test]
+fn mock_extracts_arguments() {
+    let mut cx = MockContext::new();
+
+    assert_eq!(cx.call(mock_area, [2.0, 3.0]), Ok(MockValue::Number(6.0)));
+    assert_eq!(cx.call(mock_index, [7]), Ok(MockValue::Number(7.0)));
+}
+
+#[neon::test]
+fn mock_throws_extraction_errors() {
+    let mut cx = MockContext::new();
+
+    assert_eq!(
+        cx.call(mock_area, ["2"]),
+        Err(MockValue::error("TypeError", "expected number")),
+    );
+    assert_eq!(
+        cx.call(mock_index, [1.5]),
+        Err(MockValue::error(
+            "RangeError",
+            "expected an integer, found 1.5"
+        )),
+    );
+
+    // The exception of a call does not leak into the next one
+    assert_eq!(cx.call(mock_area, [1, 1]), Ok(MockValue::Number(1.0)));
+}
+
+fn mock_double_values(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let values = cx.argument::<JsValue>(0)?;
+    let values = BTreeMap::<String, Option<f64>>::from_js(&mut cx, values)?;
+    let list = cx.argument::<JsValue>(1)?;
+    let list = Vec::<u32>::from_js(&mut cx, list)?;
+
+    let values = values
+        .into_iter()
+        .map(|(key, value)| (key, value.map(|value| value * 2.0)))
+        .collect::<BTreeMap<_, _>>()
+        .try_into_js(&mut cx)?;
+
+    let list = list
+        .into_iter()
+        .map(|n| n * 2)
+        .collect::<Vec<_>>()
+        .try_into_js(&mut cx)?;
+
+    values.set(&mut cx, "list", list)?;
+
+    Ok(values)
+}
+
+#[neon::test]
+fn mock_converts_containers() {
+    let mut cx = MockContext::new();
+    let object = MockValue::object();
+
+    object.set("a", 1.0);
+    object.set("b", MockValue::Null);
+
+    let result = cx
+        .call(mock_double_values, [object.clone(), vec![1, 2, 3].into()])
+        .unwrap();
+
+    assert_eq!(result.get("a"), MockValue::Number(2.0));
+    assert_eq!(result.get("b"), MockValue::Undefined);
+    assert_eq!(result.get("list"), vec![2, 4, 6].into());
+
+    assert!(cx
+        .call(mock_double_values, [object, vec!["a"].into()])
+        .is_err());
+}
+
+fn mock_mark(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let object = cx.argument::<JsObject>(0)?;
+    let marked = cx.boolean(true);
+
+    object.set(&mut cx, "marked", marked)?;
+
+    Ok(cx.undefined())
+}
+
+#[neon::test]
+fn mock_shares_objects() {
+    let mut cx = MockContext::new();
+    let object = MockValue::object();
+    let alias = object.clone();
+
+    assert_eq!(cx.call(mock_mark, [alias]), Ok(MockValue::Undefined));
+    assert_eq!(object.get("marked"), MockValue::Boolean(true));
+}
+
+static MOCK_CALLS: ContextLocal<Cell<u32>> = ContextLocal::new();
+
+// Counts calls on the channel, which runs after the call returns
+fn mock_count_calls(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let calls = MOCK_CALLS.get_or_init(&mut cx, Default::default).get();
+
+    cx.channel().send(|mut cx| {
+        let calls = MOCK_CALLS.get_or_init(&mut cx, Default::default);
+
+        calls.set(calls.get() + 1);
+
+        Ok(())
+    });
+
+    Ok(cx.number(calls))
+}
+
+#[neon::test]
+fn mock_keeps_instance_state() {
+    let mut cx = MockContext::new();
+
+    assert_eq!(
+        cx.call(mock_count_calls, Vec::<MockValue>::new()),
+        Ok(MockValue::Number(0.0))
+    );
+    assert_eq!(
+        cx.call(mock_count_calls, Vec::<MockValue>::new()),
+        Ok(MockValue::Number(1.0))
+    );
+
+    // Each instance has its own state
+    let mut other = MockContext::new();
+
+    assert_eq!(
+        other.call(mock_count_calls, Vec::<MockValue>::new()),
+        Ok(MockValue::Number(0.0))
+    );
+}
+
+fn mock_apply(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let arg = cx.argument::<JsValue>(1)?;
+
+    let undefined = cx.undefined();
+
+    f.call(&mut cx, undefined, [arg])
+}
+
+#[neon::test]
+fn mock_calls_functions() {
+    let mut cx = MockContext::new();
+    let double = MockFunction::new(|_, args| match args.first() {
+        Some(MockValue::Number(n)) => Ok(MockValue::Number(n * 2.0)),
+        _ => Err(MockValue::error("TypeError", "expected a number")),
+    });
+
+    assert_eq!(
+        cx.call(mock_apply, [double.clone().into(), MockValue::Number(2.0)]),
+        Ok(MockValue::Number(4.0)),
+    );
+    assert_eq!(
+        cx.call(mock_apply, [double.into(), MockValue::Null]),
+        Err(MockValue::error("TypeError", "expected a number")),
+    );
+    assert!(cx
+        .call(mock_apply, [MockValue::Null, MockValue::Null])
+        .is_err());
+}
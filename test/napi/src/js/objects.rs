@@ -1,4 +1,8 @@
+use std::cell::RefCell;
+
+use neon::object::{ObjectAssoc, ObjectKey};
 use neon::prelude::*;
+use neon::types::{BufferPool, JsProxy, JsSharedArrayBuffer, JsWeakMap, JsWeakSet, ProxyHandler};
 
 pub fn return_js_global_object(mut cx: FunctionContext) -> JsResult<JsObject> {
     Ok(cx.global())
@@ -116,6 +120,107 @@ pub fn return_external_array_buffer(mut cx: FunctionContext) -> JsResult<JsArray
     Ok(buf)
 }
 
+pub fn supports_external_buffers(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let supported = cx.supports_external_buffers();
+
+    Ok(cx.boolean(supported))
+}
+
+pub fn return_pooled_buffers(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let pool = BufferPool::new(1);
+    let data = cx.argument::<JsString>(0)?.value(&mut cx);
+    let data = data.as_bytes();
+
+    // Dropped bytes are returned to the pool and zero-filled when reused
+    drop(pool.take(8));
+    drop(pool.take(8));
+    assert_eq!(pool.available(), 1);
+    assert!(pool.take(4).iter().all(|&b| b == 0));
+
+    let buf = pool.buffer(&mut cx, data.len(), |bytes| bytes.copy_from_slice(data));
+    let array_buf = pool.array_buffer(&mut cx, data.len(), |bytes| bytes.copy_from_slice(data));
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, buf)?;
+    result.set(&mut cx, 1, array_buf)?;
+
+    Ok(result)
+}
+
+pub fn lend_array_buffer(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let f = cx.argument::<JsFunction>(0)?;
+    let (buf, lent) = JsArrayBuffer::lend(&mut cx, vec![1u8, 2, 3]);
+    let this = cx.undefined();
+
+    f.call(&mut cx, this, [buf])?;
+
+    let bytes = lent.reclaim(&mut cx)?;
+    let result = JsArray::from_iter(&mut cx, bytes)?;
+
+    Ok(result)
+}
+
+pub fn detach_array_buffer(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let buf = cx.argument::<JsArrayBuffer>(0)?;
+
+    buf.detach(&mut cx)?;
+
+    let detached = buf.is_detached(&mut cx);
+
+    Ok(cx.boolean(detached))
+}
+
+pub fn resize_array_buffer(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let buf = JsArrayBuffer::resizable(&mut cx, 4, 16)?;
+
+    buf.resize(&mut cx, 8)?;
+
+    let len = buf.byte_length(&mut cx) as f64;
+    let max_len = buf.max_byte_length(&mut cx)? as f64;
+    let resizable = buf.is_resizable(&mut cx)?;
+    let fixed = JsArrayBuffer::new(&mut cx, 4)?;
+    let fixed_resizable = fixed.is_resizable(&mut cx)?;
+    let fixed_max_len = fixed.max_byte_length(&mut cx)? as f64;
+
+    let result = cx.empty_array();
+    let values = [
+        cx.number(len).upcast::<JsValue>(),
+        cx.number(max_len).upcast(),
+        cx.boolean(resizable).upcast(),
+        cx.boolean(fixed_resizable).upcast(),
+        cx.number(fixed_max_len).upcast(),
+    ];
+
+    for (i, value) in values.iter().enumerate() {
+        result.set(&mut cx, i as u32, *value)?;
+    }
+
+    Ok(result)
+}
+
+pub fn grow_shared_array_buffer(mut cx: FunctionContext) -> JsResult<JsSharedArrayBuffer> {
+    let buf = JsSharedArrayBuffer::growable(&mut cx, 4, 16)?;
+
+    buf.grow(&mut cx, 8)?;
+    assert_eq!(buf.byte_length(&mut cx)?, 8);
+    assert_eq!(buf.max_byte_length(&mut cx)?, 16);
+    assert!(buf.is_growable(&mut cx)?);
+
+    let fixed = JsSharedArrayBuffer::new(&mut cx, 4)?;
+
+    assert!(!fixed.is_growable(&mut cx)?);
+
+    Ok(buf)
+}
+
+pub fn resize_fixed_array_buffer(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let buf = cx.argument::<JsArrayBuffer>(0)?;
+
+    buf.resize(&mut cx, 8)?;
+
+    Ok(cx.undefined())
+}
+
 pub fn read_buffer_with_lock(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let b: Handle<JsBuffer> = cx.argument(0)?;
     let i = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32 as usize;
@@ -171,3 +276,256 @@ pub fn increment_buffer_with_borrow_mut(mut cx: FunctionContext) -> JsResult<JsU
     });
     Ok(cx.undefined())
 }
+
+pub fn adjust_external_memory(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let delta = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let total = cx.adjust_external_memory(delta as i64);
+
+    Ok(cx.number(total as f64))
+}
+
+pub fn get_number_path(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let path = cx.argument::<JsString>(1)?.value(&mut cx);
+
+    obj.prop_path(&mut cx, &path)
+}
+
+pub fn set_path(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let path = cx.argument::<JsString>(1)?.value(&mut cx);
+    let value = cx.argument::<JsValue>(2)?;
+    let result = obj.set_prop_path(&mut cx, &path, value)?;
+
+    Ok(cx.boolean(result))
+}
+
+pub fn get_opt_string(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let obj = cx.argument::<JsObject>(0)?;
+
+    match obj.get_opt::<JsString>(&mut cx, "name")? {
+        Some(name) => Ok(name.upcast()),
+        None => Ok(cx.null().upcast()),
+    }
+}
+
+pub fn get_or_number(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let obj = cx.argument::<JsObject>(0)?;
+
+    obj.get_or(&mut cx, "port", |cx| cx.number(8080))
+}
+
+pub fn has_property(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = cx.argument::<JsValue>(1)?;
+    let has = obj.has(&mut cx, key)?;
+    let has_own = obj.has_own(&mut cx, key)?;
+    let has = cx.boolean(has);
+    let has_own = cx.boolean(has_own);
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, has)?;
+    result.set(&mut cx, 1, has_own)?;
+
+    Ok(result)
+}
+
+pub fn has_own_index(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let index = cx.argument::<JsNumber>(1)?.value(&mut cx) as u32;
+    let has_own = obj.has_own(&mut cx, index)?;
+
+    Ok(cx.boolean(has_own))
+}
+
+pub fn delete_property(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = cx.argument::<JsString>(1)?.value(&mut cx);
+    let deleted = obj.delete(&mut cx, key.as_str())?;
+
+    Ok(cx.boolean(deleted))
+}
+
+pub fn call_method_with_args(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let name = cx.argument::<JsString>(1)?.value(&mut cx);
+    let args = (2..cx.len())
+        .map(|i| cx.argument::<JsValue>(i))
+        .collect::<NeonResult<Vec<_>>>()?;
+
+    obj.method(&mut cx, &name)?.args(args).call(&mut cx)
+}
+
+pub fn push_twice(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let array = cx.argument::<JsArray>(0)?;
+    let value = cx.argument::<JsValue>(1)?;
+
+    array
+        .method(&mut cx, "push")?
+        .arg(value)
+        .arg(value)
+        .call(&mut cx)
+}
+
+// A record whose properties are stored in Rust
+struct Record(RefCell<Vec<(String, f64)>>);
+
+impl ProxyHandler for Record {
+    fn get<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> JsResult<'a, JsValue> {
+        match self.0.borrow().iter().find(|(k, _)| k == key) {
+            Some((_, v)) => Ok(cx.number(*v).upcast()),
+            None => target.get(cx, key),
+        }
+    }
+
+    fn set<'a>(
+        &self,
+        cx: &mut FunctionContext<'a>,
+        _target: Handle<'a, JsObject>,
+        key: &str,
+        value: Handle<'a, JsValue>,
+    ) -> NeonResult<bool> {
+        let value = value.downcast_or_throw::<JsNumber, _>(cx)?.value(cx);
+        let mut fields = self.0.borrow_mut();
+
+        match fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => fields.push((key.to_string(), value)),
+        }
+
+        Ok(true)
+    }
+
+    fn has<'a>(
+        &self,
+        _cx: &mut FunctionContext<'a>,
+        _target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> NeonResult<bool> {
+        Ok(self.0.borrow().iter().any(|(k, _)| k == key))
+    }
+
+    fn delete<'a>(
+        &self,
+        _cx: &mut FunctionContext<'a>,
+        _target: Handle<'a, JsObject>,
+        key: &str,
+    ) -> NeonResult<bool> {
+        self.0.borrow_mut().retain(|(k, _)| k != key);
+
+        Ok(true)
+    }
+
+    fn own_keys<'a>(
+        &self,
+        _cx: &mut FunctionContext<'a>,
+        _target: Handle<'a, JsObject>,
+    ) -> NeonResult<Vec<String>> {
+        Ok(self.0.borrow().iter().map(|(k, _)| k.clone()).collect())
+    }
+}
+
+pub fn new_record_proxy(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let target = cx.empty_object();
+    let fields = vec![("x".to_string(), 1.0), ("y".to_string(), 2.0)];
+
+    JsProxy::new(&mut cx, target, Record(RefCell::new(fields)))
+}
+
+// Forwards every trap to the target
+struct Transparent;
+
+impl ProxyHandler for Transparent {}
+
+pub fn new_transparent_proxy(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let target = cx.argument::<JsObject>(0)?;
+
+    JsProxy::new(&mut cx, target, Transparent)
+}
+
+pub fn object_key(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let key = ObjectKey::new(&mut cx, obj)?;
+
+    Ok(cx.number(key.as_u64() as f64))
+}
+
+pub fn count_distinct_objects(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let objects = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut keys = std::collections::HashSet::new();
+
+    for obj in objects {
+        let obj = obj.downcast_or_throw::<JsObject, _>(&mut cx)?;
+
+        keys.insert(ObjectKey::new(&mut cx, obj)?);
+    }
+
+    Ok(cx.number(keys.len() as f64))
+}
+
+pub fn weak_map_roundtrip(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let key = cx.argument::<JsObject>(0)?;
+    let value = cx.argument::<JsValue>(1)?;
+    let map = JsWeakMap::new(&mut cx)?;
+
+    let before = map.has(&mut cx, key)?;
+    map.set(&mut cx, key, value)?;
+    let stored = map.get(&mut cx, key)?;
+    let deleted = map.delete(&mut cx, key)?;
+    let after = map.get(&mut cx, key)?;
+
+    let before = cx.boolean(before);
+    let deleted = cx.boolean(deleted);
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, before)?;
+    result.set(&mut cx, 1, stored)?;
+    result.set(&mut cx, 2, deleted)?;
+    result.set(&mut cx, 3, after)?;
+
+    Ok(result)
+}
+
+pub fn weak_set_add(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let set = cx.argument::<JsWeakSet>(0)?;
+    let value = cx.argument::<JsObject>(1)?;
+
+    if set.has(&mut cx, value)? {
+        return Ok(cx.boolean(false));
+    }
+
+    set.add(&mut cx, value)?;
+
+    Ok(cx.boolean(true))
+}
+
+struct Memo {
+    calls: u32,
+}
+
+impl Finalize for Memo {}
+
+static MEMO: ObjectAssoc<RefCell<Memo>> = ObjectAssoc::new();
+
+pub fn object_assoc_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let memo =
+        MEMO.get_or_try_insert_with(&mut cx, obj, |_| Ok(RefCell::new(Memo { calls: 0 })))?;
+    let mut memo = memo.borrow_mut();
+
+    memo.calls += 1;
+
+    Ok(cx.number(memo.calls))
+}
+
+pub fn object_assoc_remove(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let obj = cx.argument::<JsObject>(0)?;
+    let removed = MEMO.remove(&mut cx, obj)?;
+
+    Ok(cx.boolean(removed))
+}
@@ -0,0 +1,129 @@
+use neon::standalone::{self, StandaloneError};
+use neon::test::MockValue;
+
+// Exports cannot be called before the module is initialized
+fn init() {
+    let config = MockValue::object();
+
+    config.set("greeting", "Hello");
+
+    let _ = standalone::call("init", vec![config]);
+}
+
+#[neon::test]
+fn standalone_calls_exports() {
+    init();
+
+    assert_eq!(
+        standalone::call_json("export_add", "[1, 2]"),
+        Ok("3".to_string())
+    );
+    assert_eq!(
+        standalone::call_json("export_greet", "[]"),
+        Ok("\"Hello, World!\"".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_join", r#"[", ", "a", "b"]"#),
+        Ok("\"a, b\"".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_repeat", r#"["ab"]"#),
+        Ok("\"abab\"".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("exportRenamed", "[]"),
+        Ok("\"renamed\"".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_with_context", "[1]"),
+        Ok("[1]".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_count_rest", "[1, 2]"),
+        Ok("2".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("init_greeting", "[]"),
+        Ok("\"Hello\"".to_string()),
+    );
+}
+
+#[neon::test]
+fn standalone_calls_json_exports() {
+    init();
+
+    assert_eq!(
+        standalone::call_json("export_json_scale", "[[1, 2.5]]"),
+        Ok("[2,5]".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_json_lookup", r#"[{"a": 1}, "a"]"#),
+        Ok("1".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_json_lookup", r#"[{"a": 1}, "b"]"#),
+        Ok("null".to_string()),
+    );
+    assert_eq!(
+        standalone::call_json("export_json_lookup", r#"[{}, ""]"#),
+        Err(StandaloneError::Threw(MockValue::String(
+            "key must not be empty".to_string()
+        ))),
+    );
+    assert!(matches!(
+        standalone::call_json("export_json_scale", r#"[["a"]]"#),
+        Err(StandaloneError::Threw(MockValue::Error { name, .. })) if name == "TypeError",
+    ));
+}
+
+#[neon::test]
+fn standalone_uses_namespaces() {
+    init();
+
+    let exports = standalone::exports();
+
+    assert!(exports.iter().any(|name| name == "math.export_negate"));
+    assert!(exports.iter().any(|name| name == "math.sync.triple"));
+    assert_eq!(
+        standalone::call("math.sync.triple", vec![MockValue::Number(2.0)]),
+        Ok(MockValue::Number(6.0)),
+    );
+}
+
+#[neon::test]
+fn standalone_reports_errors() {
+    init();
+
+    assert_eq!(
+        standalone::call_json("export_missing", "[]"),
+        Err(StandaloneError::NotFound("export_missing".to_string())),
+    );
+    assert_eq!(
+        standalone::call_json("export_task_sum", "[[1, 2, 3]]"),
+        Err(StandaloneError::Unsupported(
+            "export_task_sum".to_string(),
+            "it runs on the worker pool"
+        )),
+    );
+    assert_eq!(
+        standalone::call_json("export_fallible", "[true]"),
+        Err(StandaloneError::Threw(MockValue::String(
+            "failed".to_string()
+        ))),
+    );
+    assert_eq!(
+        standalone::call_json("export_add", r#"["1", 2]"#),
+        Err(StandaloneError::Threw(MockValue::error(
+            "TypeError",
+            "argument 1 (\"a\") expected number, found string"
+        ))),
+    );
+    assert!(matches!(
+        standalone::call_json("export_add", "[1,"),
+        Err(StandaloneError::InvalidJson(_)),
+    ));
+    assert!(matches!(
+        standalone::call_json("export_add", "{}"),
+        Err(StandaloneError::InvalidJson(_)),
+    ));
+}
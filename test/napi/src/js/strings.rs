@@ -1,5 +1,6 @@
 use neon::prelude::*;
 use neon::reflect::eval;
+use neon::types::InvalidUtf8;
 
 pub fn return_js_string(mut cx: FunctionContext) -> JsResult<JsString> {
     Ok(cx.string("hello node"))
@@ -9,3 +10,66 @@ pub fn run_string_as_script(mut cx: FunctionContext) -> JsResult<JsValue> {
     let string_script = cx.argument::<JsString>(0)?;
     eval(&mut cx, string_script)
 }
+
+pub fn join_strings_borrowed(mut cx: FunctionContext) -> JsResult<JsString> {
+    let strings = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut parts = Vec::with_capacity(strings.len());
+
+    for s in strings {
+        let s = s.downcast_or_throw::<JsString, _>(&mut cx)?;
+
+        parts.push(s.to_str(&mut cx));
+    }
+
+    Ok(cx.string(parts.join(",")))
+}
+
+pub fn string_value_cow(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let s = cx.argument::<JsString>(0)?.value_cow(&mut cx);
+    let borrowed = cx.boolean(matches!(s, std::borrow::Cow::Borrowed(_)));
+    let value = cx.string(s);
+    let result = cx.empty_array();
+
+    result.set(&mut cx, 0, value)?;
+    result.set(&mut cx, 1, borrowed)?;
+
+    Ok(result)
+}
+
+pub fn encode_utf8(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+    let s = cx.argument::<JsString>(0)?;
+
+    cx.encode_utf8(s)
+}
+
+pub fn decode_utf8(mut cx: FunctionContext) -> JsResult<JsString> {
+    let buffer = cx.argument::<JsBuffer>(0)?;
+    let invalid = if cx.argument::<JsBoolean>(1)?.value(&mut cx) {
+        InvalidUtf8::Throw
+    } else {
+        InvalidUtf8::Replace
+    };
+
+    cx.decode_utf8(buffer, invalid)
+}
+
+pub fn interned_strings(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let event = cx.empty_object();
+    let key = neon::js_string!(&mut cx, "type");
+    let value = neon::js_string!(&mut cx, "progress");
+
+    event.set(&mut cx, key, value)?;
+    set_interned_source(&mut cx, event)?;
+
+    Ok(event)
+}
+
+// Interns a string with a context that is already borrowed
+fn set_interned_source<'a, C: Context<'a>>(cx: &mut C, event: Handle<JsObject>) -> NeonResult<()> {
+    let key = neon::js_string!(cx, "source");
+    let value = neon::js_string!(cx, "helper");
+
+    event.set(cx, key, value)?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use nalgebra::{DMatrix, SMatrix};
+use ndarray::{Array2, ArrayD};
+use neon::prelude::*;
+use neon::types::extract::TryIntoJs;
+
+#[neon::export]
+fn ndarray_identity(a: ArrayD<u8>) -> ArrayD<u8> {
+    a
+}
+
+#[neon::export]
+fn ndarray_double(a: Array2<f64>) -> Array2<f64> {
+    a.mapv(|x| x * 2.0)
+}
+
+// Swaps the strides instead of moving the elements, so the result is not in
+// row-major order in memory
+#[neon::export]
+fn ndarray_transpose(a: Array2<f32>) -> Array2<f32> {
+    a.reversed_axes()
+}
+
+// A column is a view with a stride of the number of columns
+#[neon::export]
+fn ndarray_column<'cx>(
+    cx: &mut FunctionContext<'cx>,
+    a: Array2<i32>,
+    i: u32,
+) -> JsResult<'cx, JsObject> {
+    a.column(i as usize).try_into_js(cx)
+}
+
+#[neon::export]
+fn nalgebra_transpose(m: DMatrix<f64>) -> DMatrix<f64> {
+    m.transpose()
+}
+
+#[neon::export]
+fn nalgebra_static(m: SMatrix<i16, 2, 3>) -> SMatrix<i16, 2, 3> {
+    m
+}
+
+// A row of a column-major matrix is a view with a stride of the number of rows
+#[neon::export]
+fn nalgebra_row<'cx>(
+    cx: &mut FunctionContext<'cx>,
+    m: DMatrix<f64>,
+    i: u32,
+) -> JsResult<'cx, JsObject> {
+    m.row(i as usize).try_into_js(cx)
+}
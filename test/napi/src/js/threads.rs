@@ -1,7 +1,12 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
+use neon::context::{ContextLocal, EnvCache};
+use neon::event::{AsyncLocalSnapshot, Call, CallMode, Emitter, ThreadsafeFunction, WeakChannel};
+use neon::handle::SharedRoot;
 use neon::prelude::*;
+use neon::transfer::Serialized;
 
 pub fn useless_root(mut cx: FunctionContext) -> JsResult<JsObject> {
     let object = cx.argument::<JsObject>(0)?;
@@ -185,3 +190,753 @@ pub fn drop_global_queue(mut cx: FunctionContext) -> JsResult<JsUndefined> {
 
     Ok(cx.undefined())
 }
+
+pub fn channel_with_resource_name(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let channel = Channel::with_resource_name(&mut cx, &name);
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.undefined();
+            let args = Vec::<Handle<JsValue>>::new();
+
+            callback.call(&mut cx, this, args)?;
+
+            Ok(())
+        })
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn weak_channel_send(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel: WeakChannel = cx.channel().downgrade();
+
+    std::thread::spawn(move || {
+        let _ = channel.try_send(move |mut cx| {
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.undefined();
+            let arg = cx.boolean(true);
+
+            callback.call(&mut cx, this, [arg.upcast::<JsValue>()])?;
+
+            Ok(())
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Returns whether a clone, a clone of an unreferenced channel and an upgraded
+// weak channel are referenced, and whether the weak channel is closed after
+// every clone is dropped
+pub fn weak_channel_refs(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let mut channel = Channel::new(&mut cx);
+    let clone = channel.clone();
+
+    channel.unref(&mut cx);
+
+    let unref_clone = channel.clone();
+    let weak = channel.downgrade();
+    let upgraded = weak.upgrade().map(|channel| channel.has_ref());
+    let refs = [
+        clone.has_ref(),
+        unref_clone.has_ref(),
+        upgraded == Some(true),
+    ];
+
+    // The referenced clone is dropped last, so that it does not queue an unref
+    // that holds on to the channel
+    drop((channel, unref_clone));
+    drop(clone);
+
+    let closed = weak.upgrade().is_none() && weak.try_send(|_| Ok(())).is_err();
+
+    JsArray::from_iter(&mut cx, refs.iter().copied().chain([closed]))
+}
+
+// Sends numbers on alternating clones of an ordered channel from another
+// thread, and calls back with the order they ran in
+pub fn channel_ordered(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel = Channel::ordered(&mut cx);
+    let channels = [channel.clone(), channel];
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    std::thread::spawn(move || {
+        for i in 0..100 {
+            let order = Arc::clone(&order);
+
+            channels[i % 2].send(move |_| {
+                order.lock().unwrap().push(i as f64);
+                Ok(())
+            });
+        }
+
+        channels[0].send(move |mut cx| {
+            let order = std::mem::take(&mut *order.lock().unwrap());
+            let order = JsArray::from_iter(&mut cx, order)?;
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.undefined();
+
+            callback.call(&mut cx, this, [order.upcast::<JsValue>()])?;
+
+            Ok(())
+        });
+    });
+
+    Ok(cx.undefined())
+}
+
+// Queues closures on a channel and then on a high priority channel created
+// from it, and calls back with the order they ran in
+pub fn channel_high_priority(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let channel = Channel::new(&mut cx);
+    let priority = channel.high_priority(&mut cx);
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let push = |name: &'static str| {
+        let order = Arc::clone(&order);
+
+        move |_: TaskContext| {
+            order.lock().unwrap().push(name);
+            Ok(())
+        }
+    };
+
+    channel.send(push("first"));
+    channel.send(push("second"));
+    priority.send(push("urgent"));
+    channel.send(move |mut cx| {
+        let order = order.lock().unwrap().join(",");
+        let order = cx.string(order);
+        let callback = callback.into_inner(&mut cx);
+        let this = cx.undefined();
+
+        callback.call(&mut cx, this, [order.upcast::<JsValue>()])?;
+
+        Ok(())
+    });
+
+    Ok(cx.undefined())
+}
+
+// Sends a batch of `n` closures that record their index from another thread,
+// where the closure at `throw_at` throws, and calls back with the order they
+// ran in
+pub fn channel_send_batch(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let throw_at = cx.argument::<JsNumber>(1)?.value(&mut cx) as i64;
+    let callback = cx.argument::<JsFunction>(2)?.root(&mut cx);
+    let channel = cx.channel();
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    std::thread::spawn(move || {
+        let done = {
+            let order = Arc::clone(&order);
+
+            move |mut cx: TaskContext| {
+                let order = std::mem::take(&mut *order.lock().unwrap());
+                let order = JsArray::from_iter(&mut cx, order)?;
+                let callback = callback.into_inner(&mut cx);
+                let this = cx.undefined();
+
+                callback.call(&mut cx, this, [order.upcast::<JsValue>()])?;
+
+                Ok(())
+            }
+        };
+
+        let batch = (0..n)
+            .map(|i| {
+                let order = Arc::clone(&order);
+
+                Box::new(move |mut cx: TaskContext| {
+                    order.lock().unwrap().push(i as f64);
+
+                    if i as i64 == throw_at {
+                        return cx.throw_error(format!("batch {}", i));
+                    }
+
+                    Ok(())
+                }) as Box<dyn FnOnce(TaskContext) -> NeonResult<()> + Send>
+            })
+            .chain(std::iter::once(Box::new(done) as Box<_>));
+
+        channel.send_batch(batch);
+    });
+
+    Ok(cx.undefined())
+}
+
+// Queues five closures on a channel with a high-water mark of three, and calls
+// back with the depth the mark was reached at, the depth after sending and the
+// statistics seen by the last closure
+pub fn channel_stats(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let mut channel = Channel::new(&mut cx);
+    let reached = Arc::new(AtomicUsize::new(0));
+
+    // Dropping a referenced clone would queue another closure
+    channel.unref(&mut cx);
+
+    channel.on_high_water_mark(3, {
+        let reached = Arc::clone(&reached);
+
+        move |depth| reached.store(depth, Ordering::Relaxed)
+    });
+
+    for _ in 0..4 {
+        channel.send(|_| Ok(()));
+    }
+
+    let queued = channel.stats().queued();
+    let last = channel.clone();
+
+    channel.send(move |mut cx| {
+        let stats = last.stats();
+        let values = [
+            reached.load(Ordering::Relaxed) as f64,
+            queued as f64,
+            stats.queued() as f64,
+            stats.delivered() as f64,
+            stats.dropped() as f64,
+            stats.mean_latency().is_some() as u8 as f64,
+        ];
+        let values = JsArray::from_iter(&mut cx, values)?;
+        let callback = callback.into_inner(&mut cx);
+        let this = cx.undefined();
+
+        callback.call(&mut cx, this, [values.upcast::<JsValue>()])?;
+
+        Ok(())
+    });
+
+    Ok(cx.undefined())
+}
+
+// A value sent from another thread, which can be awaited
+struct Oneshot<T>(Arc<std::sync::Mutex<(Option<T>, Option<std::task::Waker>)>>);
+
+impl<T> Oneshot<T> {
+    fn channel() -> (Self, Self) {
+        let state = Arc::new(std::sync::Mutex::new((None, None)));
+
+        (Self(state.clone()), Self(state))
+    }
+
+    fn send(self, value: T) {
+        let mut state = self.0.lock().unwrap();
+
+        state.0 = Some(value);
+
+        if let Some(waker) = state.1.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> std::future::Future for Oneshot<T> {
+    type Output = T;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context) -> std::task::Poll<T> {
+        let mut state = self.0.lock().unwrap();
+
+        match state.0.take() {
+            Some(value) => std::task::Poll::Ready(value),
+            None => {
+                state.1 = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
+
+// Awaits a number doubled on another thread, holding the callback as a `Root`
+// across the `.await`, and calls back with the result
+pub fn spawn_local_double(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let (sender, receiver) = Oneshot::channel();
+
+    cx.spawn_local(async move {
+        let result = receiver.await;
+
+        let _ = neon::event::with_local_context(|mut cx| -> NeonResult<()> {
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.undefined();
+            let result = cx.number(result);
+
+            callback.call(&mut cx, this, [result.upcast::<JsValue>()])?;
+
+            Ok(())
+        });
+    });
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        sender.send(n * 2.0);
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn local_context_outside(_cx: FunctionContext) -> JsResult<JsUndefined> {
+    neon::event::with_local_context(|_| ());
+
+    unreachable!()
+}
+
+type BoxedSnapshot = JsBox<RefCell<Option<AsyncLocalSnapshot>>>;
+
+pub fn async_local_capture(mut cx: FunctionContext) -> JsResult<BoxedSnapshot> {
+    let storage = cx.argument::<JsObject>(0)?;
+    let snapshot = AsyncLocalSnapshot::capture(&mut cx, storage)?;
+
+    Ok(cx.boxed(RefCell::new(Some(snapshot))))
+}
+
+pub fn async_local_run(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let snapshot = cx.argument::<BoxedSnapshot>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?;
+    let snapshot = match snapshot.borrow_mut().take() {
+        Some(snapshot) => snapshot,
+        None => return cx.throw_error("snapshot was already used"),
+    };
+
+    snapshot.run(&mut cx, |cx| {
+        let this = cx.undefined();
+
+        callback.call(cx, this, Vec::<Handle<JsValue>>::new())
+    })
+}
+
+fn call_root(cx: &mut TaskContext, callback: Root<JsFunction>, arg: &str) -> NeonResult<()> {
+    let callback = callback.into_inner(cx);
+    let this = cx.undefined();
+    let arg = cx.string(arg);
+
+    callback.call(cx, this, vec![arg])?;
+
+    Ok(())
+}
+
+pub fn schedule_all(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let microtask = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let immediate = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let timeout = cx.argument::<JsFunction>(0)?.root(&mut cx);
+
+    cx.set_timeout(10, move |mut cx| call_root(&mut cx, timeout, "timeout"))?;
+    cx.set_immediate(move |mut cx| call_root(&mut cx, immediate, "immediate"))?;
+    cx.queue_microtask(move |mut cx| call_root(&mut cx, microtask, "microtask"))?;
+
+    Ok(cx.undefined())
+}
+
+pub fn emitter_subscribe(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let emitter = Emitter::new(cx.argument::<JsObject>(0)?);
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+
+    let subscription = emitter.on(&mut cx, "data", move |mut cx, (name, n): (String, f64)| {
+        let callback = callback.to_inner(&mut cx);
+        let this = cx.undefined();
+        let message = cx.string(format!("{}:{}", name, n * 2.0));
+
+        callback.call(&mut cx, this, vec![message])?;
+
+        Ok(())
+    })?;
+
+    let mut subscription = Some(subscription);
+
+    emitter
+        .on(&mut cx, "close", move |mut cx, ()| {
+            match subscription.take() {
+                Some(subscription) => subscription.unsubscribe(&mut cx),
+                None => Ok(()),
+            }
+        })?
+        .detach(&mut cx);
+
+    Ok(cx.undefined())
+}
+
+pub fn emitter_emit(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let emitter = Emitter::new(cx.argument::<JsObject>(0)?);
+    let event = cx.argument::<JsString>(1)?.value(&mut cx);
+    let arg = cx.argument::<JsValue>(2)?;
+    let emitted = emitter.emit(&mut cx, &event, vec![arg])?;
+
+    Ok(cx.boolean(emitted))
+}
+
+static COUNTER: ContextLocal<Cell<u32>> = ContextLocal::new();
+
+pub fn context_local_increment(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let counter = COUNTER.get_or_init(&mut cx, Default::default);
+
+    counter.set(counter.get() + 1);
+
+    Ok(cx.number(counter.get()))
+}
+
+// Calls the callback once from each of four threads, sharing one root
+pub fn shared_root_threads(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let callback = SharedRoot::new(&mut cx, &*callback);
+    let channel = cx.channel();
+
+    for i in 0..4 {
+        let callback = callback.clone();
+        let channel = channel.clone();
+
+        std::thread::spawn(move || {
+            channel.send(move |mut cx| {
+                let callback = callback.to_inner(&mut cx);
+                let this = cx.undefined();
+                let i = cx.number(i);
+
+                callback.call(&mut cx, this, [i.upcast::<JsValue>()])?;
+
+                Ok(())
+            });
+        });
+    }
+
+    Ok(cx.undefined())
+}
+
+// Drops a clone on another thread, and returns the object from the `Root` of
+// the remaining clone
+pub fn shared_root_unwrap(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let object = cx.argument::<JsObject>(0)?;
+    let shared = object.root(&mut cx).into_shared(&mut cx);
+    let clone = shared.clone();
+
+    std::thread::spawn(move || drop(clone)).join().unwrap();
+
+    match shared.try_unwrap() {
+        Ok(root) => Ok(root.into_inner(&mut cx)),
+        Err(_) => cx.throw_error("expected a single clone"),
+    }
+}
+
+static REGEXPS: EnvCache<String, Root<JsObject>> = EnvCache::new();
+
+// Returns the `RegExp` for a pattern, which is only compiled once in each
+// instance of the module
+pub fn env_cache_regexp(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let pattern = cx.argument::<JsString>(0)?.value(&mut cx);
+    let regexp = REGEXPS.get_or_try_init(&mut cx, pattern.clone(), |cx| {
+        let class = cx.global().get(cx, "RegExp")?;
+        let class = class.downcast_or_throw::<JsFunction, _>(cx)?;
+        let pattern = cx.string(pattern);
+
+        NeonResult::Ok(class.construct(cx, [pattern])?.root(cx))
+    })?;
+
+    Ok(regexp.to_inner(&mut cx))
+}
+
+pub fn env_cache_len(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let len = REGEXPS.len(&mut cx);
+
+    Ok(cx.number(len as f64))
+}
+
+pub fn transfer_roundtrip(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let value = cx.argument::<JsValue>(0)?;
+    let transfer = match cx.argument_opt(1) {
+        Some(transfer) => transfer
+            .downcast_or_throw::<JsArray, _>(&mut cx)?
+            .to_vec(&mut cx)?
+            .into_iter()
+            .map(|buffer| buffer.downcast_or_throw(&mut cx))
+            .collect::<NeonResult<Vec<Handle<JsArrayBuffer>>>>()?,
+        None => Vec::new(),
+    };
+
+    Serialized::with_transfer(&mut cx, value, &transfer)?.deserialize(&mut cx)
+}
+
+pub fn structured_clone(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let value = cx.argument::<JsValue>(0)?;
+    let transfer = match cx.argument_opt(1) {
+        Some(transfer) => transfer
+            .downcast_or_throw::<JsArray, _>(&mut cx)?
+            .to_vec(&mut cx)?,
+        None => Vec::new(),
+    };
+
+    cx.structured_clone(value, &transfer)
+}
+
+pub fn transfer_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let value = cx.argument::<JsValue>(0)?;
+    let callback = cx.argument::<JsFunction>(1)?.root(&mut cx);
+    let value = Serialized::new(&mut cx, value)?;
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        channel.send(move |mut cx| {
+            let callback = callback.into_inner(&mut cx);
+            let this = cx.undefined();
+            let value = value.deserialize(&mut cx)?;
+
+            callback.call(&mut cx, this, vec![value])?;
+
+            Ok(())
+        })
+    });
+
+    Ok(cx.undefined())
+}
+
+fn live_roots_in_this_file() -> usize {
+    neon::diagnostics::report()
+        .roots
+        .into_iter()
+        .filter(|site| site.location.file().ends_with("threads.rs"))
+        .map(|site| site.count)
+        .sum()
+}
+
+pub fn diagnostics_count_roots(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as usize;
+    let object = cx.empty_object();
+    let before = live_roots_in_this_file();
+    let roots = (0..n).map(|_| object.root(&mut cx)).collect::<Vec<_>>();
+    let live = live_roots_in_this_file() - before;
+
+    for root in roots {
+        root.drop(&mut cx);
+    }
+
+    let after = live_roots_in_this_file() - before;
+
+    JsArray::from_iter(&mut cx, vec![live as f64, after as f64])
+}
+
+pub fn settle_with_on_thread(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    std::thread::spawn(move || {
+        deferred.settle_with(&channel, move |mut cx| {
+            if n < 0.0 {
+                return cx.throw_range_error("expected a positive number");
+            }
+
+            if n.is_nan() {
+                panic!("not a number");
+            }
+
+            Ok(cx.number(n * 2.0))
+        });
+    });
+
+    Ok(promise)
+}
+
+pub fn promise_then_double(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx.argument::<JsPromise>(0)?;
+
+    promise.then(&mut cx, |_, n: f64| n * 2.0)
+}
+
+pub fn promise_catch_message(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx.argument::<JsPromise>(0)?;
+
+    promise.catch(&mut cx, |_, reason: String| format!("caught: {}", reason))
+}
+
+pub fn promise_finally_count(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let promise = cx.argument::<JsPromise>(0)?;
+    let counter = cx.argument::<JsObject>(1)?.root(&mut cx);
+
+    promise.finally(&mut cx, move |cx| {
+        let counter = counter.into_inner(cx);
+        let count = counter
+            .get(cx, "count")?
+            .downcast_or_throw::<JsNumber, _>(cx)?
+            .value(cx);
+        let count = cx.number(count + 1.0);
+
+        counter.set(cx, "count", count)?;
+
+        Ok(())
+    })
+}
+
+pub fn track_rejections(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let reasons = cx.argument::<JsArray>(0)?.root(&mut cx);
+
+    neon::event::on_unhandled_rejection(&mut cx, move |cx, reason| {
+        let reasons = reasons.to_inner(cx);
+        let len = reasons.len(cx);
+
+        reasons.set(cx, len, reason)?;
+
+        Ok(())
+    })?;
+
+    Ok(cx.undefined())
+}
+
+pub fn reject_with(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let reason = cx.argument::<JsValue>(0)?;
+    let (deferred, promise) = cx.promise();
+
+    deferred.reject(&mut cx, reason);
+
+    Ok(promise)
+}
+
+pub fn uv_loop_address(mut cx: FunctionContext) -> JsResult<JsString> {
+    let uv_loop = cx.uv_loop();
+
+    assert!(!uv_loop.is_null());
+
+    Ok(cx.string(format!("{:p}", uv_loop)))
+}
+
+static WORKERS_BLOCKED: (std::sync::Mutex<bool>, std::sync::Condvar) =
+    (std::sync::Mutex::new(false), std::sync::Condvar::new());
+
+// Occupies a worker until `task_unblock_workers` is called
+pub fn task_block_worker(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    *WORKERS_BLOCKED.0.lock().unwrap() = true;
+
+    let promise = cx
+        .task(|| {
+            let (blocked, condvar) = &WORKERS_BLOCKED;
+            let blocked = blocked.lock().unwrap();
+            let timeout = std::time::Duration::from_secs(5);
+
+            let _ = condvar.wait_timeout_while(blocked, timeout, |blocked| *blocked);
+        })
+        .promise(|mut cx, ()| Ok(cx.undefined()));
+
+    Ok(promise)
+}
+
+pub fn task_unblock_workers(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    *WORKERS_BLOCKED.0.lock().unwrap() = false;
+    WORKERS_BLOCKED.1.notify_all();
+
+    Ok(cx.undefined())
+}
+
+pub fn task_cancellable(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let signal = cx.argument::<JsObject>(0)?;
+    let (task, promise) = cx
+        .task(|| 42.0)
+        .cancellable_promise(|mut cx, n| Ok(cx.number(n)));
+
+    task.abort_on(&mut cx, signal)?;
+
+    if let Some(signal) = cx.argument_opt(1) {
+        let signal = signal.downcast_or_throw(&mut cx)?;
+
+        task.abort_on(&mut cx, signal)?;
+    }
+
+    Ok(promise)
+}
+
+// Calls `f(n)` from another thread and passes the result or the error to `done`
+pub fn call_on_thread(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let f = cx.argument::<JsFunction>(0)?.root(&mut cx);
+    let n = cx.argument::<JsNumber>(1)?.value(&mut cx);
+    let done = cx.argument::<JsFunction>(2)?.root(&mut cx);
+    let channel = cx.channel();
+
+    std::thread::spawn(move || {
+        let result = Call::new(f).arg(n).apply_on::<f64>(&channel).join();
+        let mut done = Call::with_capacity(done, 1);
+
+        match result {
+            Ok(n) => done.push_arg(n),
+            Err(err) => done.push_arg(err.to_string()),
+        };
+
+        done.exec_on(&channel);
+    });
+
+    Ok(cx.undefined())
+}
+
+static UNLOADED: AtomicUsize = AtomicUsize::new(0);
+
+pub fn lifecycle_on_unload(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    neon::lifecycle::on_unload(&mut cx, || {
+        UNLOADED.fetch_add(1, Ordering::SeqCst);
+    });
+
+    Ok(cx.undefined())
+}
+
+pub fn lifecycle_unload_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    Ok(cx.number(UNLOADED.load(Ordering::SeqCst) as f64))
+}
+
+// Calls `callback` with each number from one to `n` from another thread,
+// through a queue of at most two calls
+pub fn tsfn_count(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let n = cx.argument::<JsNumber>(0)?.value(&mut cx) as u32;
+    let callback = cx.argument::<JsFunction>(1)?;
+    let tsfn = ThreadsafeFunction::<u32>::with_capacity(&mut cx, callback, 2)?;
+    let handle = tsfn
+        .acquire()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+
+    // Only the acquired handle is left once the original is released
+    tsfn.release();
+
+    std::thread::spawn(move || {
+        for i in 1..=n {
+            handle.call(i, CallMode::Blocking).unwrap();
+        }
+    });
+
+    Ok(cx.undefined())
+}
+
+// Fills a queue of one call without blocking, and returns why the second call
+// failed. The first call still runs.
+pub fn tsfn_queue_full(mut cx: FunctionContext) -> JsResult<JsString> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let tsfn = ThreadsafeFunction::<String>::with_capacity(&mut cx, callback, 1)?;
+
+    tsfn.call("first".to_string(), CallMode::NonBlocking)
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+
+    let err = match tsfn.call("second".to_string(), CallMode::NonBlocking) {
+        Ok(()) => return cx.throw_error("expected the queue to be full"),
+        Err(err) => err,
+    };
+
+    Ok(cx.string(format!("{:?} {}", err.kind(), err.into_inner())))
+}
+
+// Aborts a function and returns why a call from another handle failed
+pub fn tsfn_abort(mut cx: FunctionContext) -> JsResult<JsString> {
+    let callback = cx.argument::<JsFunction>(0)?;
+    let tsfn = ThreadsafeFunction::<f64>::new(&mut cx, callback)?;
+    let handle = tsfn
+        .acquire()
+        .or_else(|err| cx.throw_error(err.to_string()))?;
+
+    tsfn.abort();
+
+    let kind = match handle.call(1.0, CallMode::NonBlocking) {
+        Ok(()) => return cx.throw_error("expected the function to be closing"),
+        Err(err) => err.kind(),
+    };
+
+    Ok(cx.string(format!("{:?}", kind)))
+}
@@ -1,4 +1,7 @@
+use neon::feature::{self, Feature};
 use neon::prelude::*;
+use neon::runtime;
+use neon::types::{JsRegExp, JsUrl, JsUrlSearchParams};
 
 pub fn is_string(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let val: Handle<JsValue> = cx.argument(0)?;
@@ -60,9 +63,195 @@ pub fn is_undefined(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     Ok(cx.boolean(is_string))
 }
 
+pub fn is_regexp(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let val: Handle<JsValue> = cx.argument(0)?;
+    let result = val.is_a::<JsRegExp, _>(&mut cx);
+    Ok(cx.boolean(result))
+}
+
 pub fn strict_equals(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let v1: Handle<JsValue> = cx.argument(0)?;
     let v2: Handle<JsValue> = cx.argument(1)?;
     let eq = v1.strict_equals(&mut cx, v2);
     Ok(cx.boolean(eq))
 }
+
+pub fn loose_equals(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let v1: Handle<JsValue> = cx.argument(0)?;
+    let v2: Handle<JsValue> = cx.argument(1)?;
+    let eq = v1.loose_equals(&mut cx, v2)?;
+    Ok(cx.boolean(eq))
+}
+
+pub fn same_value(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let v1: Handle<JsValue> = cx.argument(0)?;
+    let v2: Handle<JsValue> = cx.argument(1)?;
+    let eq = v1.same_value(&mut cx, v2);
+    Ok(cx.boolean(eq))
+}
+
+pub fn to_debug_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let v: Handle<JsValue> = cx.argument(0)?;
+    let s = v.to_debug_string(&mut cx)?;
+    Ok(cx.string(s))
+}
+
+pub fn debug_handle(mut cx: FunctionContext) -> JsResult<JsString> {
+    let v: Handle<JsValue> = cx.argument(0)?;
+    Ok(cx.string(format!("{:?}", v)))
+}
+
+pub fn napi_version(mut cx: FunctionContext) -> JsResult<JsNumber> {
+    let version = cx.napi_version();
+
+    Ok(cx.number(version))
+}
+
+pub fn supports_date_and_bigint(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let supported = feature::supports(Feature::Date | Feature::BigInt);
+
+    Ok(cx.boolean(supported))
+}
+
+pub fn runtime_host(mut cx: FunctionContext) -> JsResult<JsString> {
+    let host = runtime::host();
+
+    Ok(cx.string(host.name()))
+}
+
+pub fn create_regexp(mut cx: FunctionContext) -> JsResult<JsRegExp> {
+    let pattern = cx.argument::<JsString>(0)?.value(&mut cx);
+    let flags = cx.argument::<JsString>(1)?.value(&mut cx);
+
+    JsRegExp::new(&mut cx, &pattern, &flags)
+}
+
+pub fn regexp_source_and_flags(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let regexp = cx.argument::<JsRegExp>(0)?;
+    let source = regexp.source(&mut cx)?;
+    let flags = regexp.flags(&mut cx)?;
+    let result = cx.empty_array();
+    let source = cx.string(source);
+    let flags = cx.string(flags);
+
+    result.set(&mut cx, 0, source)?;
+    result.set(&mut cx, 1, flags)?;
+
+    Ok(result)
+}
+
+pub fn regexp_exec(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let regexp = cx.argument::<JsRegExp>(0)?;
+    let input = cx.argument::<JsString>(1)?.value(&mut cx);
+
+    match regexp.exec(&mut cx, &input)? {
+        Some(result) => Ok(result.upcast()),
+        None => Ok(cx.null().upcast()),
+    }
+}
+
+pub fn regexp_test(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let regexp = cx.argument::<JsRegExp>(0)?;
+    let input = cx.argument::<JsString>(1)?.value(&mut cx);
+    let result = regexp.test(&mut cx, &input)?;
+
+    Ok(cx.boolean(result))
+}
+
+pub fn url_components(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let input = cx.argument::<JsString>(0)?.value(&mut cx);
+    let url = match cx.argument_opt(1) {
+        Some(base) => {
+            let base = base
+                .downcast_or_throw::<JsString, _>(&mut cx)?
+                .value(&mut cx);
+            JsUrl::with_base(&mut cx, &input, &base)?
+        }
+        None => JsUrl::new(&mut cx, &input)?,
+    };
+
+    let components = [
+        ("href", url.href(&mut cx)?),
+        ("protocol", url.protocol(&mut cx)?),
+        ("username", url.username(&mut cx)?),
+        ("password", url.password(&mut cx)?),
+        ("host", url.host(&mut cx)?),
+        ("hostname", url.hostname(&mut cx)?),
+        ("pathname", url.pathname(&mut cx)?),
+        ("search", url.search(&mut cx)?),
+        ("hash", url.hash(&mut cx)?),
+        ("origin", url.origin(&mut cx)?),
+    ];
+
+    let result = cx.empty_object();
+
+    for (name, value) in components.iter() {
+        let value = cx.string(value);
+        result.set(&mut cx, *name, value)?;
+    }
+
+    let port = match url.port(&mut cx)? {
+        Some(port) => cx.number(port).upcast::<JsValue>(),
+        None => cx.null().upcast(),
+    };
+
+    result.set(&mut cx, "port", port)?;
+
+    Ok(result)
+}
+
+pub fn url_to_string(mut cx: FunctionContext) -> JsResult<JsString> {
+    let url = cx.argument::<JsValue>(0)?;
+    let url = JsUrl::from_value(&mut cx, url)?.to_string(&mut cx)?;
+
+    Ok(cx.string(url))
+}
+
+pub fn url_set_param(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let url = cx.argument::<JsValue>(0)?;
+    let name = cx.argument::<JsString>(1)?.value(&mut cx);
+    let value = cx.argument::<JsString>(2)?.value(&mut cx);
+    let url = JsUrl::from_value(&mut cx, url)?;
+
+    url.search_params(&mut cx)?.set(&mut cx, &name, &value)?;
+
+    Ok(url.as_object())
+}
+
+pub fn url_search_params(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let query = cx.argument::<JsString>(0)?.value(&mut cx);
+    let params = JsUrlSearchParams::new(&mut cx, &query)?;
+
+    params.append(&mut cx, "added", "1")?;
+    params.delete(&mut cx, "removed")?;
+
+    let first = params.get(&mut cx, "a")?;
+    let all = params.get_all(&mut cx, "a")?;
+    let missing = params.get(&mut cx, "missing")?;
+    let has_added = params.has(&mut cx, "added")?;
+    let query = params.to_string(&mut cx)?;
+
+    let result = cx.empty_object();
+    let first = match first {
+        Some(first) => cx.string(first).upcast::<JsValue>(),
+        None => cx.null().upcast(),
+    };
+    let all_array = cx.empty_array();
+
+    for (i, value) in all.iter().enumerate() {
+        let value = cx.string(value);
+        all_array.set(&mut cx, i as u32, value)?;
+    }
+
+    let missing = cx.boolean(missing.is_none());
+    let has_added = cx.boolean(has_added);
+    let query = cx.string(query);
+
+    result.set(&mut cx, "first", first)?;
+    result.set(&mut cx, "all", all_array)?;
+    result.set(&mut cx, "missing", missing)?;
+    result.set(&mut cx, "hasAdded", has_added)?;
+    result.set(&mut cx, "query", query)?;
+
+    Ok(result)
+}
@@ -3,13 +3,26 @@ use neon::prelude::*;
 mod js {
     pub mod arrays;
     pub mod boxed;
+    pub mod class;
     pub mod coercions;
     pub mod date;
     pub mod errors;
+    pub mod export;
+    pub mod extract;
     pub mod functions;
+    // Modules with only `#[neon::test]` functions, which are left out of
+    // release builds
+    #[cfg(debug_assertions)]
+    pub mod harness;
+    pub mod logging;
+    #[cfg(debug_assertions)]
+    pub mod mock;
     pub mod numbers;
     pub mod objects;
+    #[cfg(debug_assertions)]
+    pub mod standalone;
     pub mod strings;
+    pub mod tensors;
     pub mod threads;
     pub mod types;
 }
@@ -19,6 +32,7 @@ use js::boxed::*;
 use js::coercions::*;
 use js::date::*;
 use js::errors::*;
+use js::extract::*;
 use js::functions::*;
 use js::numbers::*;
 use js::objects::*;
@@ -28,6 +42,9 @@ use js::types::*;
 
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    #[cfg(debug_assertions)]
+    js::harness::mark_module_thread();
+
     let greeting = cx.string("Hello, World!");
     let greeting_copy = greeting.value(&mut cx);
     let greeting_copy = cx.string(greeting_copy);
@@ -117,6 +134,11 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("add1", add1)?;
 
     cx.export_function("return_js_string", return_js_string)?;
+    cx.export_function("join_strings_borrowed", join_strings_borrowed)?;
+    cx.export_function("string_value_cow", string_value_cow)?;
+    cx.export_function("encode_utf8", encode_utf8)?;
+    cx.export_function("decode_utf8", decode_utf8)?;
+    cx.export_function("interned_strings", interned_strings)?;
     cx.export_function("run_string_as_script", run_string_as_script)?;
 
     cx.export_function("return_js_number", return_js_number)?;
@@ -144,6 +166,17 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("return_js_function", return_js_function)?;
     cx.export_function("call_js_function", call_js_function)?;
     cx.export_function("construct_js_function", construct_js_function)?;
+    cx.export_function("call_js_function_with_args", call_js_function_with_args)?;
+    cx.export_function(
+        "call_js_function_without_args",
+        call_js_function_without_args,
+    )?;
+    cx.export_function("call_site_sum", call_site_sum)?;
+    cx.export_function("call_site_later", call_site_later)?;
+    cx.export_function(
+        "construct_js_function_with_args",
+        construct_js_function_with_args,
+    )?;
     cx.export_function("num_arguments", num_arguments)?;
     cx.export_function("return_this", return_this)?;
     cx.export_function("require_object_this", require_object_this)?;
@@ -157,10 +190,55 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("return_js_array_with_number", return_js_array_with_number)?;
     cx.export_function("return_js_array_with_string", return_js_array_with_string)?;
     cx.export_function("read_js_array", read_js_array)?;
+    cx.export_function("return_js_array_from_iter", return_js_array_from_iter)?;
+    cx.export_function("return_js_array_from_strings", return_js_array_from_strings)?;
+    cx.export_function("count_js_array_strings", count_js_array_strings)?;
+    cx.export_function("sum_js_array_with_try_map", sum_js_array_with_try_map)?;
+    cx.export_function("return_js_array_from_slice", return_js_array_from_slice)?;
+    cx.export_function("copy_js_array_range", copy_js_array_range)?;
+
+    cx.export_function("extract_vec_sum", extract_vec_sum)?;
+    cx.export_function("extract_f32_vec", extract_f32_vec)?;
+    cx.export_function("extract_i32_vec_doubled", extract_i32_vec_doubled)?;
+    cx.export_function("extract_u8_vec_len", extract_u8_vec_len)?;
+    cx.export_function("extract_nested_vec", extract_nested_vec)?;
+    cx.export_function("extract_hash_map", extract_hash_map)?;
+    cx.export_function("extract_btree_map_keys", extract_btree_map_keys)?;
+    cx.export_function("extract_point", extract_point)?;
+    cx.export_function("extract_mixed_tuple", extract_mixed_tuple)?;
+    cx.export_function("extract_either", extract_either)?;
+    cx.export_function("extract_either_list", extract_either_list)?;
+    cx.export_function("extract_nullable", extract_nullable)?;
+    cx.export_function("extract_defined", extract_defined)?;
+    cx.export_function("extract_u8", extract_u8)?;
+    cx.export_function("extract_i64_string", extract_i64_string)?;
+    cx.export_function("extract_lossy_u8", extract_lossy_u8)?;
+    cx.export_function("extract_bytes_len", extract_bytes_len)?;
+    cx.export_function("extract_bytes_reversed", extract_bytes_reversed)?;
+    cx.export_function("extract_base64_to_hex", extract_base64_to_hex)?;
+    cx.export_function("extract_hex_to_base64", extract_hex_to_base64)?;
+    cx.export_function("extract_path", extract_path)?;
 
     cx.export_function("to_string", to_string)?;
 
     cx.export_function("return_js_global_object", return_js_global_object)?;
+    cx.export_function("get_number_path", get_number_path)?;
+    cx.export_function("set_path", set_path)?;
+    cx.export_function("get_opt_string", get_opt_string)?;
+    cx.export_function("get_or_number", get_or_number)?;
+    cx.export_function("has_property", has_property)?;
+    cx.export_function("has_own_index", has_own_index)?;
+    cx.export_function("delete_property", delete_property)?;
+    cx.export_function("call_method_with_args", call_method_with_args)?;
+    cx.export_function("push_twice", push_twice)?;
+    cx.export_function("new_record_proxy", new_record_proxy)?;
+    cx.export_function("new_transparent_proxy", new_transparent_proxy)?;
+    cx.export_function("object_key", object_key)?;
+    cx.export_function("count_distinct_objects", count_distinct_objects)?;
+    cx.export_function("weak_map_roundtrip", weak_map_roundtrip)?;
+    cx.export_function("weak_set_add", weak_set_add)?;
+    cx.export_function("object_assoc_count", object_assoc_count)?;
+    cx.export_function("object_assoc_remove", object_assoc_remove)?;
     cx.export_function("return_js_object", return_js_object)?;
     cx.export_function("return_js_object_with_number", return_js_object_with_number)?;
     cx.export_function("return_js_object_with_string", return_js_object_with_string)?;
@@ -189,6 +267,14 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("return_buffer", return_buffer)?;
     cx.export_function("return_external_buffer", return_external_buffer)?;
     cx.export_function("return_external_array_buffer", return_external_array_buffer)?;
+    cx.export_function("adjust_external_memory", adjust_external_memory)?;
+    cx.export_function("supports_external_buffers", supports_external_buffers)?;
+    cx.export_function("return_pooled_buffers", return_pooled_buffers)?;
+    cx.export_function("lend_array_buffer", lend_array_buffer)?;
+    cx.export_function("detach_array_buffer", detach_array_buffer)?;
+    cx.export_function("resize_array_buffer", resize_array_buffer)?;
+    cx.export_function("grow_shared_array_buffer", grow_shared_array_buffer)?;
+    cx.export_function("resize_fixed_array_buffer", resize_fixed_array_buffer)?;
     cx.export_function("read_buffer_with_lock", read_buffer_with_lock)?;
     cx.export_function("read_buffer_with_borrow", read_buffer_with_borrow)?;
     cx.export_function("sum_buffer_with_borrow", sum_buffer_with_borrow)?;
@@ -208,6 +294,12 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("nan_dates", nan_dates)?;
     cx.export_function("create_date_from_value", create_date_from_value)?;
     cx.export_function("create_and_get_invalid_date", create_and_get_invalid_date)?;
+    cx.export_function("date_from_system_time", date_from_system_time)?;
+    cx.export_function("date_to_system_time", date_to_system_time)?;
+    cx.export_function("temporal_instant_from_millis", temporal_instant_from_millis)?;
+    cx.export_function("temporal_instant_to_millis", temporal_instant_to_millis)?;
+    cx.export_function("temporal_duration_from_nanos", temporal_duration_from_nanos)?;
+    cx.export_function("temporal_duration_to_nanos", temporal_duration_to_nanos)?;
 
     cx.export_function("is_array", is_array)?;
     cx.export_function("is_array_buffer", is_array_buffer)?;
@@ -219,13 +311,34 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("is_object", is_object)?;
     cx.export_function("is_string", is_string)?;
     cx.export_function("is_undefined", is_undefined)?;
+    cx.export_function("is_regexp", is_regexp)?;
     cx.export_function("strict_equals", strict_equals)?;
+    cx.export_function("loose_equals", loose_equals)?;
+    cx.export_function("same_value", same_value)?;
+    cx.export_function("to_debug_string", to_debug_string)?;
+    cx.export_function("debug_handle", debug_handle)?;
+    cx.export_function("create_regexp", create_regexp)?;
+    cx.export_function("regexp_source_and_flags", regexp_source_and_flags)?;
+    cx.export_function("regexp_exec", regexp_exec)?;
+    cx.export_function("regexp_test", regexp_test)?;
+    cx.export_function("url_components", url_components)?;
+    cx.export_function("url_to_string", url_to_string)?;
+    cx.export_function("url_set_param", url_set_param)?;
+    cx.export_function("url_search_params", url_search_params)?;
+    cx.export_function("napi_version", napi_version)?;
+    cx.export_function("supports_date_and_bigint", supports_date_and_bigint)?;
+    cx.export_function("runtime_host", runtime_host)?;
 
     cx.export_function("new_error", new_error)?;
     cx.export_function("new_type_error", new_type_error)?;
     cx.export_function("new_range_error", new_range_error)?;
+    cx.export_function("new_syntax_error", new_syntax_error)?;
+    cx.export_function("throw_syntax_error", throw_syntax_error)?;
     cx.export_function("throw_error", throw_error)?;
     cx.export_function("downcast_error", downcast_error)?;
+    cx.export_function("downcast_error_found", downcast_error_found)?;
+    cx.export_function("pending_exception", pending_exception)?;
+    cx.export_function("fatal", fatal)?;
 
     cx.export_function("panic", panic)?;
     cx.export_function("panic_after_throw", panic_after_throw)?;
@@ -234,6 +347,16 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("call_and_catch", call_and_catch)?;
     cx.export_function("get_number_or_default", get_number_or_default)?;
     cx.export_function("is_construct", is_construct)?;
+    cx.export_function("new_target_name", new_target_name)?;
+    cx.export_function("sum_args", sum_args)?;
+    cx.export_function("count_args", count_args)?;
+    cx.export_function("set_global_with_raw_env", set_global_with_raw_env)?;
+    cx.export_function("greeter", greeter)?;
+    cx.export_function("greeter_with_wrong_data", greeter_with_wrong_data)?;
+    cx.export_function("greeter_without_data", greeter_without_data)?;
+    cx.export_function("bind_function", bind_function)?;
+    cx.export_function("bind_root_and_call", bind_root_and_call)?;
+    cx.export_function("build_object_with_cx", build_object_with_cx)?;
 
     fn call_get_own_property_names(mut cx: FunctionContext) -> JsResult<JsArray> {
         let object = cx.argument::<JsObject>(0)?;
@@ -257,6 +380,45 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("greeter_greet", greeter_greet)?;
     cx.export_function("leak_channel", leak_channel)?;
     cx.export_function("drop_global_queue", drop_global_queue)?;
+    cx.export_function("channel_with_resource_name", channel_with_resource_name)?;
+    cx.export_function("weak_channel_send", weak_channel_send)?;
+    cx.export_function("weak_channel_refs", weak_channel_refs)?;
+    cx.export_function("channel_ordered", channel_ordered)?;
+    cx.export_function("channel_high_priority", channel_high_priority)?;
+    cx.export_function("channel_send_batch", channel_send_batch)?;
+    cx.export_function("channel_stats", channel_stats)?;
+    cx.export_function("spawn_local_double", spawn_local_double)?;
+    cx.export_function("local_context_outside", local_context_outside)?;
+    cx.export_function("async_local_capture", async_local_capture)?;
+    cx.export_function("async_local_run", async_local_run)?;
+    cx.export_function("schedule_all", schedule_all)?;
+    cx.export_function("emitter_subscribe", emitter_subscribe)?;
+    cx.export_function("emitter_emit", emitter_emit)?;
+    cx.export_function("context_local_increment", context_local_increment)?;
+    cx.export_function("shared_root_threads", shared_root_threads)?;
+    cx.export_function("shared_root_unwrap", shared_root_unwrap)?;
+    cx.export_function("env_cache_regexp", env_cache_regexp)?;
+    cx.export_function("env_cache_len", env_cache_len)?;
+    cx.export_function("lifecycle_on_unload", lifecycle_on_unload)?;
+    cx.export_function("lifecycle_unload_count", lifecycle_unload_count)?;
+    cx.export_function("tsfn_count", tsfn_count)?;
+    cx.export_function("tsfn_queue_full", tsfn_queue_full)?;
+    cx.export_function("tsfn_abort", tsfn_abort)?;
+    cx.export_function("uv_loop_address", uv_loop_address)?;
+    cx.export_function("transfer_roundtrip", transfer_roundtrip)?;
+    cx.export_function("structured_clone", structured_clone)?;
+    cx.export_function("transfer_on_thread", transfer_on_thread)?;
+    cx.export_function("diagnostics_count_roots", diagnostics_count_roots)?;
+    cx.export_function("settle_with_on_thread", settle_with_on_thread)?;
+    cx.export_function("promise_then_double", promise_then_double)?;
+    cx.export_function("promise_catch_message", promise_catch_message)?;
+    cx.export_function("promise_finally_count", promise_finally_count)?;
+    cx.export_function("track_rejections", track_rejections)?;
+    cx.export_function("reject_with", reject_with)?;
+    cx.export_function("task_block_worker", task_block_worker)?;
+    cx.export_function("task_unblock_workers", task_unblock_workers)?;
+    cx.export_function("task_cancellable", task_cancellable)?;
+    cx.export_function("call_on_thread", call_on_thread)?;
 
     Ok(())
 }